@@ -9,18 +9,23 @@
       - app-manager-X.Y.Z-ARCH.msi  (Windows Installer package)
 
 .PARAMETER Target
-    Build target: exe, zip, msi, all, or clean. Default is "all".
+    Build target: exe, zip, msi, all, bench, test, or clean. Default is "all".
+    bench builds the EXE and writes collector timings to dist\bench-X.Y.Z.json.
+    test runs the unit tests, then builds the EXE and checks that one
+    --bench-collectors pass finds processes, services, and installed apps.
 
 .EXAMPLE
     .\build.ps1 exe
     .\build.ps1 zip
     .\build.ps1 msi
     .\build.ps1 all
+    .\build.ps1 bench
+    .\build.ps1 test
     .\build.ps1 clean
 #>
 
 param(
-    [ValidateSet("exe", "zip", "msi", "all", "clean")]
+    [ValidateSet("exe", "zip", "msi", "all", "bench", "test", "clean")]
     [string]$Target = "all"
 )
 
@@ -114,6 +119,37 @@ function Build-Msi {
     Write-Host "  -> $DistDir\$MsiName" -ForegroundColor Green
 }
 
+function Invoke-Bench {
+    Write-Host "==> Benchmarking collectors..." -ForegroundColor Cyan
+
+    $benchPath = "$DistDir\bench-$Version.json"
+    & "$DistDir\$ExeName" --bench-collectors 5 --json | Out-File $benchPath -Encoding utf8
+    if ($LASTEXITCODE -ne 0) { Write-Error "collector benchmark failed" }
+
+    # Fail the run if a collector found nothing, which means it broke
+    $bench = Get-Content $benchPath -Raw | ConvertFrom-Json
+    $bench.collectors | Format-Table name, min_ms, avg_ms, max_ms, items -AutoSize
+    $empty = $bench.collectors | Where-Object { $_.items -eq 0 -and $_.name -in "Installed Apps", "Services", "Processes" }
+    if ($empty) { Write-Error "Collectors returned nothing: $($empty.name -join ', ')" }
+    Write-Host "  -> $benchPath" -ForegroundColor Green
+}
+
+function Invoke-Test {
+    Write-Host "==> Running tests (release)..." -ForegroundColor Cyan
+    cargo test --release
+    if ($LASTEXITCODE -ne 0) { Write-Error "cargo test failed" }
+
+    # Smoke-test the CLI: one collector pass must produce valid JSON with
+    # the collectors that always find something
+    Write-Host "==> Smoke-testing the CLI..." -ForegroundColor Cyan
+    $output = & "$DistDir\$ExeName" --bench-collectors 1 --json
+    if ($LASTEXITCODE -ne 0) { Write-Error "--bench-collectors failed" }
+    $bench = ($output -join "`n") | ConvertFrom-Json
+    $empty = $bench.collectors | Where-Object { $_.items -eq 0 -and $_.name -in "Installed Apps", "Services", "Processes" }
+    if ($empty) { Write-Error "Collectors returned nothing: $($empty.name -join ', ')" }
+    Write-Host "  -> Tests passed" -ForegroundColor Green
+}
+
 function Invoke-Clean {
     Write-Host "==> Cleaning $DistDir\..." -ForegroundColor Cyan
     if (Test-Path $DistDir) { Remove-Item $DistDir -Recurse -Force }
@@ -152,6 +188,14 @@ switch ($Target) {
         Build-Msi
         Show-Summary
     }
+    "bench" {
+        Build-Exe
+        Invoke-Bench
+    }
+    "test" {
+        Build-Exe
+        Invoke-Test
+    }
     "clean" {
         Invoke-Clean
     }
@@ -1,12 +1,52 @@
+use crate::backup;
+use crate::cmdline;
 use crate::models::*;
+use crate::startup_folders;
 use anyhow::{Context, Result};
 use std::os::windows::process::CommandExt;
 use std::process::Command;
+use windows::core::{Interface, HSTRING};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, IPersistFile, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+};
+use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
 use winreg::enums::*;
 use winreg::RegKey;
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+pub const RUN_KEY_PATH: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+
+/// `StartupApproved` subkeys that a deleted Run entry's state is removed from.
+pub const STARTUP_APPROVED_SUBKEYS: [&str; 3] = ["Run", "Run32", "StartupFolder"];
+
+/// Where "Add to Startup" creates a new entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NewEntryLocation {
+    /// `HKCU\...\Run`
+    UserRun,
+    /// `HKLM\...\Run` (all users; requires admin)
+    MachineRun,
+    /// A shortcut in the current user's Startup folder
+    UserStartupFolder,
+}
+
+impl NewEntryLocation {
+    pub const ALL: [NewEntryLocation; 3] = [
+        NewEntryLocation::UserRun,
+        NewEntryLocation::MachineRun,
+        NewEntryLocation::UserStartupFolder,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NewEntryLocation::UserRun => "Run key (current user)",
+            NewEntryLocation::MachineRun => "Run key (all users, admin)",
+            NewEntryLocation::UserStartupFolder => "Startup folder (current user)",
+        }
+    }
+}
+
 /// Enable a startup entry.
 pub fn enable_entry(entry: &StartupEntry) -> Result<()> {
     match &entry.source {
@@ -16,16 +56,24 @@ pub fn enable_entry(entry: &StartupEntry) -> Result<()> {
         Source::RegistryRunOnce { .. } => {
             anyhow::bail!("RunOnce entries cannot be toggled")
         }
-        Source::StartupFolder { path, is_common } => {
+        Source::Ifeo { .. } => {
+            anyhow::bail!("IFEO debugger entries cannot be toggled; delete the entry to remove the debugger")
+        }
+        Source::StartupFolder { path, is_common, other_user } => {
+            let file_name = std::path::Path::new(path)
+                .file_name()
+                .and_then(|f| f.to_str())
+                .unwrap_or(&entry.name);
+            if let Some(user) = other_user {
+                let root = startup_folders::open_user_hive(&user.sid, KEY_READ | KEY_SET_VALUE)
+                    .with_context(|| format!("Failed to open the registry of {}", user.name))?;
+                return set_startup_approved_in(&root, "StartupFolder", file_name, true);
+            }
             let hive = if *is_common {
                 RegistryHive::HKLM
             } else {
                 RegistryHive::HKCU
             };
-            let file_name = std::path::Path::new(path)
-                .file_name()
-                .and_then(|f| f.to_str())
-                .unwrap_or(&entry.name);
             set_startup_approved(&hive, "StartupFolder", file_name, true)
         }
         Source::TaskScheduler { task_path } => {
@@ -64,16 +112,24 @@ pub fn disable_entry(entry: &StartupEntry) -> Result<()> {
         Source::RegistryRunOnce { .. } => {
             anyhow::bail!("RunOnce entries cannot be toggled")
         }
-        Source::StartupFolder { path, is_common } => {
+        Source::Ifeo { .. } => {
+            anyhow::bail!("IFEO debugger entries cannot be toggled; delete the entry to remove the debugger")
+        }
+        Source::StartupFolder { path, is_common, other_user } => {
+            let file_name = std::path::Path::new(path)
+                .file_name()
+                .and_then(|f| f.to_str())
+                .unwrap_or(&entry.name);
+            if let Some(user) = other_user {
+                let root = startup_folders::open_user_hive(&user.sid, KEY_READ | KEY_SET_VALUE)
+                    .with_context(|| format!("Failed to open the registry of {}", user.name))?;
+                return set_startup_approved_in(&root, "StartupFolder", file_name, false);
+            }
             let hive = if *is_common {
                 RegistryHive::HKLM
             } else {
                 RegistryHive::HKCU
             };
-            let file_name = std::path::Path::new(path)
-                .file_name()
-                .and_then(|f| f.to_str())
-                .unwrap_or(&entry.name);
             set_startup_approved(&hive, "StartupFolder", file_name, false)
         }
         Source::TaskScheduler { task_path } => {
@@ -118,7 +174,7 @@ pub fn start_entry(entry: &StartupEntry) -> Result<()> {
         return Ok(());
     }
 
-    let (exe, args) = parse_command(&entry.command);
+    let (exe, args) = cmdline::parse(&entry.command);
     Command::new(&exe)
         .args(&args)
         .spawn()
@@ -172,6 +228,90 @@ pub fn stop_entry(entry: &StartupEntry) -> Result<()> {
     }
 }
 
+/// Create a new startup entry that runs `command` at logon.
+pub fn add_entry(name: &str, command: &str, location: NewEntryLocation) -> Result<()> {
+    let name = name.trim();
+    let command = command.trim();
+    if name.is_empty() || command.is_empty() {
+        anyhow::bail!("A name and a command are required");
+    }
+
+    match location {
+        NewEntryLocation::UserRun | NewEntryLocation::MachineRun => {
+            let predef = if location == NewEntryLocation::UserRun {
+                RegKey::predef(HKEY_CURRENT_USER)
+            } else {
+                RegKey::predef(HKEY_LOCAL_MACHINE)
+            };
+            let (key, _) = predef
+                .create_subkey_with_flags(RUN_KEY_PATH, KEY_READ | KEY_SET_VALUE)
+                .context("Failed to open the Run key for writing")?;
+            if key.get_raw_value(name).is_ok() {
+                anyhow::bail!("A Run entry named '{}' already exists", name);
+            }
+            key.set_raw_value(name, &string_value(command, new_value_type(command)))
+                .with_context(|| format!("Failed to write value '{}'", name))?;
+            Ok(())
+        }
+        NewEntryLocation::UserStartupFolder => {
+            let link = new_shortcut_path(name)?;
+            let folder = link.parent().context("Startup folder location is not available")?;
+            std::fs::create_dir_all(folder)
+                .with_context(|| format!("Failed to create {}", folder.display()))?;
+
+            let (exe, args) = cmdline::split(command);
+            let target = cmdline::expand_env_vars(&exe);
+            if target.to_lowercase().ends_with(".lnk") && args.is_empty() {
+                // Already a shortcut: copy it as-is
+                std::fs::copy(&target, &link).with_context(|| format!("Failed to copy {}", target))?;
+                return Ok(());
+            }
+            create_shortcut(&link, &target, &args)
+        }
+    }
+}
+
+/// The shortcut "Add to Startup" creates in the user's Startup folder for an
+/// entry named `name`. Fails if it already exists.
+pub fn new_shortcut_path(name: &str) -> Result<std::path::PathBuf> {
+    let folder = startup_folders::user_startup_folder().context("Startup folder location is not available")?;
+    let file_name: String = name
+        .chars()
+        .map(|c| if r#"<>:"/\|?*"#.contains(c) || c.is_control() { '_' } else { c })
+        .collect();
+    let link = folder.join(format!("{}.lnk", file_name));
+    if link.exists() {
+        anyhow::bail!("{} already exists", link.display());
+    }
+    Ok(link)
+}
+
+/// Create a `.lnk` shortcut through the shell's `ShellLink` COM object.
+fn create_shortcut(link: &std::path::Path, target: &str, args: &str) -> Result<()> {
+    // S_FALSE (already initialized) still needs balancing; a thread already in
+    // another apartment mode can use COM as it is
+    let initialized = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) }.is_ok();
+    let result = unsafe { save_shortcut(link, target, args) };
+    if initialized {
+        unsafe { CoUninitialize() };
+    }
+    result.context("Failed to create shortcut")
+}
+
+unsafe fn save_shortcut(link: &std::path::Path, target: &str, args: &str) -> Result<()> {
+    let working_dir = std::path::Path::new(target)
+        .parent()
+        .map(|p| p.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let shell_link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+    shell_link.SetPath(&HSTRING::from(target))?;
+    shell_link.SetArguments(&HSTRING::from(args))?;
+    shell_link.SetWorkingDirectory(&HSTRING::from(working_dir))?;
+    let file: IPersistFile = shell_link.cast()?;
+    file.Save(&HSTRING::from(link.as_os_str()), true)?;
+    Ok(())
+}
+
 /// Delete a startup entry entirely.
 pub fn delete_entry(entry: &StartupEntry) -> Result<()> {
     match &entry.source {
@@ -190,8 +330,17 @@ pub fn delete_entry(entry: &StartupEntry) -> Result<()> {
             let _ = cleanup_startup_approved(hive, &entry.name);
             Ok(())
         }
+        Source::Ifeo { key_path } => {
+            RegKey::predef(HKEY_LOCAL_MACHINE)
+                .open_subkey_with_flags(key_path, KEY_SET_VALUE)
+                .context("Failed to open registry key for writing")?
+                .delete_value(IFEO_DEBUGGER_VALUE)
+                .context("Failed to delete the Debugger value")?;
+            Ok(())
+        }
         Source::StartupFolder { path, .. } => {
-            std::fs::remove_file(path)
+            // Moved to the backup store rather than removed, so it can be restored
+            backup::backup_file(std::path::Path::new(path))
                 .with_context(|| format!("Failed to delete file: {}", path))?;
             Ok(())
         }
@@ -222,8 +371,98 @@ pub fn delete_entry(entry: &StartupEntry) -> Result<()> {
     }
 }
 
+/// Replace the arguments of a startup entry's command, keeping its executable.
+pub fn set_entry_arguments(entry: &StartupEntry, args: &str) -> Result<()> {
+    let (exe, _) = cmdline::split(&entry.command);
+    if exe.is_empty() {
+        anyhow::bail!("Could not determine executable for '{}'", entry.name);
+    }
+    let new_command = cmdline::join(&exe, args);
+
+    match &entry.source {
+        Source::RegistryRun { hive, key_path } | Source::RegistryRunOnce { hive, key_path } => {
+            let predef = match hive {
+                RegistryHive::HKCU => RegKey::predef(HKEY_CURRENT_USER),
+                RegistryHive::HKLM => RegKey::predef(HKEY_LOCAL_MACHINE),
+            };
+            let key = predef
+                .open_subkey_with_flags(key_path, KEY_READ | KEY_SET_VALUE)
+                .context("Failed to open registry key for writing")?;
+
+            // Keep the existing value type (REG_SZ or REG_EXPAND_SZ)
+            let vtype = key
+                .get_raw_value(&entry.name)
+                .map(|v| v.vtype)
+                .unwrap_or_else(|_| new_value_type(&new_command));
+            key.set_raw_value(&entry.name, &string_value(&new_command, vtype))
+                .with_context(|| format!("Failed to write value '{}'", entry.name))?;
+            Ok(())
+        }
+        Source::Ifeo { key_path } => {
+            let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+                .open_subkey_with_flags(key_path, KEY_READ | KEY_SET_VALUE)
+                .context("Failed to open registry key for writing")?;
+            let vtype = key
+                .get_raw_value(IFEO_DEBUGGER_VALUE)
+                .map(|v| v.vtype)
+                .unwrap_or_else(|_| new_value_type(&new_command));
+            key.set_raw_value(IFEO_DEBUGGER_VALUE, &string_value(&new_command, vtype))
+                .context("Failed to write the Debugger value")?;
+            Ok(())
+        }
+        Source::StartupFolder { .. } => {
+            anyhow::bail!("Editing arguments of startup folder items is not supported")
+        }
+        Source::TaskScheduler { task_path } => {
+            let output = Command::new("schtasks")
+                .args(["/Change", "/TN", task_path, "/TR", &new_command])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .context("Failed to run schtasks")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("schtasks failed: {}", stderr.trim());
+            }
+            Ok(())
+        }
+        Source::Service { service_name, .. } => {
+            let output = Command::new("sc")
+                .args(["config", service_name, "binPath=", &new_command])
+                .creation_flags(CREATE_NO_WINDOW)
+                .output()
+                .context("Failed to run sc config")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("sc config failed: {}", stderr.trim());
+            }
+            Ok(())
+        }
+    }
+}
+
 // --- Helpers ---
 
+/// The type a new registry command is written as: REG_EXPAND_SZ when it
+/// uses `%VARIABLES%`, so they are expanded when it runs rather than taken
+/// literally.
+pub fn new_value_type(command: &str) -> RegType {
+    if cmdline::has_env_vars(command) {
+        REG_EXPAND_SZ
+    } else {
+        REG_SZ
+    }
+}
+
+/// A null-terminated UTF-16 string value of type `vtype`.
+fn string_value(text: &str, vtype: RegType) -> winreg::RegValue {
+    let bytes: Vec<u8> = text
+        .encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(|c| c.to_le_bytes())
+        .collect();
+    winreg::RegValue { vtype, bytes }
+}
+
 fn set_startup_approved(
     hive: &RegistryHive,
     subkey: &str,
@@ -234,21 +473,41 @@ fn set_startup_approved(
         RegistryHive::HKCU => RegKey::predef(HKEY_CURRENT_USER),
         RegistryHive::HKLM => RegKey::predef(HKEY_LOCAL_MACHINE),
     };
+    set_startup_approved_in(&predef, subkey, value_name, enable)
+}
 
-    let path = format!(
-        r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\{}",
-        subkey
-    );
-
-    let key = predef
+/// Like [`set_startup_approved`], under an already opened hive root (another
+/// user's registry).
+fn set_startup_approved_in(root: &RegKey, subkey: &str, value_name: &str, enable: bool) -> Result<()> {
+    let path = startup_approved_path(subkey);
+    let key = root
         .open_subkey_with_flags(&path, KEY_READ | KEY_SET_VALUE)
         .with_context(|| format!("Failed to open {}", path))?;
+    let current = key.get_raw_value(value_name).ok().map(|v| v.bytes);
+    let reg_value = winreg::RegValue {
+        vtype: REG_BINARY,
+        bytes: startup_approved_data(current, enable),
+    };
+    key.set_raw_value(value_name, &reg_value)
+        .with_context(|| format!("Failed to write StartupApproved for '{}'", value_name))?;
+
+    Ok(())
+}
+
+/// Key under a hive root holding the enabled state of Run entries (`Run`,
+/// `Run32`) or startup folder items (`StartupFolder`).
+pub fn startup_approved_path(subkey: &str) -> String {
+    format!(
+        r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\{}",
+        subkey
+    )
+}
 
-    // Read existing value or create a new 12-byte buffer
-    let mut data: Vec<u8> = key
-        .get_raw_value(value_name)
-        .map(|v| v.bytes)
-        .unwrap_or_else(|_| vec![0u8; 12]);
+/// The `StartupApproved` value that enables or disables an entry, starting
+/// from its current value (if any).
+pub fn startup_approved_data(current: Option<Vec<u8>>, enable: bool) -> Vec<u8> {
+    // Use the existing value or create a new 12-byte buffer
+    let mut data: Vec<u8> = current.unwrap_or_else(|| vec![0u8; 12]);
 
     if data.len() < 12 {
         data.resize(12, 0);
@@ -271,15 +530,7 @@ fn set_startup_approved(
             (since_epoch.as_nanos() / 100) as u64 + 116_444_736_000_000_000u64;
         data[4..12].copy_from_slice(&filetime.to_le_bytes());
     }
-
-    let reg_value = winreg::RegValue {
-        vtype: REG_BINARY,
-        bytes: data,
-    };
-    key.set_raw_value(value_name, &reg_value)
-        .with_context(|| format!("Failed to write StartupApproved for '{}'", value_name))?;
-
-    Ok(())
+    data
 }
 
 fn cleanup_startup_approved(hive: &RegistryHive, value_name: &str) -> Result<()> {
@@ -288,11 +539,8 @@ fn cleanup_startup_approved(hive: &RegistryHive, value_name: &str) -> Result<()>
         RegistryHive::HKLM => RegKey::predef(HKEY_LOCAL_MACHINE),
     };
 
-    for subkey in &["Run", "Run32", "StartupFolder"] {
-        let path = format!(
-            r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\{}",
-            subkey
-        );
+    for subkey in STARTUP_APPROVED_SUBKEYS {
+        let path = startup_approved_path(subkey);
         if let Ok(key) = predef.open_subkey_with_flags(&path, KEY_SET_VALUE) {
             let _ = key.delete_value(value_name);
         }
@@ -300,59 +548,3 @@ fn cleanup_startup_approved(hive: &RegistryHive, value_name: &str) -> Result<()>
 
     Ok(())
 }
-
-/// Parse a command string into (exe, args).
-fn parse_command(command: &str) -> (String, Vec<String>) {
-    let command = command.trim();
-    if command.is_empty() {
-        return (String::new(), Vec::new());
-    }
-
-    if let Some(stripped) = command.strip_prefix('"') {
-        // Quoted path: "C:\path\to\exe.exe" arg1 arg2
-        if let Some(end_quote) = stripped.find('"') {
-            let exe = &stripped[..end_quote];
-            let rest = stripped[end_quote + 1..].trim();
-            let args: Vec<String> = if rest.is_empty() {
-                Vec::new()
-            } else {
-                shell_split(rest)
-            };
-            return (exe.to_string(), args);
-        }
-    }
-
-    // Unquoted: split on whitespace
-    let parts: Vec<&str> = command.splitn(2, char::is_whitespace).collect();
-    let exe = parts[0].to_string();
-    let args = if parts.len() > 1 {
-        shell_split(parts[1].trim())
-    } else {
-        Vec::new()
-    };
-    (exe, args)
-}
-
-/// Simple shell-like argument splitting (handles quoted args).
-fn shell_split(s: &str) -> Vec<String> {
-    let mut args = Vec::new();
-    let mut current = String::new();
-    let mut in_quotes = false;
-    let mut chars = s.chars();
-
-    while let Some(c) = chars.next() {
-        match c {
-            '"' => in_quotes = !in_quotes,
-            ' ' | '\t' if !in_quotes => {
-                if !current.is_empty() {
-                    args.push(std::mem::take(&mut current));
-                }
-            }
-            _ => current.push(c),
-        }
-    }
-    if !current.is_empty() {
-        args.push(current);
-    }
-    args
-}
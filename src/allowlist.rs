@@ -0,0 +1,192 @@
+//! User-editable additions to (and removals from) the built-in lists behind
+//! the "Hide Windows Processes" and "Hide Windows Services" filters, so
+//! corporate IT agents can be hidden along with Windows itself.
+//!
+//! Stored as `allowlist.txt` in the app data directory and edited in
+//! Notepad from Settings:
+//!
+//! ```text
+//! [processes]
+//! ccmexec.exe          # also treat as a Windows process
+//! -searchhost.exe      # never treat as a Windows process
+//! [services]
+//! CcmExec              # a service name
+//! %programfiles%\contoso\agent.exe   # a command-line prefix
+//! ```
+
+use crate::app_data;
+use crate::cmdline;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock};
+
+const FILE_NAME: &str = "allowlist.txt";
+
+const TEMPLATE: &str = "\
+# App Manager allowlist v1
+#
+# Extends the built-in lists used by Hide Windows Processes and
+# Hide Windows Services. One item per line; matching ignores case.
+# Prefix a line with - to stop treating a built-in item as Windows.
+#
+# [processes]: executable names, e.g. ccmexec.exe
+# [services]:  service names (e.g. CcmExec), or command-line prefixes
+#              containing a backslash (e.g. %programfiles%\\contoso\\agent.exe)
+
+[processes]
+
+[services]
+";
+
+#[derive(Debug, Default)]
+pub struct Allowlist {
+    added_processes: HashSet<String>,
+    removed_processes: HashSet<String>,
+    added_service_names: HashSet<String>,
+    removed_service_names: HashSet<String>,
+    added_service_prefixes: Vec<String>,
+    removed_service_prefixes: Vec<String>,
+}
+
+impl Allowlist {
+    fn parse(text: &str) -> Self {
+        let mut list = Allowlist::default();
+        let mut section = "";
+        for line in text.lines() {
+            let line = line.split(" #").next().unwrap_or_default().trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = match line.to_lowercase().as_str() {
+                    "[processes]" => "processes",
+                    "[services]" => "services",
+                    _ => "",
+                };
+                continue;
+            }
+            let (removed, item) = match line.strip_prefix('-') {
+                Some(rest) => (true, rest.trim().to_lowercase()),
+                None => (false, line.to_lowercase()),
+            };
+            match (section, removed, item.contains('\\')) {
+                ("processes", false, _) => {
+                    list.added_processes.insert(item);
+                }
+                ("processes", true, _) => {
+                    list.removed_processes.insert(item);
+                }
+                ("services", false, false) => {
+                    list.added_service_names.insert(item);
+                }
+                ("services", true, false) => {
+                    list.removed_service_names.insert(item);
+                }
+                ("services", false, true) => list.added_service_prefixes.push(expand_lower(&item)),
+                ("services", true, true) => list.removed_service_prefixes.push(expand_lower(&item)),
+                _ => {}
+            }
+        }
+        list
+    }
+
+    /// `Some(true)`/`Some(false)` if the allowlist adds/removes the process
+    /// name (lowercase), `None` to use the built-in list.
+    pub fn process_override(&self, name_lower: &str) -> Option<bool> {
+        if self.removed_processes.contains(name_lower) {
+            Some(false)
+        } else if self.added_processes.contains(name_lower) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    /// Like [`Allowlist::process_override`], for a service by name and
+    /// command line (both lowercase, command without a leading quote).
+    /// Environment variables are expanded in both the command and the
+    /// prefixes before comparing.
+    pub fn service_override(&self, name_lower: &str, cmd_lower: &str) -> Option<bool> {
+        let cmd_lower = expand_lower(cmd_lower);
+        let cmd_lower = cmd_lower.as_str();
+        if self.removed_service_names.contains(name_lower)
+            || self.removed_service_prefixes.iter().any(|p| cmd_lower.starts_with(p))
+        {
+            Some(false)
+        } else if self.added_service_names.contains(name_lower)
+            || self.added_service_prefixes.iter().any(|p| cmd_lower.starts_with(p))
+        {
+            Some(true)
+        } else {
+            None
+        }
+    }
+}
+
+fn expand_lower(s: &str) -> String {
+    cmdline::expand_env_vars(s).to_lowercase()
+}
+
+fn store() -> &'static RwLock<Allowlist> {
+    static STORE: OnceLock<RwLock<Allowlist>> = OnceLock::new();
+    STORE.get_or_init(|| RwLock::new(read_file()))
+}
+
+fn read_file() -> Allowlist {
+    path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map(|text| Allowlist::parse(&text))
+        .unwrap_or_default()
+}
+
+/// Run `f` with the current allowlist.
+pub fn with<T>(f: impl FnOnce(&Allowlist) -> T) -> T {
+    match store().read() {
+        Ok(list) => f(&list),
+        Err(_) => f(&Allowlist::default()),
+    }
+}
+
+/// Re-read the file after it was edited.
+pub fn reload() {
+    let list = read_file();
+    if let Ok(mut current) = store().write() {
+        *current = list;
+    }
+}
+
+/// Open the allowlist in Notepad, creating it from a commented template
+/// first if it doesn't exist yet.
+pub fn open_in_editor() -> Result<(), String> {
+    let path = path().ok_or("App data directory is not available")?;
+    if !path.exists() {
+        std::fs::write(&path, TEMPLATE).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    }
+    std::process::Command::new("notepad.exe")
+        .arg(&path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open Notepad: {}", e))
+}
+
+fn path() -> Option<PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn service_prefix_expands_env_vars() {
+        let list = Allowlist::parse("[services]\n%programfiles%\\contoso\\agent.exe\n");
+        let program_files = std::env::var("ProgramFiles").unwrap();
+        let cmd = format!(r"{}\Contoso\agent.exe -service", program_files).to_lowercase();
+        assert_eq!(list.service_override("contosoagent", &cmd), Some(true));
+        assert_eq!(
+            list.service_override("contosoagent", r"%programfiles%\contoso\agent.exe"),
+            Some(true)
+        );
+        assert_eq!(list.service_override("other", r"c:\other\agent.exe"), None);
+    }
+}
@@ -0,0 +1,21 @@
+//! Location of App Manager's own persistent files.
+
+use std::path::{Path, PathBuf};
+
+/// `%LOCALAPPDATA%\App Manager`, created on first use. `None` if the
+/// directory can't be determined or created.
+pub fn data_dir() -> Option<PathBuf> {
+    let dir = PathBuf::from(std::env::var("LOCALAPPDATA").ok()?).join("App Manager");
+    std::fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// Replace `path` with `contents` through a temporary file, so a crash or
+/// power loss mid-write leaves either the old file or the new one, never a
+/// truncated mix.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
@@ -0,0 +1,204 @@
+//! Audit trail of every change App Manager makes to the machine: who made it,
+//! when, to what, and the value before and after. Unlike the session summary
+//! it is kept across sessions (and written by the headless monitor too), and
+//! can be exported as CSV or JSON Lines for helpdesk records.
+//!
+//! The trail is a tab-separated text file in the app data directory:
+//! `<timestamp>\t<user>\t<machine>\t<item>\t<location>\t<action>\t<old value>\t<new value>`.
+
+use crate::app_data;
+use crate::models::{StartupEntry, TaskConditions};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::io::Write;
+
+const LOG_FILE_NAME: &str = "audit-log.tsv";
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Column names of the exports, in field order.
+pub const COLUMNS: &str = "When,User,Machine,Item,Location,Action,Old Value,New Value";
+
+/// Export file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::JsonLines => "jsonl",
+        }
+    }
+
+    pub fn filter_name(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV Files",
+            ExportFormat::JsonLines => "JSON Lines Files",
+        }
+    }
+}
+
+/// One change.
+#[derive(Debug, Clone)]
+pub struct AuditRecord {
+    pub when: DateTime<Local>,
+    /// `DOMAIN\user` the change was made as.
+    pub user: String,
+    pub machine: String,
+    pub item: String,
+    pub location: String,
+    pub action: String,
+    /// Empty when the item didn't exist before (Add, Restore).
+    pub old_value: String,
+    /// Empty when the item no longer exists (Delete).
+    pub new_value: String,
+}
+
+impl AuditRecord {
+    /// A change made now by the current user.
+    pub fn new(item: &str, location: &str, action: &str, old_value: &str, new_value: &str) -> Self {
+        let env = |name| std::env::var(name).unwrap_or_default();
+        let user = match (env("USERDOMAIN"), env("USERNAME")) {
+            (domain, user) if domain.is_empty() => user,
+            (domain, user) => format!("{}\\{}", domain, user),
+        };
+        Self {
+            when: Local::now(),
+            user,
+            machine: env("COMPUTERNAME"),
+            item: item.to_string(),
+            location: location.to_string(),
+            action: action.to_string(),
+            old_value: old_value.to_string(),
+            new_value: new_value.to_string(),
+        }
+    }
+
+    /// A change to a startup entry or service.
+    pub fn for_entry(entry: &StartupEntry, action: &str, old_value: &str, new_value: &str) -> Self {
+        Self::new(&entry.name, &entry.source.display_location(), action, old_value, new_value)
+    }
+
+    /// The fields in [`COLUMNS`] order, with the time as written to the trail.
+    pub fn fields(&self) -> [String; 8] {
+        [
+            self.when.format(TIME_FORMAT).to_string(),
+            self.user.clone(),
+            self.machine.clone(),
+            self.item.clone(),
+            self.location.clone(),
+            self.action.clone(),
+            self.old_value.clone(),
+            self.new_value.clone(),
+        ]
+    }
+
+    /// The record as one JSON object, with the time in RFC 3339.
+    pub fn to_json(&self) -> String {
+        let keys = ["when", "user", "machine", "item", "location", "action", "old_value", "new_value"];
+        let mut values = self.fields();
+        values[0] = self.when.to_rfc3339();
+        let pairs: Vec<String> = keys
+            .iter()
+            .zip(&values)
+            .map(|(key, value)| format!("\"{}\":\"{}\"", key, json_escape(value)))
+            .collect();
+        format!("{{{}}}", pairs.join(","))
+    }
+}
+
+/// Append changes to the trail. Failing to write is not an error for the
+/// change itself, so it is ignored.
+pub fn append(records: &[AuditRecord]) {
+    let Some(path) = log_path() else {
+        return;
+    };
+    let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) else {
+        return;
+    };
+    for record in records {
+        let fields = record.fields().map(|f| f.replace(['\t', '\r', '\n'], " "));
+        let _ = writeln!(file, "{}", fields.join("\t"));
+    }
+}
+
+/// Every recorded change, newest first.
+pub fn read_log() -> Vec<AuditRecord> {
+    let Some(text) = log_path().and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    text.lines()
+        .rev()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [when, user, machine, item, location, action, old_value, new_value] = fields[..] else {
+                return None;
+            };
+            let when = NaiveDateTime::parse_from_str(when, TIME_FORMAT).ok()?;
+            Some(AuditRecord {
+                when: Local.from_local_datetime(&when).single()?,
+                user: user.to_string(),
+                machine: machine.to_string(),
+                item: item.to_string(),
+                location: location.to_string(),
+                action: action.to_string(),
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The conditions that differ between `original` and `conditions`, as
+/// `Setting=value` lists (old, new), named as in Task Scheduler.
+pub fn condition_values(original: &TaskConditions, conditions: &TaskConditions) -> (String, String) {
+    let fields = [
+        (
+            "DisallowStartIfOnBatteries",
+            original.disallow_start_on_batteries.to_string(),
+            conditions.disallow_start_on_batteries.to_string(),
+        ),
+        (
+            "StopIfGoingOnBatteries",
+            original.stop_on_batteries.to_string(),
+            conditions.stop_on_batteries.to_string(),
+        ),
+        ("RunOnlyIfIdle", original.run_only_if_idle.to_string(), conditions.run_only_if_idle.to_string()),
+        ("IdleMinutes", original.idle_minutes.to_string(), conditions.idle_minutes.to_string()),
+        (
+            "RunOnlyIfNetworkAvailable",
+            original.run_only_if_network.to_string(),
+            conditions.run_only_if_network.to_string(),
+        ),
+        ("WakeToRun", original.wake_to_run.to_string(), conditions.wake_to_run.to_string()),
+    ];
+    let (mut old, mut new) = (Vec::new(), Vec::new());
+    for (setting, from, to) in fields.iter().filter(|(_, from, to)| from != to) {
+        old.push(format!("{}={}", setting, from));
+        new.push(format!("{}={}", setting, to));
+    }
+    (old.join("; "), new.join("; "))
+}
+
+fn json_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn log_path() -> Option<std::path::PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(LOG_FILE_NAME))
+}
@@ -0,0 +1,137 @@
+//! Backup store for deleted startup-folder files. Instead of being removed,
+//! a deleted shortcut or program is moved to `Backups\<timestamp>\` in the app
+//! data directory along with a note of where it came from, so it can be put
+//! back from the Restore dialog.
+
+use crate::app_data;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::path::{Path, PathBuf};
+
+const BACKUPS_DIR: &str = "Backups";
+/// File inside each backup folder holding the original full path.
+const ORIGIN_FILE: &str = "origin.txt";
+const STAMP_FORMAT: &str = "%Y%m%d_%H%M%S";
+
+/// One backed-up file.
+#[derive(Debug, Clone)]
+pub struct BackupItem {
+    /// Folder holding the backed-up file and its origin note.
+    pub dir: PathBuf,
+    /// Where the file was deleted from.
+    pub original_path: PathBuf,
+    pub deleted_at: Option<DateTime<Local>>,
+}
+
+impl BackupItem {
+    pub fn file_name(&self) -> String {
+        self.original_path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    fn stored_path(&self) -> PathBuf {
+        self.dir.join(self.original_path.file_name().unwrap_or_default())
+    }
+}
+
+/// Move `path` into the backup store. Returns the new location.
+pub fn backup_file(path: &Path) -> Result<PathBuf> {
+    let root = backups_root().context("App data directory is not available")?;
+    let file_name = path.file_name().context("Path has no file name")?;
+
+    // One folder per deletion; suffix a counter if two land in the same second
+    let stamp = Local::now().format(STAMP_FORMAT).to_string();
+    let mut dir = root.join(&stamp);
+    let mut n = 1;
+    while dir.exists() {
+        n += 1;
+        dir = root.join(format!("{}-{}", stamp, n));
+    }
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create backup folder {}", dir.display()))?;
+
+    // Record the origin first: if the app dies mid-move, the file is still
+    // listed for restore instead of sitting in an unlabeled folder
+    let dest = dir.join(file_name);
+    let recorded = std::fs::write(dir.join(ORIGIN_FILE), path.to_string_lossy().as_bytes())
+        .context("Failed to record the original location");
+    if let Err(e) = recorded {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+    if let Err(e) = move_file(path, &dest) {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e).with_context(|| format!("Failed to move {} to backup", path.display()));
+    }
+    Ok(dest)
+}
+
+/// List backed-up files, newest first.
+pub fn list() -> Vec<BackupItem> {
+    let Some(root) = backups_root() else {
+        return Vec::new();
+    };
+    let Ok(dirs) = std::fs::read_dir(&root) else {
+        return Vec::new();
+    };
+    let mut items: Vec<BackupItem> = dirs
+        .flatten()
+        .filter_map(|d| {
+            let dir = d.path();
+            let origin = std::fs::read_to_string(dir.join(ORIGIN_FILE)).ok()?;
+            let folder = d.file_name().to_string_lossy().into_owned();
+            let stamp = folder.split('-').next().unwrap_or_default();
+            let deleted_at = NaiveDateTime::parse_from_str(stamp, STAMP_FORMAT)
+                .ok()
+                .and_then(|dt| Local.from_local_datetime(&dt).single());
+            Some(BackupItem {
+                dir,
+                original_path: PathBuf::from(origin.trim()),
+                deleted_at,
+            })
+        })
+        .collect();
+    items.sort_by(|a, b| b.dir.cmp(&a.dir));
+    items
+}
+
+/// Move a backed-up file back to where it was deleted from.
+pub fn restore(item: &BackupItem) -> Result<()> {
+    if item.original_path.exists() {
+        anyhow::bail!("{} already exists", item.original_path.display());
+    }
+    if let Some(parent) = item.original_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    move_file(&item.stored_path(), &item.original_path)
+        .with_context(|| format!("Failed to restore {}", item.original_path.display()))?;
+    let _ = std::fs::remove_dir_all(&item.dir);
+    Ok(())
+}
+
+/// Permanently delete a backup.
+pub fn discard(item: &BackupItem) -> Result<()> {
+    std::fs::remove_dir_all(&item.dir)
+        .with_context(|| format!("Failed to delete backup {}", item.dir.display()))
+}
+
+fn backups_root() -> Option<PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(BACKUPS_DIR))
+}
+
+/// Rename, falling back to copy + delete when the backup store is on a
+/// different volume.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    if let Err(e) = std::fs::remove_file(from) {
+        let _ = std::fs::remove_file(to);
+        return Err(e);
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! Collector benchmark mode (`app-manager.exe --bench-collectors [runs]
+//! [--json]`, not in the UI). Runs each collector the given number of times
+//! (5 by default) one after another, and prints the min/avg/max time and the
+//! number of items it returned, as a table or as JSON for scripts
+//! (`build.ps1 bench` keeps a copy per build).
+//!
+//! Collectors run in sequence here, unlike a load, so each time is the
+//! collector's own rather than its share of a busy machine.
+
+use crate::collector;
+use crate::enrichment;
+use crate::execution_history;
+use crate::installed_apps;
+use crate::prefetch;
+use crate::processes;
+use crate::security_center;
+use crate::services;
+use crate::settings::Settings;
+use crate::skipped::SkippedItems;
+use std::time::{Duration, Instant};
+use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+pub const BENCH_FLAG: &str = "--bench-collectors";
+/// Print JSON instead of a table.
+pub const JSON_FLAG: &str = "--json";
+const DEFAULT_RUNS: usize = 5;
+
+/// Timings for one collector.
+struct Timing {
+    name: &'static str,
+    times: Vec<Duration>,
+    /// Items returned by the last run.
+    items: usize,
+}
+
+impl Timing {
+    fn min(&self) -> Duration {
+        self.times.iter().copied().min().unwrap_or_default()
+    }
+
+    fn max(&self) -> Duration {
+        self.times.iter().copied().max().unwrap_or_default()
+    }
+
+    fn avg(&self) -> Duration {
+        self.times.iter().sum::<Duration>() / self.times.len().max(1) as u32
+    }
+}
+
+/// Run the benchmark with the arguments after `--bench-collectors` and print
+/// the results to the console it was started from.
+pub fn run(args: &[String]) {
+    // Release builds have no console of their own
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+    let runs = args
+        .first()
+        .and_then(|a| a.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_RUNS);
+    let json = args.iter().any(|a| a == JSON_FLAG);
+    let scope = Settings::load().scan_scope;
+
+    let mut timings = vec![
+        time("Installed Apps", runs, || installed_apps::collect_installed_apps().len()),
+        time("Startup Apps", runs, || collector::collect_all_entries(&scope).entries.len()),
+        time("Services", runs, || {
+            services::collect_services(&mut SkippedItems::default()).map_or(0, |s| s.len())
+        }),
+        time("Processes", runs, || processes::collect_processes().len()),
+        time("Execution History", runs, || execution_history::collect_execution_history().records.len()),
+        time("Security", runs, || security_center::collect_security_status().len()),
+        time("Prefetch", runs, || {
+            let updates = prefetch::load_async(prefetch::TIME_BUDGET);
+            updates
+                .iter()
+                .find_map(|update| match update {
+                    prefetch::Update::Finished { executables, .. } => Some(executables),
+                    prefetch::Update::Batch(_) => None,
+                })
+                .unwrap_or(0)
+        }),
+    ];
+    // Enrichment caches by file, so the first run is the cold one
+    let mut entries = collector::collect_all_entries(&scope).entries;
+    entries.extend(services::collect_services(&mut SkippedItems::default()).unwrap_or_default());
+    let mut procs = processes::collect_processes();
+    timings.push(time("Enrichment", runs, || {
+        enrichment::enrich_entries(&mut entries);
+        enrichment::enrich_processes(&mut procs);
+        entries.len() + procs.len()
+    }));
+
+    if json {
+        println!("{}", to_json(&timings, runs));
+    } else {
+        print_table(&timings, runs);
+    }
+}
+
+fn time(name: &'static str, runs: usize, mut collect: impl FnMut() -> usize) -> Timing {
+    let mut times = Vec::with_capacity(runs);
+    let mut items = 0;
+    for _ in 0..runs {
+        let start = Instant::now();
+        items = collect();
+        times.push(start.elapsed());
+    }
+    Timing { name, times, items }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn print_table(timings: &[Timing], runs: usize) {
+    println!(
+        "App Manager {} collector benchmark, {} runs{}",
+        env!("CARGO_PKG_VERSION"),
+        runs,
+        if prefetch::is_accessible() { " (administrator)" } else { "" }
+    );
+    println!();
+    println!("{:<20}{:>12}{:>12}{:>12}{:>10}", "Collector", "Min ms", "Avg ms", "Max ms", "Items");
+    for t in timings {
+        println!(
+            "{:<20}{:>12.1}{:>12.1}{:>12.1}{:>10}",
+            t.name,
+            ms(t.min()),
+            ms(t.avg()),
+            ms(t.max()),
+            t.items
+        );
+    }
+}
+
+/// Collector names are plain ASCII, so nothing needs escaping.
+fn to_json(timings: &[Timing], runs: usize) -> String {
+    let collectors: Vec<String> = timings
+        .iter()
+        .map(|t| {
+            format!(
+                "    {{\"name\": \"{}\", \"min_ms\": {:.3}, \"avg_ms\": {:.3}, \"max_ms\": {:.3}, \"items\": {}}}",
+                t.name,
+                ms(t.min()),
+                ms(t.avg()),
+                ms(t.max()),
+                t.items
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"version\": \"{}\",\n  \"runs\": {},\n  \"administrator\": {},\n  \"collectors\": [\n{}\n  ]\n}}",
+        env!("CARGO_PKG_VERSION"),
+        runs,
+        prefetch::is_accessible(),
+        collectors.join(",\n")
+    )
+}
@@ -0,0 +1,106 @@
+//! Heuristic categories for the startup entries and services people disable
+//! most: updaters, telemetry and crash reporters, and helpers (tray icons,
+//! launchers, agents). Matching is by name and executable only, so the
+//! grouping is a suggestion to review, not a verdict.
+
+use crate::cmdline;
+use crate::models::{Source, StartupEntry};
+use crate::services;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Updater,
+    Telemetry,
+    Helper,
+}
+
+impl Category {
+    pub const ALL: [Category; 3] = [Category::Updater, Category::Telemetry, Category::Helper];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Category::Updater => "Updaters",
+            Category::Telemetry => "Telemetry and crash reporting",
+            Category::Helper => "Helpers",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Category::Updater => {
+                "Check for and install updates in the background. The app can usually still update \
+                 itself when opened, or from its own Check for Updates menu"
+            }
+            Category::Telemetry => "Send usage data or crash reports to the vendor. The app works without them",
+            Category::Helper => {
+                "Tray icons, launchers, and agents. Some provide features (hotkeys, sync, device \
+                 control), so they are not selected by default"
+            }
+        }
+    }
+
+    /// Whether entries in this category start out selected for disabling.
+    pub fn preselected(self) -> bool {
+        self != Category::Helper
+    }
+}
+
+/// Substrings that put an entry in a category, checked in this order so a
+/// "crash reporter helper" counts as telemetry.
+const PATTERNS: &[(Category, &[&str])] = &[
+    (
+        Category::Telemetry,
+        &[
+            "telemetry",
+            "crashhandler",
+            "crashpad",
+            "crashreport",
+            "crash_report",
+            "crashsvc",
+            "bugreport",
+            "errorreport",
+            "usagestats",
+            "analytics",
+            "metrics",
+            "ceip",
+            "feedback",
+        ],
+    ),
+    (
+        Category::Updater,
+        &["update", "autoupd", "maintenanceservice", "patcher", "swupd"],
+    ),
+    (
+        Category::Helper,
+        &["helper", "tray", "launcher", "agent", "notifier", "assistant"],
+    ),
+];
+
+/// The category an entry looks like it belongs to, and the pattern that
+/// matched. Windows' own services and scheduled tasks are never classified;
+/// disabling Windows Update this way would do more harm than good.
+pub fn classify(entry: &StartupEntry) -> Option<(Category, &'static str)> {
+    match &entry.source {
+        Source::RegistryRunOnce { .. } | Source::Ifeo { .. } => return None,
+        Source::Service { .. } if services::is_microsoft_service(entry) => return None,
+        Source::TaskScheduler { task_path } if task_path.to_lowercase().starts_with(r"\microsoft\windows\") => {
+            return None
+        }
+        _ => {}
+    }
+    let target = cmdline::target_path(&entry.command);
+    let file_name = target.rsplit(['\\', '/']).next().unwrap_or(&target);
+    let mut text = format!("{} {}", entry.name, file_name);
+    if let Source::Service { service_name, .. } = &entry.source {
+        text.push(' ');
+        text.push_str(service_name);
+    }
+    // "Google Update" and "GoogleUpdate.exe" alike
+    let text = text.to_lowercase().replace([' ', '-'], "");
+    PATTERNS.iter().find_map(|(category, patterns)| {
+        patterns
+            .iter()
+            .find(|p| text.contains(*p))
+            .map(|p| (*category, *p))
+    })
+}
@@ -0,0 +1,103 @@
+//! Clean boot: the msconfig troubleshooting workflow of starting Windows
+//! with only Microsoft services and no startup items, then turning things
+//! back on in halves until the culprit is found.
+//!
+//! Before anything is disabled, a revert file (a cleanup plan of Enable
+//! steps, see [`crate::plan`]) is saved under `Clean Boot\` in the app data
+//! directory, and optionally a System Restore point is created. Only
+//! automatic services are disabled: enabling a service sets it back to
+//! automatic, so a manual one couldn't be restored as it was.
+
+use crate::app_data;
+use crate::plan::CleanupPlan;
+use anyhow::{Context, Result};
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+const CLEAN_BOOT_DIR: &str = "Clean Boot";
+const REVERT_FILE_PREFIX: &str = "clean-boot-revert-";
+
+/// Seconds between scheduling the restart and Windows going down, long
+/// enough to save work or run `shutdown /a`.
+pub const RESTART_DELAY_SECS: u32 = 30;
+
+/// Description given to the restore point.
+pub const RESTORE_POINT_DESCRIPTION: &str = "App Manager clean boot";
+
+/// Create a System Restore point. Needs administrator rights and System
+/// Protection turned on for the system drive; Windows skips it (without an
+/// error) if another restore point was made in the last 24 hours.
+pub fn create_restore_point() -> Result<()> {
+    let script = format!(
+        "Checkpoint-Computer -Description '{}' -RestorePointType MODIFY_SETTINGS -ErrorAction Stop",
+        RESTORE_POINT_DESCRIPTION
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .context("Failed to run PowerShell")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().map(str::trim).find(|l| !l.is_empty()).unwrap_or("unknown error");
+        anyhow::bail!("Failed to create a restore point: {}", message);
+    }
+    Ok(())
+}
+
+/// Save the plan that undoes a clean boot. Returns where it was written.
+pub fn save_revert_plan(plan: &CleanupPlan) -> Result<PathBuf> {
+    let dir = clean_boot_dir().context("No app data directory")?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let path = dir.join(format!("{}{}.txt", REVERT_FILE_PREFIX, stamp));
+    plan.save(&path)?;
+    Ok(path)
+}
+
+/// The most recently saved revert file, if any.
+pub fn latest_revert_plan() -> Option<PathBuf> {
+    std::fs::read_dir(clean_boot_dir()?)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(REVERT_FILE_PREFIX) && n.ends_with(".txt"))
+        })
+        // The timestamp in the name sorts in time order
+        .max()
+}
+
+/// Schedule a restart in [`RESTART_DELAY_SECS`] seconds.
+pub fn restart() -> Result<()> {
+    let output = Command::new("shutdown")
+        .args(restart_args())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .context("Failed to run shutdown")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to schedule the restart: {}", stderr.trim());
+    }
+    Ok(())
+}
+
+/// The arguments [`restart`] passes to `shutdown`.
+pub fn restart_args() -> Vec<String> {
+    vec![
+        "/r".to_string(),
+        "/t".to_string(),
+        RESTART_DELAY_SECS.to_string(),
+        "/c".to_string(),
+        "App Manager is restarting Windows for a clean boot.".to_string(),
+    ]
+}
+
+fn clean_boot_dir() -> Option<PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(CLEAN_BOOT_DIR))
+}
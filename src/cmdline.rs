@@ -0,0 +1,443 @@
+//! Command-line parsing shared by actions, version-info lookups, and the GUI.
+//!
+//! Windows stores commands in many slightly different shapes (quoted paths,
+//! unquoted paths with spaces, `%VAR%` prefixes, `rundll32` invocations), so
+//! all splitting goes through this module to keep the behavior consistent.
+
+use crate::models::ScriptPayload;
+use std::path::Path;
+
+/// File extensions that mark the end of an unquoted executable path.
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    ".exe", ".com", ".bat", ".cmd", ".dll", ".sys", ".ocx", ".scr", ".cpl",
+];
+
+/// Split a command string into (executable, raw argument string).
+///
+/// Handles these forms:
+/// 1. Quoted: `"C:\Program Files\app.exe" /S` → split at closing quote
+/// 2. Unquoted with a known extension: `C:\Program Files\app.exe /S` → split after `.exe`
+/// 3. Env-var-only: `%ComSpec%` → the whole variable is the executable
+/// 4. Fallback: split on first whitespace
+pub fn split(command: &str) -> (String, String) {
+    let cmd = command.trim();
+    if cmd.is_empty() {
+        return (String::new(), String::new());
+    }
+
+    // Case 1: Quoted executable
+    if let Some(stripped) = cmd.strip_prefix('"') {
+        return match stripped.find('"') {
+            Some(end) => (
+                stripped[..end].to_string(),
+                stripped[end + 1..].trim().to_string(),
+            ),
+            // Unterminated quote: treat the rest as the path
+            None => (stripped.to_string(), String::new()),
+        };
+    }
+
+    // Case 2: Find the first known extension followed by whitespace or end of
+    // string — handles unquoted paths with spaces like
+    // C:\Program Files (x86)\App\uninstall.exe /silent
+    // (ASCII lowercasing keeps byte offsets valid for slicing `cmd`)
+    let lower = cmd.to_ascii_lowercase();
+    let mut best: Option<usize> = None;
+    for ext in EXECUTABLE_EXTENSIONS {
+        let mut from = 0;
+        while let Some(pos) = lower[from..].find(ext) {
+            let end = from + pos + ext.len();
+            let at_boundary = lower[end..]
+                .chars()
+                .next()
+                .is_none_or(|c| c.is_whitespace() || c == ',');
+            if at_boundary {
+                if best.is_none_or(|b| end < b) {
+                    best = Some(end);
+                }
+                break;
+            }
+            from = end;
+        }
+    }
+    if let Some(end) = best {
+        // Only trust the extension boundary if any whitespace comes after the
+        // first path separator (i.e. it belongs to a directory name, as in
+        // "C:\Program Files\..." rather than "cmd /c C:\x\run.bat").
+        let exe = &cmd[..end];
+        let first_space = exe.find(char::is_whitespace);
+        let first_sep = exe.find('\\');
+        let trusted = match (first_space, first_sep) {
+            (None, _) => true,
+            (Some(space), Some(sep)) => sep < space,
+            (Some(_), None) => false,
+        };
+        if trusted {
+            return (exe.to_string(), cmd[end..].trim().to_string());
+        }
+    }
+
+    // Case 3/4: Split on first whitespace (env-var-only commands have none)
+    match cmd.find(char::is_whitespace) {
+        Some(pos) => (cmd[..pos].to_string(), cmd[pos..].trim().to_string()),
+        None => (cmd.to_string(), String::new()),
+    }
+}
+
+/// Split a raw argument string into individual arguments.
+///
+/// Follows the `CommandLineToArgvW` rules closely enough for startup commands:
+/// whitespace separates arguments outside quotes, `\"` is a literal quote, and
+/// `""` inside a quoted section is a literal quote.
+pub fn split_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+    let mut chars = args.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                // Count the run of backslashes
+                let mut backslashes = 1;
+                while chars.peek() == Some(&'\\') {
+                    chars.next();
+                    backslashes += 1;
+                }
+                if chars.peek() == Some(&'"') {
+                    // 2n backslashes + quote → n backslashes, quote toggles
+                    // 2n+1 backslashes + quote → n backslashes + literal quote
+                    current.extend(std::iter::repeat_n('\\', backslashes / 2));
+                    if backslashes % 2 == 1 {
+                        chars.next();
+                        current.push('"');
+                    }
+                } else {
+                    current.extend(std::iter::repeat_n('\\', backslashes));
+                }
+                has_token = true;
+            }
+            '"' => {
+                if in_quotes && chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.push('"');
+                } else {
+                    in_quotes = !in_quotes;
+                }
+                has_token = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    result.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            _ => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        result.push(current);
+    }
+    result
+}
+
+/// Parse a command string into (executable, individual arguments).
+pub fn parse(command: &str) -> (String, Vec<String>) {
+    let (exe, args) = split(command);
+    (exe, split_args(&args))
+}
+
+/// Join an executable and a raw argument string back into a command,
+/// quoting the executable when it contains whitespace, before or after
+/// environment variables are expanded (`%ProgramFiles%\App\app.exe`).
+pub fn join(exe: &str, args: &str) -> String {
+    let needs_quotes = exe.contains(char::is_whitespace) || expand_env_vars(exe).contains(char::is_whitespace);
+    let exe = if needs_quotes && !exe.starts_with('"') {
+        format!("\"{}\"", exe)
+    } else {
+        exe.to_string()
+    };
+    let args = args.trim();
+    if args.is_empty() {
+        exe
+    } else {
+        format!("{} {}", exe, args)
+    }
+}
+
+/// Return the executable path of a command with environment variables expanded.
+pub fn executable_path(command: &str) -> String {
+    let (exe, _) = split(command);
+    expand_env_vars(&exe)
+}
+
+/// For `rundll32.exe <dll>,<entry>` commands, return the DLL path (expanded).
+pub fn rundll32_target(command: &str) -> Option<String> {
+    let (exe, args) = split(command);
+    let exe_name = Path::new(&expand_env_vars(&exe))
+        .file_name()?
+        .to_str()?
+        .to_lowercase();
+    if exe_name != "rundll32.exe" && exe_name != "rundll32" {
+        return None;
+    }
+
+    let args = args.trim();
+    let dll = if let Some(stripped) = args.strip_prefix('"') {
+        stripped.split('"').next()?
+    } else {
+        args.split([',', ' ', '\t']).next()?
+    };
+    let dll = dll.trim();
+    if dll.is_empty() {
+        None
+    } else {
+        Some(expand_env_vars(dll))
+    }
+}
+
+/// The file that actually carries the code for a command: the DLL for
+/// `rundll32` invocations, otherwise the executable itself.
+pub fn target_path(command: &str) -> String {
+    rundll32_target(command).unwrap_or_else(|| executable_path(command))
+}
+
+/// For commands that run a script host (`wscript`, `cscript`, `powershell`,
+/// `pwsh`, `mshta`, `rundll32`), extract the script, inline command, or DLL
+/// that is the effective payload. PowerShell `-EncodedCommand` text is decoded.
+pub fn script_payload(command: &str) -> Option<ScriptPayload> {
+    let (exe, args) = parse(command);
+    let host = Path::new(&expand_env_vars(&exe))
+        .file_stem()?
+        .to_str()?
+        .to_lowercase();
+
+    let payload = match host.as_str() {
+        // Host options start with `//` (e.g. //B //Nologo); the first other
+        // argument is the script
+        "wscript" | "cscript" => args.iter().find(|a| !a.starts_with("//")).cloned(),
+        "powershell" | "pwsh" => powershell_payload(&args),
+        // mshta takes an .hta path, a URL, or inline vbscript:/javascript:
+        "mshta" => (!args.is_empty()).then(|| args.join(" ")),
+        "rundll32" => rundll32_target(command),
+        _ => return None,
+    }?;
+
+    let payload = payload.trim().to_string();
+    if payload.is_empty() {
+        return None;
+    }
+    Some(ScriptPayload { host, payload })
+}
+
+/// Find the `-File`, `-Command`, or `-EncodedCommand` payload of a PowerShell
+/// command line. Parameter names may be abbreviated to any unique prefix.
+fn powershell_payload(args: &[String]) -> Option<String> {
+    // Parameters that consume the following argument
+    const VALUE_PARAMS: &[&str] = &[
+        "executionpolicy", "windowstyle", "configurationname", "inputformat",
+        "outputformat", "psconsolefile", "version", "workingdirectory",
+        "settingsfile", "custompipename", "encodedarguments",
+    ];
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        let Some(name) = arg.strip_prefix('-').or_else(|| arg.strip_prefix('/')) else {
+            // First positional argument: a command (or file, for pwsh)
+            return Some(args[i..].join(" "));
+        };
+        let name = name.to_lowercase();
+        if name.is_empty() {
+            // `-` reads commands from stdin
+            return None;
+        }
+        if "file".starts_with(&name) {
+            return args.get(i + 1).cloned();
+        }
+        if name == "ec" || name == "e" || (name.len() >= 2 && "encodedcommand".starts_with(&name)) {
+            return args.get(i + 1).map(|enc| {
+                decode_encoded_command(enc).unwrap_or_else(|| enc.clone())
+            });
+        }
+        if name == "c" || (name.len() >= 2 && "command".starts_with(&name)) {
+            return Some(args[i + 1..].join(" "));
+        }
+        if name == "ep" || VALUE_PARAMS.iter().any(|p| p.starts_with(&name)) {
+            i += 1;
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Decode a PowerShell `-EncodedCommand` argument (base64 of UTF-16LE text).
+fn decode_encoded_command(encoded: &str) -> Option<String> {
+    let bytes = decode_base64(encoded)?;
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(s.len() * 3 / 4);
+    let mut buf = 0u32;
+    let mut bits = 0;
+    for c in s.trim().bytes().take_while(|&c| c != b'=') {
+        let v = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buf = (buf << 6) | v as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buf >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// True when a command points at an absolute path that no longer exists.
+/// Bare names (resolved via `PATH`) and unexpanded variables are not judged.
+pub fn is_target_missing(command: &str) -> bool {
+    let target = target_path(command);
+    if target.is_empty() || target.contains('%') {
+        return false;
+    }
+    let path = Path::new(&target);
+    path.is_absolute() && !path.exists()
+}
+
+/// Whether `s` contains a `%VARIABLE%` reference, which only a REG_EXPAND_SZ
+/// registry value expands.
+pub fn has_env_vars(s: &str) -> bool {
+    // Every other part sits between a pair of %
+    let mut parts = s.split('%').skip(1).peekable();
+    while let Some(name) = parts.next() {
+        if parts.peek().is_none() {
+            return false;
+        }
+        if !name.is_empty() && !name.contains(char::is_whitespace) {
+            return true;
+        }
+        parts.next();
+    }
+    false
+}
+
+/// Expand `%VAR%` references using the current process environment.
+/// Unknown variables are left as-is.
+pub fn expand_env_vars(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('%') {
+            Some(end) => {
+                let var_name = &after[..end];
+                match std::env::var(var_name) {
+                    Ok(value) if !var_name.is_empty() => result.push_str(&value),
+                    _ => {
+                        result.push('%');
+                        result.push_str(var_name);
+                        result.push('%');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_quoted_path() {
+        assert_eq!(
+            split(r#""C:\Program Files\App\app.exe" /S --tray"#),
+            (r"C:\Program Files\App\app.exe".to_string(), "/S --tray".to_string())
+        );
+        assert_eq!(split(r#""C:\App\app.exe"#), (r"C:\App\app.exe".to_string(), String::new()));
+    }
+
+    #[test]
+    fn split_unquoted_path_with_spaces() {
+        assert_eq!(
+            split(r"C:\Program Files (x86)\App\uninstall.exe /silent"),
+            (r"C:\Program Files (x86)\App\uninstall.exe".to_string(), "/silent".to_string())
+        );
+        assert_eq!(
+            split(r"C:\Program Files\App\App.EXE"),
+            (r"C:\Program Files\App\App.EXE".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn split_rundll32() {
+        let command = r"rundll32.exe C:\Windows\System32\shell32.dll,Control_RunDLL";
+        assert_eq!(
+            split(command),
+            ("rundll32.exe".to_string(), r"C:\Windows\System32\shell32.dll,Control_RunDLL".to_string())
+        );
+        assert_eq!(rundll32_target(command), Some(r"C:\Windows\System32\shell32.dll".to_string()));
+        assert_eq!(
+            rundll32_target(r#"rundll32 "C:\Program Files\App\hook.dll",Start"#),
+            Some(r"C:\Program Files\App\hook.dll".to_string())
+        );
+    }
+
+    #[test]
+    fn split_cmd_c() {
+        assert_eq!(
+            split(r"cmd /c C:\Tools\run.bat"),
+            ("cmd".to_string(), r"/c C:\Tools\run.bat".to_string())
+        );
+        assert_eq!(
+            split(r"C:\Windows\System32\cmd.exe /c start app.exe"),
+            (r"C:\Windows\System32\cmd.exe".to_string(), "/c start app.exe".to_string())
+        );
+    }
+
+    #[test]
+    fn split_non_ascii_path() {
+        // Characters whose lowercase form has a different UTF-8 length
+        assert_eq!(
+            split(r"C:\Users\İlker\App Data\app.exe --tray"),
+            (r"C:\Users\İlker\App Data\app.exe".to_string(), "--tray".to_string())
+        );
+        assert_eq!(
+            split(r"C:\Program Files\ẞtudio\app.exe /S"),
+            (r"C:\Program Files\ẞtudio\app.exe".to_string(), "/S".to_string())
+        );
+    }
+
+    #[test]
+    fn split_env_var_and_bare_commands() {
+        assert_eq!(split("%ComSpec%"), ("%ComSpec%".to_string(), String::new()));
+        assert_eq!(split("  notepad  file.txt "), ("notepad".to_string(), "file.txt".to_string()));
+        assert_eq!(split(""), (String::new(), String::new()));
+    }
+}
@@ -1,11 +1,18 @@
+use crate::cmdline;
+use crate::drives;
+use crate::last_ran;
 use crate::models::*;
+use crate::parallel;
 use crate::prefetch;
 use crate::process;
 use crate::registry;
+use crate::settings::ScanScope;
+use crate::skipped::{SkippedItems, SkippedKind};
 use crate::startup_folders;
 use crate::status;
 use crate::task_scheduler;
 use crate::version_info;
+use std::cell::Cell;
 use std::collections::HashSet;
 
 const NONADMIN_PATHS_FILE: &str = "app-manager-nonadmin.txt";
@@ -13,6 +20,51 @@ const NONADMIN_PATHS_FILE: &str = "app-manager-nonadmin.txt";
 pub struct CollectionResult {
     pub entries: Vec<StartupEntry>,
     pub is_admin: bool,
+    /// What each scanner did, for the scan-summary tooltip.
+    pub scanners: Vec<ScannerReport>,
+    /// Keys, folders, and tasks that were found but couldn't be read.
+    pub skipped: SkippedItems,
+}
+
+/// Outcome of one scanner in a startup scan.
+pub struct ScannerReport {
+    pub name: &'static str,
+    pub outcome: ScanOutcome,
+}
+
+#[derive(Clone, Copy)]
+pub enum ScanOutcome {
+    Found(usize),
+    /// Turned off in the scan scope settings.
+    Skipped,
+    /// In scope but not readable (e.g. Prefetch without admin rights).
+    Unavailable,
+    /// Turned off in Windows (e.g. Prefetch disabled by policy).
+    TurnedOff,
+    /// Still reading in the background.
+    InProgress,
+    /// Stopped at its time budget after finding this many.
+    TimedOut(usize),
+}
+
+/// Name of the Prefetch scanner's report, updated when the background read
+/// finishes.
+pub const PREFETCH_SCANNER: &str = "Prefetch (Last Ran)";
+
+/// One line per scanner, e.g. "Task Scheduler: 14 entries".
+pub fn summary(scanners: &[ScannerReport]) -> String {
+    scanners
+        .iter()
+        .map(|s| match s.outcome {
+            ScanOutcome::Found(n) => format!("{}: {} entries", s.name, n),
+            ScanOutcome::Skipped => format!("{}: skipped", s.name),
+            ScanOutcome::Unavailable => format!("{}: unavailable", s.name),
+            ScanOutcome::TurnedOff => format!("{}: turned off in Windows", s.name),
+            ScanOutcome::InProgress => format!("{}: reading...", s.name),
+            ScanOutcome::TimedOut(n) => format!("{}: {} entries (stopped at time limit)", s.name, n),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
 /// Save the task paths visible to the current (non-admin) user.
@@ -37,50 +89,142 @@ fn load_nonadmin_task_paths() -> Option<HashSet<String>> {
     Some(content.lines().map(|s| s.to_string()).collect())
 }
 
-pub fn collect_all_entries() -> CollectionResult {
-    // Phase 1: Collect raw entries from all sources
+pub fn collect_all_entries(scope: &ScanScope) -> CollectionResult {
+    // Phase 1: Collect raw entries from the sources in scope
     let mut entries: Vec<StartupEntry> = Vec::new();
+    let mut scanners = Vec::new();
+    let mut skipped = SkippedItems::default();
 
-    entries.extend(registry::collect_registry_entries());
-    entries.extend(startup_folders::collect_startup_folder_entries());
-
-    match task_scheduler::collect_task_scheduler_entries() {
-        Ok(tasks) => entries.extend(tasks),
-        Err(_) => {}
+    let mut run = |name: &'static str, enabled: bool, collect: &dyn Fn(&mut SkippedItems) -> Vec<StartupEntry>| {
+        let outcome = if enabled {
+            let found = collect(&mut skipped);
+            let n = found.len();
+            entries.extend(found);
+            ScanOutcome::Found(n)
+        } else {
+            ScanOutcome::Skipped
+        };
+        scanners.push(ScannerReport { name, outcome });
+    };
+    run("Registry Run keys", scope.registry_run, &registry::collect_registry_entries);
+    run(
+        "Startup folders",
+        scope.startup_folders,
+        &startup_folders::collect_startup_folder_entries,
+    );
+    let task_scheduler_failed = Cell::new(false);
+    run("Task Scheduler", scope.task_scheduler, &|skipped| {
+        task_scheduler::collect_task_scheduler_entries(skipped).unwrap_or_else(|e| {
+            skipped.add(SkippedKind::TaskFolder, "\\", e.to_string());
+            task_scheduler_failed.set(true);
+            Vec::new()
+        })
+    });
+    run("IFEO debuggers", scope.ifeo, &registry::collect_ifeo_entries);
+    // The service couldn't be read at all, which isn't "0 entries"; the
+    // reason is listed with the skipped items
+    if task_scheduler_failed.get() {
+        if let Some(report) = scanners.iter_mut().find(|s| s.name == "Task Scheduler") {
+            report.outcome = ScanOutcome::Unavailable;
+        }
     }
 
     // Phase 2: Build enrichment caches
     let approvals = status::load_all_approvals();
     let process_snapshot = process::ProcessSnapshot::new();
-    let prefetch_cache = prefetch::PrefetchCache::new();
+    // Prefetch itself is read in the background (see `prefetch::load_async`)
+    // and fills in last-ran times after the entries are shown
+    let prefetch_accessible = prefetch::is_accessible();
+    scanners.push(ScannerReport {
+        name: PREFETCH_SCANNER,
+        outcome: if !scope.prefetch {
+            ScanOutcome::Skipped
+        } else if prefetch_accessible {
+            ScanOutcome::InProgress
+        } else {
+            ScanOutcome::Unavailable
+        },
+    });
 
-    let is_admin = prefetch_cache.accessible;
+    let is_admin = prefetch_accessible;
+
+    // Without usable Prefetch data, fall back to UserAssist and the
+    // Background Activity Moderator
+    let prefetch_disabled = scope.prefetch && prefetch::is_disabled();
+    let alternatives = (scope.prefetch && (prefetch_disabled || !prefetch_accessible))
+        .then(last_ran::AlternativeSources::load);
+    if prefetch_disabled {
+        if let Some(report) = scanners.last_mut() {
+            report.outcome = ScanOutcome::TurnedOff;
+        }
+    }
+    if let Some(alt) = &alternatives {
+        scanners.push(ScannerReport {
+            name: "UserAssist (Last Ran)",
+            outcome: ScanOutcome::Found(alt.user_assist_len()),
+        });
+        scanners.push(ScannerReport {
+            name: "Background Activity (Last Ran)",
+            outcome: if alt.background_activity_len() > 0 {
+                ScanOutcome::Found(alt.background_activity_len())
+            } else {
+                ScanOutcome::Unavailable
+            },
+        });
+    }
 
     // Get current username for entries that run as the logged-in user
     let current_user = std::env::var("USERNAME").unwrap_or_default();
 
+    // Version info and file checks are the slow part; read them in parallel
+    let file_info = parallel::map(&entries, |e| {
+        (
+            version_info::get_product_name(&e.command).unwrap_or_default(),
+            version_info::get_company_name(&e.command).unwrap_or_default(),
+            drives::check_target(&e.command),
+            version_info::get_bitness(&e.command),
+        )
+    });
+
     // Phase 3: Enrich each entry
-    for entry in &mut entries {
-        // Set runs_as for non-task-scheduler entries (they run as current user)
-        if !matches!(entry.source, Source::TaskScheduler { .. }) {
-            entry.runs_as = current_user.clone();
-        } else if entry.runs_as.is_empty() {
+    for (entry, (product_name, company_name, (target_missing, volatile_drive), bitness)) in
+        entries.iter_mut().zip(file_info)
+    {
+        // Entries without their own account (everything but tasks and other
+        // users' startup folders) run as the current user
+        if entry.runs_as.is_empty() {
             entry.runs_as = current_user.clone();
         }
-        // Enabled/disabled from StartupApproved (skip Task Scheduler, already set)
-        if !matches!(entry.source, Source::TaskScheduler { .. }) {
+        // Enabled/disabled from StartupApproved (skip Task Scheduler and other
+        // users' startup folders, already set)
+        let approval_known = matches!(
+            entry.source,
+            Source::TaskScheduler { .. } | Source::StartupFolder { other_user: Some(_), .. }
+        );
+        if !approval_known {
             let (enabled, disabled_ts) =
                 status::get_approval_status(&entry.name, &entry.source, &approvals);
             entry.enabled = enabled;
 
             // Use disabled timestamp as last_ran fallback if no better source
-            if entry.last_ran.is_none() {
+            if entry.last_ran.is_none() && disabled_ts.is_some() {
                 entry.last_ran = disabled_ts;
+                entry.last_ran_source = Some(LastRanSource::DisabledTime);
             }
         }
 
         // Product name from PE version info
-        entry.product_name = version_info::get_product_name(&entry.command).unwrap_or_default();
+        entry.product_name = product_name;
+        entry.company_name = company_name;
+        entry.target_missing = target_missing;
+        entry.volatile_drive = volatile_drive;
+        entry.script_payload = cmdline::script_payload(&entry.command);
+        // Wow6432Node entries are read by 32-bit programs, whatever they launch
+        entry.bitness = if entry.source.is_wow64_registry() {
+            Some(Bitness::Bit32)
+        } else {
+            bitness
+        };
 
         // Running/stopped
         if let Some(exe) = entry.exe_name() {
@@ -90,14 +234,20 @@ pub fn collect_all_entries() -> CollectionResult {
                 // Use process start time as last_ran (most accurate when running)
                 if let Some(start) = process_snapshot.start_time(&exe) {
                     entry.last_ran = Some(start);
+                    entry.last_ran_source = Some(LastRanSource::ProcessStart);
                 }
             } else {
                 entry.run_state = RunState::Stopped;
 
-                // Try prefetch for last_ran if we don't already have a time
+                // Try the Prefetch fallbacks for last_ran if we don't
+                // already have a time; Prefetch data arrives later
                 if entry.last_ran.is_none() {
-                    let upper_exe = exe.to_uppercase();
-                    entry.last_ran = prefetch_cache.last_ran(&upper_exe);
+                    if let Some((dt, source)) =
+                        alternatives.as_ref().and_then(|alt| alt.last_ran(&exe.to_uppercase()))
+                    {
+                        entry.last_ran = Some(dt);
+                        entry.last_ran_source = Some(source);
+                    }
                 }
             }
         }
@@ -136,5 +286,10 @@ pub fn collect_all_entries() -> CollectionResult {
             .then(a.name.to_lowercase().cmp(&b.name.to_lowercase()))
     });
 
-    CollectionResult { entries, is_admin }
+    CollectionResult {
+        entries,
+        is_admin,
+        scanners,
+        skipped,
+    }
 }
@@ -0,0 +1,219 @@
+//! TCP and UDP endpoints owned by a process (the same tables `netstat -ano`
+//! reads), and closing individual IPv4 TCP connections with `SetTcpEntry`.
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use windows::Win32::NetworkManagement::IpHelper::{
+    GetExtendedTcpTable, GetExtendedUdpTable, SetTcpEntry, MIB_TCP6ROW_OWNER_PID,
+    MIB_TCPROW_LH, MIB_TCPROW_LH_0, MIB_TCPROW_OWNER_PID, MIB_TCP_STATE_DELETE_TCB,
+    MIB_UDP6ROW_OWNER_PID, MIB_UDPROW_OWNER_PID, TCP_TABLE_OWNER_PID_ALL, UDP_TABLE_OWNER_PID,
+};
+
+// Address families (ws2def.h)
+const AF_INET: u32 = 2;
+const AF_INET6: u32 = 23;
+
+const ERROR_INSUFFICIENT_BUFFER: u32 = 122;
+const ERROR_ACCESS_DENIED: u32 = 5;
+// SetTcpEntry reports missing privileges with this code on Vista and later
+const ERROR_MR_MID_NOT_FOUND: u32 = 317;
+
+// MIB_TCP_STATE values
+const TCP_STATE_LISTEN: u32 = 2;
+const TCP_STATE_ESTABLISHED: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Protocol::Tcp => write!(f, "TCP"),
+            Protocol::Udp => write!(f, "UDP"),
+        }
+    }
+}
+
+/// One TCP connection/listener or UDP endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    /// Remote endpoint (TCP only).
+    pub remote: Option<SocketAddr>,
+    /// Raw `MIB_TCP_STATE` value (TCP only).
+    pub state: Option<u32>,
+}
+
+impl Connection {
+    pub fn state_name(&self) -> &'static str {
+        match self.state {
+            None => "",
+            Some(1) => "Closed",
+            Some(TCP_STATE_LISTEN) => "Listening",
+            Some(3) => "SYN Sent",
+            Some(4) => "SYN Received",
+            Some(TCP_STATE_ESTABLISHED) => "Established",
+            Some(6) => "FIN Wait 1",
+            Some(7) => "FIN Wait 2",
+            Some(8) => "Close Wait",
+            Some(9) => "Closing",
+            Some(10) => "Last ACK",
+            Some(11) => "Time Wait",
+            Some(_) => "Unknown",
+        }
+    }
+
+    /// Only established IPv4 TCP connections can be closed; Windows has no
+    /// equivalent of `SetTcpEntry` for IPv6 or for listeners.
+    pub fn can_close(&self) -> bool {
+        self.protocol == Protocol::Tcp
+            && self.local.is_ipv4()
+            && self.state == Some(TCP_STATE_ESTABLISHED)
+    }
+}
+
+/// List the TCP and UDP endpoints (IPv4 and IPv6) owned by `pid`, sorted by
+/// protocol and local port.
+pub fn for_process(pid: u32) -> Vec<Connection> {
+    let mut result = Vec::new();
+
+    if let Some(buf) = read_table(|b, s| unsafe {
+        GetExtendedTcpTable(b, s, false, AF_INET, TCP_TABLE_OWNER_PID_ALL, 0)
+    }) {
+        for row in table_rows::<MIB_TCPROW_OWNER_PID>(&buf) {
+            if row.dwOwningPid == pid {
+                result.push(Connection {
+                    protocol: Protocol::Tcp,
+                    local: v4(row.dwLocalAddr, row.dwLocalPort),
+                    remote: Some(v4(row.dwRemoteAddr, row.dwRemotePort)),
+                    state: Some(row.dwState),
+                });
+            }
+        }
+    }
+    if let Some(buf) = read_table(|b, s| unsafe {
+        GetExtendedTcpTable(b, s, false, AF_INET6, TCP_TABLE_OWNER_PID_ALL, 0)
+    }) {
+        for row in table_rows::<MIB_TCP6ROW_OWNER_PID>(&buf) {
+            if row.dwOwningPid == pid {
+                result.push(Connection {
+                    protocol: Protocol::Tcp,
+                    local: v6(row.ucLocalAddr, row.dwLocalPort),
+                    remote: Some(v6(row.ucRemoteAddr, row.dwRemotePort)),
+                    state: Some(row.dwState),
+                });
+            }
+        }
+    }
+    if let Some(buf) = read_table(|b, s| unsafe {
+        GetExtendedUdpTable(b, s, false, AF_INET, UDP_TABLE_OWNER_PID, 0)
+    }) {
+        for row in table_rows::<MIB_UDPROW_OWNER_PID>(&buf) {
+            if row.dwOwningPid == pid {
+                result.push(Connection {
+                    protocol: Protocol::Udp,
+                    local: v4(row.dwLocalAddr, row.dwLocalPort),
+                    remote: None,
+                    state: None,
+                });
+            }
+        }
+    }
+    if let Some(buf) = read_table(|b, s| unsafe {
+        GetExtendedUdpTable(b, s, false, AF_INET6, UDP_TABLE_OWNER_PID, 0)
+    }) {
+        for row in table_rows::<MIB_UDP6ROW_OWNER_PID>(&buf) {
+            if row.dwOwningPid == pid {
+                result.push(Connection {
+                    protocol: Protocol::Udp,
+                    local: v6(row.ucLocalAddr, row.dwLocalPort),
+                    remote: None,
+                    state: None,
+                });
+            }
+        }
+    }
+
+    result.sort_by_key(|c| (c.protocol == Protocol::Udp, c.local.port(), c.local.is_ipv6()));
+    result
+}
+
+/// Close an established IPv4 TCP connection. Requires administrator rights.
+pub fn close_tcp(conn: &Connection) -> Result<(), String> {
+    let (SocketAddr::V4(local), Some(SocketAddr::V4(remote))) = (conn.local, conn.remote) else {
+        return Err("Only IPv4 TCP connections can be closed".to_string());
+    };
+
+    let row = MIB_TCPROW_LH {
+        Anonymous: MIB_TCPROW_LH_0 {
+            State: MIB_TCP_STATE_DELETE_TCB,
+        },
+        dwLocalAddr: u32::from_ne_bytes(local.ip().octets()),
+        dwLocalPort: local.port().to_be() as u32,
+        dwRemoteAddr: u32::from_ne_bytes(remote.ip().octets()),
+        dwRemotePort: remote.port().to_be() as u32,
+    };
+    match unsafe { SetTcpEntry(&row) } {
+        0 => Ok(()),
+        ERROR_ACCESS_DENIED | ERROR_MR_MID_NOT_FOUND => {
+            Err("Closing connections requires administrator rights".to_string())
+        }
+        code => Err(format!("SetTcpEntry failed (error {})", code)),
+    }
+}
+
+/// Call a size-probing table function until the buffer is large enough.
+/// The buffer is `u32`-backed so the table rows are suitably aligned.
+fn read_table(mut call: impl FnMut(Option<*mut core::ffi::c_void>, *mut u32) -> u32) -> Option<Vec<u32>> {
+    let mut size = 0u32;
+    let mut buf: Vec<u32> = Vec::new();
+    for _ in 0..4 {
+        let ptr = if buf.is_empty() {
+            None
+        } else {
+            Some(buf.as_mut_ptr() as *mut core::ffi::c_void)
+        };
+        match call(ptr, &mut size) {
+            0 if !buf.is_empty() => return Some(buf),
+            0 => return None,
+            ERROR_INSUFFICIENT_BUFFER => {
+                // Leave headroom for connections opened between calls
+                buf = vec![0u32; (size as usize).div_ceil(4) + 64];
+                size = (buf.len() * 4) as u32;
+            }
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Interpret a `MIB_*TABLE_OWNER_PID` buffer: a `DWORD` count followed by rows.
+fn table_rows<T: Copy>(buf: &[u32]) -> Vec<T> {
+    let Some(&count) = buf.first() else {
+        return Vec::new();
+    };
+    let rows_offset = std::mem::size_of::<u32>().max(std::mem::align_of::<T>());
+    let available = (buf.len() * 4).saturating_sub(rows_offset) / std::mem::size_of::<T>();
+    let count = (count as usize).min(available);
+    unsafe {
+        let first = (buf.as_ptr() as *const u8).add(rows_offset) as *const T;
+        std::slice::from_raw_parts(first, count).to_vec()
+    }
+}
+
+/// Addresses are stored in network byte order; ports in the low 16 bits,
+/// also in network byte order.
+fn v4(addr: u32, port: u32) -> SocketAddr {
+    SocketAddr::new(
+        IpAddr::V4(Ipv4Addr::from(addr.to_ne_bytes())),
+        u16::from_be(port as u16),
+    )
+}
+
+fn v6(addr: [u8; 16], port: u32) -> SocketAddr {
+    SocketAddr::new(IpAddr::V6(Ipv6Addr::from(addr)), u16::from_be(port as u16))
+}
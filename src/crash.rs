@@ -0,0 +1,160 @@
+//! Panic reporting. Release builds have no console, so a panic would
+//! otherwise vanish without a trace; the hook writes a report with a
+//! backtrace to `Crashes\` in the app data directory, keeps any unsaved
+//! settings, and leaves a marker so the next launch can offer the report.
+//! Panics the app recovers from through [`catch_unwind`] are reported but
+//! leave no marker.
+
+use crate::app_data;
+use crate::settings::Settings;
+use chrono::Local;
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+const CRASHES_DIR: &str = "Crashes";
+/// Holds the path of the report the next launch hasn't shown yet.
+const PENDING_FILE: &str = "crash-pending.txt";
+/// Settings that were being edited when the app crashed.
+const UNSAVED_SETTINGS_FILE: &str = "settings-unsaved.ini";
+
+/// Settings edited but not yet confirmed, kept for the panic hook.
+static UNSAVED_SETTINGS: Mutex<Option<Settings>> = Mutex::new(None);
+
+/// Reports written this session, so panics in the same second on several
+/// threads get their own files.
+static REPORT_COUNT: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    /// Whether this thread is inside [`catch_unwind`].
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A report left by a previous session.
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    pub path: PathBuf,
+    /// The panic message and where it happened.
+    pub summary: String,
+    /// Settings that were being edited, if any.
+    pub unsaved_settings: Option<Settings>,
+}
+
+/// Install the panic hook. Panics still go to the default hook as well, so
+/// debug builds print them to the console.
+pub fn install_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic| {
+        write_report(panic);
+        default_hook(panic);
+    }));
+}
+
+/// `std::panic::catch_unwind` for work whose panic the app recovers from:
+/// the hook still writes a report, but the next launch won't offer it.
+pub fn catch_unwind<R>(f: impl FnOnce() -> R) -> std::thread::Result<R> {
+    let outer = CATCHING.with(|c| c.replace(true));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(outer));
+    result
+}
+
+/// Remember the settings being edited, or `None` once they are saved or
+/// discarded.
+pub fn set_unsaved_settings(settings: Option<&Settings>) {
+    if let Ok(mut unsaved) = UNSAVED_SETTINGS.lock() {
+        *unsaved = settings.cloned();
+    }
+}
+
+/// The report of a crash not yet shown to the user, if there is one. The
+/// marker is removed, so each report is offered once.
+pub fn take_pending() -> Option<CrashReport> {
+    let dir = app_data::data_dir()?;
+    let marker = dir.join(PENDING_FILE);
+    let path = PathBuf::from(std::fs::read_to_string(&marker).ok()?.trim());
+    let _ = std::fs::remove_file(&marker);
+    let text = std::fs::read_to_string(&path).ok()?;
+    let summary = text
+        .lines()
+        .skip_while(|l| !l.starts_with("Panic:"))
+        .take_while(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let unsaved_path = dir.join(UNSAVED_SETTINGS_FILE);
+    let unsaved_settings = std::fs::read_to_string(&unsaved_path)
+        .ok()
+        .map(|text| Settings::parse(&text));
+    let _ = std::fs::remove_file(&unsaved_path);
+    Some(CrashReport {
+        path,
+        summary,
+        unsaved_settings,
+    })
+}
+
+/// Everything here runs inside the panic hook, so failures are ignored and
+/// nothing may panic again.
+fn write_report(panic: &std::panic::PanicHookInfo<'_>) {
+    let Some(data_dir) = app_data::data_dir() else {
+        return;
+    };
+    let caught = CATCHING.with(|c| c.get());
+    if !caught {
+        // A poisoned lock still holds the settings
+        let unsaved = match UNSAVED_SETTINGS.lock() {
+            Ok(unsaved) => unsaved.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if let Some(settings) = unsaved {
+            let _ = app_data::write_atomic(&data_dir.join(UNSAVED_SETTINGS_FILE), settings.to_text());
+        }
+    }
+
+    let message = panic
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| panic.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(no message)".to_string());
+    let location = panic
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "unknown".to_string());
+    let thread = std::thread::current();
+    let now = Local::now();
+    let report = format!(
+        "App Manager {} crash report\n\
+         Time: {}\n\
+         Architecture: {}\n\
+         \n\
+         Panic: {}\n\
+         Location: {}\n\
+         Thread: {}\n\
+         \n\
+         Backtrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        now.format("%Y-%m-%d %H:%M:%S"),
+        std::env::consts::ARCH,
+        message,
+        location,
+        thread.name().unwrap_or("unnamed"),
+        Backtrace::force_capture(),
+    );
+
+    let dir = data_dir.join(CRASHES_DIR);
+    if std::fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let path = dir.join(format!(
+        "crash-{}-{}-{}.txt",
+        now.format("%Y%m%d_%H%M%S"),
+        std::process::id(),
+        REPORT_COUNT.fetch_add(1, Ordering::Relaxed),
+    ));
+    if std::fs::write(&path, report).is_ok() && !caught {
+        let _ = std::fs::write(data_dir.join(PENDING_FILE), path.to_string_lossy().as_bytes());
+    }
+}
@@ -0,0 +1,60 @@
+//! Index of the executables behind startup entries, services, and running
+//! processes, keyed by normalized path, for answering "where else does this
+//! file show up" across tabs.
+
+use crate::cmdline;
+use crate::execution_history::normalize_path;
+use crate::models::{ProcessInfo, StartupEntry};
+use std::collections::{HashMap, HashSet};
+
+pub struct CrossRefIndex<'a> {
+    startup: HashMap<String, Vec<&'a StartupEntry>>,
+    services: HashMap<String, Vec<&'a StartupEntry>>,
+    services_by_pid: HashMap<u32, Vec<&'a StartupEntry>>,
+    running: HashSet<String>,
+}
+
+impl<'a> CrossRefIndex<'a> {
+    pub fn build(entries: &'a [StartupEntry], services: &'a [StartupEntry], processes: &[ProcessInfo]) -> Self {
+        let mut index = CrossRefIndex {
+            startup: HashMap::new(),
+            services: HashMap::new(),
+            services_by_pid: HashMap::new(),
+            running: processes.iter().map(|p| normalize_path(&p.exe_path)).collect(),
+        };
+        for entry in entries {
+            index.startup.entry(target(entry)).or_default().push(entry);
+        }
+        for service in services {
+            index.services.entry(target(service)).or_default().push(service);
+            if let Some(pid) = service.pid {
+                index.services_by_pid.entry(pid).or_default().push(service);
+            }
+        }
+        index
+    }
+
+    /// Startup entries (including scheduled tasks) that launch `path`.
+    pub fn startup_entries(&self, path: &str) -> &[&'a StartupEntry] {
+        self.startup.get(&normalize_path(path)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Services whose binary is `path`. Shared hosts like svchost.exe match
+    /// every service they host; see [`CrossRefIndex::services_in_process`].
+    pub fn services(&self, path: &str) -> &[&'a StartupEntry] {
+        self.services.get(&normalize_path(path)).map_or(&[], Vec::as_slice)
+    }
+
+    /// Running services hosted by the process `pid`.
+    pub fn services_in_process(&self, pid: u32) -> &[&'a StartupEntry] {
+        self.services_by_pid.get(&pid).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_running(&self, path: &str) -> bool {
+        self.running.contains(&normalize_path(path))
+    }
+}
+
+fn target(entry: &StartupEntry) -> String {
+    normalize_path(&cmdline::target_path(&entry.command))
+}
@@ -0,0 +1,193 @@
+//! Which startup entries and services need each other ("this tray app needs
+//! service X"), so disabling or stopping one can warn about what else stops
+//! working. Combines the user's own notes with links inferred from shared
+//! install folders and vendors.
+//!
+//! Notes are stored as a tab-separated text file in the app data directory:
+//! `<dependent key>\t<dependency key>\t<dependent name>\t<dependency name>`.
+
+use crate::app_data;
+use crate::cmdline;
+use crate::execution_history::normalize_path;
+use crate::history::entry_key;
+use crate::models::{EnabledStatus, Source, StartupEntry};
+use crate::services;
+use crate::vendors;
+use std::path::PathBuf;
+
+const FILE_NAME: &str = "dependencies.tsv";
+const HEADER: &str = "# App Manager dependencies v1";
+
+/// A user note that one item needs another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub dependent: String,
+    pub dependency: String,
+    pub dependent_name: String,
+    pub dependency_name: String,
+}
+
+/// Something that needs an item, and why it's thought to.
+#[derive(Debug, Clone)]
+pub struct Dependent {
+    pub name: String,
+    pub reason: String,
+}
+
+pub struct Dependencies {
+    path: Option<PathBuf>,
+    links: Vec<Link>,
+}
+
+impl Dependencies {
+    /// Load the notes file, starting empty if it doesn't exist or can't be read.
+    pub fn load() -> Self {
+        let path = app_data::data_dir().map(|dir| dir.join(FILE_NAME));
+        let mut links = Vec::new();
+        if let Some(text) = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for line in text.lines() {
+                if line.starts_with('#') {
+                    continue;
+                }
+                let mut fields = line.splitn(4, '\t');
+                let (Some(dependent), Some(dependency), Some(dependent_name), Some(dependency_name)) =
+                    (fields.next(), fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                links.push(Link {
+                    dependent: dependent.to_string(),
+                    dependency: dependency.to_string(),
+                    dependent_name: dependent_name.to_string(),
+                    dependency_name: dependency_name.to_string(),
+                });
+            }
+        }
+        Self { path, links }
+    }
+
+    /// Note that `dependent` needs `dependency`. Returns false if the note
+    /// already exists or would link an item to itself.
+    pub fn add(&mut self, dependent: (&str, &str), dependency: (&str, &str)) -> bool {
+        if dependent.0 == dependency.0
+            || self.links.iter().any(|l| l.dependent == dependent.0 && l.dependency == dependency.0)
+        {
+            return false;
+        }
+        self.links.push(Link {
+            dependent: dependent.0.to_string(),
+            dependency: dependency.0.to_string(),
+            dependent_name: dependent.1.replace(['\t', '\n', '\r'], " "),
+            dependency_name: dependency.1.replace(['\t', '\n', '\r'], " "),
+        });
+        self.save();
+        true
+    }
+
+    pub fn remove(&mut self, dependent: &str, dependency: &str) {
+        let before = self.links.len();
+        self.links.retain(|l| !(l.dependent == dependent && l.dependency == dependency));
+        if self.links.len() != before {
+            self.save();
+        }
+    }
+
+    /// Notes on what the item `key` needs.
+    pub fn needs(&self, key: &str) -> Vec<&Link> {
+        self.links.iter().filter(|l| l.dependent == key).collect()
+    }
+
+    /// Items that need `target` and would be affected by disabling or
+    /// stopping it: the user's notes first, then services' companion startup
+    /// entries found by install folder and vendor. Disabled items are left out.
+    pub fn dependents(
+        &self,
+        target: &StartupEntry,
+        entries: &[StartupEntry],
+        services: &[StartupEntry],
+    ) -> Vec<Dependent> {
+        let key = entry_key(&target.id());
+        let find = |key: &str| {
+            entries
+                .iter()
+                .chain(services)
+                .find(|e| entry_key(&e.id()) == key)
+        };
+        let mut result: Vec<Dependent> = self
+            .links
+            .iter()
+            .filter(|l| l.dependency == key)
+            .filter_map(|l| find(&l.dependent))
+            .filter(|e| e.enabled != EnabledStatus::Disabled)
+            .map(|e| Dependent {
+                name: e.name.clone(),
+                reason: "noted by you".to_string(),
+            })
+            .collect();
+
+        if matches!(target.source, Source::Service { .. }) && !services::is_microsoft_service(target) {
+            for entry in entries.iter().filter(|e| e.enabled == EnabledStatus::Enabled) {
+                if result.iter().any(|d| d.name == entry.name) {
+                    continue;
+                }
+                if let Some(reason) = correlation(target, entry) {
+                    result.push(Dependent {
+                        name: entry.name.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for link in &self.links {
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                link.dependent, link.dependency, link.dependent_name, link.dependency_name
+            ));
+        }
+        // Best effort, like the watch list
+        let _ = app_data::write_atomic(path, out);
+    }
+}
+
+/// Why a startup entry looks like a companion of `service`, if it does: it
+/// runs from the same folder, or from the same vendor's install folder.
+fn correlation(service: &StartupEntry, entry: &StartupEntry) -> Option<String> {
+    let service_dir = install_dir(&service.command)?;
+    let entry_dir = install_dir(&entry.command)?;
+    if service_dir == entry_dir {
+        return Some("runs from the same folder".to_string());
+    }
+    let vendor = service.company_name.trim();
+    let same_vendor = !vendor.is_empty() && vendors::vendor_key(vendor) == vendors::vendor_key(&entry.company_name);
+    if same_vendor && install_root(&service_dir) == install_root(&entry_dir) {
+        return Some(format!("same vendor ({}) and install folder", vendor));
+    }
+    None
+}
+
+/// Normalized folder of a command's target, or `None` for Windows' own
+/// folders, which every built-in tool shares.
+fn install_dir(command: &str) -> Option<String> {
+    let path = normalize_path(&cmdline::target_path(command));
+    let dir = path.rsplit_once('\\')?.0;
+    let system = ["%systemroot%", "%windir%"].iter().any(|v| dir.starts_with(v))
+        || dir.get(1..).is_some_and(|rest| rest.starts_with(":\\windows"));
+    (!dir.is_empty() && !system).then(|| dir.to_string())
+}
+
+/// The first three components of a folder, e.g. `c:\program files\vendor`.
+fn install_root(dir: &str) -> &str {
+    match dir.match_indices('\\').nth(2) {
+        Some((i, _)) => &dir[..i],
+        None => dir,
+    }
+}
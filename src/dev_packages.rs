@@ -0,0 +1,322 @@
+//! Global installs of developer package managers for the optional Dev
+//! Packages tab: pip (for the default Python), npm, `cargo install`, Scoop,
+//! and Chocolatey. Packages are removed through the manager's own CLI so its
+//! records stay consistent with what is on disk.
+//!
+//! pip, npm, and cargo are asked for their list; Scoop and Chocolatey are
+//! read from their install directories, which is much faster than starting
+//! PowerShell. npm and Scoop are batch shims, so they run as `npm.cmd` and
+//! `scoop.cmd`, which lets std escape their arguments for `cmd`. Package
+//! names read from directory names are only offered for uninstall when they
+//! are valid names for their manager.
+
+use crate::models::{DevPackage, DevPackages, PackageManager};
+use crate::parallel;
+use anyhow::{Context, Result};
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Exit code of `cmd` for an unknown command (also returned by the Microsoft
+/// Store's `python` alias when Python isn't installed).
+const COMMAND_NOT_FOUND: i32 = 9009;
+
+/// Python launchers tried in order for pip; `py` picks the default Python
+/// when several are installed.
+const PYTHON_LAUNCHERS: [&str; 2] = ["py", "python"];
+
+/// Why a manager's packages could not be listed.
+enum ListError {
+    /// The manager isn't installed.
+    NotFound,
+    Failed(String),
+}
+
+/// List every manager's global packages, sorted by name.
+pub fn collect_dev_packages() -> DevPackages {
+    let results = parallel::map(&PackageManager::ALL, |manager| match manager {
+        PackageManager::Pip => list_pip(),
+        PackageManager::Npm => list_npm(),
+        PackageManager::Cargo => list_cargo(),
+        PackageManager::Scoop => list_scoop(),
+        PackageManager::Chocolatey => list_chocolatey(),
+    });
+    let mut dev = DevPackages::default();
+    for (manager, result) in PackageManager::ALL.into_iter().zip(results) {
+        match result {
+            Ok(packages) => dev.packages.extend(packages),
+            Err(ListError::NotFound) => dev.missing.push(manager),
+            Err(ListError::Failed(e)) => dev.notes.push(format!("{}: {}", manager.label(), e)),
+        }
+    }
+    dev.packages.sort_by_key(|p| p.name.to_lowercase());
+    dev
+}
+
+/// Remove a package through its manager's CLI.
+pub fn uninstall(package: &DevPackage) -> Result<()> {
+    let (program, args) = package
+        .uninstall_command
+        .split_first()
+        .context("No uninstall command")?;
+    let output = Command::new(program)
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !output.status.success() {
+        let message = last_line(&output.stderr)
+            .or_else(|| last_line(&output.stdout))
+            .unwrap_or_else(|| format!("exit code {}", output.status.code().unwrap_or(-1)));
+        anyhow::bail!("{} uninstall failed: {}", package.manager.label(), message);
+    }
+    Ok(())
+}
+
+/// The uninstall command as shown to the user.
+pub fn command_line(package: &DevPackage) -> String {
+    package.uninstall_command.join(" ")
+}
+
+fn list_pip() -> Result<Vec<DevPackage>, ListError> {
+    for launcher in PYTHON_LAUNCHERS {
+        let list = match output(launcher, &["-m", "pip", "list", "--format=freeze", "--disable-pip-version-check"]) {
+            Err(ListError::NotFound) => continue,
+            result => result?,
+        };
+        // "pip 24.0 from C:\...\site-packages\pip (python 3.12)"
+        let location = output(launcher, &["-m", "pip", "--version"])
+            .ok()
+            .and_then(|v| {
+                let pip_dir = v.split_once(" from ")?.1.rsplit_once(" (")?.0.to_string();
+                Some(Path::new(&pip_dir).parent()?.display().to_string())
+            })
+            .unwrap_or_default();
+        let packages = list
+            .lines()
+            .filter_map(|line| {
+                // "name==1.0", or "name @ file:///..." for direct installs
+                let line = line.trim();
+                let (name, version) = line
+                    .split_once("==")
+                    .or_else(|| line.split_once(" @ ").map(|(name, _)| (name, "")))?;
+                Some(package(
+                    PackageManager::Pip,
+                    name,
+                    version,
+                    &location,
+                    &[launcher, "-m", "pip", "uninstall", "-y", name],
+                ))
+            })
+            .collect();
+        return Ok(packages);
+    }
+    Err(ListError::NotFound)
+}
+
+fn list_npm() -> Result<Vec<DevPackage>, ListError> {
+    let root = PathBuf::from(output("npm.cmd", &["root", "-g"])?.trim());
+    let location = root.display().to_string();
+    let mut packages = Vec::new();
+    let mut add = |name: String, dir: &Path| {
+        if !is_npm_name(&name) {
+            return;
+        }
+        let manifest = std::fs::read_to_string(dir.join("package.json")).unwrap_or_default();
+        let version = json_string(&manifest, "version").unwrap_or_default();
+        packages.push(package(
+            PackageManager::Npm,
+            &name,
+            &version,
+            &location,
+            &["npm.cmd", "uninstall", "-g", &name],
+        ));
+    };
+    for dir in subdirectories(&root) {
+        let name = file_name(&dir);
+        if name.starts_with('@') {
+            // Scoped packages: @scope\name
+            for scoped in subdirectories(&dir) {
+                add(format!("{}/{}", name, file_name(&scoped)), &scoped);
+            }
+        } else if !name.starts_with('.') {
+            add(name, &dir);
+        }
+    }
+    Ok(packages)
+}
+
+fn list_cargo() -> Result<Vec<DevPackage>, ListError> {
+    let list = output("cargo", &["install", "--list"])?;
+    let location = env_path("CARGO_HOME")
+        .or_else(|| env_path("USERPROFILE").map(|p| p.join(".cargo")))
+        .map(|p| p.join("bin").display().to_string())
+        .unwrap_or_default();
+    // "ripgrep v14.1.0:" followed by indented binary names
+    Ok(list
+        .lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let mut words = line.trim_end_matches(':').split_whitespace();
+            let name = words.next()?;
+            let version = words.next()?.trim_start_matches('v');
+            Some(package(PackageManager::Cargo, name, version, &location, &["cargo", "uninstall", name]))
+        })
+        .collect())
+}
+
+fn list_scoop() -> Result<Vec<DevPackage>, ListError> {
+    let roots = [
+        (env_path("SCOOP").or_else(|| env_path("USERPROFILE").map(|p| p.join("scoop"))), false),
+        (env_path("SCOOP_GLOBAL").or_else(|| env_path("ProgramData").map(|p| p.join("scoop"))), true),
+    ];
+    let mut found = false;
+    let mut packages = Vec::new();
+    for (root, global) in roots {
+        let Some(apps) = root.map(|r| r.join("apps")).filter(|a| a.is_dir()) else {
+            continue;
+        };
+        found = true;
+        let location = apps.display().to_string();
+        for dir in subdirectories(&apps) {
+            let name = file_name(&dir);
+            // Scoop itself, and directories that aren't app names
+            if name.eq_ignore_ascii_case("scoop") || !is_scoop_name(&name) {
+                continue;
+            }
+            let manifest = std::fs::read_to_string(dir.join("current").join("manifest.json")).unwrap_or_default();
+            let version = json_string(&manifest, "version").unwrap_or_default();
+            let mut command = vec!["scoop.cmd", "uninstall", &name];
+            if global {
+                command.push("--global");
+            }
+            packages.push(package(PackageManager::Scoop, &name, &version, &location, &command));
+        }
+    }
+    if !found {
+        return Err(ListError::NotFound);
+    }
+    Ok(packages)
+}
+
+fn list_chocolatey() -> Result<Vec<DevPackage>, ListError> {
+    let lib = env_path("ChocolateyInstall")
+        .or_else(|| env_path("ProgramData").map(|p| p.join("chocolatey")))
+        .map(|root| root.join("lib"))
+        .filter(|lib| lib.is_dir())
+        .ok_or(ListError::NotFound)?;
+    let location = lib.display().to_string();
+    Ok(subdirectories(&lib)
+        .into_iter()
+        .map(|dir| file_name(&dir))
+        // Chocolatey itself
+        .filter(|name| !name.eq_ignore_ascii_case("chocolatey"))
+        .map(|name| {
+            let nuspec = std::fs::read_to_string(lib.join(&name).join(format!("{}.nuspec", name))).unwrap_or_default();
+            let version = xml_element(&nuspec, "version").unwrap_or_default();
+            package(PackageManager::Chocolatey, &name, &version, &location, &["choco", "uninstall", &name, "-y"])
+        })
+        .collect())
+}
+
+/// Whether `name` is a valid npm package name (`name` or `@scope/name`).
+fn is_npm_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "@._/-".contains(c))
+}
+
+/// Whether `name` is a valid Scoop app name.
+fn is_scoop_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+}
+
+fn package(manager: PackageManager, name: &str, version: &str, location: &str, uninstall: &[&str]) -> DevPackage {
+    DevPackage {
+        manager,
+        name: name.to_string(),
+        version: version.to_string(),
+        location: location.to_string(),
+        uninstall_command: uninstall.iter().map(|s| s.to_string()).collect(),
+    }
+}
+
+/// Run a command and return its standard output.
+fn output(program: &str, args: &[&str]) -> Result<String, ListError> {
+    let output = Command::new(program)
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ListError::NotFound,
+            _ => ListError::Failed(format!("Failed to run {}: {}", program, e)),
+        })?;
+    if output.status.code() == Some(COMMAND_NOT_FOUND) {
+        return Err(ListError::NotFound);
+    }
+    if !output.status.success() {
+        return Err(ListError::Failed(
+            last_line(&output.stderr).unwrap_or_else(|| format!("{} exited with an error", program)),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn last_line(output: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+fn subdirectories(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+/// The first `"key": "value"` string in a JSON document. Enough for the
+/// top-level `version` of a package manifest.
+fn json_string(text: &str, key: &str) -> Option<String> {
+    let quoted = format!("\"{}\"", key);
+    let after = text[text.find(&quoted)? + quoted.len()..].trim_start();
+    let value = after.strip_prefix(':')?.trim_start().strip_prefix('"')?;
+    let mut result = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(result),
+            '\\' => result.push(chars.next()?),
+            c => result.push(c),
+        }
+    }
+    None
+}
+
+/// The text of the first `<tag>` element in an XML document.
+fn xml_element(text: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let start = text.find(&open)? + open.len();
+    let end = start + text[start..].find(&format!("</{}>", tag))?;
+    Some(text[start..end].trim().to_string())
+}
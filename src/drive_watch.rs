@@ -0,0 +1,154 @@
+//! Drive arrival and removal: a background thread owns a hidden window that
+//! receives the `WM_DEVICECHANGE` broadcasts Windows sends when a volume
+//! (USB stick, SD card, mapped drive) comes or goes, so entries pointing at
+//! it can be re-checked without a full reload.
+//!
+//! Volume broadcasts only reach top-level windows, so the window is a real
+//! (never shown) one rather than a message-only window.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicIsize, Ordering};
+use std::sync::{mpsc, Arc};
+use windows::core::w;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetMessageW, PostMessageW, PostQuitMessage,
+    RegisterClassW, DBT_DEVICEARRIVAL, DBT_DEVICEREMOVECOMPLETE, DBT_DEVTYP_VOLUME, DEV_BROADCAST_HDR,
+    DEV_BROADCAST_VOLUME, MSG, WINDOW_EX_STYLE, WM_CLOSE, WM_DESTROY, WM_DEVICECHANGE, WNDCLASSW, WS_OVERLAPPED,
+};
+
+/// Drive letters whose volume arrived or was removed, as a bit mask (bit 0
+/// is A:).
+#[derive(Debug, Clone, Copy)]
+pub struct DriveChange {
+    pub letters: u32,
+    pub arrived: bool,
+}
+
+impl DriveChange {
+    /// The letters as `E:`, `F:` etc.
+    pub fn letter_names(&self) -> Vec<String> {
+        (0..26u8)
+            .filter(|i| self.letters & (1 << i) != 0)
+            .map(|i| format!("{}:", (b'A' + i) as char))
+            .collect()
+    }
+
+    /// Whether `path` is on one of the changed drives.
+    pub fn affects(&self, path: &str) -> bool {
+        let bytes = path.trim().trim_matches('"').as_bytes();
+        bytes.len() >= 2
+            && bytes[1] == b':'
+            && bytes[0].is_ascii_alphabetic()
+            && self.letters & (1 << (bytes[0].to_ascii_uppercase() - b'A')) != 0
+    }
+}
+
+/// Watches for drive changes until dropped.
+pub struct DriveWatcher {
+    /// The hidden window, once created (0 before).
+    hwnd: Arc<AtomicIsize>,
+    pub changes: mpsc::Receiver<DriveChange>,
+}
+
+impl Drop for DriveWatcher {
+    fn drop(&mut self) {
+        let hwnd = self.hwnd.load(Ordering::Relaxed);
+        if hwnd != 0 {
+            unsafe {
+                let _ = PostMessageW(Some(HWND(hwnd as *mut _)), WM_CLOSE, WPARAM(0), LPARAM(0));
+            }
+        }
+    }
+}
+
+/// Start watching. `wake` is called after each change (e.g. to repaint the
+/// window).
+pub fn watch(wake: impl Fn() + Send + 'static) -> DriveWatcher {
+    let hwnd = Arc::new(AtomicIsize::new(0));
+    let (tx, rx) = mpsc::channel();
+    let thread_hwnd = hwnd.clone();
+    std::thread::spawn(move || unsafe { run(&thread_hwnd, tx, Box::new(wake)) });
+    DriveWatcher { hwnd, changes: rx }
+}
+
+/// Where the window procedure reports changes, and what it wakes.
+type Sink = (mpsc::Sender<DriveChange>, Box<dyn Fn()>);
+
+thread_local! {
+    /// The watcher thread's sink, for its window procedure.
+    static SINK: RefCell<Option<Sink>> = const { RefCell::new(None) };
+}
+
+unsafe fn run(hwnd_out: &AtomicIsize, tx: mpsc::Sender<DriveChange>, wake: Box<dyn Fn()>) {
+    let Ok(instance) = GetModuleHandleW(None) else {
+        return;
+    };
+    let class = WNDCLASSW {
+        lpfnWndProc: Some(window_proc),
+        hInstance: instance.into(),
+        lpszClassName: w!("AppManagerDriveWatch"),
+        ..Default::default()
+    };
+    RegisterClassW(&class);
+    let Ok(hwnd) = CreateWindowExW(
+        WINDOW_EX_STYLE::default(),
+        class.lpszClassName,
+        w!(""),
+        WS_OVERLAPPED,
+        0,
+        0,
+        0,
+        0,
+        None,
+        None,
+        Some(class.hInstance),
+        None,
+    ) else {
+        return;
+    };
+    SINK.with(|sink| *sink.borrow_mut() = Some((tx, wake)));
+    hwnd_out.store(hwnd.0 as isize, Ordering::Relaxed);
+
+    let mut msg = MSG::default();
+    while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+        DispatchMessageW(&msg);
+    }
+}
+
+unsafe extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    match msg {
+        WM_DEVICECHANGE => {
+            let event = wparam.0 as u32;
+            let header = lparam.0 as *const DEV_BROADCAST_HDR;
+            if matches!(event, DBT_DEVICEARRIVAL | DBT_DEVICEREMOVECOMPLETE)
+                && !header.is_null()
+                && (*header).dbch_devicetype == DBT_DEVTYP_VOLUME
+            {
+                let volume = &*(lparam.0 as *const DEV_BROADCAST_VOLUME);
+                let change = DriveChange {
+                    letters: volume.dbcv_unitmask,
+                    arrived: event == DBT_DEVICEARRIVAL,
+                };
+                SINK.with(|sink| {
+                    if let Some((tx, wake)) = &*sink.borrow() {
+                        if tx.send(change).is_ok() {
+                            wake();
+                        }
+                    }
+                });
+            }
+            LRESULT(1)
+        }
+        WM_CLOSE => {
+            let _ = DestroyWindow(hwnd);
+            LRESULT(0)
+        }
+        WM_DESTROY => {
+            PostQuitMessage(0);
+            LRESULT(0)
+        }
+        _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+    }
+}
@@ -0,0 +1,63 @@
+//! Detect binaries on removable or network drives. These disappear when the
+//! device is unplugged or the share is offline, which silently breaks startup.
+//! A target on a drive that isn't connected at all isn't reported as missing,
+//! since it comes back with the drive (see `drive_watch`).
+
+use crate::cmdline;
+use crate::models::DriveKind;
+use std::os::windows::ffi::OsStrExt;
+use windows::core::PCWSTR;
+use windows::Win32::Storage::FileSystem::GetDriveTypeW;
+
+// GetDriveTypeW return values
+const DRIVE_NO_ROOT_DIR: u32 = 1;
+const DRIVE_REMOVABLE: u32 = 2;
+const DRIVE_REMOTE: u32 = 4;
+const DRIVE_CDROM: u32 = 5;
+
+/// Whether `command`'s target is missing, and the kind of drive it is on.
+/// A target on a disconnected drive is not missing.
+pub fn check_target(command: &str) -> (bool, Option<DriveKind>) {
+    let kind = volatile_drive_kind(&cmdline::target_path(command));
+    let missing = kind != Some(DriveKind::Disconnected) && cmdline::is_target_missing(command);
+    (missing, kind)
+}
+
+/// Classify the drive holding `path`. Returns `None` for fixed disks, RAM
+/// disks, relative paths, and anything that can't be determined.
+pub fn volatile_drive_kind(path: &str) -> Option<DriveKind> {
+    let path = path.trim().trim_matches('"');
+    let lower = path.to_lowercase();
+
+    // UNC paths are network paths; \\?\ and \\.\ are device/long-path prefixes
+    if let Some(rest) = lower.strip_prefix(r"\\?\") {
+        if rest.starts_with(r"unc\") {
+            return Some(DriveKind::Network);
+        }
+        return volatile_drive_kind(&path[4..]);
+    }
+    if lower.starts_with(r"\\.\") {
+        return None;
+    }
+    if lower.starts_with(r"\\") {
+        return Some(DriveKind::Network);
+    }
+
+    let bytes = path.as_bytes();
+    if bytes.len() < 2 || !bytes[0].is_ascii_alphabetic() || bytes[1] != b':' {
+        return None;
+    }
+    let root = format!("{}:\\", bytes[0] as char);
+    let wide: Vec<u16> = std::ffi::OsStr::new(&root)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    match unsafe { GetDriveTypeW(PCWSTR(wide.as_ptr())) } {
+        DRIVE_NO_ROOT_DIR => Some(DriveKind::Disconnected),
+        // A card reader or optical drive keeps its letter without media
+        DRIVE_REMOVABLE | DRIVE_CDROM if !std::path::Path::new(&root).exists() => Some(DriveKind::Disconnected),
+        DRIVE_REMOVABLE | DRIVE_CDROM => Some(DriveKind::Removable),
+        DRIVE_REMOTE => Some(DriveKind::Network),
+        _ => None,
+    }
+}
@@ -0,0 +1,409 @@
+//! Dry run: what an action would change, worked out from the same state the
+//! real action in [`crate::actions`] reads, without writing anything. Each
+//! function mirrors one action and fails where it would fail, so a dry run
+//! also shows which actions wouldn't work.
+
+use crate::actions::{self, NewEntryLocation};
+use crate::backup::BackupItem;
+use crate::clean_boot;
+use crate::cmdline;
+use crate::dev_packages;
+use crate::models::*;
+use crate::service_hardening::StartMode;
+use crate::startup_folders;
+use anyhow::{Context, Result};
+use winreg::enums::*;
+use winreg::RegKey;
+
+/// Command-line flag that starts App Manager (or the monitor mode) in dry-run
+/// mode.
+pub const DRY_RUN_FLAG: &str = "--dry-run";
+
+/// One action handled by the dry run and the changes it would have made.
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub when: chrono::DateTime<chrono::Local>,
+    /// What was asked for, e.g. "Disable 'OneDrive'".
+    pub action: String,
+    /// One line per registry value, file, service, task, or process that
+    /// would change; the error if the action would fail.
+    pub changes: Result<Vec<String>, String>,
+}
+
+impl Simulation {
+    pub fn new(action: String, changes: Result<Vec<String>>) -> Self {
+        Self {
+            when: chrono::Local::now(),
+            action,
+            changes: changes.map_err(|e| format!("{:#}", e)),
+        }
+    }
+}
+
+pub fn enable_entry(entry: &StartupEntry) -> Result<Vec<String>> {
+    toggle_entry(entry, true)
+}
+
+pub fn disable_entry(entry: &StartupEntry) -> Result<Vec<String>> {
+    toggle_entry(entry, false)
+}
+
+fn toggle_entry(entry: &StartupEntry, enable: bool) -> Result<Vec<String>> {
+    match &entry.source {
+        Source::RegistryRun { hive, .. } => approved_change(&hive.to_string(), predef(hive), "Run", &entry.name, enable),
+        Source::RegistryRunOnce { .. } => anyhow::bail!("RunOnce entries cannot be toggled"),
+        Source::Ifeo { .. } => {
+            anyhow::bail!("IFEO debugger entries cannot be toggled; delete the entry to remove the debugger")
+        }
+        Source::StartupFolder { path, is_common, other_user } => {
+            let file_name = std::path::Path::new(path)
+                .file_name()
+                .and_then(|f| f.to_str())
+                .unwrap_or(&entry.name);
+            if let Some(user) = other_user {
+                let root = startup_folders::open_user_hive(&user.sid, KEY_READ)
+                    .with_context(|| format!("Failed to open the registry of {}", user.name))?;
+                let label = format!("HKU\\{}", user.sid);
+                return approved_change(&label, root, "StartupFolder", file_name, enable);
+            }
+            let hive = if *is_common {
+                RegistryHive::HKLM
+            } else {
+                RegistryHive::HKCU
+            };
+            approved_change(&hive.to_string(), predef(&hive), "StartupFolder", file_name, enable)
+        }
+        Source::TaskScheduler { task_path } => {
+            let verb = if enable { "Enable" } else { "Disable" };
+            Ok(vec![format!(
+                "{} scheduled task {} (schtasks /Change /TN \"{}\" /{})",
+                verb,
+                task_path,
+                task_path,
+                verb.to_uppercase()
+            )])
+        }
+        Source::Service { service_name, .. } => {
+            let start = if enable { "auto" } else { "disabled" };
+            Ok(vec![format!(
+                "Set service {} start type to {} (sc config {} start= {})",
+                service_name,
+                if enable { "Automatic" } else { "Disabled" },
+                service_name,
+                start
+            )])
+        }
+    }
+}
+
+pub fn set_start_mode(entry: &StartupEntry, mode: StartMode) -> Result<Vec<String>> {
+    let Source::Service { service_name, .. } = &entry.source else {
+        anyhow::bail!("'{}' is not a service", entry.name);
+    };
+    Ok(vec![format!(
+        "Set service {} start type to {} (sc config {} start= {})",
+        service_name,
+        mode.label(),
+        service_name,
+        mode.sc_value()
+    )])
+}
+
+pub fn start_entry(entry: &StartupEntry) -> Result<Vec<String>> {
+    if let Source::Service { service_name, .. } = &entry.source {
+        return Ok(vec![format!("Start service {} (sc start {})", service_name, service_name)]);
+    }
+    let (exe, args) = cmdline::parse(&entry.command);
+    Ok(vec![format!("Launch {} {}", exe, args.join(" ")).trim_end().to_string()])
+}
+
+pub fn stop_entry(entry: &StartupEntry) -> Result<Vec<String>> {
+    if let Source::Service { service_name, .. } = &entry.source {
+        return Ok(vec![format!("Stop service {} (sc stop {})", service_name, service_name)]);
+    }
+    let exe_name = entry.exe_name().context("Could not determine executable name")?;
+    let mut sys = sysinfo::System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let mut changes: Vec<String> = sys
+        .processes()
+        .values()
+        .filter(|p| p.name().to_string_lossy().to_lowercase() == exe_name)
+        .map(|p| kill_process(p.pid().as_u32(), &exe_name))
+        .collect();
+    if changes.is_empty() {
+        anyhow::bail!("No running process found for {}", exe_name);
+    }
+    changes.sort();
+    Ok(changes)
+}
+
+pub fn add_entry(name: &str, command: &str, location: NewEntryLocation) -> Result<Vec<String>> {
+    let name = name.trim();
+    let command = command.trim();
+    if name.is_empty() || command.is_empty() {
+        anyhow::bail!("A name and a command are required");
+    }
+    match location {
+        NewEntryLocation::UserRun | NewEntryLocation::MachineRun => {
+            let hive = if location == NewEntryLocation::UserRun {
+                RegistryHive::HKCU
+            } else {
+                RegistryHive::HKLM
+            };
+            let exists = predef(&hive)
+                .open_subkey_with_flags(actions::RUN_KEY_PATH, KEY_READ)
+                .is_ok_and(|key| key.get_raw_value(name).is_ok());
+            if exists {
+                anyhow::bail!("A Run entry named '{}' already exists", name);
+            }
+            Ok(vec![format!(
+                "Create value {}\\{}\\{} ({:?}) = {}",
+                hive,
+                actions::RUN_KEY_PATH,
+                name,
+                actions::new_value_type(command),
+                command
+            )])
+        }
+        NewEntryLocation::UserStartupFolder => {
+            let link = actions::new_shortcut_path(name)?;
+            let (exe, args) = cmdline::split(command);
+            let target = cmdline::expand_env_vars(&exe);
+            if target.to_lowercase().ends_with(".lnk") && args.is_empty() {
+                return Ok(vec![format!("Copy shortcut {} to {}", target, link.display())]);
+            }
+            Ok(vec![format!(
+                "Create shortcut {} to {} {}",
+                link.display(),
+                target,
+                args
+            )
+            .trim_end()
+            .to_string()])
+        }
+    }
+}
+
+pub fn delete_entry(entry: &StartupEntry) -> Result<Vec<String>> {
+    match &entry.source {
+        Source::RegistryRun { hive, key_path } | Source::RegistryRunOnce { hive, key_path } => {
+            let root = predef(hive);
+            let key = root
+                .open_subkey_with_flags(key_path, KEY_READ)
+                .context("Failed to open registry key")?;
+            let current = key
+                .get_raw_value(&entry.name)
+                .with_context(|| format!("Value '{}' not found", entry.name))?;
+            let mut changes = vec![format!(
+                "Delete value {}\\{}\\{} (was {})",
+                hive,
+                key_path,
+                entry.name,
+                describe_value(&current)
+            )];
+            // The StartupApproved cleanup that follows the delete
+            for subkey in actions::STARTUP_APPROVED_SUBKEYS {
+                let path = actions::startup_approved_path(subkey);
+                let approved = root
+                    .open_subkey_with_flags(&path, KEY_READ)
+                    .is_ok_and(|k| k.get_raw_value(&entry.name).is_ok());
+                if approved {
+                    changes.push(format!("Delete value {}\\{}\\{}", hive, path, entry.name));
+                }
+            }
+            Ok(changes)
+        }
+        Source::Ifeo { key_path } => {
+            let current: String = RegKey::predef(HKEY_LOCAL_MACHINE)
+                .open_subkey_with_flags(key_path, KEY_READ)
+                .context("Failed to open registry key")?
+                .get_value(IFEO_DEBUGGER_VALUE)
+                .context("The Debugger value is not set")?;
+            Ok(vec![format!(
+                "Delete value HKLM\\{}\\{} (was \"{}\")",
+                key_path, IFEO_DEBUGGER_VALUE, current
+            )])
+        }
+        Source::StartupFolder { path, .. } => {
+            if !std::path::Path::new(path).exists() {
+                anyhow::bail!("{} does not exist", path);
+            }
+            Ok(vec![format!("Move {} to the backup store (can be restored)", path)])
+        }
+        Source::TaskScheduler { task_path } => Ok(vec![format!(
+            "Delete scheduled task {} (schtasks /Delete /TN \"{}\" /F)",
+            task_path, task_path
+        )]),
+        Source::Service { service_name, .. } => Ok(vec![format!(
+            "Delete service {} (sc delete {})",
+            service_name, service_name
+        )]),
+    }
+}
+
+pub fn set_entry_arguments(entry: &StartupEntry, args: &str) -> Result<Vec<String>> {
+    let (exe, _) = cmdline::split(&entry.command);
+    if exe.is_empty() {
+        anyhow::bail!("Could not determine executable for '{}'", entry.name);
+    }
+    let new_command = cmdline::join(&exe, args);
+    match &entry.source {
+        Source::RegistryRun { hive, key_path } | Source::RegistryRunOnce { hive, key_path } => {
+            let current = predef(hive)
+                .open_subkey_with_flags(key_path, KEY_READ)
+                .context("Failed to open registry key")?
+                .get_raw_value(&entry.name)
+                .ok();
+            let vtype = current
+                .as_ref()
+                .map_or_else(|| actions::new_value_type(&new_command), |v| v.vtype.clone());
+            Ok(vec![format!(
+                "Set value {}\\{}\\{} ({:?}) from {} to \"{}\"",
+                hive,
+                key_path,
+                entry.name,
+                vtype,
+                current.as_ref().map_or("(not set)".to_string(), describe_value),
+                new_command
+            )])
+        }
+        Source::Ifeo { key_path } => Ok(vec![format!(
+            "Set value HKLM\\{}\\{} ({}) from \"{}\" to \"{}\"",
+            key_path,
+            IFEO_DEBUGGER_VALUE,
+            if entry.expand_sz { "REG_EXPAND_SZ" } else { "REG_SZ" },
+            entry.command,
+            new_command
+        )]),
+        Source::StartupFolder { .. } => {
+            anyhow::bail!("Editing arguments of startup folder items is not supported")
+        }
+        Source::TaskScheduler { task_path } => Ok(vec![format!(
+            "Set the action of scheduled task {} to \"{}\" (schtasks /Change /TR)",
+            task_path, new_command
+        )]),
+        Source::Service { service_name, .. } => Ok(vec![format!(
+            "Set service {} binary path to \"{}\" (sc config {} binPath=)",
+            service_name, new_command, service_name
+        )]),
+    }
+}
+
+/// The settings of a scheduled task that would change from `original` to
+/// `conditions`.
+pub fn set_task_conditions(task_path: &str, original: &TaskConditions, conditions: &TaskConditions) -> Vec<String> {
+    let fields = [
+        (
+            "DisallowStartIfOnBatteries",
+            original.disallow_start_on_batteries,
+            conditions.disallow_start_on_batteries,
+        ),
+        ("StopIfGoingOnBatteries", original.stop_on_batteries, conditions.stop_on_batteries),
+        ("RunOnlyIfIdle", original.run_only_if_idle, conditions.run_only_if_idle),
+        ("RunOnlyIfNetworkAvailable", original.run_only_if_network, conditions.run_only_if_network),
+        ("WakeToRun", original.wake_to_run, conditions.wake_to_run),
+    ];
+    let mut changes: Vec<String> = fields
+        .iter()
+        .filter(|(_, from, to)| from != to)
+        .map(|(setting, from, to)| format!("Set {} of scheduled task {} from {} to {}", setting, task_path, from, to))
+        .collect();
+    if conditions.run_only_if_idle && original.idle_minutes != conditions.idle_minutes {
+        changes.push(format!(
+            "Set IdleDuration of scheduled task {} from PT{}M to PT{}M",
+            task_path,
+            original.idle_minutes,
+            conditions.idle_minutes.max(1)
+        ));
+    }
+    changes
+}
+
+pub fn restore_backup(item: &BackupItem) -> Result<Vec<String>> {
+    if item.original_path.exists() {
+        anyhow::bail!("{} already exists", item.original_path.display());
+    }
+    Ok(vec![
+        format!("Move {} back to {}", item.file_name(), item.original_path.display()),
+        format!("Delete backup folder {}", item.dir.display()),
+    ])
+}
+
+pub fn discard_backup(item: &BackupItem) -> Vec<String> {
+    vec![format!("Permanently delete backup folder {}", item.dir.display())]
+}
+
+/// An uninstall or modify command run through the UAC prompt.
+pub fn launch_elevated(command: &str) -> Vec<String> {
+    vec![format!("Run elevated: {}", command)]
+}
+
+pub fn uninstall_package(package: &DevPackage) -> Vec<String> {
+    vec![format!("Run: {}", dev_packages::command_line(package))]
+}
+
+/// What a clean boot does before disabling anything.
+pub fn prepare_clean_boot(restore_point: bool) -> Vec<String> {
+    let mut changes = vec!["Save a revert file under %LOCALAPPDATA%\\App Manager\\Clean Boot".to_string()];
+    if restore_point {
+        changes.push(format!(
+            "Create System Restore point '{}' (Checkpoint-Computer)",
+            clean_boot::RESTORE_POINT_DESCRIPTION
+        ));
+    }
+    changes
+}
+
+pub fn restart() -> Vec<String> {
+    vec![format!("Restart Windows (shutdown {})", clean_boot::restart_args()[..3].join(" "))]
+}
+
+pub fn kill_process(pid: u32, name: &str) -> String {
+    format!("End process {} (PID {}) (taskkill /PID {} /F)", name, pid, pid)
+}
+
+/// A `StartupApproved` value change under `root` (labeled `root_label`).
+fn approved_change(root_label: &str, root: RegKey, subkey: &str, value_name: &str, enable: bool) -> Result<Vec<String>> {
+    let path = actions::startup_approved_path(subkey);
+    let key = root
+        .open_subkey_with_flags(&path, KEY_READ)
+        .with_context(|| format!("Failed to open {}", path))?;
+    let current = key.get_raw_value(value_name).ok().map(|v| v.bytes);
+    let from = current.as_deref().map_or("(not set)".to_string(), hex);
+    let to = actions::startup_approved_data(current, enable);
+    Ok(vec![format!(
+        "Set value {}\\{}\\{} (REG_BINARY) from {} to {} ({})",
+        root_label,
+        path,
+        value_name,
+        from,
+        hex(&to),
+        if enable { "enabled" } else { "disabled" }
+    )])
+}
+
+fn predef(hive: &RegistryHive) -> RegKey {
+    match hive {
+        RegistryHive::HKCU => RegKey::predef(HKEY_CURRENT_USER),
+        RegistryHive::HKLM => RegKey::predef(HKEY_LOCAL_MACHINE),
+    }
+}
+
+/// A registry value as text: strings quoted, anything else as hex.
+fn describe_value(value: &winreg::RegValue) -> String {
+    match value.vtype {
+        REG_SZ | REG_EXPAND_SZ => {
+            let units: Vec<u16> = value
+                .bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            format!("\"{}\"", String::from_utf16_lossy(&units).trim_end_matches('\0'))
+        }
+        _ => hex(&value.bytes),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,181 @@
+//! Keep-disabled enforcement: re-applies the disabled state of entries the
+//! user asked to always keep disabled, and records every action it takes.
+//!
+//! A pass runs periodically while App Manager is open, and from the headless
+//! monitor mode (`app-manager.exe --monitor`), which can be started at logon
+//! so rules are enforced without the window open. In dry-run mode
+//! (`--dry-run`) a pass only records what it would disable.
+//!
+//! The activity record is a tab-separated text file in the app data
+//! directory: `<timestamp>\t<name>\t<location>\t<outcome>`.
+
+use crate::actions;
+use crate::app_data;
+use crate::audit::{self, AuditRecord};
+use crate::collector;
+use crate::dry_run;
+use crate::history::entry_key;
+use crate::models::{EnabledStatus, StartupEntry};
+use crate::services;
+use crate::skipped::SkippedItems;
+use crate::settings::Settings;
+use crate::watchlist::Watchlist;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::collections::HashSet;
+use std::io::Write;
+
+const LOG_FILE_NAME: &str = "enforcement-log.tsv";
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Command-line flag that starts the headless monitor mode.
+pub const MONITOR_FLAG: &str = "--monitor";
+
+/// One enforcement action.
+#[derive(Debug, Clone)]
+pub struct EnforcementRecord {
+    pub when: DateTime<Local>,
+    /// Watchlist key of the entry; empty for records read back from the log.
+    pub key: String,
+    pub name: String,
+    pub location: String,
+    /// `Ok` if the entry was disabled again (or would be, in a dry run),
+    /// otherwise the error.
+    pub outcome: Result<(), String>,
+    /// Only simulated; nothing was changed.
+    pub dry_run: bool,
+}
+
+impl EnforcementRecord {
+    pub fn outcome_text(&self) -> String {
+        match (&self.outcome, self.dry_run) {
+            (Ok(()), false) => "Disabled".to_string(),
+            (Ok(()), true) => "Would disable (dry run)".to_string(),
+            (Err(e), _) => format!("Failed: {}", e),
+        }
+    }
+}
+
+/// Disable every entry in `entries` that has a keep-disabled rule and is
+/// currently enabled. Actions are appended to the activity record. A dry run
+/// checks that each entry could be disabled without disabling it.
+pub fn enforce(entries: &[StartupEntry], rules: &HashSet<String>, dry_run: bool) -> Vec<EnforcementRecord> {
+    let mut records = Vec::new();
+    for entry in entries {
+        if matches!(entry.enabled, EnabledStatus::Disabled | EnabledStatus::Unknown)
+            || !rules.contains(&entry_key(&entry.id()))
+        {
+            continue;
+        }
+        let outcome = if dry_run {
+            dry_run::disable_entry(entry).map(|_| ())
+        } else {
+            actions::disable_entry(entry)
+        };
+        records.push(EnforcementRecord {
+            when: Local::now(),
+            key: entry_key(&entry.id()),
+            name: entry.name.clone(),
+            location: entry.source.display_location(),
+            outcome: outcome.map_err(|e| e.to_string()),
+            dry_run,
+        });
+    }
+    append_log(&records);
+    if !dry_run {
+        let changes: Vec<AuditRecord> = records
+            .iter()
+            .filter(|r| r.outcome.is_ok())
+            .map(|r| AuditRecord::new(&r.name, &r.location, "Re-disabled (keep-disabled rule)", "Enabled", "Disabled"))
+            .collect();
+        audit::append(&changes);
+    }
+    records
+}
+
+/// Scan startup entries and services and enforce the current rules.
+pub fn run_pass(settings: &Settings, dry_run: bool) -> Vec<EnforcementRecord> {
+    let rules = Watchlist::load().keep_disabled_keys();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let mut entries = collector::collect_all_entries(&settings.scan_scope).entries;
+    entries.extend(services::collect_services(&mut SkippedItems::default()).unwrap_or_default());
+    enforce(&entries, &rules, dry_run)
+}
+
+/// Headless monitor mode: enforce the rules once, then every configured
+/// interval until the process is ended. Settings are re-read each pass; while
+/// the interval is 0 (periodic enforcement off) no further passes run.
+pub fn run_monitor(dry_run: bool) {
+    run_pass(&Settings::load(), dry_run);
+    loop {
+        let minutes = Settings::load().enforce_interval_minutes;
+        if minutes == 0 {
+            // Check again later in case the interval is turned back on
+            std::thread::sleep(std::time::Duration::from_secs(60));
+            continue;
+        }
+        std::thread::sleep(std::time::Duration::from_secs(u64::from(minutes) * 60));
+        run_pass(&Settings::load(), dry_run);
+    }
+}
+
+/// The most recent enforcement actions, newest first.
+pub fn read_log(limit: usize) -> Vec<EnforcementRecord> {
+    let Some(text) = log_path().and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    text.lines()
+        .rev()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            let when = NaiveDateTime::parse_from_str(fields.next()?, TIME_FORMAT).ok()?;
+            let when = Local.from_local_datetime(&when).single()?;
+            let name = fields.next()?.to_string();
+            let location = fields.next()?.to_string();
+            let text = fields.next()?;
+            let dry_run = text.ends_with("(dry run)");
+            let outcome = match text {
+                "Disabled" => Ok(()),
+                _ if dry_run => Ok(()),
+                other => Err(other.trim_start_matches("Failed: ").to_string()),
+            };
+            Some(EnforcementRecord {
+                when,
+                key: String::new(),
+                name,
+                location,
+                outcome,
+                dry_run,
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+fn append_log(records: &[EnforcementRecord]) {
+    if records.is_empty() {
+        return;
+    }
+    let Some(path) = log_path() else {
+        return;
+    };
+    let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) else {
+        return;
+    };
+    for r in records {
+        let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+        let _ = writeln!(
+            file,
+            "{}\t{}\t{}\t{}",
+            r.when.format(TIME_FORMAT),
+            clean(&r.name),
+            clean(&r.location),
+            clean(&r.outcome_text())
+        );
+    }
+}
+
+fn log_path() -> Option<std::path::PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(LOG_FILE_NAME))
+}
@@ -0,0 +1,96 @@
+//! Enrichment providers: optional integrations that add columns and badges
+//! to startup entries, services, and processes after they are collected.
+//! Collectors stay unaware of them; each provider is compiled in behind its
+//! own cargo feature and registered in [`providers`].
+//!
+//! To add one, implement [`Provider`] in a submodule, gate the module and its
+//! registration on a feature, and list the feature in `Cargo.toml`.
+
+// With no provider compiled in, nothing reads the subjects
+#![cfg_attr(not(feature = "signature"), allow(dead_code))]
+
+#[cfg(feature = "signature")]
+mod signature;
+
+use crate::cmdline;
+use crate::models::{Enrichment, ProcessInfo, StartupEntry};
+use crate::parallel;
+use std::sync::OnceLock;
+
+/// What a provider is asked about. Running services and processes carry
+/// their PID.
+pub enum Subject<'a> {
+    /// A startup entry or a service.
+    Entry(&'a StartupEntry),
+    Process(&'a ProcessInfo),
+}
+
+impl Subject<'_> {
+    /// The file that runs: a startup entry's target (the DLL for rundll32
+    /// commands) or a process's image.
+    pub fn path(&self) -> String {
+        match self {
+            Subject::Entry(entry) => cmdline::target_path(&entry.command),
+            Subject::Process(proc) => proc.exe_path.clone(),
+        }
+    }
+}
+
+pub trait Provider: Send + Sync {
+    /// Columns this provider fills, in display order. Names must not clash
+    /// with the built-in columns.
+    fn columns(&self) -> &'static [&'static str];
+
+    /// Add this provider's values and badges for `subject` to `out`. Called
+    /// from worker threads, once per item on every load, so slow lookups
+    /// should be cached.
+    fn enrich(&self, subject: &Subject<'_>, out: &mut Enrichment);
+}
+
+/// The providers compiled into this build.
+fn providers() -> &'static [Box<dyn Provider>] {
+    static PROVIDERS: OnceLock<Vec<Box<dyn Provider>>> = OnceLock::new();
+    PROVIDERS.get_or_init(|| {
+        vec![
+            #[cfg(feature = "signature")]
+            Box::new(signature::SignatureProvider::default()),
+        ]
+    })
+}
+
+/// Every provider column, in display order.
+pub fn columns() -> Vec<&'static str> {
+    providers().iter().flat_map(|p| p.columns().iter().copied()).collect()
+}
+
+pub fn is_column(column: &str) -> bool {
+    providers().iter().any(|p| p.columns().contains(&column))
+}
+
+pub fn enrich_entries(entries: &mut [StartupEntry]) {
+    if providers().is_empty() {
+        return;
+    }
+    let results = parallel::map(entries, |e| enrich(&Subject::Entry(e)));
+    for (entry, enrichment) in entries.iter_mut().zip(results) {
+        entry.enrichment = enrichment;
+    }
+}
+
+pub fn enrich_processes(processes: &mut [ProcessInfo]) {
+    if providers().is_empty() {
+        return;
+    }
+    let results = parallel::map(processes, |p| enrich(&Subject::Process(p)));
+    for (proc, enrichment) in processes.iter_mut().zip(results) {
+        proc.enrichment = enrichment;
+    }
+}
+
+fn enrich(subject: &Subject<'_>) -> Enrichment {
+    let mut out = Enrichment::default();
+    for provider in providers() {
+        provider.enrich(subject, &mut out);
+    }
+    out
+}
@@ -0,0 +1,263 @@
+//! Authenticode signature check with `WinVerifyTrust`. Files without an
+//! embedded signature are also looked up in the system catalogs, which is how
+//! most of Windows' own binaries are signed.
+//!
+//! An embedded signature with a timestamp countersignature also gives the
+//! time the file was signed. A catalog's timestamp is when the catalog was
+//! signed, not the file, so catalog-signed files have none.
+
+use super::{Provider, Subject};
+use crate::execution_history::normalize_path;
+use crate::models::{Badge, Enrichment};
+use crate::status;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use std::os::windows::io::AsRawHandle;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use windows::core::{w, GUID, HRESULT, HSTRING, PCWSTR};
+use windows::Win32::Foundation::{HANDLE, HWND, TRUST_E_NOSIGNATURE, TRUST_E_SUBJECT_FORM_UNKNOWN};
+use windows::Win32::Security::Cryptography::Catalog::{
+    CryptCATAdminAcquireContext2, CryptCATAdminCalcHashFromFileHandle2, CryptCATAdminEnumCatalogFromHash,
+    CryptCATAdminReleaseCatalogContext, CryptCATAdminReleaseContext, CryptCATCatalogInfoFromContext, CATALOG_INFO,
+};
+use windows::Win32::Security::Cryptography::{CertGetNameStringW, CERT_NAME_SIMPLE_DISPLAY_TYPE};
+use windows::Win32::Security::WinTrust::{
+    WTHelperGetProvSignerFromChain, WTHelperProvDataFromStateData, WinVerifyTrust, WINTRUST_ACTION_GENERIC_VERIFY_V2,
+    WINTRUST_CATALOG_INFO, WINTRUST_DATA, WINTRUST_DATA_0, WINTRUST_FILE_INFO, WTD_CACHE_ONLY_URL_RETRIEVAL,
+    WTD_CHOICE_CATALOG, WTD_CHOICE_FILE, WTD_REVOKE_NONE, WTD_STATEACTION_CLOSE, WTD_STATEACTION_VERIFY,
+    WTD_UI_NONE,
+};
+
+const COLUMNS: &[&str] = &["Signature", "Signer", "Signed On"];
+
+/// Path (normalized), modification time, and size of a checked file.
+type CacheKey = (String, Option<SystemTime>, u64);
+/// The signer's name and, if timestamped, when it signed.
+type Signer = (String, Option<DateTime<Local>>);
+
+#[derive(Default)]
+pub struct SignatureProvider {
+    /// The same binaries show up in several tabs and on every refresh, and a
+    /// catalog lookup hashes the whole file.
+    cache: Mutex<HashMap<CacheKey, Enrichment>>,
+}
+
+impl Provider for SignatureProvider {
+    fn columns(&self) -> &'static [&'static str] {
+        COLUMNS
+    }
+
+    fn enrich(&self, subject: &Subject<'_>, out: &mut Enrichment) {
+        let path = subject.path();
+        let Ok(meta) = std::fs::metadata(&path) else {
+            return;
+        };
+        let key = (normalize_path(&path), meta.modified().ok(), meta.len());
+        let cached = self.cache.lock().ok().and_then(|cache| cache.get(&key).cloned());
+        let result = cached.unwrap_or_else(|| {
+            let result = check(&path).into_enrichment();
+            if let Ok(mut cache) = self.cache.lock() {
+                cache.insert(key, result.clone());
+            }
+            result
+        });
+        out.values.extend(result.values);
+        out.badges.extend(result.badges);
+    }
+}
+
+enum Status {
+    Signed {
+        signer: String,
+        catalog: bool,
+        /// From the timestamp countersignature, if there is one.
+        signed_on: Option<DateTime<Local>>,
+    },
+    Unsigned,
+    Invalid(String),
+}
+
+impl Status {
+    fn into_enrichment(self) -> Enrichment {
+        match self {
+            Status::Signed { signer, catalog, signed_on } => Enrichment {
+                values: vec![
+                    ("Signature", if catalog { "Signed (catalog)" } else { "Signed" }.to_string()),
+                    ("Signer", signer),
+                    (
+                        "Signed On",
+                        signed_on.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default(),
+                    ),
+                ],
+                badges: Vec::new(),
+            },
+            Status::Unsigned => Enrichment {
+                values: vec![
+                    ("Signature", "Unsigned".to_string()),
+                    ("Signer", String::new()),
+                    ("Signed On", String::new()),
+                ],
+                badges: vec![Badge {
+                    text: "UNSIGNED".to_string(),
+                    tooltip: "No Authenticode signature, embedded or in a system catalog".to_string(),
+                    warning: true,
+                }],
+            },
+            Status::Invalid(reason) => Enrichment {
+                values: vec![
+                    ("Signature", format!("Invalid: {}", reason)),
+                    ("Signer", String::new()),
+                    ("Signed On", String::new()),
+                ],
+                badges: vec![Badge {
+                    text: "BAD SIGNATURE".to_string(),
+                    tooltip: format!("The signature doesn't verify: {}", reason),
+                    warning: true,
+                }],
+            },
+        }
+    }
+}
+
+fn check(path: &str) -> Status {
+    let wide = HSTRING::from(path);
+    let mut file_info = WINTRUST_FILE_INFO {
+        cbStruct: std::mem::size_of::<WINTRUST_FILE_INFO>() as u32,
+        pcwszFilePath: PCWSTR(wide.as_ptr()),
+        ..Default::default()
+    };
+    let mut data = WINTRUST_DATA {
+        dwUnionChoice: WTD_CHOICE_FILE,
+        Anonymous: WINTRUST_DATA_0 { pFile: &mut file_info },
+        ..Default::default()
+    };
+    match unsafe { verify(&mut data) } {
+        Ok((signer, signed_on)) => Status::Signed {
+            signer,
+            catalog: false,
+            signed_on,
+        },
+        Err(code) if code == TRUST_E_NOSIGNATURE || code == TRUST_E_SUBJECT_FORM_UNKNOWN => {
+            match unsafe { verify_catalog(path, &wide) } {
+                Some(Ok((signer, _))) => Status::Signed {
+                    signer,
+                    catalog: true,
+                    signed_on: None,
+                },
+                Some(Err(code)) => Status::Invalid(reason(code)),
+                None => Status::Unsigned,
+            }
+        }
+        Err(code) => Status::Invalid(reason(code)),
+    }
+}
+
+fn reason(code: HRESULT) -> String {
+    windows::core::Error::from_hresult(code).message().trim().to_string()
+}
+
+/// Verify the file or catalog member `data` points at, returning the
+/// signer's name and signing time. Revocation isn't checked, so nothing is
+/// downloaded.
+unsafe fn verify(data: &mut WINTRUST_DATA) -> Result<Signer, HRESULT> {
+    let mut action: GUID = WINTRUST_ACTION_GENERIC_VERIFY_V2;
+    data.cbStruct = std::mem::size_of::<WINTRUST_DATA>() as u32;
+    data.dwUIChoice = WTD_UI_NONE;
+    data.fdwRevocationChecks = WTD_REVOKE_NONE;
+    data.dwProvFlags = WTD_CACHE_ONLY_URL_RETRIEVAL;
+    data.dwStateAction = WTD_STATEACTION_VERIFY;
+    let status = WinVerifyTrust(HWND::default(), &mut action, data as *mut WINTRUST_DATA as *mut _);
+    let signer = if status == 0 {
+        signer_info(data.hWVTStateData)
+    } else {
+        (String::new(), None)
+    };
+    // Release the state kept for reading the signer
+    data.dwStateAction = WTD_STATEACTION_CLOSE;
+    WinVerifyTrust(HWND::default(), &mut action, data as *mut WINTRUST_DATA as *mut _);
+    if status == 0 {
+        Ok(signer)
+    } else {
+        Err(HRESULT(status))
+    }
+}
+
+/// Subject name of the first signer's certificate, and the time it signed
+/// if a timestamp countersignature vouches for it.
+unsafe fn signer_info(state: HANDLE) -> Signer {
+    let provider_data = WTHelperProvDataFromStateData(state);
+    if provider_data.is_null() {
+        return (String::new(), None);
+    }
+    let signer = WTHelperGetProvSignerFromChain(provider_data, 0, false, 0);
+    if signer.is_null() || (*signer).csCertChain == 0 || (*signer).pasCertChain.is_null() {
+        return (String::new(), None);
+    }
+    // Without a countersignature this is the time of the check instead
+    let signed_on = ((*signer).csCounterSigners > 0)
+        .then(|| {
+            let ft = (*signer).sftVerifyAsOf;
+            status::filetime_to_datetime(((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64)
+        })
+        .flatten();
+    let cert = (*(*signer).pasCertChain).pCert;
+    let mut name = [0u16; 256];
+    let len = CertGetNameStringW(cert, CERT_NAME_SIMPLE_DISPLAY_TYPE, 0, None, Some(&mut name)) as usize;
+    // The length includes the terminating null
+    let name = String::from_utf16_lossy(&name[..len.saturating_sub(1).min(name.len())]);
+    (name, signed_on)
+}
+
+/// Verify a file against the system catalog that lists its hash. `None` if
+/// no catalog does.
+unsafe fn verify_catalog(path: &str, wide: &HSTRING) -> Option<Result<Signer, HRESULT>> {
+    let file = std::fs::File::open(path).ok()?;
+    let handle = HANDLE(file.as_raw_handle());
+    let mut admin = 0isize;
+    CryptCATAdminAcquireContext2(&mut admin, None, w!("SHA256"), None, None).ok()?;
+
+    let lookup = || {
+        // The first call only reports the hash size
+        let mut size = 0u32;
+        let _ = CryptCATAdminCalcHashFromFileHandle2(admin, handle, &mut size, None, None);
+        if size == 0 {
+            return None;
+        }
+        let mut hash = vec![0u8; size as usize];
+        CryptCATAdminCalcHashFromFileHandle2(admin, handle, &mut size, Some(hash.as_mut_ptr()), None).ok()?;
+        let catalog = CryptCATAdminEnumCatalogFromHash(admin, &hash, None, None);
+        if catalog == 0 {
+            return None;
+        }
+        let mut info = CATALOG_INFO {
+            cbStruct: std::mem::size_of::<CATALOG_INFO>() as u32,
+            ..Default::default()
+        };
+        let result = CryptCATCatalogInfoFromContext(catalog, &mut info, 0).ok().map(|_| {
+            let tag = HSTRING::from(hash.iter().map(|b| format!("{:02X}", b)).collect::<String>());
+            let mut catalog_info = WINTRUST_CATALOG_INFO {
+                cbStruct: std::mem::size_of::<WINTRUST_CATALOG_INFO>() as u32,
+                pcwszCatalogFilePath: PCWSTR(info.wszCatalogFile.as_ptr()),
+                pcwszMemberTag: PCWSTR(tag.as_ptr()),
+                pcwszMemberFilePath: PCWSTR(wide.as_ptr()),
+                hMemberFile: handle,
+                hCatAdmin: admin,
+                ..Default::default()
+            };
+            let mut data = WINTRUST_DATA {
+                dwUnionChoice: WTD_CHOICE_CATALOG,
+                Anonymous: WINTRUST_DATA_0 {
+                    pCatalog: &mut catalog_info,
+                },
+                ..Default::default()
+            };
+            verify(&mut data)
+        });
+        let _ = CryptCATAdminReleaseCatalogContext(admin, catalog, 0);
+        result
+    };
+    let result = lookup();
+    let _ = CryptCATAdminReleaseContext(admin, 0);
+    result
+}
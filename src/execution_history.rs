@@ -0,0 +1,177 @@
+//! Program execution history for the Execution History tab, from the two
+//! records Windows keeps for application compatibility:
+//!
+//! - Amcache (`C:\Windows\AppCompat\Programs\Amcache.hve`): one key per file
+//!   under `Root\InventoryApplicationFile`, with the path, SHA-1, publisher
+//!   and version. The key's last write time is when the file was first seen.
+//!   Opened as an application hive; requires admin rights.
+//! - Shimcache (`AppCompatCache` registry value): executables the
+//!   compatibility engine has looked at, most recent first, with the file's
+//!   modification time. Written at shutdown, so it lags the current session.
+//!
+//! Neither proves a program ran, only that Windows saw it; the tab says so.
+
+use crate::models::{ExecutionHistory, ExecutionRecord};
+use crate::status::filetime_to_datetime;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use winreg::enums::*;
+use winreg::RegKey;
+
+const AMCACHE_PATH: &str = r"C:\Windows\AppCompat\Programs\Amcache.hve";
+const AMCACHE_FILES_KEY: &str = r"Root\InventoryApplicationFile";
+const SHIMCACHE_KEY: &str = r"SYSTEM\CurrentControlSet\Control\Session Manager\AppCompatCache";
+/// Signature of each Windows 10/11 Shimcache entry.
+const SHIMCACHE_ENTRY_SIGNATURE: &[u8; 4] = b"10ts";
+
+/// A Shimcache path and the file modification time recorded with it.
+type ShimcacheEntry = (String, Option<DateTime<Local>>);
+
+/// Read Amcache and the Shimcache, merging rows for the same path.
+pub fn collect_execution_history() -> ExecutionHistory {
+    let mut notes = Vec::new();
+    let mut by_path: HashMap<String, ExecutionRecord> = HashMap::new();
+
+    match read_amcache() {
+        Ok(records) => {
+            for record in records {
+                by_path.insert(normalize_path(&record.path), record);
+            }
+        }
+        Err(e) => notes.push(format!("Amcache: {}", e)),
+    }
+
+    match read_shimcache() {
+        Ok(entries) => {
+            for (position, (path, modified)) in entries.into_iter().enumerate() {
+                let record = by_path.entry(normalize_path(&path)).or_insert_with(|| ExecutionRecord {
+                    name: file_name(&path),
+                    path: path.clone(),
+                    ..Default::default()
+                });
+                // Keep the most recent position if a path repeats
+                if record.shimcache_position.is_none() {
+                    record.shimcache_position = Some(position);
+                    record.file_modified = modified;
+                }
+            }
+        }
+        Err(e) => notes.push(format!("Shimcache: {}", e)),
+    }
+
+    let mut records: Vec<ExecutionRecord> = by_path.into_values().collect();
+    // Newest first: Amcache first-seen time, then Shimcache order
+    records.sort_by(|a, b| {
+        b.first_seen
+            .cmp(&a.first_seen)
+            .then(a.shimcache_position.unwrap_or(usize::MAX).cmp(&b.shimcache_position.unwrap_or(usize::MAX)))
+    });
+    ExecutionHistory { records, notes }
+}
+
+/// Lower-cased path without the `\??\` NT prefix, for matching rows across
+/// sources and against the other tabs.
+pub fn normalize_path(path: &str) -> String {
+    path.trim_start_matches(r"\??\").to_lowercase()
+}
+
+fn read_amcache() -> Result<Vec<ExecutionRecord>, String> {
+    // Not locked (options 0), so the hive Windows already has loaded is shared
+    let hive = RegKey::load_app_key_with_flags(AMCACHE_PATH, KEY_READ, 0).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            "requires administrator rights".to_string()
+        } else {
+            e.to_string()
+        }
+    })?;
+    let files = hive
+        .open_subkey_with_flags(AMCACHE_FILES_KEY, KEY_READ)
+        .map_err(|_| "older Amcache format (before Windows 10 1709) is not supported".to_string())?;
+
+    let mut records = Vec::new();
+    for id in files.enum_keys().flatten() {
+        let Ok(key) = files.open_subkey_with_flags(&id, KEY_READ) else {
+            continue;
+        };
+        let path: String = key.get_value("LowerCaseLongPath").unwrap_or_default();
+        if path.is_empty() {
+            continue;
+        }
+        let get = |name: &str| key.get_value::<String, _>(name).unwrap_or_default();
+        // FileId is the SHA-1 prefixed with four zeros
+        let file_id = get("FileId");
+        let sha1 = file_id
+            .strip_prefix("0000")
+            .filter(|hash| hash.len() == 40)
+            .map(str::to_string);
+        let first_seen = key.query_info().ok().and_then(|info| {
+            let ft = &info.last_write_time;
+            filetime_to_datetime(((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64)
+        });
+        let name = match get("Name") {
+            n if n.is_empty() => file_name(&path),
+            n => n,
+        };
+        records.push(ExecutionRecord {
+            name,
+            publisher: get("Publisher"),
+            product_name: get("ProductName"),
+            version: get("Version"),
+            sha1,
+            first_seen,
+            in_amcache: true,
+            path,
+            ..Default::default()
+        });
+    }
+    Ok(records)
+}
+
+/// Parse the Windows 10/11 `AppCompatCache` value into (path, file
+/// modification time), most recent first.
+fn read_shimcache() -> Result<Vec<ShimcacheEntry>, String> {
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey_with_flags(SHIMCACHE_KEY, KEY_READ)
+        .map_err(|e| e.to_string())?;
+    let data = key.get_raw_value("AppCompatCache").map_err(|e| e.to_string())?.bytes;
+
+    let read_u16 = |at: usize| data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize);
+    let read_u32 =
+        |at: usize| data.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+
+    // The header size (0x30 or 0x34) is the first field
+    let mut offset = read_u32(0).ok_or("value is empty")?;
+    if data.get(offset..offset + 4) != Some(SHIMCACHE_ENTRY_SIGNATURE.as_slice()) {
+        return Err("unsupported format (Windows 10 or later is required)".to_string());
+    }
+
+    let mut entries = Vec::new();
+    while data.get(offset..offset + 4) == Some(SHIMCACHE_ENTRY_SIGNATURE.as_slice()) {
+        // signature, unknown, entry size, then the entry itself
+        let Some(entry_size) = read_u32(offset + 8) else {
+            break;
+        };
+        let entry_start = offset + 12;
+        let Some(path_len) = read_u16(entry_start) else {
+            break;
+        };
+        let path_start = entry_start + 2;
+        let Some(path_bytes) = data.get(path_start..path_start + path_len) else {
+            break;
+        };
+        let wide: Vec<u16> = path_bytes
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .collect();
+        let modified = data
+            .get(path_start + path_len..path_start + path_len + 8)
+            .and_then(|b| filetime_to_datetime(u64::from_le_bytes(b.try_into().unwrap())));
+        entries.push((String::from_utf16_lossy(&wide), modified));
+        offset = entry_start + entry_size;
+    }
+    Ok(entries)
+}
+
+fn file_name(path: &str) -> String {
+    path.rsplit(['\\', '/']).next().unwrap_or(path).to_string()
+}
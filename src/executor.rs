@@ -0,0 +1,169 @@
+//! Single-writer action executor: every change to the registry, a service, a
+//! scheduled task, the Startup folder, or a developer package runs on one background thread, one
+//! at a time in the order requested, so the window never waits on `sc`,
+//! `schtasks`, or a slow registry hive.
+//!
+//! Each job runs on a worker the executor waits on for at most [`TIMEOUT`].
+//! A job that takes longer is reported as timed out and the executor moves
+//! on, so a hung job may still be running alongside later ones; its result
+//! is still reported if it ever finishes. A job that panics is reported as
+//! failed.
+
+use crate::actions::{self, NewEntryLocation};
+use crate::crash;
+use crate::dev_packages;
+use crate::models::{DevPackage, Source, StartupEntry, TaskConditions};
+use crate::service_hardening::{self, StartMode};
+use crate::task_scheduler;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long a job may run before the executor stops waiting for it.
+pub const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A change to make.
+#[derive(Debug, Clone)]
+pub enum Job {
+    Enable(StartupEntry),
+    Disable(StartupEntry),
+    Start(StartupEntry),
+    Stop(StartupEntry),
+    Delete(StartupEntry),
+    /// Replace the arguments of the entry's command.
+    SetArguments(StartupEntry, String),
+    /// Set the conditions of a scheduled task entry.
+    SetTaskConditions(StartupEntry, TaskConditions),
+    /// Set the start type of a service entry.
+    SetStartMode(StartupEntry, StartMode),
+    Add {
+        name: String,
+        command: String,
+        location: NewEntryLocation,
+    },
+    /// Remove a developer package through its package manager.
+    UninstallPackage(DevPackage),
+}
+
+impl Job {
+    /// The entry the job changes (none for Add and package uninstalls).
+    pub fn entry(&self) -> Option<&StartupEntry> {
+        match self {
+            Job::Enable(entry)
+            | Job::Disable(entry)
+            | Job::Start(entry)
+            | Job::Stop(entry)
+            | Job::Delete(entry)
+            | Job::SetArguments(entry, _)
+            | Job::SetTaskConditions(entry, _)
+            | Job::SetStartMode(entry, _) => Some(entry),
+            Job::Add { .. } | Job::UninstallPackage(_) => None,
+        }
+    }
+
+    /// What the job does, e.g. "Disable 'OneDrive'".
+    pub fn label(&self) -> String {
+        let verb = match self {
+            Job::Enable(_) => "Enable",
+            Job::Disable(_) => "Disable",
+            Job::Start(_) => "Start",
+            Job::Stop(_) => "Stop",
+            Job::Delete(_) => "Delete",
+            Job::SetArguments(..) => "Change arguments of",
+            Job::SetTaskConditions(..) => "Change conditions of",
+            Job::SetStartMode(..) => "Change start type of",
+            Job::Add { name, .. } => return format!("Add '{}' to startup", name),
+            Job::UninstallPackage(package) => {
+                return format!("Uninstall {} package '{}'", package.manager.label(), package.name)
+            }
+        };
+        let name = self.entry().map_or("", |e| &e.name);
+        format!("{} '{}'", verb, name)
+    }
+
+    fn run(&self) -> anyhow::Result<()> {
+        match self {
+            Job::Enable(entry) => actions::enable_entry(entry),
+            Job::Disable(entry) => actions::disable_entry(entry),
+            Job::Start(entry) => actions::start_entry(entry),
+            Job::Stop(entry) => actions::stop_entry(entry),
+            Job::Delete(entry) => actions::delete_entry(entry),
+            Job::SetArguments(entry, args) => actions::set_entry_arguments(entry, args),
+            Job::SetTaskConditions(entry, conditions) => match &entry.source {
+                Source::TaskScheduler { task_path } => task_scheduler::set_task_conditions(task_path, conditions),
+                _ => anyhow::bail!("'{}' is not a scheduled task", entry.name),
+            },
+            Job::SetStartMode(entry, mode) => service_hardening::set_start_mode(entry, *mode),
+            Job::Add { name, command, location } => actions::add_entry(name, command, *location),
+            Job::UninstallPackage(package) => dev_packages::uninstall(package),
+        }
+    }
+}
+
+/// Progress of a submitted job, by the id [`Executor::submit`] returned.
+#[derive(Debug, Clone)]
+pub enum Update {
+    Started(u64),
+    /// The job finished; the error is formatted for the status bar.
+    Finished(u64, Result<(), String>),
+    /// The job has run for [`TIMEOUT`]; the executor has moved on to later
+    /// jobs, and a `Finished` follows if it ever completes.
+    TimedOut(u64),
+}
+
+/// Runs jobs until dropped (the thread ends once the queue is empty).
+pub struct Executor {
+    requests: mpsc::Sender<(u64, Job)>,
+    pub updates: mpsc::Receiver<Update>,
+    next_id: u64,
+}
+
+impl Executor {
+    /// Start the executor thread. `wake` is called after each update (e.g.
+    /// to repaint the window).
+    pub fn start(wake: impl Fn() + Send + Sync + 'static) -> Self {
+        let (requests, jobs) = mpsc::channel::<(u64, Job)>();
+        let (tx, updates) = mpsc::channel();
+        let wake = std::sync::Arc::new(wake);
+        std::thread::spawn(move || {
+            for (id, job) in jobs {
+                let _ = tx.send(Update::Started(id));
+                wake();
+                let (done_tx, done) = mpsc::channel();
+                let worker_tx = tx.clone();
+                let worker_wake = wake.clone();
+                std::thread::spawn(move || {
+                    let result = crash::catch_unwind(|| job.run())
+                        .unwrap_or_else(|_| Err(anyhow::anyhow!("the change stopped unexpectedly")))
+                        .map_err(|e| format!("{:#}", e));
+                    let _ = done_tx.send(());
+                    let _ = worker_tx.send(Update::Finished(id, result));
+                    worker_wake();
+                });
+                match done.recv_timeout(TIMEOUT) {
+                    Ok(()) => {}
+                    Err(mpsc::RecvTimeoutError::Timeout) => {
+                        let _ = tx.send(Update::TimedOut(id));
+                        wake();
+                    }
+                    // The worker ended without reporting a result
+                    Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        let _ = tx.send(Update::Finished(id, Err("the change stopped unexpectedly".to_string())));
+                        wake();
+                    }
+                }
+            }
+        });
+        Self {
+            requests,
+            updates,
+            next_id: 0,
+        }
+    }
+
+    /// Queue a job behind any still running. Returns its id.
+    pub fn submit(&mut self, job: Job) -> u64 {
+        self.next_id += 1;
+        let _ = self.requests.send((self.next_id, job));
+        self.next_id
+    }
+}
@@ -0,0 +1,128 @@
+//! Translated column names and metadata labels for CSV exports. Only header
+//! text is translated; values stay as the app shows them, so exports from
+//! machines set to different languages can still be compared.
+
+use crate::settings::ExportLanguage;
+use windows::Win32::Globalization::GetUserDefaultUILanguage;
+
+/// English, German, French, and Spanish for each header and label. Text
+/// without an entry is written in English.
+const TERMS: &[[&str; 4]] = &[
+    // Columns
+    ["Name", "Name", "Nom", "Nombre"],
+    ["Product Name", "Produktname", "Nom du produit", "Nombre del producto"],
+    ["Command", "Befehl", "Commande", "Comando"],
+    ["Command Line", "Befehlszeile", "Ligne de commande", "Línea de comandos"],
+    ["Source", "Quelle", "Source", "Origen"],
+    ["Status", "Status", "Statut", "Estado"],
+    ["State", "Zustand", "État", "Ejecución"],
+    ["Runs As", "Ausgeführt als", "Exécuté en tant que", "Se ejecuta como"],
+    ["Visible As", "Sichtbar als", "Visible en tant que", "Visible como"],
+    ["Last Ran", "Zuletzt ausgeführt", "Dernière exécution", "Última ejecución"],
+    ["Seen Running", "Als aktiv gesehen", "Vu en cours d'exécution", "Visto en ejecución"],
+    ["Task Author", "Aufgabenautor", "Auteur de la tâche", "Autor de la tarea"],
+    ["Task Registered", "Aufgabe registriert", "Tâche enregistrée", "Tarea registrada"],
+    ["Script Payload", "Skriptinhalt", "Contenu du script", "Contenido del script"],
+    ["Bitness", "Bitbreite", "Bits", "Bits"],
+    ["PID", "PID", "PID", "PID"],
+    ["Parent PID", "Übergeordnete PID", "PID parent", "PID padre"],
+    ["CPU %", "CPU %", "Processeur %", "CPU %"],
+    ["Memory", "Arbeitsspeicher", "Mémoire", "Memoria"],
+    ["Last Started", "Zuletzt gestartet", "Dernier démarrage", "Último inicio"],
+    ["Installed", "Installiert", "Installé", "Instalado"],
+    ["Path", "Pfad", "Chemin", "Ruta"],
+    ["Kernel Time", "Kernelzeit", "Temps noyau", "Tiempo de kernel"],
+    ["User Time", "Benutzerzeit", "Temps utilisateur", "Tiempo de usuario"],
+    ["CPU Time", "CPU-Zeit", "Temps processeur", "Tiempo de CPU"],
+    ["Disk Read", "Datenträger gelesen", "Lecture disque", "Lectura de disco"],
+    ["Disk Write", "Datenträger geschrieben", "Écriture disque", "Escritura de disco"],
+    ["Start Time", "Startzeit", "Heure de démarrage", "Hora de inicio"],
+    ["Since Boot", "Seit Systemstart", "Depuis le démarrage", "Desde el arranque"],
+    ["Platform", "Plattform", "Plateforme", "Plataforma"],
+    ["Image Mismatch", "Abbild abweichend", "Image différente", "Imagen distinta"],
+    ["Orphaned By", "Zurückgelassen von", "Laissé par", "Dejado por"],
+    ["Publisher", "Herausgeber", "Éditeur", "Editor"],
+    ["Version", "Version", "Version", "Versión"],
+    ["Install Date", "Installationsdatum", "Date d'installation", "Fecha de instalación"],
+    ["Size (KB)", "Größe (KB)", "Taille (Ko)", "Tamaño (KB)"],
+    ["Uninstall Command", "Deinstallationsbefehl", "Commande de désinstallation", "Comando de desinstalación"],
+    ["Modify Path", "Änderungspfad", "Chemin de modification", "Ruta de modificación"],
+    ["Install Location", "Installationsort", "Emplacement d'installation", "Ubicación de instalación"],
+    ["MSI Product Code", "MSI-Produktcode", "Code produit MSI", "Código de producto MSI"],
+    ["First Seen", "Zuerst gesehen", "Première apparition", "Visto por primera vez"],
+    ["File Modified", "Datei geändert", "Fichier modifié", "Archivo modificado"],
+    ["Shimcache Position", "Shimcache-Position", "Position Shimcache", "Posición en Shimcache"],
+    ["Startup Entry", "Autostarteintrag", "Entrée de démarrage", "Entrada de inicio"],
+    ["Service", "Dienst", "Service", "Servicio"],
+    ["Running", "Aktiv", "En cours d'exécution", "En ejecución"],
+    ["Component", "Komponente", "Composant", "Componente"],
+    ["Details", "Details", "Détails", "Detalles"],
+    ["Signature", "Signatur", "Signature", "Firma"],
+    ["Signer", "Signaturgeber", "Signataire", "Firmante"],
+    ["Signed On", "Signiert am", "Signé le", "Firmado el"],
+    ["Built", "Erstellt", "Compilé", "Compilado"],
+    ["When", "Zeitpunkt", "Date", "Fecha"],
+    ["Item", "Element", "Élément", "Elemento"],
+    ["Location", "Speicherort", "Emplacement", "Ubicación"],
+    ["Action", "Aktion", "Action", "Acción"],
+    ["Old Value", "Alter Wert", "Ancienne valeur", "Valor anterior"],
+    ["New Value", "Neuer Wert", "Nouvelle valeur", "Valor nuevo"],
+    ["Manager", "Paketmanager", "Gestionnaire", "Gestor"],
+    // Tabs
+    ["Installed Apps", "Installierte Apps", "Applications installées", "Aplicaciones instaladas"],
+    ["Startup Apps", "Autostart-Apps", "Applications au démarrage", "Aplicaciones de inicio"],
+    ["Processes", "Prozesse", "Processus", "Procesos"],
+    ["Services", "Dienste", "Services", "Servicios"],
+    ["Execution History", "Ausführungsverlauf", "Historique d'exécution", "Historial de ejecución"],
+    ["Security", "Sicherheit", "Sécurité", "Seguridad"],
+    ["Dev Packages", "Entwicklerpakete", "Paquets de développement", "Paquetes de desarrollo"],
+    // Metadata header
+    ["Tab", "Registerkarte", "Onglet", "Pestaña"],
+    ["Machine", "Computer", "Ordinateur", "Equipo"],
+    ["User", "Benutzer", "Utilisateur", "Usuario"],
+    ["Exported", "Exportiert", "Exporté", "Exportado"],
+    ["App Version", "App-Version", "Version de l'application", "Versión de la aplicación"],
+    ["Administrator", "Administrator", "Administrateur", "Administrador"],
+    ["Filters", "Filter", "Filtres", "Filtros"],
+    ["Yes", "Ja", "Oui", "Sí"],
+    ["No", "Nein", "Non", "No"],
+    ["None", "Keine", "Aucun", "Ninguno"],
+];
+
+/// The language to write, with `System` resolved from the Windows display
+/// language (English if it isn't one of the translations).
+pub fn resolve(language: ExportLanguage) -> ExportLanguage {
+    if language != ExportLanguage::System {
+        return language;
+    }
+    // The low 10 bits of a LANGID are the primary language
+    match unsafe { GetUserDefaultUILanguage() } & 0x3FF {
+        0x07 => ExportLanguage::German,
+        0x0C => ExportLanguage::French,
+        0x0A => ExportLanguage::Spanish,
+        _ => ExportLanguage::English,
+    }
+}
+
+/// `text` in `language` (already resolved).
+pub fn translate(text: &str, language: ExportLanguage) -> &str {
+    let column = match language {
+        ExportLanguage::German => 1,
+        ExportLanguage::French => 2,
+        ExportLanguage::Spanish => 3,
+        ExportLanguage::English | ExportLanguage::System => return text,
+    };
+    TERMS
+        .iter()
+        .find(|terms| terms[0] == text)
+        .map_or(text, |terms| terms[column])
+}
+
+/// A CSV header row with each comma-separated column name translated.
+pub fn header_row(columns: &str, language: ExportLanguage) -> String {
+    columns
+        .split(',')
+        .map(|c| translate(c, language))
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,130 @@
+//! "Fit columns to content" for the tables: every column from the header's
+//! context menu, or one column by double-clicking its divider. Widths are
+//! measured from the text of every row, not just the visible ones, and
+//! applied by resetting the table's stored widths to the measured ones.
+
+use eframe::egui;
+use egui_extras::Column;
+use std::cell::Cell;
+
+/// Room for cell spacing and the small badges some cells show next to their text.
+const PADDING: f32 = 16.0;
+/// One long path shouldn't push every other column off screen.
+const MAX_WIDTH: f32 = 900.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    All,
+    Column(usize),
+}
+
+impl Fit {
+    pub fn includes(&self, column: usize) -> bool {
+        match self {
+            Fit::All => true,
+            Fit::Column(c) => *c == column,
+        }
+    }
+}
+
+/// Column widths for one frame of a table.
+pub struct ColumnFit {
+    ctx: egui::Context,
+    id: egui::Id,
+    /// Widths to apply this frame, if a fit was requested. `None` entries
+    /// keep the column's default.
+    widths: Option<Vec<Option<f32>>>,
+    next: Cell<usize>,
+}
+
+impl ColumnFit {
+    /// `salt` must be the table's `id_salt`. `measure` returns the width each
+    /// column needs (`None` for columns that aren't fit, like Actions) and
+    /// only runs when a fit was requested.
+    pub fn new(ui: &egui::Ui, salt: &str, measure: impl FnOnce() -> Vec<Option<f32>>) -> Self {
+        let id = ui.id().with(egui::Id::new(salt));
+        let current: Vec<f32> = ui.data(|d| d.get_temp(id.with("widths"))).unwrap_or_default();
+        // egui_extras looks for double-clicks on the dividers under a
+        // different id than it gives them, so check them here
+        let divider = (0..current.len()).find(|&i| {
+            ui.ctx()
+                .read_response(id.with("resize_column").with(i))
+                .is_some_and(|r| r.double_clicked())
+        });
+        let request = divider.map(Fit::Column).or_else(|| {
+            ui.data_mut(|d| {
+                let menu_request = d.get_temp::<Fit>(id.with("request"));
+                d.remove::<Fit>(id.with("request"));
+                menu_request
+            })
+        });
+        let widths = request.map(|fit| {
+            measure()
+                .into_iter()
+                .enumerate()
+                .map(|(i, needed)| match needed {
+                    Some(width) if fit.includes(i) => Some(width),
+                    _ => current.get(i).copied(),
+                })
+                .collect()
+        });
+        Self {
+            ctx: ui.ctx().clone(),
+            id,
+            widths,
+            next: Cell::new(0),
+        }
+    }
+
+    /// The next column, in table order: `initial` wide unless it was just fit.
+    pub fn column(&self, initial: f32, min: f32) -> Column {
+        let i = self.next.get();
+        self.next.set(i + 1);
+        let width = self.widths.as_ref().and_then(|w| w.get(i).copied().flatten());
+        Column::initial(width.unwrap_or(initial).max(min)).at_least(min)
+    }
+
+    /// Whether the table's stored widths have to be reset to apply a fit.
+    pub fn pending(&self) -> bool {
+        self.widths.is_some()
+    }
+
+    /// Remember the widths laid out this frame; fitting one column keeps the
+    /// others as they are.
+    pub fn record(&self, widths: &[f32]) {
+        self.ctx.data_mut(|d| d.insert_temp(self.id.with("widths"), widths.to_vec()));
+    }
+
+    /// Right-click menu for the header row.
+    pub fn context_menu(&self, header: &egui::Response) {
+        header.context_menu(|ui| {
+            if ui.button("Fit columns to content").clicked() {
+                self.ctx.data_mut(|d| d.insert_temp(self.id.with("request"), Fit::All));
+                self.ctx.request_repaint();
+                ui.close();
+            }
+            ui.label(
+                egui::RichText::new("Double-click a column divider to fit that column")
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        });
+    }
+}
+
+/// Width needed to show the widest of `texts` in full.
+pub fn text_width<S: AsRef<str>>(ui: &egui::Ui, texts: impl IntoIterator<Item = S>) -> f32 {
+    let font = egui::TextStyle::Body.resolve(ui.style());
+    let widest = ui.fonts_mut(|fonts| {
+        texts
+            .into_iter()
+            .map(|t| {
+                fonts
+                    .layout_no_wrap(t.as_ref().to_owned(), font.clone(), egui::Color32::WHITE)
+                    .size()
+                    .x
+            })
+            .fold(0.0, f32::max)
+    });
+    (widest + PADDING).min(MAX_WIDTH)
+}
@@ -1,6 +1,35 @@
-use crate::models::{EnabledStatus, RunState, Source};
+use super::help;
+use super::process_table;
+use crate::actions;
+use crate::allowlist;
+use crate::audit::{self, AuditRecord};
+use crate::backup::BackupItem;
+use crate::categories::Category;
+use crate::clean_boot;
+use crate::cmdline;
+use crate::connections::{self, Connection};
+use crate::crash::CrashReport;
+use crate::dependencies::Dependent;
+use crate::dev_packages;
+use crate::dry_run::Simulation;
+use crate::enforcement::{self, EnforcementRecord};
+use crate::installed_apps;
+use crate::kill_guard::KillGuard;
+use crate::launch_origin::LaunchOrigin;
+use crate::plan::{CleanupPlan, StepStatus};
+use crate::process_ui::DpiAwareness;
+use crate::processes;
+use crate::service_hardening::{AppliedChange, Suggestion};
+use crate::settings::{self, ExportLanguage, Settings, UsageThresholds};
+use crate::skipped::SkippedItems;
+use crate::vendors::VendorFootprint;
+use crate::watchlist::{KeepDisabledRule, Reversion};
+use crate::models::{
+    Bitness, CpuTimes, DevPackage, EnabledStatus, Enrichment, ImageMismatch, LastRanSource, LinkTime, RunHistoryStats, RunState, ScriptPayload, ServiceSecurity, ServiceTrigger, SessionOrigin, Source, TaskConditions, TaskRegistration,
+};
 use chrono::{DateTime, Local};
 use eframe::egui;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DialogResult {
@@ -18,12 +47,32 @@ pub struct ServicePropertiesInfo {
     pub status: RunState,
     pub startup_type: EnabledStatus,
     pub executable_path: String,
+    /// The file implementing the service (the ServiceDll for svchost services),
+    /// with environment variables expanded.
+    pub binary_path: String,
     pub log_on_as: String,
     pub product_name: String,
+    pub bitness: Option<Bitness>,
+    /// Conditions that start/stop a trigger-start service (empty if none).
+    pub triggers: Vec<ServiceTrigger>,
+    /// SID type and required privileges (`None` if the service couldn't be queried).
+    pub security: Option<ServiceSecurity>,
+    /// Values from enrichment providers.
+    pub enrichment: Enrichment,
+    pub dependencies: DependencyInfo,
+    /// Set when the user clicks "Open Location"; the caller opens Explorer
+    /// and clears it.
+    pub open_location_requested: bool,
+    /// Set when the user clicks "Windows Properties"; the caller opens the
+    /// native properties sheet and clears it.
+    pub windows_properties_requested: bool,
 }
 
 /// Show a service properties dialog. Returns true while the dialog is open.
-pub fn show_service_properties(ctx: &egui::Context, info: &ServicePropertiesInfo) -> DialogResult {
+pub fn show_service_properties(
+    ctx: &egui::Context,
+    info: &mut ServicePropertiesInfo,
+) -> DialogResult {
     let mut result = DialogResult::Open;
 
     // Constrain dialog to fit within the window content area (below title bar, above status bar)
@@ -52,9 +101,27 @@ pub fn show_service_properties(ctx: &egui::Context, info: &ServicePropertiesInfo
                         label_row(ui, "Startup Type:", &info.startup_type.to_string());
                         label_row(ui, "Log On As:", &info.log_on_as);
                         label_row_wrap(ui, "Executable:", &info.executable_path);
+                        if !info.binary_path.is_empty()
+                            && !info.executable_path.to_lowercase().contains(&info.binary_path.to_lowercase())
+                        {
+                            label_row_wrap(ui, "Binary:", &info.binary_path);
+                        }
                         if !info.product_name.is_empty() {
                             label_row(ui, "Product Name:", &info.product_name);
                         }
+                        if let Some(bitness) = info.bitness {
+                            label_row(ui, "Bitness:", &bitness.to_string());
+                        }
+                        if let Some(security) = &info.security {
+                            ui.label(egui::RichText::new("SID Type:").strong());
+                            ui.label(security.sid_type.to_string()).on_hover_text(
+                                "Unrestricted: the service has its own SID for access checks.\n\
+                                 Restricted: its token is also write-restricted to resources granted to that SID.\n\
+                                 None: no per-service SID.",
+                            );
+                            ui.end_row();
+                        }
+                        enrichment_rows(ui, &info.enrichment);
                     });
 
                 if !info.description.is_empty() {
@@ -66,19 +133,221 @@ pub fn show_service_properties(ctx: &egui::Context, info: &ServicePropertiesInfo
                     ui.label(&info.description);
                 }
 
+                if !info.triggers.is_empty() {
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new("Triggers").strong());
+                    ui.add_space(2.0);
+                    for trigger in &info.triggers {
+                        ui.label(format!("{} on: {}", trigger.action, trigger.condition));
+                        for item in &trigger.data {
+                            ui.indent(ui.id().with(item), |ui| {
+                                ui.add(
+                                    egui::Label::new(
+                                        egui::RichText::new(item).color(egui::Color32::GRAY),
+                                    )
+                                    .wrap(),
+                                );
+                            });
+                        }
+                    }
+                }
+
+                dependency_section(ui, &mut info.dependencies);
+
+                if let Some(security) = &info.security {
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.add_space(4.0);
+                    ui.label(egui::RichText::new("Required Privileges").strong());
+                    ui.add_space(2.0);
+                    if security.required_privileges.is_empty() {
+                        ui.label(
+                            egui::RichText::new(
+                                "None declared \u{2014} the service keeps every privilege of its account",
+                            )
+                            .color(egui::Color32::GRAY),
+                        );
+                    } else {
+                        for privilege in &security.required_privileges {
+                            ui.label(privilege);
+                        }
+                    }
+                }
+
                 ui.add_space(12.0);
                 ui.vertical_centered(|ui| {
-                    if ui.button("   Close   ").clicked() {
+                    ui.horizontal(|ui| {
+                        let has_binary = !info.binary_path.is_empty();
+                        if ui
+                            .add_enabled(has_binary, egui::Button::new("Open Location"))
+                            .on_hover_text("Show the service binary in Explorer")
+                            .clicked()
+                        {
+                            info.open_location_requested = true;
+                        }
+                        if ui
+                            .add_enabled(has_binary, egui::Button::new("Copy Path"))
+                            .on_hover_text("Copy the service binary path (variables expanded)")
+                            .clicked()
+                        {
+                            ui.ctx().copy_text(info.binary_path.clone());
+                        }
+                        if ui.button("Copy Name").clicked() {
+                            ui.ctx().copy_text(info.service_name.clone());
+                        }
+                        if ui
+                            .add_enabled(has_binary, egui::Button::new("Windows Properties"))
+                            .on_hover_text("Open the Windows file properties for the service binary")
+                            .clicked()
+                        {
+                            info.windows_properties_requested = true;
+                        }
+                        if ui.button("   Close   ").clicked() {
+                            result = DialogResult::Cancelled;
+                        }
+                    });
+                });
+                ui.add_space(4.0);
+            });
+        });
+
+    result
+}
+
+/// Dependencies section shared by the properties dialogs.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyInfo {
+    /// Key of the item the dialog shows.
+    pub key: String,
+    /// Items this one was noted as needing, as (key, name).
+    pub needs: Vec<(String, String)>,
+    /// Items that need this one, noted or inferred.
+    pub needed_by: Vec<Dependent>,
+    /// Services and startup entries offered under "Needs", as (key, name).
+    pub choices: Vec<(String, String)>,
+    pub selected: Option<usize>,
+    /// Key of the item to add or remove under "Needs"; the caller updates
+    /// the notes, refreshes this section, and clears it.
+    pub add_requested: Option<String>,
+    pub remove_requested: Option<String>,
+}
+
+fn dependency_section(ui: &mut egui::Ui, info: &mut DependencyInfo) {
+    ui.add_space(8.0);
+    ui.separator();
+    ui.add_space(4.0);
+    ui.label(egui::RichText::new("Dependencies").strong());
+    ui.add_space(2.0);
+    if info.needed_by.is_empty() {
+        ui.label(egui::RichText::new("Nothing is known to need this item").color(egui::Color32::GRAY));
+    } else {
+        ui.label("Needed by:");
+        for dependent in &info.needed_by {
+            ui.indent(ui.id().with(&dependent.name), |ui| {
+                ui.label(format!("{} ({})", dependent.name, dependent.reason));
+            });
+        }
+    }
+    ui.add_space(4.0);
+    ui.label("Needs:");
+    for (key, name) in &info.needs {
+        ui.indent(ui.id().with(key), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(name);
+                if ui.small_button("Remove").clicked() {
+                    info.remove_requested = Some(key.clone());
+                }
+            });
+        });
+    }
+    ui.horizontal(|ui| {
+        let selected_text = info
+            .selected
+            .and_then(|i| info.choices.get(i))
+            .map_or("Choose a service or startup entry", |(_, name)| name.as_str());
+        egui::ComboBox::from_id_salt("dependency_choice")
+            .width(260.0)
+            .selected_text(selected_text)
+            .show_ui(ui, |ui| {
+                for (i, (_, name)) in info.choices.iter().enumerate() {
+                    ui.selectable_value(&mut info.selected, Some(i), name);
+                }
+            });
+        if ui
+            .add_enabled(info.selected.is_some(), egui::Button::new("Add"))
+            .on_hover_text("Note that this item needs the chosen one, to be warned before disabling or stopping it")
+            .clicked()
+        {
+            info.add_requested = info.selected.take().and_then(|i| info.choices.get(i)).map(|(key, _)| key.clone());
+        }
+    });
+}
+
+/// Shown before disabling or stopping an item that something else needs.
+#[derive(Debug, Clone)]
+pub struct DependencyWarningInfo {
+    pub name: String,
+    /// "disable" or "stop".
+    pub verb: &'static str,
+    pub dependents: Vec<Dependent>,
+}
+
+/// Returns `Confirmed` to go ahead anyway.
+pub fn show_dependency_warning(ctx: &egui::Context, info: &DependencyWarningInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Other Items Need This")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label(format!(
+                "These items need '{}' and may stop working if you {} it:",
+                info.name, info.verb
+            ));
+            ui.add_space(6.0);
+            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                egui::Grid::new("dependency_warning_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for dependent in &info.dependents {
+                            ui.label(&dependent.name);
+                            ui.label(egui::RichText::new(&dependent.reason).color(egui::Color32::GRAY));
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    let verb = if info.verb == "stop" { "Stop" } else { "Disable" };
+                    if ui.button(format!("   {} Anyway   ", verb)).clicked() {
+                        result = DialogResult::Confirmed;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
                         result = DialogResult::Cancelled;
                     }
                 });
-                ui.add_space(4.0);
             });
+            ui.add_space(4.0);
         });
 
     result
 }
 
+/// Grid rows for the values enrichment providers added.
+fn enrichment_rows(ui: &mut egui::Ui, enrichment: &Enrichment) {
+    for (column, value) in &enrichment.values {
+        let value = if value.is_empty() { "\u{2014}" } else { value };
+        label_row_wrap(ui, &format!("{}:", column), value);
+    }
+}
+
 fn label_row(ui: &mut egui::Ui, label: &str, value: &str) {
     ui.label(egui::RichText::new(label).strong());
     ui.label(value);
@@ -91,101 +360,1911 @@ fn label_row_wrap(ui: &mut egui::Ui, label: &str, value: &str) {
     ui.end_row();
 }
 
-/// Show the About dialog.
-pub fn show_about(ctx: &egui::Context) -> DialogResult {
+/// Show the About dialog.
+pub fn show_about(ctx: &egui::Context) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("about_dialog")
+        .title_bar(false)
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(8.0);
+                ui.label(egui::RichText::new("App Manager").strong().size(18.0));
+                ui.add_space(2.0);
+                ui.label("v1.0.0");
+                ui.add_space(8.0);
+                ui.label("Copyright (C) 2026 Matt Smith");
+                ui.label("MIT License");
+                ui.add_space(8.0);
+                ui.hyperlink_to(
+                    "github.com/mattx86/app-manager",
+                    "https://github.com/mattx86/app-manager",
+                );
+                ui.add_space(12.0);
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+                ui.add_space(4.0);
+            });
+        });
+
+    result
+}
+
+/// Show what each row action does (Help > What do the actions do?).
+pub fn show_action_help(ctx: &egui::Context) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("What do the actions do?")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.set_max_width(460.0);
+            egui::Grid::new("action_help_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    for action in help::Action::ALL {
+                        label_row_wrap(ui, action.label(), action.help());
+                    }
+                });
+            ui.add_space(4.0);
+            ui.label(
+                egui::RichText::new("Hover over a button in the table to see what it changes for that entry.")
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+            ui.add_space(8.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+            });
+        });
+
+    result
+}
+
+/// `backed_up` is set for startup-folder files, which are moved to the backup
+/// store instead of being deleted outright.
+pub fn show_delete_confirmation(
+    ctx: &egui::Context,
+    entry_name: &str,
+    backed_up: bool,
+) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Confirm Delete")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(8.0);
+                ui.label(format!(
+                    "Are you sure you want to delete '{}'?",
+                    entry_name
+                ));
+                if backed_up {
+                    ui.label("The file is kept in App Manager's backup store and can be restored.");
+                } else {
+                    ui.label("This action cannot be undone.");
+                }
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button("   Yes, Delete   ").clicked() {
+                        result = DialogResult::Confirmed;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+                ui.add_space(8.0);
+            });
+        });
+
+    result
+}
+
+/// Confirm removing a dev package, showing the package manager command
+/// that will run.
+pub fn show_package_uninstall_confirmation(ctx: &egui::Context, package: &DevPackage) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Confirm Uninstall")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(460.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.add_space(8.0);
+                ui.label(format!(
+                    "Are you sure you want to uninstall the {} package '{}'?",
+                    package.manager.label(),
+                    package.name
+                ));
+            });
+            ui.add_space(8.0);
+
+            egui::Grid::new("package_uninstall_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    if !package.version.is_empty() {
+                        label_row(ui, "Version:", &package.version);
+                    }
+                    if !package.location.is_empty() {
+                        label_row_wrap(ui, "Location:", &package.location);
+                    }
+                    label_row_wrap(ui, "Command:", &dev_packages::command_line(package));
+                });
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("   Yes, Uninstall   ").clicked() {
+                        result = DialogResult::Confirmed;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+            ui.add_space(8.0);
+        });
+
+    result
+}
+
+/// Data for the Restore dialog listing backed-up startup-folder files.
+#[derive(Debug, Clone)]
+pub struct RestoreBackupsInfo {
+    pub items: Vec<BackupItem>,
+    /// Index of the item whose "Restore" was clicked; the caller restores it,
+    /// reloads the list, and clears this.
+    pub restore_requested: Option<usize>,
+    /// Index of the item whose "Delete" was clicked.
+    pub discard_requested: Option<usize>,
+}
+
+/// State of the Add Startup Entry dialog.
+pub struct AddStartupEntryInfo {
+    pub name: String,
+    pub command: String,
+    pub location: actions::NewEntryLocation,
+}
+
+impl AddStartupEntryInfo {
+    /// Pre-fill from a program or shortcut path (Explorer's "Add to Startup").
+    pub fn for_path(path: &str) -> Self {
+        let name = Path::new(path)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            name,
+            command: format!("\"{}\"", path.trim_matches('"')),
+            location: actions::NewEntryLocation::UserRun,
+        }
+    }
+}
+
+/// Show the Add Startup Entry dialog. `Confirmed` means "Add" was clicked.
+pub fn show_add_startup_entry(ctx: &egui::Context, info: &mut AddStartupEntryInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Add Startup Entry")
+        .collapsible(false)
+        .resizable(false)
+        .default_width(520.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            egui::Grid::new("add_startup_entry_grid")
+                .num_columns(2)
+                .spacing([12.0, 8.0])
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("Name:").strong());
+                    ui.add(egui::TextEdit::singleline(&mut info.name).desired_width(380.0));
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("Command:").strong());
+                    ui.add(
+                        egui::TextEdit::singleline(&mut info.command)
+                            .desired_width(380.0)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("Location:").strong());
+                    ui.vertical(|ui| {
+                        for location in actions::NewEntryLocation::ALL {
+                            ui.radio_value(&mut info.location, location, location.label());
+                        }
+                    });
+                    ui.end_row();
+                });
+
+            if info.location != actions::NewEntryLocation::UserStartupFolder
+                && cmdline::has_env_vars(&info.command)
+            {
+                ui.add_space(6.0);
+                ui.label(
+                    egui::RichText::new(
+                        "The command uses environment variables, so it is saved as REG_EXPAND_SZ \
+                         and they are expanded each time it runs.",
+                    )
+                    .weak(),
+                );
+            }
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    let ready = !info.name.trim().is_empty() && !info.command.trim().is_empty();
+                    if ui.add_enabled(ready, egui::Button::new("   Add   ")).clicked() {
+                        result = DialogResult::Confirmed;
+                    }
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+            ui.add_space(4.0);
+        });
+
+    result
+}
+
+/// Entries that came back after the user disabled or deleted them.
+pub struct ReversionAlertInfo {
+    pub items: Vec<Reversion>,
+    /// Index of the item whose "Disable Again" was clicked; the caller
+    /// disables it, removes it from `items`, and clears this.
+    pub disable_requested: Option<usize>,
+    /// Index of the item whose "Always Keep Disabled" was clicked.
+    pub keep_disabled_requested: Option<usize>,
+    /// Index of the item whose "Allow" was clicked (stop watching it).
+    pub allow_requested: Option<usize>,
+}
+
+/// Alert listing disabled entries that were re-enabled or re-created by
+/// their application, with options to disable them again.
+pub fn show_reversion_alert(ctx: &egui::Context, info: &mut ReversionAlertInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Disabled Entries Came Back")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(640.0)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label("These entries were disabled or deleted with App Manager, but their application has turned them back on.");
+            ui.add_space(8.0);
+            egui::ScrollArea::vertical().max_height(280.0).show(ui, |ui| {
+                egui::Grid::new("reversion_alert_grid")
+                    .num_columns(4)
+                    .spacing([12.0, 6.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Name");
+                        ui.strong("Change");
+                        ui.strong("Location");
+                        ui.label("");
+                        ui.end_row();
+
+                        for (i, item) in info.items.iter().enumerate() {
+                            ui.label(&item.name);
+                            ui.colored_label(egui::Color32::from_rgb(230, 160, 50), item.description());
+                            ui.add(egui::Label::new(&item.location).truncate())
+                                .on_hover_text(&item.location);
+                            ui.horizontal(|ui| {
+                                if ui.small_button("Disable Again").clicked() {
+                                    info.disable_requested = Some(i);
+                                }
+                                if ui
+                                    .small_button("Always Keep Disabled")
+                                    .on_hover_text("Disable it now and automatically whenever it comes back")
+                                    .clicked()
+                                {
+                                    info.keep_disabled_requested = Some(i);
+                                }
+                                if ui
+                                    .small_button("Allow")
+                                    .on_hover_text("Leave it enabled and stop watching it")
+                                    .clicked()
+                                {
+                                    info.allow_requested = Some(i);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+            });
+            ui.add_space(4.0);
+        });
+
+    result
+}
+
+/// State of the Keep-Disabled Rules dialog.
+pub struct KeepDisabledRulesInfo {
+    pub rules: Vec<KeepDisabledRule>,
+    /// Most recent enforcement actions, newest first.
+    pub activity: Vec<EnforcementRecord>,
+    /// Index of the rule whose "Remove" was clicked; the caller removes it,
+    /// reloads the list, and clears this.
+    pub remove_requested: Option<usize>,
+    /// Set when the user clicks "Copy Monitor Command".
+    pub copy_monitor_command_requested: bool,
+}
+
+/// Show the keep-disabled rules and the record of enforcement actions.
+pub fn show_keep_disabled_rules(ctx: &egui::Context, info: &mut KeepDisabledRulesInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(240.0);
+
+    egui::Window::new("Keep-Disabled Rules")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(620.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            if info.rules.is_empty() {
+                ui.label(
+                    egui::RichText::new(
+                        "No rules. Choose \"Always Keep Disabled\" when a disabled entry comes back to add one.",
+                    )
+                    .color(egui::Color32::GRAY),
+                );
+            } else {
+                egui::Grid::new("keep_disabled_rules_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (i, rule) in info.rules.iter().enumerate() {
+                            ui.label(&rule.name).on_hover_text(&rule.key);
+                            if ui.small_button("Remove").clicked() {
+                                info.remove_requested = Some(i);
+                            }
+                            ui.end_row();
+                        }
+                    });
+            }
+
+            ui.add_space(8.0);
+            ui.label(
+                egui::RichText::new(format!(
+                    "Rules are enforced while App Manager is open. To enforce them without the window, run it with {} at logon.",
+                    enforcement::MONITOR_FLAG
+                ))
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            if ui.small_button("Copy Monitor Command").clicked() {
+                info.copy_monitor_command_requested = true;
+            }
+
+            ui.add_space(8.0);
+            ui.separator();
+            ui.strong("Activity");
+            if info.activity.is_empty() {
+                ui.label(egui::RichText::new("No enforcement actions yet").color(egui::Color32::GRAY));
+            } else {
+                egui::ScrollArea::vertical().max_height(max_h - 260.0).show(ui, |ui| {
+                    egui::Grid::new("enforcement_activity_grid")
+                        .num_columns(3)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for record in &info.activity {
+                                ui.label(record.when.format("%Y-%m-%d %H:%M:%S").to_string());
+                                ui.label(&record.name).on_hover_text(&record.location);
+                                let color = if record.outcome.is_ok() {
+                                    egui::Color32::from_rgb(80, 200, 80)
+                                } else {
+                                    egui::Color32::from_rgb(230, 80, 80)
+                                };
+                                ui.colored_label(color, record.outcome_text());
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+            });
+        });
+
+    result
+}
+
+/// Requests from the Cleanup Plan dialog, handled and cleared by the caller.
+#[derive(Default)]
+pub struct CleanupPlanInfo {
+    /// Index of the step whose "Run" was clicked.
+    pub run_requested: Option<usize>,
+    /// Index of the step whose "Remove" was clicked.
+    pub remove_requested: Option<usize>,
+    pub export_requested: bool,
+    pub import_requested: bool,
+    pub clear_requested: bool,
+}
+
+/// Show the staged cleanup plan with each step's result. `staging` is the
+/// plan mode toggle; `busy` disables running while a refresh is in flight.
+pub fn show_cleanup_plan(
+    ctx: &egui::Context,
+    plan: &CleanupPlan,
+    staging: &mut bool,
+    busy: bool,
+    info: &mut CleanupPlanInfo,
+) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(240.0);
+
+    egui::Window::new("Cleanup Plan")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(640.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.checkbox(staging, "Stage changes instead of applying them")
+                .on_hover_text("While on, Enable, Disable, Start, Stop, and Uninstall add a step to this plan");
+            ui.add_space(4.0);
+
+            if plan.steps.is_empty() {
+                ui.label(
+                    egui::RichText::new(
+                        "No steps. Turn on staging and use the table buttons, or import a plan.",
+                    )
+                    .color(egui::Color32::GRAY),
+                );
+            } else {
+                egui::ScrollArea::vertical().max_height(max_h - 160.0).show(ui, |ui| {
+                    egui::Grid::new("cleanup_plan_grid")
+                        .num_columns(5)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (i, step) in plan.steps.iter().enumerate() {
+                                ui.label(format!("{}.", i + 1));
+                                ui.label(egui::RichText::new(step.action.label()).strong());
+                                ui.label(&step.name).on_hover_text(&step.target);
+                                match &step.status {
+                                    StepStatus::Pending => {
+                                        ui.label(egui::RichText::new("Pending").color(egui::Color32::GRAY));
+                                    }
+                                    StepStatus::Running => {
+                                        ui.colored_label(egui::Color32::from_rgb(100, 160, 230), "Running...");
+                                    }
+                                    StepStatus::Done(msg) => {
+                                        ui.colored_label(egui::Color32::from_rgb(80, 200, 80), msg);
+                                    }
+                                    StepStatus::Failed(msg) => {
+                                        ui.colored_label(egui::Color32::from_rgb(230, 80, 80), msg);
+                                    }
+                                }
+                                ui.horizontal(|ui| {
+                                    let runnable = !matches!(step.status, StepStatus::Done(_) | StepStatus::Running);
+                                    if runnable
+                                        && ui
+                                            .add_enabled(!busy, egui::Button::new("Run").small())
+                                            .on_disabled_hover_text("Wait for the refresh to finish")
+                                            .clicked()
+                                    {
+                                        info.run_requested = Some(i);
+                                    }
+                                    if ui.small_button("Remove").clicked() {
+                                        info.remove_requested = Some(i);
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                let next = plan.next_pending();
+                if ui
+                    .add_enabled(next.is_some() && !busy, egui::Button::new("Run Next Step"))
+                    .clicked()
+                {
+                    info.run_requested = next;
+                }
+                if ui.button("Export...").clicked() {
+                    info.export_requested = true;
+                }
+                if ui.button("Import...").clicked() {
+                    info.import_requested = true;
+                }
+                if ui.add_enabled(!plan.steps.is_empty(), egui::Button::new("Clear")).clicked() {
+                    info.clear_requested = true;
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("   Close   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+        });
+
+    result
+}
+
+/// Actions handled by the dry run and what each would have changed. `dry_run`
+/// is the mode toggle. Returns `Confirmed` when the user clears the log.
+pub fn show_dry_run_log(ctx: &egui::Context, log: &[Simulation], dry_run: &mut bool) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(240.0);
+
+    egui::Window::new("Dry Run")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(720.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.checkbox(dry_run, "Dry run: report what actions would change instead of changing it")
+                .on_hover_text("Also available as the --dry-run command-line flag");
+            ui.add_space(4.0);
+
+            if log.is_empty() {
+                ui.label(egui::RichText::new("No actions yet.").color(egui::Color32::GRAY));
+            } else {
+                egui::ScrollArea::vertical().max_height(max_h - 140.0).show(ui, |ui| {
+                    for simulation in log.iter().rev() {
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                egui::RichText::new(simulation.when.format("%H:%M:%S").to_string())
+                                    .color(egui::Color32::GRAY),
+                            );
+                            ui.label(egui::RichText::new(&simulation.action).strong());
+                        });
+                        match &simulation.changes {
+                            Ok(changes) if changes.is_empty() => {
+                                ui.label(egui::RichText::new("    Nothing would change").color(egui::Color32::GRAY));
+                            }
+                            Ok(changes) => {
+                                for change in changes {
+                                    ui.label(format!("    {}", change));
+                                }
+                            }
+                            Err(e) => {
+                                ui.colored_label(egui::Color32::from_rgb(230, 80, 80), format!("    Would fail: {}", e));
+                            }
+                        }
+                        ui.add_space(4.0);
+                    }
+                });
+            }
+
+            ui.add_space(12.0);
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!log.is_empty(), egui::Button::new("Copy")).clicked() {
+                    ctx.copy_text(dry_run_text(log));
+                }
+                if ui.add_enabled(!log.is_empty(), egui::Button::new("Clear")).clicked() {
+                    result = DialogResult::Confirmed;
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button("   Close   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+        });
+
+    result
+}
+
+/// The dry-run log as plain text, oldest first.
+fn dry_run_text(log: &[Simulation]) -> String {
+    let mut out = String::new();
+    for simulation in log {
+        out.push_str(&format!("{} {}\n", simulation.when.format("%Y-%m-%d %H:%M:%S"), simulation.action));
+        match &simulation.changes {
+            Ok(changes) => {
+                for change in changes {
+                    out.push_str(&format!("    {}\n", change));
+                }
+            }
+            Err(e) => out.push_str(&format!("    Would fail: {}\n", e)),
+        }
+    }
+    out
+}
+
+/// An entry or service in the categories dialog.
+#[derive(Debug, Clone)]
+pub struct CategoryItem {
+    /// `history::entry_key` of the entry.
+    pub key: String,
+    pub name: String,
+    pub location: String,
+    /// The name or file name pattern that put it in its category.
+    pub pattern: &'static str,
+    pub already_disabled: bool,
+    pub dependents: Vec<Dependent>,
+    pub selected: bool,
+}
+
+/// State of the categories dialog: entries grouped by category, then a
+/// review of the selection before anything is disabled.
+#[derive(Debug, Clone)]
+pub struct CategoryCleanupInfo {
+    pub groups: Vec<(Category, Vec<CategoryItem>)>,
+    pub reviewing: bool,
+    /// Stage plan steps instead of disabling (plan staging is on).
+    pub staging: bool,
+    pub dry_run: bool,
+}
+
+impl CategoryCleanupInfo {
+    pub fn selected(&self) -> impl Iterator<Item = &CategoryItem> {
+        self.groups
+            .iter()
+            .flat_map(|(_, items)| items)
+            .filter(|item| item.selected && !item.already_disabled)
+    }
+}
+
+/// Show updater, telemetry, and helper entries grouped by category, with a
+/// review step. `Confirmed` when the reviewed selection should be disabled.
+pub fn show_category_cleanup(ctx: &egui::Context, info: &mut CategoryCleanupInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+    let title = if info.reviewing { "Review: Disable Entries" } else { "Updaters, Telemetry, and Helpers" };
+    let verb = if info.staging { "Stage" } else { "Disable" };
+
+    egui::Window::new(title)
+        .id(egui::Id::new("category_cleanup"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(680.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let selected = info.selected().count();
+            if info.reviewing {
+                ui.label(if info.staging {
+                    "These entries will be added to the cleanup plan as Disable steps:"
+                } else {
+                    "These entries will be disabled. They are kept, so each can be enabled again later:"
+                });
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical().max_height(max_h - 140.0).show(ui, |ui| {
+                    egui::Grid::new("category_review_grid")
+                        .num_columns(2)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for item in info.selected() {
+                                ui.label(&item.name);
+                                ui.add(egui::Label::new(
+                                    egui::RichText::new(&item.location).color(egui::Color32::GRAY),
+                                ).truncate());
+                                ui.end_row();
+                                for dependent in &item.dependents {
+                                    ui.label("");
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(230, 160, 50),
+                                        format!("\u{26A0} {} needs it: {}", dependent.name, dependent.reason),
+                                    );
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+                if info.dry_run {
+                    ui.add_space(4.0);
+                    ui.colored_label(
+                        egui::Color32::from_rgb(230, 160, 50),
+                        "Dry run: the changes will only be reported",
+                    );
+                }
+                ui.add_space(12.0);
+                ui.vertical_centered(|ui| {
+                    ui.horizontal(|ui| {
+                        if ui.button(format!("   {} {} Entries   ", verb, selected)).clicked() {
+                            result = DialogResult::Confirmed;
+                        }
+                        ui.add_space(16.0);
+                        if ui.button("   Back   ").clicked() {
+                            info.reviewing = false;
+                        }
+                    });
+                });
+                return;
+            }
+
+            ui.label(
+                egui::RichText::new(
+                    "Grouped by name and file name, so check each one. Already disabled entries are shown for reference.",
+                )
+                .color(egui::Color32::GRAY),
+            );
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(max_h - 120.0).show(ui, |ui| {
+                if info.groups.iter().all(|(_, items)| items.is_empty()) {
+                    ui.label("No updaters, telemetry, or helpers were found.");
+                }
+                for (category, items) in &mut info.groups {
+                    if items.is_empty() {
+                        continue;
+                    }
+                    ui.add_space(6.0);
+                    ui.horizontal(|ui| {
+                        ui.strong(format!("{} ({})", category.label(), items.len()));
+                        let selectable = items.iter().filter(|i| !i.already_disabled);
+                        let all = selectable.clone().all(|i| i.selected);
+                        if selectable.count() > 0 && ui.small_button(if all { "None" } else { "All" }).clicked() {
+                            for item in items.iter_mut().filter(|i| !i.already_disabled) {
+                                item.selected = !all;
+                            }
+                        }
+                    });
+                    ui.label(egui::RichText::new(category.description()).small().color(egui::Color32::GRAY));
+                    for item in items.iter_mut() {
+                        ui.horizontal(|ui| {
+                            ui.add_enabled_ui(!item.already_disabled, |ui| {
+                                ui.checkbox(&mut item.selected, &item.name)
+                                    .on_hover_text(format!("{}\nMatched \"{}\"", item.location, item.pattern));
+                            });
+                            if item.already_disabled {
+                                ui.label(egui::RichText::new("Disabled").small().color(egui::Color32::GRAY));
+                            } else if !item.dependents.is_empty() {
+                                ui.colored_label(egui::Color32::from_rgb(230, 160, 50), "\u{26A0}")
+                                    .on_hover_text(
+                                        item.dependents
+                                            .iter()
+                                            .map(|d| format!("{} needs it: {}", d.name, d.reason))
+                                            .collect::<Vec<_>>()
+                                            .join("\n"),
+                                    );
+                            }
+                        });
+                    }
+                }
+            });
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(selected > 0, egui::Button::new(format!("   Review {}...   ", selected)))
+                        .clicked()
+                    {
+                        info.reviewing = true;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+        });
+
+    result
+}
+
+/// An entry left behind in the install folder of an uninstalled app.
+#[derive(Debug, Clone)]
+pub struct OrphanItem {
+    /// `history::entry_key` of the entry.
+    pub key: String,
+    pub name: String,
+    /// "Startup entry", "Scheduled task", or "Service".
+    pub kind: &'static str,
+    pub location: String,
+    pub command: String,
+    /// The uninstalled app.
+    pub app: String,
+    pub selected: bool,
+}
+
+/// State of the Orphaned by Uninstall dialog: the orphans, then a review of the
+/// selection before anything is deleted.
+#[derive(Debug, Clone)]
+pub struct OrphanCleanupInfo {
+    pub items: Vec<OrphanItem>,
+    pub reviewing: bool,
+    /// Set when the selection should be disabled rather than deleted.
+    pub disable_instead: bool,
+    /// Plan staging is on: deleting can't be staged, disabling can.
+    pub staging: bool,
+    pub dry_run: bool,
+}
+
+impl OrphanCleanupInfo {
+    pub fn selected(&self) -> impl Iterator<Item = &OrphanItem> {
+        self.items.iter().filter(|item| item.selected)
+    }
+}
+
+/// Show startup entries, tasks, and services left behind by uninstalled
+/// apps, with a review step. `Confirmed` when the reviewed selection should
+/// be deleted (or disabled, with `disable_instead`).
+pub fn show_orphan_cleanup(ctx: &egui::Context, info: &mut OrphanCleanupInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+    let title = if info.reviewing { "Review: Clean Up Orphaned Entries" } else { "Orphaned by Uninstall" };
+
+    egui::Window::new(title)
+        .id(egui::Id::new("orphan_cleanup"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(680.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            let selected = info.selected().count();
+            if info.reviewing {
+                ui.label("These entries will be deleted:");
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical().max_height(max_h - 160.0).show(ui, |ui| {
+                    egui::Grid::new("orphan_review_grid")
+                        .num_columns(2)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for item in info.selected() {
+                                ui.label(&item.name);
+                                ui.add(egui::Label::new(
+                                    egui::RichText::new(&item.location).color(egui::Color32::GRAY),
+                                ).truncate());
+                                ui.end_row();
+                            }
+                        });
+                });
+                ui.label(
+                    egui::RichText::new(
+                        "Deleting can't be undone, except for Startup folder shortcuts, which go to the backup store. \
+                         Disabling keeps the entries so each can be enabled again.",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+                if info.dry_run {
+                    ui.add_space(4.0);
+                    ui.colored_label(
+                        egui::Color32::from_rgb(230, 160, 50),
+                        "Dry run: the changes will only be reported",
+                    );
+                }
+                ui.add_space(12.0);
+                ui.vertical_centered(|ui| {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!info.staging, egui::Button::new(format!("   Delete {} Entries   ", selected)))
+                            .on_disabled_hover_text("Deleting can't be staged; disable instead or turn off plan staging")
+                            .clicked()
+                        {
+                            info.disable_instead = false;
+                            result = DialogResult::Confirmed;
+                        }
+                        ui.add_space(16.0);
+                        let disable = if info.staging { "Stage Disable Instead" } else { "Disable Instead" };
+                        if ui.button(disable).clicked() {
+                            info.disable_instead = true;
+                            result = DialogResult::Confirmed;
+                        }
+                        ui.add_space(16.0);
+                        if ui.button("   Back   ").clicked() {
+                            info.reviewing = false;
+                        }
+                    });
+                });
+                return;
+            }
+
+            ui.label(
+                egui::RichText::new(
+                    "Their binary is in the install folder of an app that is no longer installed. Only apps App Manager has seen installed are known.",
+                )
+                .color(egui::Color32::GRAY),
+            );
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(max_h - 120.0).show(ui, |ui| {
+                if info.items.is_empty() {
+                    ui.label("No orphaned entries were found.");
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    let all = info.items.iter().all(|i| i.selected);
+                    if ui.small_button(if all { "None" } else { "All" }).clicked() {
+                        for item in info.items.iter_mut() {
+                            item.selected = !all;
+                        }
+                    }
+                });
+                egui::Grid::new("orphan_cleanup_grid")
+                    .num_columns(3)
+                    .spacing([12.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Entry");
+                        ui.strong("Kind");
+                        ui.strong("Left by");
+                        ui.end_row();
+                        for item in info.items.iter_mut() {
+                            ui.checkbox(&mut item.selected, &item.name)
+                                .on_hover_text(format!("{}\n{}", item.location, item.command));
+                            ui.label(egui::RichText::new(item.kind).color(egui::Color32::GRAY));
+                            ui.label(&item.app);
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(selected > 0, egui::Button::new(format!("   Review {}...   ", selected)))
+                        .clicked()
+                    {
+                        info.reviewing = true;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+        });
+
+    result
+}
+
+/// A service or startup item a clean boot would disable.
+#[derive(Debug, Clone)]
+pub struct CleanBootItem {
+    /// `history::entry_key` of the entry.
+    pub key: String,
+    pub name: String,
+    pub location: String,
+    pub selected: bool,
+}
+
+/// State of the Clean Boot Assistant.
+#[derive(Debug, Clone)]
+pub struct CleanBootInfo {
+    /// Non-Microsoft services that start automatically.
+    pub services: Vec<CleanBootItem>,
+    /// Enabled non-Microsoft Run key and Startup folder entries.
+    pub startup_items: Vec<CleanBootItem>,
+    pub restore_point: bool,
+    pub restart: bool,
+    /// The last clean boot's revert file, if one was saved.
+    pub revert_file: Option<std::path::PathBuf>,
+    pub revert_requested: bool,
+    /// Stage plan steps instead of disabling (plan staging is on).
+    pub staging: bool,
+    pub dry_run: bool,
+}
+
+impl CleanBootInfo {
+    pub fn selected(&self) -> impl Iterator<Item = &CleanBootItem> {
+        self.services.iter().chain(&self.startup_items).filter(|item| item.selected)
+    }
+}
+
+/// Guide through a clean boot: pick the services and startup items to
+/// disable, with a restore point and restart. `Confirmed` when the
+/// selection should be disabled.
+pub fn show_clean_boot(ctx: &egui::Context, info: &mut CleanBootInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+
+    egui::Window::new("Clean Boot Assistant")
+        .id(egui::Id::new("clean_boot"))
+        .collapsible(false)
+        .resizable(true)
+        .default_width(620.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("A clean boot starts Windows with only Microsoft services and no startup items:");
+            for step in [
+                "1. Disable everything below and restart.",
+                "2. If the problem is gone, one of them caused it. Enable half, restart, and keep halving.",
+                "3. When you're done, revert the clean boot to turn everything back on.",
+            ] {
+                ui.label(egui::RichText::new(step).color(egui::Color32::GRAY));
+            }
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(max_h - 220.0).show(ui, |ui| {
+                for (label, items) in [("Services", &mut info.services), ("Startup items", &mut info.startup_items)] {
+                    ui.add_space(6.0);
+                    ui.horizontal(|ui| {
+                        ui.strong(format!("{} ({})", label, items.len()));
+                        let all = items.iter().all(|i| i.selected);
+                        if !items.is_empty() && ui.small_button(if all { "None" } else { "All" }).clicked() {
+                            for item in items.iter_mut() {
+                                item.selected = !all;
+                            }
+                        }
+                    });
+                    if items.is_empty() {
+                        ui.label(egui::RichText::new("None enabled").small().color(egui::Color32::GRAY));
+                    }
+                    for item in items.iter_mut() {
+                        ui.checkbox(&mut item.selected, &item.name).on_hover_text(&item.location);
+                    }
+                }
+            });
+
+            ui.add_space(8.0);
+            ui.separator();
+            if info.staging {
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 160, 50),
+                    "Plan staging is on: Disable steps will be added to the cleanup plan",
+                );
+            } else {
+                ui.checkbox(&mut info.restore_point, "Create a restore point first").on_hover_text(
+                    "Needs administrator rights and System Protection. Windows makes at most one restore point a day.",
+                );
+                ui.checkbox(&mut info.restart, "Restart Windows when done").on_hover_text(format!(
+                    "Restarts {} seconds after the last entry is disabled; run \"shutdown /a\" to cancel",
+                    clean_boot::RESTART_DELAY_SECS
+                ));
+                ui.label(
+                    egui::RichText::new(
+                        "Manual services are left alone. A revert file is saved before anything changes.",
+                    )
+                    .small()
+                    .color(egui::Color32::GRAY),
+                );
+            }
+            if info.dry_run {
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 160, 50),
+                    "Dry run: the changes will only be reported",
+                );
+            }
+
+            ui.add_space(12.0);
+            let selected = info.selected().count();
+            let verb = if info.staging { "Stage" } else { "Disable" };
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(selected > 0, egui::Button::new(format!("   {} {} Entries   ", verb, selected)))
+                        .clicked()
+                    {
+                        result = DialogResult::Confirmed;
+                    }
+                    ui.add_space(16.0);
+                    let revert = ui
+                        .add_enabled(info.revert_file.is_some(), egui::Button::new("Revert Last Clean Boot..."))
+                        .on_hover_text("Load the revert file into the cleanup plan to enable the entries again")
+                        .on_disabled_hover_text("No clean boot has been applied");
+                    if revert.clicked() {
+                        info.revert_requested = true;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+        });
+
+    result
+}
+
+/// State of the service hardening dialog. The requests are handled and
+/// cleared by the caller.
+#[derive(Debug, Clone, Default)]
+pub struct ServiceHardeningInfo {
+    /// Index of the suggestion whose "Apply" was clicked.
+    pub apply_requested: Option<usize>,
+    /// Index of the applied change whose "Revert" was clicked.
+    pub revert_requested: Option<usize>,
+    /// Plan staging is on; start type changes can't be staged.
+    pub staging: bool,
+    pub dry_run: bool,
+}
+
+/// Suggest safer start types for non-Microsoft services and list the ones
+/// already applied, each with a button to undo it. `busy` disables the
+/// buttons while changes are running.
+pub fn show_service_hardening(
+    ctx: &egui::Context,
+    suggestions: &[Suggestion],
+    applied: &[AppliedChange],
+    busy: bool,
+    info: &mut ServiceHardeningInfo,
+) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+    let enabled = !busy && !info.staging;
+    let disabled_hint = if info.staging {
+        "Start type changes can't be staged; turn off plan staging"
+    } else {
+        "Wait for the running changes to finish"
+    };
+
+    egui::Window::new("Service Hardening")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(720.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label("Non-Microsoft services that start with Windows, and a start type that costs less at boot:");
+            ui.label(
+                egui::RichText::new(
+                    "Based on App Manager's run history and each service's own memory use. A manual service still starts when a program asks for it.",
+                )
+                .small()
+                .color(egui::Color32::GRAY),
+            );
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(max_h - 200.0).show(ui, |ui| {
+                if suggestions.is_empty() {
+                    ui.label(egui::RichText::new("No suggestions").color(egui::Color32::GRAY));
+                } else {
+                    egui::Grid::new("service_hardening_grid")
+                        .num_columns(5)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Service");
+                            ui.strong("Current");
+                            ui.strong("Suggested");
+                            ui.strong("Why");
+                            ui.label("");
+                            ui.end_row();
+                            for (i, suggestion) in suggestions.iter().enumerate() {
+                                ui.add(egui::Label::new(&suggestion.service.name).truncate())
+                                    .on_hover_text(&suggestion.service.command);
+                                ui.label(suggestion.current.label());
+                                ui.colored_label(egui::Color32::from_rgb(80, 200, 80), suggestion.suggested.label());
+                                ui.add(egui::Label::new(&suggestion.reason).wrap());
+                                if ui
+                                    .add_enabled(enabled, egui::Button::new("Apply").small())
+                                    .on_disabled_hover_text(disabled_hint)
+                                    .clicked()
+                                {
+                                    info.apply_requested = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+
+                if !applied.is_empty() {
+                    ui.add_space(12.0);
+                    ui.strong(format!("Applied ({})", applied.len()));
+                    egui::Grid::new("service_hardening_applied_grid")
+                        .num_columns(5)
+                        .spacing([12.0, 4.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("Service");
+                            ui.strong("Was");
+                            ui.strong("Now");
+                            ui.strong("When");
+                            ui.label("");
+                            ui.end_row();
+                            for (i, change) in applied.iter().enumerate() {
+                                ui.add(egui::Label::new(&change.name).truncate())
+                                    .on_hover_text(&change.service_name);
+                                ui.label(change.previous.label());
+                                ui.label(change.applied.label());
+                                ui.label(change.when.format("%Y-%m-%d %H:%M").to_string());
+                                if ui
+                                    .add_enabled(enabled, egui::Button::new("Revert").small())
+                                    .on_hover_text(format!("Set the start type back to {}", change.previous.label()))
+                                    .on_disabled_hover_text(disabled_hint)
+                                    .clicked()
+                                {
+                                    info.revert_requested = Some(i);
+                                }
+                                ui.end_row();
+                            }
+                        });
+                }
+            });
+
+            if info.dry_run {
+                ui.add_space(4.0);
+                ui.colored_label(
+                    egui::Color32::from_rgb(230, 160, 50),
+                    "Dry run: the changes will only be reported",
+                );
+            }
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+            });
+        });
+
+    result
+}
+
+/// State of the Vendors dialog.
+pub struct VendorSummaryInfo {
+    pub vendors: Vec<VendorFootprint>,
+    pub hide_microsoft: bool,
+}
+
+/// Show each vendor's footprint: installed apps, services, startup entries,
+/// and the processes (and memory) it has running.
+pub fn show_vendor_summary(ctx: &egui::Context, info: &mut VendorSummaryInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+
+    egui::Window::new("Vendors")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(680.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.checkbox(&mut info.hide_microsoft, "Hide Microsoft");
+            ui.add_space(4.0);
+            egui::ScrollArea::vertical().max_height(max_h - 100.0).show(ui, |ui| {
+                egui::Grid::new("vendor_summary_grid")
+                    .num_columns(6)
+                    .spacing([16.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Vendor");
+                        ui.strong("Installed");
+                        ui.strong("Services");
+                        ui.strong("Startup");
+                        ui.strong("Processes");
+                        ui.strong("Memory");
+                        ui.end_row();
+
+                        let count = |ui: &mut egui::Ui, n: usize| {
+                            if n == 0 {
+                                ui.label(egui::RichText::new("\u{2014}").color(egui::Color32::GRAY));
+                            } else {
+                                ui.label(n.to_string());
+                            }
+                        };
+                        for v in &info.vendors {
+                            if info.hide_microsoft && v.vendor.to_lowercase().starts_with("microsoft") {
+                                continue;
+                            }
+                            if v.vendor.is_empty() {
+                                ui.label(egui::RichText::new("(Unknown vendor)").italics().color(egui::Color32::GRAY))
+                                    .on_hover_text("Binaries without a company name in their version info");
+                            } else {
+                                ui.add(egui::Label::new(&v.vendor).truncate());
+                            }
+                            count(ui, v.installed_apps);
+                            if v.services == 0 {
+                                count(ui, 0);
+                            } else {
+                                ui.label(format!("{} ({} running)", v.services, v.running_services));
+                            }
+                            count(ui, v.startup_entries);
+                            count(ui, v.processes);
+                            if v.memory_bytes == 0 {
+                                count(ui, 0);
+                            } else {
+                                ui.label(format_memory(v.memory_bytes));
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+            });
+        });
+
+    result
+}
+
+/// State of the audit trail dialog.
+pub struct AuditTrailInfo {
+    /// Every recorded change, newest first.
+    pub records: Vec<AuditRecord>,
+    pub export_requested: Option<audit::ExportFormat>,
+}
+
+/// Show the changes App Manager has made on this machine, across sessions,
+/// with export to CSV or JSON Lines.
+pub fn show_audit_trail(ctx: &egui::Context, info: &mut AuditTrailInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+
+    egui::Window::new("Audit Trail")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(860.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!(
+                    "{} changes made with App Manager on this machine, including keep-disabled enforcement. Newest first.",
+                    info.records.len()
+                ))
+                .color(egui::Color32::GRAY),
+            );
+            ui.add_space(4.0);
+            if info.records.is_empty() {
+                ui.label("No changes have been recorded yet.");
+            }
+            egui::ScrollArea::both().max_height(max_h - 110.0).show(ui, |ui| {
+                egui::Grid::new("audit_trail_grid")
+                    .num_columns(6)
+                    .spacing([12.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for column in ["When", "User", "Item", "Action", "Old Value", "New Value"] {
+                            ui.strong(column);
+                        }
+                        ui.end_row();
+                        for record in &info.records {
+                            ui.label(record.when.format("%Y-%m-%d %H:%M:%S").to_string());
+                            ui.label(&record.user).on_hover_text(&record.machine);
+                            ui.add(egui::Label::new(&record.item).truncate()).on_hover_text(&record.location);
+                            ui.label(&record.action);
+                            for value in [&record.old_value, &record.new_value] {
+                                if value.is_empty() {
+                                    ui.label(egui::RichText::new("\u{2014}").color(egui::Color32::GRAY));
+                                } else {
+                                    ui.add(egui::Label::new(value).truncate()).on_hover_text(value);
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    let any = !info.records.is_empty();
+                    if ui.add_enabled(any, egui::Button::new("   Export CSV...   ")).clicked() {
+                        info.export_requested = Some(audit::ExportFormat::Csv);
+                    }
+                    if ui.add_enabled(any, egui::Button::new("   Export JSONL...   ")).clicked() {
+                        info.export_requested = Some(audit::ExportFormat::JsonLines);
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Close   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+        });
+
+    result
+}
+
+/// Show the Restore dialog for deleted startup-folder files.
+pub fn show_restore_backups(ctx: &egui::Context, info: &mut RestoreBackupsInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    let content = ctx.content_rect();
+    let max_h = (content.height() - 16.0).max(200.0);
+
+    egui::Window::new("Restore Deleted Startup Files")
+        .collapsible(false)
+        .resizable(true)
+        .default_width(620.0)
+        .max_height(max_h)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            if info.items.is_empty() {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(8.0);
+                    ui.label(
+                        egui::RichText::new("No deleted startup files are backed up")
+                            .color(egui::Color32::GRAY),
+                    );
+                });
+            } else {
+                egui::ScrollArea::vertical().max_height(max_h - 80.0).show(ui, |ui| {
+                    egui::Grid::new("restore_backups_grid")
+                        .num_columns(4)
+                        .spacing([12.0, 6.0])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong("File");
+                            ui.strong("Original Location");
+                            ui.strong("Deleted");
+                            ui.label("");
+                            ui.end_row();
+
+                            for (i, item) in info.items.iter().enumerate() {
+                                ui.label(item.file_name());
+                                let folder = item
+                                    .original_path
+                                    .parent()
+                                    .map(|p| p.display().to_string())
+                                    .unwrap_or_default();
+                                ui.add(egui::Label::new(&folder).truncate())
+                                    .on_hover_text(item.original_path.display().to_string());
+                                let deleted = match item.deleted_at {
+                                    Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+                                    None => "\u{2014}".to_string(),
+                                };
+                                ui.label(deleted);
+                                ui.horizontal(|ui| {
+                                    if ui.small_button("Restore").clicked() {
+                                        info.restore_requested = Some(i);
+                                    }
+                                    if ui
+                                        .small_button("Delete")
+                                        .on_hover_text("Permanently delete this backup")
+                                        .clicked()
+                                    {
+                                        info.discard_requested = Some(i);
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+            }
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                if ui.button("   Close   ").clicked() {
+                    result = DialogResult::Cancelled;
+                }
+            });
+            ui.add_space(4.0);
+        });
+
+    result
+}
+
+/// Data for the uninstall confirmation dialog: the exact command that will run,
+/// with editable arguments (e.g. to add `/S`).
+#[derive(Debug, Clone)]
+pub struct UninstallPreview {
+    pub app_name: String,
+    pub product_code: Option<String>,
+    /// The registered UninstallString.
+    pub uninstall_string: String,
+    /// Log file written when `with_log` is set (MSI products only).
+    pub log_path: PathBuf,
+    pub with_log: bool,
+    pub executable: String,
+    pub arguments: String,
+}
+
+impl UninstallPreview {
+    /// The command before any user edits: the registered uninstall string, or
+    /// the logged `msiexec` command.
+    fn base_command(&self) -> String {
+        match self.product_code.as_deref().filter(|_| self.with_log) {
+            Some(code) => installed_apps::msi_uninstall_with_log_command(code, &self.log_path),
+            None => self.uninstall_string.clone(),
+        }
+    }
+
+    /// Re-split the base command into executable and arguments, discarding edits.
+    pub fn reset_command(&mut self) {
+        let (exe, args) = cmdline::split(&self.base_command());
+        self.executable = exe;
+        self.arguments = args;
+    }
+
+    pub fn is_edited(&self) -> bool {
+        let (_, args) = cmdline::split(&self.base_command());
+        args.trim() != self.arguments.trim()
+    }
+
+    /// The command that will be launched.
+    pub fn command(&self) -> String {
+        cmdline::join(&self.executable, &self.arguments)
+    }
+
+    /// The log file, if this uninstall writes one.
+    pub fn active_log_path(&self) -> Option<&Path> {
+        (self.with_log && self.product_code.is_some()).then_some(self.log_path.as_path())
+    }
+}
+
+/// Confirm an uninstall, showing the parsed executable and arguments that
+/// will run. Logging is only offered for MSI products.
+pub fn show_uninstall_confirmation(
+    ctx: &egui::Context,
+    preview: &mut UninstallPreview,
+) -> DialogResult {
     let mut result = DialogResult::Open;
 
-    egui::Window::new("about_dialog")
-        .title_bar(false)
+    egui::Window::new("Confirm Uninstall")
         .collapsible(false)
         .resizable(false)
+        .default_width(460.0)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(8.0);
-                ui.label(egui::RichText::new("App Manager").strong().size(18.0));
-                ui.add_space(2.0);
-                ui.label("v1.0.0");
-                ui.add_space(8.0);
-                ui.label("Copyright (C) 2026 Matt Smith");
-                ui.label("MIT License");
-                ui.add_space(8.0);
-                ui.hyperlink_to(
-                    "github.com/mattx86/app-manager",
-                    "https://github.com/mattx86/app-manager",
-                );
-                ui.add_space(12.0);
-                if ui.button("   Close   ").clicked() {
-                    result = DialogResult::Cancelled;
+                ui.label(format!(
+                    "Are you sure you want to uninstall '{}'?",
+                    preview.app_name
+                ));
+            });
+            ui.add_space(8.0);
+
+            egui::Grid::new("uninstall_preview_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    if let Some(code) = &preview.product_code {
+                        label_row(ui, "MSI Product Code:", code);
+                    }
+                    label_row_wrap(ui, "Executable:", &preview.executable);
+                    ui.label(egui::RichText::new("Arguments:").strong());
+                    ui.add(
+                        egui::TextEdit::singleline(&mut preview.arguments)
+                            .desired_width(340.0)
+                            .hint_text("(none)"),
+                    );
+                    ui.end_row();
+                });
+
+            if preview.product_code.is_some() {
+                let r = ui
+                    .checkbox(&mut preview.with_log, "Uninstall with logging (msiexec /l*v)")
+                    .on_hover_text("Write a verbose Windows Installer log you can open afterwards");
+                if r.changed() {
+                    preview.reset_command();
                 }
-                ui.add_space(4.0);
+            }
+            if preview.is_edited() {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new("Arguments differ from the registered command")
+                            .color(egui::Color32::from_rgb(230, 160, 50)),
+                    );
+                    if ui.small_button("Reset").clicked() {
+                        preview.reset_command();
+                    }
+                });
+            }
+
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    let total = ui.available_width();
+                    // Approximate button widths from text + padding
+                    let btn1 = ui.spacing().button_padding.x * 2.0 + 130.0;
+                    let btn2 = ui.spacing().button_padding.x * 2.0 + 55.0;
+                    let gap = 16.0;
+                    let pad = ((total - btn1 - btn2 - gap) / 2.0).max(0.0);
+                    ui.add_space(pad);
+                    if ui
+                        .add_enabled(!preview.executable.is_empty(), egui::Button::new("   Yes, Uninstall   "))
+                        .clicked()
+                    {
+                        result = DialogResult::Confirmed;
+                    }
+                    ui.add_space(gap);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
             });
+            ui.add_space(8.0);
         });
 
     result
 }
 
-pub fn show_delete_confirmation(ctx: &egui::Context, entry_name: &str) -> DialogResult {
+/// Show the settings dialog. Returns `Confirmed` when the user saves.
+pub fn show_settings(ctx: &egui::Context, settings: &mut Settings) -> DialogResult {
     let mut result = DialogResult::Open;
+    // Kept in egui memory so an error stays visible while the dialog is open
+    let error_id = egui::Id::new("settings_allowlist_error");
+    let mut allowlist_error: Option<String> = ctx.data(|d| d.get_temp(error_id));
 
-    egui::Window::new("Confirm Delete")
+    egui::Window::new("Settings")
         .collapsible(false)
         .resizable(false)
+        .default_width(460.0)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
+            egui::Grid::new("settings_grid")
+                .num_columns(2)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("Debugger:").strong());
+                    let detected = settings::detect_debugger();
+                    let hint = detected.clone().unwrap_or_else(|| {
+                        format!("\"C:\\path\\to\\windbg.exe\" -p {}", settings::PID_PLACEHOLDER)
+                    });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut settings.debugger_command)
+                            .hint_text(hint)
+                            .desired_width(360.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("");
+                    let note = match detected {
+                        Some(_) => format!(
+                            "Command used by Attach Debugger; {} is replaced with the process ID. Leave empty to use the detected debugger.",
+                            settings::PID_PLACEHOLDER
+                        ),
+                        None => format!(
+                            "Command used by Attach Debugger; {} is replaced with the process ID. No debugger was detected.",
+                            settings::PID_PLACEHOLDER
+                        ),
+                    };
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(note).small().color(egui::Color32::GRAY))
+                            .wrap(),
+                    );
+                    ui.end_row();
+
+                    let t = &mut settings.thresholds;
+                    ui.label(egui::RichText::new("CPU warning:").strong());
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut t.cpu_warning_percent)
+                                .range(0.0..=100.0)
+                                .suffix(" %"),
+                        );
+                        ui.label("high:");
+                        ui.add(
+                            egui::DragValue::new(&mut t.cpu_high_percent)
+                                .range(t.cpu_warning_percent..=100.0)
+                                .suffix(" %"),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("Memory warning:").strong());
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::DragValue::new(&mut t.memory_warning_mb)
+                                .range(0..=1_048_576)
+                                .speed(10)
+                                .suffix(" MB"),
+                        );
+                        ui.label("high:");
+                        ui.add(
+                            egui::DragValue::new(&mut t.memory_high_mb)
+                                .range(t.memory_warning_mb..=1_048_576)
+                                .speed(10)
+                                .suffix(" MB"),
+                        );
+                    });
+                    ui.end_row();
+
+                    ui.label("");
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(
+                                "Processes using more than these are shown in orange (warning) or red (high).",
+                            )
+                            .small()
+                            .color(egui::Color32::GRAY),
+                        )
+                        .wrap(),
+                    );
+                    ui.end_row();
+
+                    ui.label("");
+                    if ui.small_button("Reset thresholds").clicked() {
+                        *t = UsageThresholds::default();
+                    }
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("Keep-disabled rules:").strong());
+                    ui.horizontal(|ui| {
+                        ui.label("Enforce every");
+                        ui.add(egui::DragValue::new(&mut settings.enforce_interval_minutes).range(0..=1440));
+                        ui.label("minutes (0 = only on scans)");
+                    });
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("Windows allowlist:").strong());
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("Edit...")
+                            .on_hover_text("Open allowlist.txt in Notepad (created with instructions on first use)")
+                            .clicked()
+                        {
+                            allowlist_error = allowlist::open_in_editor().err();
+                        }
+                        if ui
+                            .button("Reload")
+                            .on_hover_text("Apply changes saved to allowlist.txt")
+                            .clicked()
+                        {
+                            allowlist::reload();
+                            allowlist_error = None;
+                        }
+                    });
+                    ui.end_row();
+
+                    ui.label("");
+                    let note = allowlist_error.clone().unwrap_or_else(|| {
+                        "Processes and services to treat as Windows (or not) in the Hide Windows filters, \
+                         e.g. corporate IT agents."
+                            .to_string()
+                    });
+                    ui.add(
+                        egui::Label::new(egui::RichText::new(note).small().color(egui::Color32::GRAY))
+                            .wrap(),
+                    );
+                    ui.end_row();
+
+                    let scope = &mut settings.scan_scope;
+                    ui.label(egui::RichText::new("Startup scan:").strong());
+                    ui.vertical(|ui| {
+                        ui.checkbox(&mut scope.registry_run, "Registry Run/RunOnce keys");
+                        ui.checkbox(&mut scope.startup_folders, "Startup folders");
+                        ui.checkbox(&mut scope.task_scheduler, "Task Scheduler");
+                        ui.checkbox(&mut scope.prefetch, "Prefetch (Last Ran times, admin only)");
+                        ui.checkbox(&mut scope.ifeo, "Image File Execution Options debuggers (advanced)")
+                            .on_hover_text(
+                                "Executables whose launch is redirected to another program by an IFEO Debugger value",
+                            );
+                    });
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("Optional tabs:").strong());
+                    ui.checkbox(&mut scope.dev_packages, "Dev Packages")
+                        .on_hover_text("Global pip, npm, cargo, Scoop, and Chocolatey packages, with uninstall through each package manager");
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("On start:").strong());
+                    ui.checkbox(&mut settings.restore_session, "Reopen the last tab, scroll position, and filters");
+                    ui.end_row();
+
+                    ui.label(egui::RichText::new("CSV export:").strong());
+                    ui.vertical(|ui| {
+                        ui.checkbox(&mut settings.export_metadata, "Metadata header").on_hover_text(
+                            "Start each file with commented lines giving the machine, user, time, app version, \
+                             administrator status, and filters applied",
+                        );
+                        ui.horizontal(|ui| {
+                            ui.label("Column names:");
+                            egui::ComboBox::from_id_salt("export_language")
+                                .selected_text(settings.export_language.label())
+                                .show_ui(ui, |ui| {
+                                    for language in ExportLanguage::ALL {
+                                        ui.selectable_value(&mut settings.export_language, language, language.label());
+                                    }
+                                });
+                        });
+                    });
+                    ui.end_row();
+                });
+
+            ctx.data_mut(|d| match &allowlist_error {
+                Some(e) => d.insert_temp(error_id, e.clone()),
+                None => d.remove::<String>(error_id),
+            });
+
+            ui.add_space(12.0);
             ui.vertical_centered(|ui| {
-                ui.add_space(8.0);
-                ui.label(format!(
-                    "Are you sure you want to delete '{}'?",
-                    entry_name
-                ));
-                ui.label("This action cannot be undone.");
-                ui.add_space(12.0);
                 ui.horizontal(|ui| {
-                    if ui.button("   Yes, Delete   ").clicked() {
+                    if ui.button("   Save   ").clicked() {
                         result = DialogResult::Confirmed;
                     }
-                    ui.add_space(16.0);
                     if ui.button("   Cancel   ").clicked() {
                         result = DialogResult::Cancelled;
                     }
                 });
-                ui.add_space(8.0);
             });
+            ui.add_space(4.0);
         });
 
     result
 }
 
-pub fn show_uninstall_confirmation(ctx: &egui::Context, app_name: &str) -> DialogResult {
-    let mut result = DialogResult::Open;
+/// Choice made in the kill confirmation dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillGuardResult {
+    Open,
+    Kill,
+    RestartExplorer,
+    Cancelled,
+}
 
-    egui::Window::new("Confirm Uninstall")
+/// Confirm killing Explorer or App Manager itself.
+pub fn show_kill_confirmation(
+    ctx: &egui::Context,
+    name: &str,
+    pid: u32,
+    guard: KillGuard,
+) -> KillGuardResult {
+    let mut result = KillGuardResult::Open;
+
+    egui::Window::new("Confirm Kill")
         .collapsible(false)
         .resizable(false)
         .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
         .show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(8.0);
-                ui.label(format!(
-                    "Are you sure you want to uninstall '{}'?",
-                    app_name
-                ));
+                match guard {
+                    KillGuard::Explorer => {
+                        ui.label(format!(
+                            "'{}' (PID {}) is the Windows shell.",
+                            name, pid
+                        ));
+                        ui.label("Killing it removes the taskbar and desktop until it is restarted.");
+                        ui.label("Restart Explorer instead?");
+                    }
+                    KillGuard::SelfProcess => {
+                        ui.label(format!("PID {} is App Manager itself.", pid));
+                        ui.label("Killing it closes App Manager immediately without a session summary.");
+                    }
+                }
                 ui.add_space(12.0);
                 ui.horizontal(|ui| {
-                    let total = ui.available_width();
-                    // Approximate button widths from text + padding
-                    let btn1 = ui.spacing().button_padding.x * 2.0 + 130.0;
-                    let btn2 = ui.spacing().button_padding.x * 2.0 + 55.0;
-                    let gap = 16.0;
-                    let pad = ((total - btn1 - btn2 - gap) / 2.0).max(0.0);
-                    ui.add_space(pad);
-                    if ui.button("   Yes, Uninstall   ").clicked() {
-                        result = DialogResult::Confirmed;
+                    if guard == KillGuard::Explorer
+                        && ui.button("   Restart Explorer   ").clicked()
+                    {
+                        result = KillGuardResult::RestartExplorer;
                     }
-                    ui.add_space(gap);
+                    let kill_label = match guard {
+                        KillGuard::Explorer => "   Kill Anyway   ",
+                        KillGuard::SelfProcess => "   Kill App Manager   ",
+                    };
+                    if ui.button(kill_label).clicked() {
+                        result = KillGuardResult::Kill;
+                    }
+                    ui.add_space(16.0);
                     if ui.button("   Cancel   ").clicked() {
-                        result = DialogResult::Cancelled;
+                        result = KillGuardResult::Cancelled;
                     }
                 });
                 ui.add_space(8.0);
@@ -202,17 +2281,42 @@ pub struct StartupEntryPropertiesInfo {
     pub product_name: String,
     pub command: String,
     pub source: Source,
+    /// The registry value is REG_EXPAND_SZ (registry entries only).
+    pub expand_sz: bool,
     pub enabled: EnabledStatus,
     pub run_state: RunState,
     pub runs_as: String,
     pub requires_admin: bool,
     pub last_ran: Option<DateTime<Local>>,
+    pub last_ran_source: Option<LastRanSource>,
+    pub run_history: Option<RunHistoryStats>,
+    pub session_origin: Option<SessionOrigin>,
+    pub task_registration: Option<TaskRegistration>,
+    /// Editable task conditions (Task Scheduler entries only).
+    pub task_conditions: Option<TaskConditions>,
+    pub original_task_conditions: Option<TaskConditions>,
+    pub script_payload: Option<ScriptPayload>,
+    pub bitness: Option<Bitness>,
+    /// Editable argument string (initialized from the parsed command).
+    pub arguments: String,
+    pub original_arguments: String,
+    /// Values from enrichment providers.
+    pub enrichment: Enrichment,
+    pub dependencies: DependencyInfo,
+    /// Set when the user clicks "Export .reg"; the caller handles the export
+    /// and clears it.
+    pub export_requested: bool,
+    /// Set when the user clicks "Windows Properties"; the caller opens the
+    /// native properties sheet and clears it.
+    pub windows_properties_requested: bool,
 }
 
 /// Show a startup entry properties dialog.
+/// Returns `Confirmed` when the user applies edited arguments or task
+/// conditions.
 pub fn show_startup_entry_properties(
     ctx: &egui::Context,
-    info: &StartupEntryPropertiesInfo,
+    info: &mut StartupEntryPropertiesInfo,
 ) -> DialogResult {
     let mut result = DialogResult::Open;
 
@@ -243,6 +2347,23 @@ pub fn show_startup_entry_properties(
 
                         label_row_wrap(ui, "Command:", &info.command);
 
+                        let editable = !matches!(info.source, Source::StartupFolder { .. });
+                        ui.label(egui::RichText::new("Arguments:").strong());
+                        ui.add_enabled(
+                            editable,
+                            egui::TextEdit::singleline(&mut info.arguments)
+                                .desired_width(f32::INFINITY),
+                        );
+                        ui.end_row();
+
+                        if let Some(script) = &info.script_payload {
+                            label_row_wrap(
+                                ui,
+                                "Script Payload:",
+                                &format!("{} ({})", script.payload, script.host),
+                            );
+                        }
+
                         let source_type = match &info.source {
                             Source::RegistryRun { .. } => "Registry (Run)",
                             Source::RegistryRunOnce { .. } => "Registry (RunOnce)",
@@ -255,9 +2376,26 @@ pub fn show_startup_entry_properties(
                             }
                             Source::TaskScheduler { .. } => "Task Scheduler",
                             Source::Service { .. } => "Service",
+                            Source::Ifeo { .. } => "Image File Execution Options (Debugger)",
                         };
                         label_row(ui, "Source:", source_type);
                         label_row_wrap(ui, "Location:", &info.source.display_location());
+                        if matches!(
+                            info.source,
+                            Source::RegistryRun { .. } | Source::RegistryRunOnce { .. } | Source::Ifeo { .. }
+                        ) {
+                            let value_type = if info.expand_sz {
+                                "REG_EXPAND_SZ (variables expanded when it runs)"
+                            } else {
+                                "REG_SZ"
+                            };
+                            label_row(ui, "Value Type:", value_type);
+                        }
+                        if info.source.is_wow64_registry() {
+                            label_row(ui, "Bitness:", "32-bit (WOW6432Node registry view)");
+                        } else if let Some(bitness) = info.bitness {
+                            label_row(ui, "Bitness:", &bitness.to_string());
+                        }
 
                         let (status_text, status_color) = match info.enabled {
                             EnabledStatus::Enabled => {
@@ -301,16 +2439,274 @@ pub fn show_startup_entry_properties(
                             None => "\u{2014}".to_string(),
                         };
                         label_row(ui, "Last Ran:", &time_text);
+                        if let Some(source) = info.last_ran_source.filter(|_| info.last_ran.is_some()) {
+                            label_row(ui, "Last Ran Source:", source.description());
+                        }
+
+                        let history_text = match info.run_history {
+                            Some(h) => h.to_string(),
+                            None => "\u{2014}".to_string(),
+                        };
+                        label_row(ui, "Run History:", &history_text);
+                        if let Some(origin) = &info.session_origin {
+                            label_row(ui, "Origin:", &origin.to_string());
+                        }
+                        enrichment_rows(ui, &info.enrichment);
+
+                        if let Some(reg) = &info.task_registration {
+                            if !reg.author.is_empty() {
+                                label_row(ui, "Author:", &reg.author);
+                            }
+                            if let Some(dt) = reg.registered {
+                                label_row(ui, "Registered:", &dt.format("%Y-%m-%d %H:%M:%S").to_string());
+                            }
+                            if !reg.description.is_empty() {
+                                label_row_wrap(ui, "Description:", &reg.description);
+                            }
+                        }
+
+                        if let Some(conditions) = &mut info.task_conditions {
+                            ui.label(egui::RichText::new("Conditions:").strong())
+                                .on_hover_text("Task Scheduler skips the task unless these are met");
+                            ui.vertical(|ui| {
+                                ui.checkbox(
+                                    &mut conditions.disallow_start_on_batteries,
+                                    "Start only if on AC power",
+                                );
+                                ui.add_enabled(
+                                    conditions.disallow_start_on_batteries,
+                                    egui::Checkbox::new(
+                                        &mut conditions.stop_on_batteries,
+                                        "Stop if the computer switches to battery power",
+                                    ),
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.checkbox(&mut conditions.run_only_if_idle, "Start only if idle for");
+                                    ui.add_enabled(
+                                        conditions.run_only_if_idle,
+                                        egui::DragValue::new(&mut conditions.idle_minutes)
+                                            .range(1..=1440)
+                                            .suffix(" min"),
+                                    );
+                                });
+                                ui.checkbox(
+                                    &mut conditions.run_only_if_network,
+                                    "Start only if a network connection is available",
+                                );
+                                ui.checkbox(&mut conditions.wake_to_run, "Wake the computer to run this task");
+                            });
+                            ui.end_row();
+                        }
                     });
 
+                dependency_section(ui, &mut info.dependencies);
+
                 ui.add_space(12.0);
                 ui.vertical_centered(|ui| {
+                    ui.horizontal(|ui| {
+                        let changed = info.arguments.trim() != info.original_arguments.trim()
+                            || info.task_conditions != info.original_task_conditions;
+                        if ui
+                            .add_enabled(changed, egui::Button::new("   Apply   "))
+                            .clicked()
+                        {
+                            result = DialogResult::Confirmed;
+                        }
+                        let is_registry = matches!(
+                            info.source,
+                            Source::RegistryRun { .. } | Source::RegistryRunOnce { .. }
+                        );
+                        if ui
+                            .button("Windows Properties")
+                            .on_hover_text("Open the Windows file properties for the entry's executable")
+                            .clicked()
+                        {
+                            info.windows_properties_requested = true;
+                        }
+                        if is_registry
+                            && ui
+                                .button("Export .reg")
+                                .on_hover_text("Save a .reg file that re-creates this entry")
+                                .clicked()
+                        {
+                            info.export_requested = true;
+                        }
+                        if ui.button("   Close   ").clicked() {
+                            result = DialogResult::Cancelled;
+                        }
+                    });
+                });
+                ui.add_space(4.0);
+            });
+        });
+
+    result
+}
+
+/// A change made during this session, listed in the session summary.
+#[derive(Debug, Clone)]
+pub struct SessionChange {
+    pub item: String,
+    pub change: String,
+}
+
+/// Show the session summary dialog listing all changes made before the app closes.
+/// `Confirmed` closes the app, `Cancelled` keeps it open.
+pub fn show_session_summary(ctx: &egui::Context, changes: &[SessionChange]) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Changes Made This Session")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label("The following items were modified. Changed services may need a restart to take effect.");
+            ui.add_space(8.0);
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                egui::Grid::new("session_summary_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for change in changes {
+                            label_row(ui, &change.item, &change.change);
+                        }
+                    });
+            });
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("   Close App   ").clicked() {
+                        result = DialogResult::Confirmed;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Cancel   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+            ui.add_space(4.0);
+        });
+
+    result
+}
+
+/// State of the dialog shown after the previous session crashed.
+pub struct CrashReportInfo {
+    pub report: CrashReport,
+    /// "Open Report" was clicked; the caller opens it and clears this.
+    pub open_requested: bool,
+    /// "Open Folder" was clicked.
+    pub open_folder_requested: bool,
+    /// "Restore Unsaved Settings" was clicked; the caller applies
+    /// `report.unsaved_settings` and clears this.
+    pub restore_settings_requested: bool,
+}
+
+/// Offer the report left by a crash in the previous session.
+pub fn show_crash_report(ctx: &egui::Context, info: &mut CrashReportInfo) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("App Manager Closed Unexpectedly")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label("The last session ended with an internal error. A report with the details was saved:");
+            ui.add_space(4.0);
+            ui.label(egui::RichText::new(info.report.path.display().to_string()).color(egui::Color32::GRAY));
+            ui.add_space(8.0);
+            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                ui.label(egui::RichText::new(&info.report.summary).monospace());
+            });
+            ui.add_space(8.0);
+            ui.label(
+                "Changes already applied stay applied. Check the affected items before repeating what \
+                 you were doing; deleted startup files can be put back with Restore...",
+            );
+            if info.report.unsaved_settings.is_some() {
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    ui.label("Settings were being edited and hadn't been saved.");
+                    if ui.button("Restore Unsaved Settings").clicked() {
+                        info.restore_settings_requested = true;
+                    }
+                });
+            }
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("   Open Report   ").clicked() {
+                        info.open_requested = true;
+                    }
+                    if ui.button("   Open Folder   ").clicked() {
+                        info.open_folder_requested = true;
+                    }
+                    ui.add_space(16.0);
+                    if ui.button("   Close   ").clicked() {
+                        result = DialogResult::Cancelled;
+                    }
+                });
+            });
+            ui.add_space(4.0);
+        });
+
+    result
+}
+
+/// Items a tab's collector couldn't read. Returns `Confirmed` when the user
+/// asks to restart as administrator.
+pub fn show_skipped_items(ctx: &egui::Context, skipped: &SkippedItems, is_admin: bool) -> DialogResult {
+    let mut result = DialogResult::Open;
+
+    egui::Window::new("Unreadable Items")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.add_space(4.0);
+            ui.label("These items were found but couldn't be read, so the list may be incomplete.");
+            for line in skipped.summary_lines() {
+                ui.label(egui::RichText::new(line).color(egui::Color32::from_rgb(230, 160, 50)));
+            }
+            ui.add_space(8.0);
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                egui::Grid::new("skipped_items_grid")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.strong("Type");
+                        ui.strong("Name");
+                        ui.strong("Reason");
+                        ui.end_row();
+                        for item in &skipped.items {
+                            ui.label(item.kind.label());
+                            ui.label(&item.name);
+                            ui.label(egui::RichText::new(&item.reason).color(egui::Color32::GRAY));
+                            ui.end_row();
+                        }
+                    });
+            });
+            ui.add_space(12.0);
+            ui.vertical_centered(|ui| {
+                ui.horizontal(|ui| {
+                    if !is_admin {
+                        let r = ui
+                            .button("   Restart as Admin   ")
+                            .on_hover_text("Items denied to your account are often readable by an administrator");
+                        if r.clicked() {
+                            result = DialogResult::Confirmed;
+                        }
+                        ui.add_space(16.0);
+                    }
                     if ui.button("   Close   ").clicked() {
                         result = DialogResult::Cancelled;
                     }
                 });
-                ui.add_space(4.0);
             });
+            ui.add_space(4.0);
         });
 
     result
@@ -332,12 +2728,35 @@ pub struct ProcessPropertiesInfo {
     pub product_name: String,
     pub user_name: String,
     pub is_elevated: bool,
+    pub bitness: Option<Bitness>,
+    pub link_time: Option<LinkTime>,
+    pub cpu_times: Option<CpuTimes>,
+    pub image_mismatch: Option<ImageMismatch>,
+    /// `None` if the process couldn't be queried.
+    pub dpi_awareness: Option<DpiAwareness>,
+    /// UI frameworks found in the process's modules.
+    pub ui_framework: Option<String>,
+    /// Values from enrichment providers.
+    pub enrichment: Enrichment,
+    /// Probable launch origin, for the "Why Is This Running?" section.
+    pub launch_origin: LaunchOrigin,
+    /// TCP/UDP endpoints owned by the process.
+    pub connections: Vec<Connection>,
+    /// Set when the user clicks "Refresh" in the connections view.
+    pub refresh_connections_requested: bool,
+    /// Set when the user clicks "Close" on a TCP connection; the caller closes
+    /// it, refreshes the list, and clears this.
+    pub close_connection_requested: Option<Connection>,
+    /// Set when the user clicks "Windows Properties"; the caller opens the
+    /// native properties sheet and clears it.
+    pub windows_properties_requested: bool,
 }
 
 /// Show a process properties dialog. Returns the dialog state.
 pub fn show_process_properties(
     ctx: &egui::Context,
-    info: &ProcessPropertiesInfo,
+    info: &mut ProcessPropertiesInfo,
+    thresholds: &UsageThresholds,
 ) -> DialogResult {
     let mut result = DialogResult::Open;
 
@@ -377,6 +2796,24 @@ pub fn show_process_properties(
                         if !info.exe_path.is_empty() {
                             label_row_wrap(ui, "Path:", &info.exe_path);
                         }
+                        if let Some(mismatch) = &info.image_mismatch {
+                            ui.label(egui::RichText::new("Image:").strong());
+                            ui.add(
+                                egui::Label::new(
+                                    egui::RichText::new(mismatch.description())
+                                        .color(egui::Color32::from_rgb(230, 80, 80)),
+                                )
+                                .wrap(),
+                            );
+                            ui.end_row();
+                        }
+
+                        if let Some(bitness) = info.bitness {
+                            label_row(ui, "Bitness:", &bitness.to_string());
+                        }
+                        if let Some(link_time) = info.link_time {
+                            label_row(ui, "Built:", &link_time.to_string());
+                        }
 
                         if !info.command_line.is_empty() {
                             label_row_wrap(ui, "Command Line:", &info.command_line);
@@ -387,9 +2824,22 @@ pub fn show_process_properties(
                         } else {
                             "0%".to_string()
                         };
-                        label_row(ui, "CPU:", &cpu_text);
+                        let cpu_color = process_table::usage_color(ui, thresholds.cpu_level(info.cpu_usage));
+                        ui.label(egui::RichText::new("CPU:").strong());
+                        ui.label(egui::RichText::new(cpu_text).color(cpu_color));
+                        ui.end_row();
+
+                        if let Some(times) = info.cpu_times {
+                            label_row(ui, "CPU Time:", &processes::format_cpu_time(times.total()));
+                            label_row(ui, "Kernel Time:", &processes::format_cpu_time(times.kernel));
+                            label_row(ui, "User Time:", &processes::format_cpu_time(times.user));
+                        }
 
-                        label_row(ui, "Memory:", &format_memory(info.memory_bytes));
+                        let memory_color =
+                            process_table::usage_color(ui, thresholds.memory_level(info.memory_bytes));
+                        ui.label(egui::RichText::new("Memory:").strong());
+                        ui.label(egui::RichText::new(format_memory(info.memory_bytes)).color(memory_color));
+                        ui.end_row();
 
                         let dr = format_bytes(info.disk_read_bytes);
                         label_row(ui, "Disk Read:", &dr);
@@ -408,13 +2858,40 @@ pub fn show_process_properties(
                             None => "\u{2014}".to_string(),
                         };
                         label_row(ui, "Start Time:", &time_text);
+
+                        ui.label(egui::RichText::new("DPI Awareness:").strong());
+                        match info.dpi_awareness {
+                            Some(awareness) => {
+                                ui.label(awareness.to_string()).on_hover_text(awareness.description());
+                            }
+                            None => {
+                                ui.label("\u{2014}");
+                            }
+                        }
+                        ui.end_row();
+
+                        label_row(ui, "UI Framework:", info.ui_framework.as_deref().unwrap_or("\u{2014}"));
+                        enrichment_rows(ui, &info.enrichment);
                     });
 
+                ui.add_space(8.0);
+                show_launch_origin(ui, &info.launch_origin);
+                show_connections(ui, info);
+
                 ui.add_space(12.0);
                 ui.vertical_centered(|ui| {
-                    if ui.button("   Close   ").clicked() {
-                        result = DialogResult::Cancelled;
-                    }
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(!info.exe_path.is_empty(), egui::Button::new("Windows Properties"))
+                            .on_hover_text("Open the Windows file properties for the executable")
+                            .clicked()
+                        {
+                            info.windows_properties_requested = true;
+                        }
+                        if ui.button("   Close   ").clicked() {
+                            result = DialogResult::Cancelled;
+                        }
+                    });
                 });
                 ui.add_space(4.0);
             });
@@ -423,6 +2900,78 @@ pub fn show_process_properties(
     result
 }
 
+/// Collapsible explanation of what probably started the process.
+fn show_launch_origin(ui: &mut egui::Ui, origin: &LaunchOrigin) {
+    egui::CollapsingHeader::new("Why Is This Running?")
+        .id_salt("process_launch_origin")
+        .default_open(true)
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(&origin.summary).strong());
+            ui.add_space(2.0);
+            ui.label(
+                egui::RichText::new(origin.parent_chain.join(" \u{2192} "))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            )
+            .on_hover_text("Parent chain, oldest first");
+            for detail in &origin.details {
+                ui.label(format!("\u{2022} {}", detail));
+            }
+        });
+}
+
+/// Collapsible TCP/UDP port view with a per-connection Close button.
+fn show_connections(ui: &mut egui::Ui, info: &mut ProcessPropertiesInfo) {
+    egui::CollapsingHeader::new(format!("Connections ({})", info.connections.len()))
+        .id_salt("process_connections")
+        .show(ui, |ui| {
+            if ui.small_button("Refresh").clicked() {
+                info.refresh_connections_requested = true;
+            }
+            if info.connections.is_empty() {
+                ui.label(egui::RichText::new("No TCP or UDP endpoints").color(egui::Color32::GRAY));
+                return;
+            }
+            egui::Grid::new("process_connections_grid")
+                .num_columns(5)
+                .spacing([12.0, 4.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Proto");
+                    ui.strong("Local");
+                    ui.strong("Remote");
+                    ui.strong("State");
+                    ui.label("");
+                    ui.end_row();
+
+                    for conn in &info.connections {
+                        ui.label(conn.protocol.to_string());
+                        ui.label(conn.local.to_string());
+                        let remote = match conn.remote {
+                            Some(addr) if addr.port() != 0 => addr.to_string(),
+                            _ => "--".to_string(),
+                        };
+                        ui.label(remote);
+                        ui.label(conn.state_name());
+                        if conn.protocol == connections::Protocol::Tcp {
+                            let resp = ui
+                                .add_enabled(conn.can_close(), egui::Button::new("Close").small())
+                                .on_hover_text("Close this connection without ending the process")
+                                .on_disabled_hover_text(
+                                    "Only established IPv4 connections can be closed",
+                                );
+                            if resp.clicked() {
+                                info.close_connection_requested = Some(conn.clone());
+                            }
+                        } else {
+                            ui.label("");
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
 fn format_memory(bytes: u64) -> String {
     if bytes >= 1_073_741_824 {
         format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
@@ -0,0 +1,447 @@
+//! Shared row filtering for the quick-filter chips and the per-column filter
+//! boxes. Every tab (and its CSV export) asks this module whether a row is
+//! visible, so the filters behave the same way everywhere.
+
+use super::Tab;
+use eframe::egui;
+use crate::enrichment;
+use crate::models::*;
+use crate::processes;
+use crate::services;
+use std::collections::{HashMap, HashSet};
+
+/// One-click filter chips shown below the title bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuickFilter {
+    NonMicrosoft,
+    RunningOnly,
+    DisabledOnly,
+    AdminOnly,
+    Broken,
+    Orphaned,
+    VolatileDrive,
+    NewLast7Days,
+    NewLast30Days,
+    ScheduledTasks,
+}
+
+impl QuickFilter {
+    pub const ALL: [QuickFilter; 10] = [
+        QuickFilter::NonMicrosoft,
+        QuickFilter::RunningOnly,
+        QuickFilter::DisabledOnly,
+        QuickFilter::AdminOnly,
+        QuickFilter::Broken,
+        QuickFilter::Orphaned,
+        QuickFilter::VolatileDrive,
+        QuickFilter::NewLast7Days,
+        QuickFilter::NewLast30Days,
+        QuickFilter::ScheduledTasks,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            QuickFilter::NonMicrosoft => "Non-Microsoft",
+            QuickFilter::RunningOnly => "Running only",
+            QuickFilter::DisabledOnly => "Disabled only",
+            QuickFilter::AdminOnly => "Admin-only",
+            QuickFilter::Broken => "Broken",
+            QuickFilter::Orphaned => "Orphaned",
+            QuickFilter::VolatileDrive => "Removable/network",
+            QuickFilter::NewLast7Days => "New in 7 days",
+            QuickFilter::NewLast30Days => "New in 30 days",
+            QuickFilter::ScheduledTasks => "Scheduled tasks",
+        }
+    }
+
+    pub fn tooltip(&self, tab: Tab) -> &'static str {
+        match (self, tab) {
+            (QuickFilter::NonMicrosoft, _) => "Hide items published by Microsoft",
+            (QuickFilter::RunningOnly, Tab::ExecutionHistory) => "Show only programs that are running now",
+            (QuickFilter::RunningOnly, _) => "Show only entries that are currently running",
+            (QuickFilter::DisabledOnly, _) => "Show only disabled entries",
+            (QuickFilter::AdminOnly, Tab::Processes) => "Show only elevated processes",
+            (QuickFilter::AdminOnly, _) => "Show only entries visible to administrators",
+            (QuickFilter::Broken, Tab::Installed) => "Show only apps whose uninstaller is missing",
+            (QuickFilter::Broken, _) => "Show only entries whose target file is missing",
+            (QuickFilter::Orphaned, _) => "Show only entries left behind in the folder of an uninstalled app",
+            (QuickFilter::VolatileDrive, _) => {
+                "Show only items whose binary is on a removable or network drive"
+            }
+            (QuickFilter::NewLast7Days, _) => "Show only services installed or reconfigured in the last 7 days",
+            (QuickFilter::NewLast30Days, _) => "Show only services installed or reconfigured in the last 30 days",
+            (QuickFilter::ScheduledTasks, _) => "Show only Task Scheduler entries, with their author and registration date",
+        }
+    }
+
+    /// Whether the chip means anything for the given tab.
+    pub fn applies_to(&self, tab: Tab) -> bool {
+        match self {
+            QuickFilter::NonMicrosoft => !matches!(tab, Tab::Security | Tab::DevPackages),
+            QuickFilter::RunningOnly => matches!(tab, Tab::StartupApps | Tab::Services | Tab::ExecutionHistory),
+            QuickFilter::DisabledOnly => matches!(tab, Tab::StartupApps | Tab::Services),
+            QuickFilter::AdminOnly => matches!(tab, Tab::StartupApps | Tab::Services | Tab::Processes),
+            QuickFilter::Broken => matches!(tab, Tab::StartupApps | Tab::Services | Tab::Installed),
+            QuickFilter::Orphaned => matches!(tab, Tab::StartupApps | Tab::Services),
+            QuickFilter::VolatileDrive => {
+                matches!(tab, Tab::StartupApps | Tab::Services | Tab::Processes)
+            }
+            QuickFilter::NewLast7Days | QuickFilter::NewLast30Days => tab == Tab::Services,
+            QuickFilter::ScheduledTasks => tab == Tab::StartupApps,
+        }
+    }
+}
+
+/// Active quick filters, tracked separately for each tab. Active chips combine
+/// with AND.
+#[derive(Default)]
+pub struct FilterState {
+    active: HashMap<Tab, HashSet<QuickFilter>>,
+}
+
+impl FilterState {
+    pub fn is_active(&self, tab: Tab, filter: QuickFilter) -> bool {
+        self.active.get(&tab).is_some_and(|set| set.contains(&filter))
+    }
+
+    pub fn toggle(&mut self, tab: Tab, filter: QuickFilter) {
+        let set = self.active.entry(tab).or_default();
+        if !set.remove(&filter) {
+            set.insert(filter);
+        }
+    }
+
+    pub fn clear(&mut self, tab: Tab) {
+        self.active.remove(&tab);
+    }
+
+    pub fn any_active(&self, tab: Tab) -> bool {
+        self.active.get(&tab).is_some_and(|set| !set.is_empty())
+    }
+
+    /// Every active chip with its tab.
+    pub fn all_active(&self) -> impl Iterator<Item = (Tab, QuickFilter)> + '_ {
+        self.active.iter().flat_map(|(tab, set)| set.iter().map(|f| (*tab, *f)))
+    }
+
+    /// Labels of the chips active on `tab`, in display order.
+    pub fn active_labels(&self, tab: Tab) -> Vec<&'static str> {
+        QuickFilter::ALL
+            .into_iter()
+            .filter(|f| f.applies_to(tab) && self.is_active(tab, *f))
+            .map(|f| f.label())
+            .collect()
+    }
+
+    fn active_for(&self, tab: Tab) -> impl Iterator<Item = QuickFilter> + '_ {
+        self.active
+            .get(&tab)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(move |f| f.applies_to(tab))
+    }
+
+    /// Whether a startup entry or service passes the active chips for `tab`.
+    pub fn entry_matches(&self, tab: Tab, entry: &StartupEntry) -> bool {
+        self.active_for(tab).all(|filter| match filter {
+            QuickFilter::NonMicrosoft => !is_microsoft_entry(entry),
+            QuickFilter::RunningOnly => entry.run_state == RunState::Running,
+            QuickFilter::DisabledOnly => entry.enabled == EnabledStatus::Disabled,
+            QuickFilter::AdminOnly => entry.requires_admin,
+            QuickFilter::Broken => entry.target_missing,
+            QuickFilter::Orphaned => entry.orphaned_by.is_some(),
+            QuickFilter::VolatileDrive => entry.volatile_drive.is_some(),
+            QuickFilter::NewLast7Days => entry.installed_within(7),
+            QuickFilter::NewLast30Days => entry.installed_within(30),
+            QuickFilter::ScheduledTasks => matches!(entry.source, Source::TaskScheduler { .. }),
+        })
+    }
+
+    pub fn process_matches(&self, proc: &ProcessInfo) -> bool {
+        self.active_for(Tab::Processes).all(|filter| match filter {
+            QuickFilter::NonMicrosoft => !processes::is_windows_process(proc),
+            QuickFilter::AdminOnly => proc.is_elevated,
+            QuickFilter::VolatileDrive => proc.volatile_drive.is_some(),
+            _ => true,
+        })
+    }
+
+    pub fn app_matches(&self, app: &InstalledApp) -> bool {
+        self.active_for(Tab::Installed).all(|filter| match filter {
+            QuickFilter::NonMicrosoft => !is_microsoft_publisher(&app.publisher),
+            QuickFilter::Broken => app.uninstaller_missing,
+            _ => true,
+        })
+    }
+
+    pub fn execution_record_matches(&self, record: &ExecutionRecord) -> bool {
+        self.active_for(Tab::ExecutionHistory).all(|filter| match filter {
+            QuickFilter::NonMicrosoft => !is_microsoft_publisher(&record.publisher),
+            QuickFilter::RunningOnly => record.is_running,
+            _ => true,
+        })
+    }
+}
+
+/// Filter text for one tab, keyed by column name.
+pub type ColumnFilterRow = HashMap<String, String>;
+
+/// Spreadsheet-style filter boxes under the table headers. Text columns match
+/// rows containing the text (or exactly, with a leading `=`); numeric columns
+/// take a comparison such as `> 100 MB` or `<= 5%`. Filled-in boxes combine
+/// with AND, and with the quick-filter chips. The text is kept while the row
+/// is hidden but only applies while it is shown.
+#[derive(Default)]
+pub struct ColumnFilters {
+    pub visible: bool,
+    text: HashMap<Tab, ColumnFilterRow>,
+}
+
+impl ColumnFilters {
+    /// The filter boxes for `tab`, or `None` while the filter row is hidden.
+    pub fn row(&mut self, tab: Tab) -> Option<&mut ColumnFilterRow> {
+        if self.visible {
+            Some(self.text.entry(tab).or_default())
+        } else {
+            None
+        }
+    }
+
+    pub fn any_active(&self, tab: Tab) -> bool {
+        self.active_for(tab).next().is_some()
+    }
+
+    pub fn clear(&mut self, tab: Tab) {
+        self.text.remove(&tab);
+    }
+
+    /// Every filled-in box as (tab, column, text), shown or not.
+    pub fn filled(&self) -> impl Iterator<Item = (Tab, &str, &str)> + '_ {
+        self.text.iter().flat_map(|(tab, row)| {
+            row.iter()
+                .filter(|(_, text)| !text.trim().is_empty())
+                .map(|(column, text)| (*tab, column.as_str(), text.as_str()))
+        })
+    }
+
+    pub fn set(&mut self, tab: Tab, column: String, text: String) {
+        self.text.entry(tab).or_default().insert(column, text);
+    }
+
+    /// The filled-in boxes on `tab` as "Column: filter", sorted by column.
+    pub fn describe(&self, tab: Tab) -> Vec<String> {
+        let mut filters: Vec<String> = self
+            .active_for(tab)
+            .map(|(column, filter)| format!("{}: {}", column, filter))
+            .collect();
+        filters.sort();
+        filters
+    }
+
+    fn active_for(&self, tab: Tab) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.text
+            .get(&tab)
+            .filter(|_| self.visible)
+            .into_iter()
+            .flatten()
+            .map(|(column, text)| (column.as_str(), text.trim()))
+            .filter(|(_, text)| !text.is_empty())
+    }
+
+    /// Whether a startup entry or service passes the filter boxes for `tab`.
+    pub fn entry_matches(&self, tab: Tab, entry: &StartupEntry) -> bool {
+        self.active_for(tab).all(|(column, filter)| match column {
+            "Name" => text_matches(filter, &entry.name),
+            "Product Name" => text_matches(filter, &entry.product_name),
+            "Command" => text_matches(filter, &entry.command),
+            "Source" => text_matches(filter, &entry.source.display_location()),
+            "User" => text_matches(filter, entry.profile_user()),
+            "Task Author" => text_matches(filter, entry.task_registration.as_ref().map_or("", |r| r.author.as_str())),
+            "Task Registered" => text_matches(
+                filter,
+                &entry.task_registration.as_ref().map(|r| r.registered_text()).unwrap_or_default(),
+            ),
+            "Status" => text_matches(filter, &format!("{:?}", entry.enabled)),
+            "State" => text_matches(filter, &format!("{:?}", entry.run_state)),
+            "CPU %" => number_matches(filter, entry.host_usage.map(|u| u.cpu_usage as f64), Unit::Percent),
+            "Memory" => {
+                number_matches(filter, entry.host_usage.map(|u| u.memory_bytes as f64), Unit::Bytes)
+            }
+            "Runs As" => text_matches(filter, &entry.runs_as),
+            "Visible As" => text_matches(filter, if entry.requires_admin { "Admin" } else { "User" }),
+            column if enrichment::is_column(column) => {
+                text_matches(filter, entry.enrichment.value(column).unwrap_or_default())
+            }
+            _ => true,
+        })
+    }
+
+    pub fn process_matches(&self, proc: &ProcessInfo) -> bool {
+        self.active_for(Tab::Processes).all(|(column, filter)| match column {
+            "PID" => number_matches(filter, Some(proc.pid as f64), Unit::Count),
+            "Name" => text_matches(filter, &proc.name),
+            "Product Name" => text_matches(filter, &proc.product_name),
+            "Command Line" => text_matches(filter, &proc.command_line),
+            "CPU %" => number_matches(filter, Some(proc.cpu_usage as f64), Unit::Percent),
+            "Memory" => number_matches(filter, Some(proc.memory_bytes as f64), Unit::Bytes),
+            "Disk Read" => number_matches(filter, Some(proc.disk_read_bytes as f64), Unit::Bytes),
+            "Disk Write" => number_matches(filter, Some(proc.disk_write_bytes as f64), Unit::Bytes),
+            "Runs As" => text_matches(filter, &proc.user_name),
+            "Visible As" => text_matches(filter, if proc.is_elevated { "Admin" } else { "User" }),
+            "Built / Signed" => text_matches(filter, &processes::format_binary_dates(proc)),
+            _ => true,
+        })
+    }
+
+    pub fn app_matches(&self, app: &InstalledApp) -> bool {
+        self.active_for(Tab::Installed).all(|(column, filter)| match column {
+            "Name" => text_matches(filter, &app.display_name),
+            "Publisher" => text_matches(filter, &app.publisher),
+            "Version" => text_matches(filter, &app.display_version),
+            "Install Date" => text_matches(filter, &app.install_date),
+            "Size" => number_matches(
+                filter,
+                (app.estimated_size_kb > 0).then_some(app.estimated_size_kb as f64 * 1024.0),
+                Unit::Bytes,
+            ),
+            "Install Location" => text_matches(filter, &app.install_location),
+            _ => true,
+        })
+    }
+
+    pub fn execution_record_matches(&self, record: &ExecutionRecord) -> bool {
+        self.active_for(Tab::ExecutionHistory).all(|(column, filter)| match column {
+            "Name" => text_matches(filter, &record.name),
+            "Publisher" => text_matches(filter, &record.publisher),
+            "SHA-1" => text_matches(filter, record.sha1.as_deref().unwrap_or_default()),
+            "Path" => text_matches(filter, &record.path),
+            _ => true,
+        })
+    }
+
+    pub fn package_matches(&self, package: &DevPackage) -> bool {
+        self.active_for(Tab::DevPackages).all(|(column, filter)| match column {
+            "Name" => text_matches(filter, &package.name),
+            "Version" => text_matches(filter, &package.version),
+            "Manager" => text_matches(filter, package.manager.label()),
+            "Location" => text_matches(filter, &package.location),
+            _ => true,
+        })
+    }
+}
+
+/// Draw the filter box for `column` under its header label, if the filter
+/// row is shown. `hint` is the placeholder, e.g. "contains" or "> 100 MB".
+pub fn filter_box(ui: &mut egui::Ui, row: &mut Option<&mut ColumnFilterRow>, column: &'static str, hint: &str) {
+    let Some(row) = row else {
+        return;
+    };
+    let text = row.entry(column.to_string()).or_default();
+    let invalid = is_numeric_hint(hint) && !text.trim().is_empty() && parse_comparison(text.trim()).is_none();
+    let mut edit = egui::TextEdit::singleline(text)
+        .hint_text(hint)
+        .desired_width(f32::INFINITY);
+    if invalid {
+        edit = edit.text_color(egui::Color32::from_rgb(230, 80, 80));
+    }
+    let resp = ui.add(edit);
+    if invalid {
+        resp.on_hover_text("Expected a comparison such as > 100 MB, <= 5%, or = 0");
+    }
+}
+
+/// Header height for tables that may show the filter row.
+pub fn header_height(row: &Option<&mut ColumnFilterRow>) -> f32 {
+    if row.is_some() { 44.0 } else { 20.0 }
+}
+
+fn is_numeric_hint(hint: &str) -> bool {
+    hint.starts_with(['>', '<'])
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Count,
+    Percent,
+    Bytes,
+}
+
+#[derive(Clone, Copy)]
+enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+}
+
+/// Case-insensitive "contains", or "equals" with a leading `=`.
+fn text_matches(filter: &str, value: &str) -> bool {
+    match filter.strip_prefix('=') {
+        Some(exact) => value.eq_ignore_ascii_case(exact.trim()),
+        None => value.to_lowercase().contains(&filter.to_lowercase()),
+    }
+}
+
+/// Compare `value` against a filter like `> 100 MB`. Rows without a value
+/// never match; a filter that doesn't parse (e.g. still being typed) keeps
+/// every row.
+fn number_matches(filter: &str, value: Option<f64>, unit: Unit) -> bool {
+    let Some((op, number, suffix)) = parse_comparison(filter) else {
+        return true;
+    };
+    let Some(value) = value else {
+        return false;
+    };
+    let threshold = match (unit, suffix.as_str()) {
+        (Unit::Bytes, "kb" | "k") => number * 1024.0,
+        (Unit::Bytes, "mb" | "m" | "") => number * 1024.0 * 1024.0,
+        (Unit::Bytes, "gb" | "g") => number * 1024.0 * 1024.0 * 1024.0,
+        (Unit::Bytes, "b") => number,
+        (Unit::Percent, "%" | "") | (Unit::Count, "") => number,
+        _ => return true,
+    };
+    match op {
+        Comparison::Greater => value > threshold,
+        Comparison::GreaterOrEqual => value >= threshold,
+        Comparison::Less => value < threshold,
+        Comparison::LessOrEqual => value <= threshold,
+        Comparison::Equal => (value - threshold).abs() < 0.5,
+    }
+}
+
+/// Split `>= 1.5 GB` into the comparison, the number, and the lower-cased
+/// unit suffix. A bare number means `=`.
+fn parse_comparison(filter: &str) -> Option<(Comparison, f64, String)> {
+    let (op, rest) = if let Some(rest) = filter.strip_prefix(">=") {
+        (Comparison::GreaterOrEqual, rest)
+    } else if let Some(rest) = filter.strip_prefix("<=") {
+        (Comparison::LessOrEqual, rest)
+    } else if let Some(rest) = filter.strip_prefix('>') {
+        (Comparison::Greater, rest)
+    } else if let Some(rest) = filter.strip_prefix('<') {
+        (Comparison::Less, rest)
+    } else if let Some(rest) = filter.strip_prefix('=') {
+        (Comparison::Equal, rest)
+    } else {
+        (Comparison::Equal, filter)
+    };
+    let rest = rest.trim();
+    let split = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(rest.len());
+    let number = rest[..split].parse::<f64>().ok()?;
+    Some((op, number, rest[split..].trim().to_lowercase()))
+}
+
+pub fn is_microsoft_entry(entry: &StartupEntry) -> bool {
+    match entry.source {
+        Source::Service { .. } => services::is_microsoft_service(entry),
+        _ => is_microsoft_publisher(&entry.product_name),
+    }
+}
+
+fn is_microsoft_publisher(name: &str) -> bool {
+    name.to_lowercase().starts_with("microsoft")
+}
@@ -0,0 +1,314 @@
+//! In-app help: the guided tour shown on first run (and from Help > Guided
+//! tour), and the text behind each action button's hover help.
+
+use super::dialogs::DialogResult;
+use crate::app_data;
+use crate::models::Source;
+use eframe::egui;
+
+/// Present once the tour has been finished or skipped.
+const TOUR_SEEN_FILE: &str = "tour-seen";
+
+/// A part of the window a tour step points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Tabs,
+    Filters,
+    AdminIndicator,
+    Table,
+    HelpMenu,
+}
+
+/// Where each target was drawn this frame. Targets not on screen (e.g. the
+/// filter chips on the Security tab) are `None`, and their step is shown
+/// in the middle of the window.
+#[derive(Debug, Clone, Default)]
+pub struct Anchors {
+    rects: [Option<egui::Rect>; 5],
+}
+
+impl Anchors {
+    pub fn set(&mut self, target: Target, rect: egui::Rect) {
+        self.rects[target as usize] = Some(rect);
+    }
+
+    /// Grow `target`'s rect to cover `rect` as well.
+    pub fn extend(&mut self, target: Target, rect: egui::Rect) {
+        let slot = &mut self.rects[target as usize];
+        *slot = Some(slot.map_or(rect, |r| r.union(rect)));
+    }
+
+    pub fn get(&self, target: Target) -> Option<egui::Rect> {
+        self.rects[target as usize]
+    }
+}
+
+struct Step {
+    target: Target,
+    title: &'static str,
+    text: &'static str,
+}
+
+const TOUR: &[Step] = &[
+    Step {
+        target: Target::Tabs,
+        title: "Tabs",
+        text: "Each tab lists one kind of thing that runs or is installed: installed apps, startup \
+               entries (Run keys, startup folders, scheduled tasks), processes, services, what has run \
+               before, and the security products Windows knows about. The number is how many are \
+               shown after filtering.",
+    },
+    Step {
+        target: Target::Filters,
+        title: "Filters",
+        text: "Quick filters narrow the active tab to entries worth a look, such as non-Microsoft, \
+               broken, or newly added ones. Column filters add a box under each column header; every filled-in box must \
+               match. Filters are kept per tab, and Clear resets them.",
+    },
+    Step {
+        target: Target::AdminIndicator,
+        title: "Administrator",
+        text: "As a standard user, App Manager can only change your own startup entries, and some \
+               machine-wide entries, services, and processes can't be read at all. Restart as Admin \
+               to see and manage everything.",
+    },
+    Step {
+        target: Target::Table,
+        title: "Actions",
+        text: "The buttons at the end of each row act on that entry. Hover over one to see exactly \
+               what it changes; disabling keeps the entry so it can be enabled again, while deleting \
+               removes it. Double-click a row for its properties.",
+    },
+    Step {
+        target: Target::HelpMenu,
+        title: "Help",
+        text: "Replay this tour or read what every action does from the Help menu at any time.",
+    },
+];
+
+/// The guided tour, on the step being shown.
+#[derive(Debug, Clone, Default)]
+pub struct Tour {
+    step: usize,
+}
+
+impl Tour {
+    /// Dim the window around the current step's target and show its callout.
+    /// `Confirmed` when the last step is done, `Cancelled` when skipped.
+    pub fn show(&mut self, ctx: &egui::Context, anchors: &Anchors) -> DialogResult {
+        let mut result = DialogResult::Open;
+        let step = &TOUR[self.step];
+        let screen = ctx.content_rect();
+        let target = anchors.get(step.target).map(|r| r.expand(4.0));
+
+        egui::Area::new(egui::Id::new("tour_dim"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen.min)
+            .interactable(false)
+            .show(ctx, |ui| {
+                let painter = ui.painter();
+                let dim = egui::Color32::from_black_alpha(150);
+                let Some(t) = target else {
+                    painter.rect_filled(screen, 0.0, dim);
+                    return;
+                };
+                // Everything but the target
+                let above = egui::Rect::from_min_max(screen.min, egui::pos2(screen.max.x, t.min.y));
+                let below = egui::Rect::from_min_max(egui::pos2(screen.min.x, t.max.y), screen.max);
+                let left = egui::Rect::from_min_max(egui::pos2(screen.min.x, t.min.y), egui::pos2(t.min.x, t.max.y));
+                let right = egui::Rect::from_min_max(egui::pos2(t.max.x, t.min.y), egui::pos2(screen.max.x, t.max.y));
+                for rect in [above, below, left, right] {
+                    painter.rect_filled(rect, 0.0, dim);
+                }
+                painter.rect_stroke(
+                    t,
+                    4.0,
+                    egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 160, 230)),
+                    egui::StrokeKind::Outside,
+                );
+            });
+
+        // Next to small targets, below them when there's room; over the
+        // middle of large ones (the table)
+        let (pivot, pos) = match target {
+            Some(t) if t.height() < screen.height() / 2.0 => {
+                if t.bottom() + 200.0 < screen.bottom() {
+                    (egui::Align2::LEFT_TOP, egui::pos2(t.left(), t.bottom() + 8.0))
+                } else {
+                    (egui::Align2::LEFT_BOTTOM, egui::pos2(t.left(), t.top() - 8.0))
+                }
+            }
+            Some(t) => (egui::Align2::CENTER_CENTER, t.center()),
+            None => (egui::Align2::CENTER_CENTER, screen.center()),
+        };
+        let callout = egui::Area::new(egui::Id::new("tour_callout"))
+            .order(egui::Order::Foreground)
+            .pivot(pivot)
+            .fixed_pos(pos)
+            .constrain(true)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(340.0);
+                    ui.horizontal(|ui| {
+                        ui.label(egui::RichText::new(step.title).strong());
+                        ui.label(
+                            egui::RichText::new(format!("{} of {}", self.step + 1, TOUR.len()))
+                                .small()
+                                .color(egui::Color32::GRAY),
+                        );
+                    });
+                    ui.add_space(4.0);
+                    ui.add(egui::Label::new(step.text).wrap());
+                    ui.add_space(8.0);
+                    ui.horizontal(|ui| {
+                        let last = self.step + 1 == TOUR.len();
+                        if ui.add_enabled(self.step > 0, egui::Button::new("Back")).clicked() {
+                            self.step -= 1;
+                        }
+                        if ui.button(if last { "Done" } else { "Next" }).clicked() {
+                            if last {
+                                result = DialogResult::Confirmed;
+                            } else {
+                                self.step += 1;
+                            }
+                        }
+                        if !last && ui.button("Skip tour").clicked() {
+                            result = DialogResult::Cancelled;
+                        }
+                    });
+                });
+            });
+        // Keep the callout above the dimming once either has been clicked
+        ctx.move_to_top(callout.response.layer_id);
+
+        result
+    }
+}
+
+/// Whether the tour has been finished or skipped before. Without a data
+/// directory there is nowhere to remember it, so it counts as seen rather
+/// than showing on every launch.
+pub fn tour_seen() -> bool {
+    app_data::data_dir().is_none_or(|dir| dir.join(TOUR_SEEN_FILE).exists())
+}
+
+pub fn mark_tour_seen() {
+    if let Some(dir) = app_data::data_dir() {
+        let _ = app_data::write_atomic(&dir.join(TOUR_SEEN_FILE), "");
+    }
+}
+
+/// A row action with help text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Enable,
+    Disable,
+    Start,
+    Stop,
+    Delete,
+    Properties,
+    Kill,
+    Modify,
+    Uninstall,
+}
+
+impl Action {
+    pub const ALL: [Action; 9] = [
+        Action::Enable,
+        Action::Disable,
+        Action::Start,
+        Action::Stop,
+        Action::Delete,
+        Action::Properties,
+        Action::Kill,
+        Action::Modify,
+        Action::Uninstall,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Enable => "Enable",
+            Action::Disable => "Disable",
+            Action::Start => "Start",
+            Action::Stop => "Stop",
+            Action::Delete => "Delete",
+            Action::Properties => "Properties",
+            Action::Kill => "Kill",
+            Action::Modify => "Modify",
+            Action::Uninstall => "Uninstall",
+        }
+    }
+
+    /// What the action does, whatever it's used on.
+    pub fn help(self) -> &'static str {
+        match self {
+            Action::Enable => "Lets the entry run again at sign-in or boot, undoing Disable.",
+            Action::Disable => {
+                "Stops the entry from running at sign-in or boot without removing it, so Enable can \
+                 put it back exactly as it was. Anything already running keeps running."
+            }
+            Action::Start => "Runs the entry now. Services start without their start type changing.",
+            Action::Stop => {
+                "Ends the entry's running processes (or stops the service) now. It runs again at the \
+                 next sign-in or boot unless it's disabled too."
+            }
+            Action::Delete => {
+                "Removes the entry for good. Startup folder files are moved to App Manager's backup \
+                 store and can be restored; other deletions can't be undone."
+            }
+            Action::Properties => "Shows everything App Manager knows about the item. Changes nothing.",
+            Action::Kill => {
+                "Ends the process immediately, without letting it save its work. Processes Windows \
+                 needs ask for confirmation first."
+            }
+            Action::Modify => "Opens the app's own setup to add or remove features or repair it.",
+            Action::Uninstall => {
+                "Runs the app's uninstaller. The list is rescanned when the uninstaller exits."
+            }
+        }
+    }
+}
+
+/// The hover help for `action` on an entry from `source`: what exactly
+/// changes for that kind of entry.
+pub fn entry_action_help(action: Action, source: &Source) -> &'static str {
+    match (action, source) {
+        (Action::Disable, Source::RegistryRun { .. } | Source::StartupFolder { .. }) => {
+            "Turns the entry off the way Task Manager does, with a StartupApproved flag in the \
+             registry. The Run value or file is kept, so Enable puts it back exactly as it was. \
+             Anything already running keeps running."
+        }
+        (Action::Disable, Source::TaskScheduler { .. }) => {
+            "Disables the scheduled task, so none of its triggers start it. The task itself is \
+             kept and Enable turns it back on."
+        }
+        (Action::Disable, Source::Service { .. }) => {
+            "Sets the service's start type to Disabled, so neither Windows nor other programs can \
+             start it. Features that depend on it may stop working. Enable sets it back to Automatic."
+        }
+        (Action::Enable, Source::Service { .. }) => "Sets the service's start type to Automatic.",
+        (Action::Start, Source::Service { .. }) => "Starts the service now; its start type is unchanged.",
+        (Action::Start, _) => "Runs the entry's command now, as you.",
+        (Action::Stop, Source::Service { .. }) => {
+            "Stops the service now. It starts again at the next boot if its start type is Automatic."
+        }
+        (Action::Stop, _) => {
+            "Ends every running process of the entry's executable. It runs again at the next \
+             sign-in unless it's disabled too."
+        }
+        (Action::Delete, Source::StartupFolder { .. }) => {
+            "Moves the file to App Manager's backup store, so it no longer runs at sign-in. It can \
+             be restored with Restore... on the Startup Apps tab."
+        }
+        (Action::Delete, Source::Service { .. }) => {
+            "Deletes the service from the Service Control Manager. The program's files are kept, \
+             but this can't be undone; Disable is usually enough."
+        }
+        (Action::Delete, Source::Ifeo { .. }) => {
+            "Removes the Debugger value, so the program starts normally again instead of through \
+             the debugger."
+        }
+        (Action::Delete, _) => "Removes the entry for good. This can't be undone; Disable can be.",
+        _ => action.help(),
+    }
+}
@@ -0,0 +1,309 @@
+use super::column_fit::{self, ColumnFit};
+use super::filters::{self, ColumnFilterRow};
+use super::table::{details_tooltip, truncated_label};
+use crate::models::ExecutionRecord;
+use chrono::{DateTime, Local};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+/// Execution history actions, addressed by the record's path.
+pub enum HistoryAction {
+    OpenLocation(String),
+    CopySha1(String),
+}
+
+pub struct HistoryTableResult {
+    pub action: Option<HistoryAction>,
+    pub clicked_row: Option<usize>,
+    pub hovered_row: Option<usize>,
+    /// Vertical scroll position, to restore in a later session.
+    pub scroll_offset: f32,
+}
+
+pub fn render_history_table(
+    ui: &mut egui::Ui,
+    records: &[ExecutionRecord],
+    selected_row: Option<usize>,
+    prev_hovered_row: Option<usize>,
+    scroll_to: Option<f32>,
+    mut filters: Option<&mut ColumnFilterRow>,
+) -> HistoryTableResult {
+    let mut action = None;
+    let mut clicked_row = None;
+    let mut hovered_row = None;
+
+    let available_height = ui.available_height();
+
+    let fit = ColumnFit::new(ui, "history_table", || {
+        let measure = |header: &str, cell: fn(&ExecutionRecord) -> String| {
+            Some(column_fit::text_width(ui, std::iter::once(header.to_string()).chain(records.iter().map(cell))))
+        };
+        vec![
+            measure("Name", |r| r.name.clone()),
+            measure("Publisher", |r| r.publisher.clone()),
+            measure("First Seen", |r| format_time(r.first_seen)),
+            measure("File Modified", |r| format_time(r.file_modified)),
+            Some(column_fit::text_width(ui, ["Also In", "Startup, Service, Running"])),
+            measure("SHA-1", |r| r.sha1.as_deref().map(|h| h[..12].to_string()).unwrap_or_default()),
+            measure("Path", |r| r.path.clone()),
+            None, // Actions
+        ]
+    });
+    let mut table = TableBuilder::new(ui)
+        .id_salt("history_table")
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(fit.column(160.0, 80.0)) // Name
+        .column(fit.column(150.0, 80.0)) // Publisher
+        .column(fit.column(130.0, 100.0)) // First Seen
+        .column(fit.column(130.0, 100.0)) // File Modified
+        .column(fit.column(90.0, 60.0)) // Also In
+        .column(fit.column(110.0, 60.0)) // SHA-1
+        .column(fit.column(260.0, 100.0)) // Path
+        .column(Column::remainder().at_least(140.0)) // Actions
+        .min_scrolled_height(0.0)
+        .max_scroll_height(available_height);
+    if fit.pending() {
+        table.reset();
+    }
+
+    if let Some(offset) = scroll_to {
+        table = table.vertical_scroll_offset(offset);
+    }
+
+    let scroll = table
+        .header(filters::header_height(&filters), |mut header| {
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Name");
+                    filters::filter_box(ui, &mut filters, "Name", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Publisher");
+                    filters::filter_box(ui, &mut filters, "Publisher", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.strong("First Seen")
+                    .on_hover_text("When Amcache first recorded the file");
+            });
+            header.col(|ui| {
+                ui.strong("File Modified")
+                    .on_hover_text("File modification time recorded by the Shimcache");
+            });
+            header.col(|ui| {
+                ui.strong("Also In")
+                    .on_hover_text("Where else the file appears: Startup Apps, Services, or running Processes");
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("SHA-1");
+                    filters::filter_box(ui, &mut filters, "SHA-1", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Path");
+                    filters::filter_box(ui, &mut filters, "Path", "contains");
+                });
+            });
+            header.col(|ui| { ui.strong("Actions"); });
+            fit.context_menu(&header.response());
+        })
+        .body(|body| {
+            fit.record(body.widths());
+            body.rows(24.0, records.len(), |mut row| {
+                let index = row.index();
+                let record = &records[index];
+                let is_selected = selected_row == Some(index);
+                let was_hovered = prev_hovered_row == Some(index);
+
+                if is_selected || was_hovered {
+                    row.set_selected(true);
+                }
+
+                let mut row_hovered = false;
+                let mut row_clicked = false;
+
+                // Name
+                let (_, cell_resp) = row.col(|ui| {
+                    let mut text = egui::RichText::new(&record.name);
+                    if record.is_startup_entry || record.is_service || record.is_running {
+                        text = text.strong();
+                    }
+                    let label = egui::Label::new(text)
+                        .truncate()
+                        .sense(egui::Sense::click());
+                    let mut resp = ui.add(label);
+                    let recorded_by = match (record.in_amcache, record.shimcache_position.is_some()) {
+                        (true, true) => "Amcache, Shimcache",
+                        (true, false) => "Amcache",
+                        _ => "Shimcache",
+                    };
+                    let mut details = vec![format!("Recorded by: {}", recorded_by)];
+                    if !record.product_name.is_empty() {
+                        details.push(format!("Product: {}", record.product_name));
+                    }
+                    if !record.version.is_empty() {
+                        details.push(format!("Version: {}", record.version));
+                    }
+                    if let Some(pos) = record.shimcache_position {
+                        details.push(format!("Shimcache position: {}", pos + 1));
+                    }
+                    resp = resp.on_hover_text(details.join("\n"));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Publisher
+                let (_, cell_resp) = row.col(|ui| {
+                    let text = if record.publisher.is_empty() { "\u{2014}" } else { &record.publisher };
+                    let color = if record.publisher.is_empty() {
+                        egui::Color32::GRAY
+                    } else {
+                        egui::Color32::from_rgb(200, 200, 200)
+                    };
+                    let resp = ui.add(truncated_label(egui::RichText::new(text).color(color)));
+                    let resp = details_tooltip(ui, resp, text, || record_details(record));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // First Seen, File Modified
+                for time in [record.first_seen, record.file_modified] {
+                    let (_, cell_resp) = row.col(|ui| {
+                        let text = format_time(time);
+                        let label = egui::Label::new(&text)
+                            .sense(egui::Sense::click());
+                        let resp = ui.add(label);
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    });
+                    row_hovered |= cell_resp.hovered();
+                    row_clicked |= cell_resp.clicked();
+                }
+
+                // Also In
+                let (_, cell_resp) = row.col(|ui| {
+                    let mut places = Vec::new();
+                    if record.is_startup_entry {
+                        places.push("Startup");
+                    }
+                    if record.is_service {
+                        places.push("Service");
+                    }
+                    if record.is_running {
+                        places.push("Running");
+                    }
+                    let (text, color) = if places.is_empty() {
+                        ("--".to_string(), egui::Color32::GRAY)
+                    } else {
+                        (places.join(", "), egui::Color32::from_rgb(100, 160, 230))
+                    };
+                    let label = egui::Label::new(egui::RichText::new(text).color(color))
+                        .truncate()
+                        .sense(egui::Sense::click());
+                    let resp = ui.add(label);
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // SHA-1 (abbreviated; full hash on hover)
+                let (_, cell_resp) = row.col(|ui| {
+                    let resp = match &record.sha1 {
+                        Some(hash) => ui
+                            .add(
+                                egui::Label::new(egui::RichText::new(&hash[..12]).monospace())
+                                    .sense(egui::Sense::click()),
+                            )
+                            .on_hover_text(hash),
+                        None => ui.add(
+                            egui::Label::new(egui::RichText::new("--").color(egui::Color32::GRAY))
+                                .sense(egui::Sense::click()),
+                        ),
+                    };
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Path
+                let (_, cell_resp) = row.col(|ui| {
+                    let resp = ui.add(truncated_label(&record.path));
+                    let resp = details_tooltip(ui, resp, &record.path, || record_details(record));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Actions
+                let (_, cell_resp) = row.col(|ui| {
+                    ui.horizontal(|ui| {
+                        let btn_size = egui::vec2(65.0, 18.0);
+
+                        if ui
+                            .add_sized(btn_size, egui::Button::new("Location"))
+                            .clicked()
+                        {
+                            action = Some(HistoryAction::OpenLocation(record.path.clone()));
+                        }
+
+                        if let Some(hash) = &record.sha1 {
+                            if ui
+                                .add_sized(btn_size, egui::Button::new("Copy SHA-1"))
+                                .clicked()
+                            {
+                                action = Some(HistoryAction::CopySha1(hash.clone()));
+                            }
+                        }
+                    });
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                if row_hovered {
+                    hovered_row = Some(index);
+                }
+                if row_clicked {
+                    clicked_row = Some(index);
+                }
+            });
+        });
+
+    HistoryTableResult {
+        action,
+        clicked_row,
+        hovered_row,
+        scroll_offset: scroll.state.offset.y,
+    }
+}
+
+/// Full details of an execution record for [`details_tooltip`].
+fn record_details(record: &ExecutionRecord) -> Vec<(&'static str, String)> {
+    vec![
+        ("Name", record.name.clone()),
+        ("Publisher", record.publisher.clone()),
+        ("Product Name", record.product_name.clone()),
+        ("Version", record.version.clone()),
+        ("Path", record.path.clone()),
+    ]
+}
+
+fn format_time(time: Option<DateTime<Local>>) -> String {
+    match time {
+        Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+        None => "--".to_string(),
+    }
+}
@@ -1,23 +1,72 @@
+use super::column_fit::{self, ColumnFit};
+use super::filters::{self, ColumnFilterRow};
+use super::help::Action;
+use super::table::{details_tooltip, truncated_label};
 use crate::models::InstalledApp;
+use crate::suites::Suite;
 use eframe::egui;
-use egui_extras::{Column, TableBuilder};
+use egui_extras::{Column, TableBuilder, TableRow};
+use std::collections::HashSet;
 
+/// Installed app actions, addressed by the app's registry key.
 pub enum InstalledAppAction {
-    Modify(usize),
-    Uninstall(usize),
+    Modify(String),
+    Uninstall(String),
+    /// Expand or collapse a suite, by its key.
+    ToggleSuite(String),
+}
+
+/// A row of the table.
+pub enum InstalledRow<'a> {
+    /// Header of a suite, with its members below it while expanded.
+    Suite { suite: &'a Suite, expanded: bool },
+    App { app: &'a InstalledApp, in_suite: bool },
+}
+
+/// The rows for `apps`: each suite's header at the position of its first
+/// member, followed by its members while it is in `expanded`.
+pub fn table_rows<'a>(apps: &'a [InstalledApp], suites: &'a [Suite], expanded: &HashSet<String>) -> Vec<InstalledRow<'a>> {
+    let mut suite_of = vec![None; apps.len()];
+    for (s, suite) in suites.iter().enumerate() {
+        for &member in &suite.members {
+            suite_of[member] = Some(s);
+        }
+    }
+    let mut shown = vec![false; suites.len()];
+    let mut rows = Vec::with_capacity(apps.len());
+    for (app, suite) in apps.iter().zip(suite_of) {
+        match suite {
+            None => rows.push(InstalledRow::App { app, in_suite: false }),
+            Some(s) if !shown[s] => {
+                shown[s] = true;
+                let suite = &suites[s];
+                let is_expanded = expanded.contains(&suite.key);
+                rows.push(InstalledRow::Suite { suite, expanded: is_expanded });
+                if is_expanded {
+                    rows.extend(suite.members.iter().map(|&m| InstalledRow::App { app: &apps[m], in_suite: true }));
+                }
+            }
+            Some(_) => {}
+        }
+    }
+    rows
 }
 
 pub struct InstalledTableResult {
     pub action: Option<InstalledAppAction>,
     pub clicked_row: Option<usize>,
     pub hovered_row: Option<usize>,
+    /// Vertical scroll position, to restore in a later session.
+    pub scroll_offset: f32,
 }
 
 pub fn render_installed_table(
     ui: &mut egui::Ui,
-    apps: &[InstalledApp],
+    rows: &[InstalledRow],
     selected_row: Option<usize>,
     prev_hovered_row: Option<usize>,
+    scroll_to: Option<f32>,
+    mut filters: Option<&mut ColumnFilterRow>,
 ) -> InstalledTableResult {
     let mut action = None;
     let mut clicked_row = None;
@@ -25,35 +74,94 @@ pub fn render_installed_table(
 
     let available_height = ui.available_height();
 
-    let table = TableBuilder::new(ui)
+    let apps = || {
+        rows.iter().filter_map(|row| match row {
+            InstalledRow::App { app, .. } => Some(*app),
+            InstalledRow::Suite { .. } => None,
+        })
+    };
+    let fit = ColumnFit::new(ui, "installed_table", || {
+        let measure = |header: &str, cell: fn(&InstalledApp) -> String| {
+            Some(column_fit::text_width(ui, std::iter::once(header.to_string()).chain(apps().map(cell))))
+        };
+        vec![
+            measure("Name", |a| a.display_name.clone()),
+            measure("Publisher", |a| a.publisher.clone()),
+            measure("Version", |a| a.display_version.clone()),
+            measure("Install Date", |a| format_install_date(&a.install_date)),
+            measure("Size", |a| format_size(a.estimated_size_kb)),
+            measure("Install Location", |a| a.install_location.clone()),
+            None, // Actions
+        ]
+    });
+    let mut table = TableBuilder::new(ui)
+        .id_salt("installed_table")
         .striped(true)
         .resizable(true)
         .sense(egui::Sense::click())
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-        .column(Column::initial(200.0).at_least(100.0)) // Name
-        .column(Column::initial(180.0).at_least(80.0))  // Publisher
-        .column(Column::initial(100.0).at_least(60.0))  // Version
-        .column(Column::initial(100.0).at_least(70.0))  // Install Date
-        .column(Column::initial(80.0).at_least(50.0))   // Size
-        .column(Column::initial(200.0).at_least(80.0))  // Install Location
+        .column(fit.column(200.0, 100.0)) // Name
+        .column(fit.column(180.0, 80.0))  // Publisher
+        .column(fit.column(100.0, 60.0))  // Version
+        .column(fit.column(100.0, 70.0))  // Install Date
+        .column(fit.column(80.0, 50.0))   // Size
+        .column(fit.column(200.0, 80.0))  // Install Location
         .column(Column::remainder().at_least(150.0))     // Actions
         .min_scrolled_height(0.0)
         .max_scroll_height(available_height);
+    if fit.pending() {
+        table.reset();
+    }
+
+    if let Some(offset) = scroll_to {
+        table = table.vertical_scroll_offset(offset);
+    }
 
-    table
-        .header(20.0, |mut header| {
-            header.col(|ui| { ui.strong("Name"); });
-            header.col(|ui| { ui.strong("Publisher"); });
-            header.col(|ui| { ui.strong("Version"); });
-            header.col(|ui| { ui.strong("Install Date"); });
-            header.col(|ui| { ui.strong("Size"); });
-            header.col(|ui| { ui.strong("Install Location"); });
+    let scroll = table
+        .header(filters::header_height(&filters), |mut header| {
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Name");
+                    filters::filter_box(ui, &mut filters, "Name", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Publisher");
+                    filters::filter_box(ui, &mut filters, "Publisher", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Version");
+                    filters::filter_box(ui, &mut filters, "Version", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Install Date");
+                    filters::filter_box(ui, &mut filters, "Install Date", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Size");
+                    filters::filter_box(ui, &mut filters, "Size", "> 100 MB");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Install Location");
+                    filters::filter_box(ui, &mut filters, "Install Location", "contains");
+                });
+            });
             header.col(|ui| { ui.strong("Actions"); });
+            fit.context_menu(&header.response());
         })
         .body(|body| {
-            body.rows(24.0, apps.len(), |mut row| {
+            fit.record(body.widths());
+            body.rows(24.0, rows.len(), |mut row| {
                 let index = row.index();
-                let app = &apps[index];
                 let is_selected = selected_row == Some(index);
                 let was_hovered = prev_hovered_row == Some(index);
 
@@ -61,15 +169,33 @@ pub fn render_installed_table(
                     row.set_selected(true);
                 }
 
+                let (app, in_suite) = match rows[index] {
+                    InstalledRow::App { app, in_suite } => (app, in_suite),
+                    InstalledRow::Suite { suite, expanded } => {
+                        let (row_hovered, row_clicked) = suite_row(&mut row, suite, expanded, &mut action);
+                        if row_hovered {
+                            hovered_row = Some(index);
+                        }
+                        if row_clicked {
+                            clicked_row = Some(index);
+                        }
+                        return;
+                    }
+                };
+
                 let mut row_hovered = false;
                 let mut row_clicked = false;
 
-                // Name
+                // Name (indented under its suite)
                 let (_, cell_resp) = row.col(|ui| {
-                    let label = egui::Label::new(&app.display_name)
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    if in_suite {
+                        ui.add_space(SUITE_INDENT);
+                    }
+                    let resp = ui.add(truncated_label(&app.display_name));
+                    let mut resp = details_tooltip(ui, resp, &app.display_name, || app_details(app));
+                    if let Some(code) = &app.msi_product_code {
+                        resp = resp.on_hover_text(format!("MSI Product Code: {}", code));
+                    }
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                 });
@@ -84,10 +210,8 @@ pub fn render_installed_table(
                     } else {
                         egui::Color32::from_rgb(200, 200, 200)
                     };
-                    let label = egui::Label::new(egui::RichText::new(text).color(color))
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let resp = ui.add(truncated_label(egui::RichText::new(text).color(color)));
+                    let resp = details_tooltip(ui, resp, text, || app_details(app));
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                 });
@@ -134,10 +258,8 @@ pub fn render_installed_table(
                 // Install Location
                 let (_, cell_resp) = row.col(|ui| {
                     let text = if app.install_location.is_empty() { "--" } else { &app.install_location };
-                    let label = egui::Label::new(text)
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let resp = ui.add(truncated_label(text));
+                    let resp = details_tooltip(ui, resp, text, || app_details(app));
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                 });
@@ -152,16 +274,18 @@ pub fn render_installed_table(
                         let has_modify = app.modify_path.is_some();
                         if ui
                             .add_enabled(has_modify, egui::Button::new("Modify").min_size(btn_size))
+                            .on_hover_text(Action::Modify.help())
                             .clicked()
                         {
-                            action = Some(InstalledAppAction::Modify(index));
+                            action = Some(InstalledAppAction::Modify(app.registry_key.clone()));
                         }
 
                         if ui
                             .add_sized(btn_size, egui::Button::new("Uninstall"))
+                            .on_hover_text(Action::Uninstall.help())
                             .clicked()
                         {
-                            action = Some(InstalledAppAction::Uninstall(index));
+                            action = Some(InstalledAppAction::Uninstall(app.registry_key.clone()));
                         }
                     });
                 });
@@ -181,9 +305,92 @@ pub fn render_installed_table(
         action,
         clicked_row,
         hovered_row,
+        scroll_offset: scroll.state.offset.y,
     }
 }
 
+/// Indent of suite members' names, past the header's expansion box.
+const SUITE_INDENT: f32 = 18.0;
+
+/// Render a suite header: an expansion box and the name, member count, and
+/// combined size. Returns whether the row was hovered and clicked.
+fn suite_row(row: &mut TableRow, suite: &Suite, expanded: bool, action: &mut Option<InstalledAppAction>) -> (bool, bool) {
+    let mut row_hovered = false;
+    let mut row_clicked = false;
+    let count = format!("{} components", suite.members.len());
+
+    // Name
+    let (_, cell_resp) = row.col(|ui| {
+        let resp = expansion_box(ui, expanded).on_hover_text(if expanded { "Collapse" } else { "Expand" });
+        if resp.clicked() {
+            *action = Some(InstalledAppAction::ToggleSuite(suite.key.clone()));
+        }
+        row_hovered |= resp.hovered();
+        let resp = ui.add(truncated_label(egui::RichText::new(&suite.name).strong()));
+        if resp.double_clicked() {
+            *action = Some(InstalledAppAction::ToggleSuite(suite.key.clone()));
+        }
+        row_hovered |= resp.hovered();
+        row_clicked |= resp.clicked();
+    });
+    row_hovered |= cell_resp.hovered();
+    row_clicked |= cell_resp.clicked();
+
+    let gray = |text: &str| egui::RichText::new(text).color(egui::Color32::GRAY);
+    let publisher = if suite.publisher.is_empty() { "\u{2014}" } else { &suite.publisher };
+    let size = format_size(suite.size_kb);
+    for text in [gray(publisher), gray(&count), gray("--"), egui::RichText::new(&size), gray("--")] {
+        let (_, cell_resp) = row.col(|ui| {
+            let resp = ui.add(truncated_label(text));
+            row_hovered |= resp.hovered();
+            row_clicked |= resp.clicked();
+        });
+        row_hovered |= cell_resp.hovered();
+        row_clicked |= cell_resp.clicked();
+    }
+
+    // Actions (uninstall members individually)
+    let (_, cell_resp) = row.col(|_| {});
+    row_hovered |= cell_resp.hovered();
+    row_clicked |= cell_resp.clicked();
+
+    (row_hovered, row_clicked)
+}
+
+/// A Windows-style [+]/[-] box.
+fn expansion_box(ui: &mut egui::Ui, expanded: bool) -> egui::Response {
+    const BOX_SIZE: f32 = 9.0;
+    let (rect, resp) = ui.allocate_exact_size(egui::vec2(BOX_SIZE + 4.0, ui.available_height()), egui::Sense::click());
+    let box_rect = egui::Rect::from_center_size(rect.center(), egui::vec2(BOX_SIZE, BOX_SIZE));
+    let painter = ui.painter();
+    painter.rect_filled(box_rect, 0.0, egui::Color32::from_rgb(32, 32, 32));
+    painter.rect_stroke(
+        box_rect,
+        0.0,
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(90, 90, 90)),
+        egui::StrokeKind::Inside,
+    );
+    let c = box_rect.center();
+    let sign = egui::Stroke::new(1.0, egui::Color32::from_rgb(180, 180, 180));
+    painter.line_segment([egui::pos2(c.x - 3.0, c.y), egui::pos2(c.x + 3.0, c.y)], sign);
+    if !expanded {
+        painter.line_segment([egui::pos2(c.x, c.y - 3.0), egui::pos2(c.x, c.y + 3.0)], sign);
+    }
+    resp
+}
+
+/// Full details of an installed app for [`details_tooltip`].
+fn app_details(app: &InstalledApp) -> Vec<(&'static str, String)> {
+    vec![
+        ("Name", app.display_name.clone()),
+        ("Publisher", app.publisher.clone()),
+        ("Version", app.display_version.clone()),
+        ("Install Location", app.install_location.clone()),
+        ("Uninstall Command", app.uninstall_string.clone()),
+        ("Registry Key", app.registry_key.clone()),
+    ]
+}
+
 fn format_install_date(raw: &str) -> String {
     if raw.len() == 8 {
         // YYYYMMDD -> YYYY-MM-DD
@@ -1,32 +1,122 @@
+mod column_fit;
 mod dialogs;
+mod filters;
+mod help;
+mod history_table;
 mod installed_table;
+mod packages_table;
 mod process_table;
+mod security_panel;
 mod table;
+mod table_image;
 
 use crate::actions;
+use crate::allowlist;
+use crate::audit;
+use crate::backup;
+use crate::categories;
+use crate::clean_boot;
+use crate::cmdline;
 use crate::collector;
+use crate::connections;
+use crate::crash;
+use crate::dry_run;
+use crate::cross_ref::CrossRefIndex;
+use crate::dependencies;
+use crate::dev_packages;
+use crate::drive_watch;
+use crate::drives;
+use crate::enforcement;
+use crate::enrichment;
+use crate::execution_history;
+use crate::executor;
+use crate::export_locale;
+use crate::history;
+use crate::install_watch;
 use crate::installed_apps;
+use crate::kill_guard;
+use crate::launch_origin;
 use crate::models::*;
+use crate::orphans;
+use crate::plan;
+use crate::prefetch;
+use crate::process_ui;
 use crate::processes;
+use crate::reg_file;
+use crate::security_center;
+use crate::service_hardening;
+use crate::service_watch;
 use crate::services;
+use crate::settings;
+use crate::shell;
+use crate::skipped::{SkippedItems, SkippedKind};
+use crate::suites;
+use crate::taskbar;
+use crate::uninstaller;
+use crate::vendors;
+use crate::watchlist;
+use crate::zip_file;
 use eframe::egui;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::os::windows::ffi::OsStrExt;
 use std::os::windows::process::CommandExt;
 use std::sync::mpsc;
 use std::time::Instant;
 
-/// Action requested from the table UI.
-#[derive(Debug, Clone)]
+/// Action requested from the table UI, addressed by stable identity rather
+/// than row position so it stays valid when the lists change between frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PendingAction {
-    Enable(usize),
-    Disable(usize),
-    Start(usize),
-    Stop(usize),
-    ConfirmDelete(usize),
-    ConfirmUninstall(usize),
-    Properties(usize),
+    Enable(EntryId),
+    Disable(EntryId),
+    Start(EntryId),
+    Stop(EntryId),
+    ConfirmDelete(EntryId),
+    /// Uninstall an installed app, by its Uninstall registry key.
+    ConfirmUninstall(String),
+    Properties(EntryId),
+}
+
+/// An entry action requested while a reload was in flight. The action carries
+/// the entry's stable identity and is resolved against the refreshed lists
+/// when the queue drains; the entry captured at request time names it in
+/// status messages.
+struct QueuedAction {
+    action: PendingAction,
+    entry: StartupEntry,
+}
+
+/// Who asked for an executor job, for reporting its result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JobOrigin {
+    /// A table action or dialog.
+    User,
+    /// A cleanup plan step, by action and target.
+    PlanStep(plan::PlanAction, String),
+    /// A bulk disable from the Categories dialog or the Clean Boot
+    /// Assistant.
+    BulkDisable,
+    /// A bulk delete from the Orphaned by Uninstall dialog.
+    BulkDelete,
+}
+
+/// A clean boot waiting for its restore point before disabling anything.
+struct PendingCleanBoot {
+    /// Run history keys and names of the entries to disable.
+    items: Vec<(String, String)>,
+    restart: bool,
+    restore_point: mpsc::Receiver<Result<(), String>>,
+}
+
+/// A change sent to the executor and not finished yet.
+struct RunningJob {
+    id: u64,
+    job: executor::Job,
+    origin: JobOrigin,
+    /// When the executor started it (`None` while queued).
+    started: Option<Instant>,
+    timed_out: bool,
 }
 
 /// Status message shown in the bottom bar.
@@ -36,20 +126,106 @@ struct StatusMessage {
     when: Instant,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Tab {
     Installed,
     StartupApps,
     Processes,
     Services,
+    ExecutionHistory,
+    Security,
+    /// Global developer packages; shown only when turned on in Settings.
+    DevPackages,
 }
 
-struct LoadResult {
-    entries: Vec<StartupEntry>,
-    all_services: Vec<StartupEntry>,
-    all_processes: Vec<ProcessInfo>,
-    installed_apps: Vec<InstalledApp>,
-    is_admin: bool,
+/// One collector's results, sent as soon as that collector finishes so each
+/// tab can be shown without waiting for the slowest one.
+enum LoadMessage {
+    Entries(collector::CollectionResult),
+    Services(Vec<StartupEntry>, SkippedItems),
+    Processes(Vec<ProcessInfo>),
+    Installed(Vec<InstalledApp>),
+    ExecutionHistory(ExecutionHistory),
+    Security(Vec<SecurityItem>),
+    DevPackages(DevPackages),
+    /// The tab's collector panicked; the tab keeps its previous data.
+    Failed(Tab),
+}
+
+impl LoadMessage {
+    fn tab(&self) -> Tab {
+        match self {
+            LoadMessage::Entries(_) => Tab::StartupApps,
+            LoadMessage::Services(..) => Tab::Services,
+            LoadMessage::Processes(_) => Tab::Processes,
+            LoadMessage::Installed(_) => Tab::Installed,
+            LoadMessage::ExecutionHistory(_) => Tab::ExecutionHistory,
+            LoadMessage::Security(_) => Tab::Security,
+            LoadMessage::DevPackages(_) => Tab::DevPackages,
+            LoadMessage::Failed(tab) => *tab,
+        }
+    }
+}
+
+/// Run all collectors in parallel, each reporting back independently.
+fn spawn_collectors(tx: mpsc::Sender<LoadMessage>, scope: settings::ScanScope) {
+    spawn_collector(&tx, Tab::Installed, || LoadMessage::Installed(installed_apps::collect_installed_apps()));
+    spawn_collector(&tx, Tab::StartupApps, move || {
+        let mut result = collector::collect_all_entries(&scope);
+        enrichment::enrich_entries(&mut result.entries);
+        LoadMessage::Entries(result)
+    });
+    spawn_collector(&tx, Tab::Services, || {
+        let mut skipped = SkippedItems::default();
+        let mut services = services::collect_services(&mut skipped).unwrap_or_else(|e| {
+            skipped.add(SkippedKind::Service, "Service Control Manager", format!("{:#}", e));
+            Vec::new()
+        });
+        enrichment::enrich_entries(&mut services);
+        LoadMessage::Services(services, skipped)
+    });
+    spawn_collector(&tx, Tab::ExecutionHistory, || {
+        LoadMessage::ExecutionHistory(execution_history::collect_execution_history())
+    });
+    spawn_collector(&tx, Tab::Security, || LoadMessage::Security(security_center::collect_security_status()));
+    if scope.dev_packages {
+        spawn_collector(&tx, Tab::DevPackages, || LoadMessage::DevPackages(dev_packages::collect_dev_packages()));
+    }
+    spawn_collector(&tx, Tab::Processes, || {
+        let mut procs = processes::collect_processes();
+        enrichment::enrich_processes(&mut procs);
+        LoadMessage::Processes(procs)
+    });
+}
+
+/// Run one collector on its own thread. A collector that panics reports
+/// [`LoadMessage::Failed`] so its tab doesn't hold the load open.
+fn spawn_collector(
+    tx: &mpsc::Sender<LoadMessage>,
+    tab: Tab,
+    collect: impl FnOnce() -> LoadMessage + Send + 'static,
+) {
+    let tx = tx.clone();
+    std::thread::spawn(move || {
+        let message = crash::catch_unwind(collect).unwrap_or(LoadMessage::Failed(tab));
+        let _ = tx.send(message);
+    });
+}
+
+/// Tabs whose collectors are still running during a full load: all of
+/// them, with Dev Packages only if `scope` includes it.
+fn all_tabs(scope: &settings::ScanScope) -> HashSet<Tab> {
+    [
+        Tab::Installed,
+        Tab::StartupApps,
+        Tab::Processes,
+        Tab::Services,
+        Tab::ExecutionHistory,
+        Tab::Security,
+    ]
+        .into_iter()
+        .chain(scope.dev_packages.then_some(Tab::DevPackages))
+        .collect()
 }
 
 pub struct StartupApp {
@@ -57,112 +233,748 @@ pub struct StartupApp {
     all_services: Vec<StartupEntry>,
     all_processes: Vec<ProcessInfo>,
     installed_apps: Vec<InstalledApp>,
+    execution_history: ExecutionHistory,
+    security_status: Vec<SecurityItem>,
+    dev_packages: DevPackages,
     is_admin: bool,
     active_tab: Tab,
     hide_microsoft_services: bool,
     hide_windows_processes: bool,
+    group_processes_by_user: bool,
+    group_svchost: bool,
+    /// Group multi-component suites on the Installed Apps tab.
+    group_suites: bool,
+    /// Keys of the suites shown expanded.
+    expanded_suites: HashSet<String>,
+    group_platforms: bool,
+    sort_processes_by_start: bool,
+    /// Order of the Startup Apps rows.
+    entry_sort: table::EntrySort,
+    /// Show the accumulated CPU Time column on the Processes tab.
+    show_cpu_time: bool,
+    quick_filters: filters::FilterState,
+    column_filters: filters::ColumnFilters,
     auto_refresh_processes: bool,
     last_process_refresh: Instant,
+    /// "Freeze" toggle: keep showing the current process snapshot while
+    /// auto-refresh continues collecting in the background.
+    freeze_processes: bool,
+    /// Latest process snapshot collected while the view was frozen.
+    frozen_pending_processes: Option<Vec<ProcessInfo>>,
+    /// Processes that started or exited at the last auto-refresh.
+    process_diff: processes::ProcessDiff,
     expanded_pids: HashSet<u32>,
+    /// Processes picked with Ctrl+click, for commands that act on several.
+    selected_processes: HashSet<ProcessKey>,
     pending_action: Option<PendingAction>,
-    rescan_receiver: Option<mpsc::Receiver<()>>,
+    action_queue: std::collections::VecDeque<QueuedAction>,
+    /// Completion of a watched uninstaller: (registry key, app name, outcome).
+    rescan_receiver: Option<mpsc::Receiver<(String, String, uninstaller::UninstallOutcome)>>,
+    /// Command preview shown in the uninstall confirmation.
+    uninstall_preview: Option<dialogs::UninstallPreview>,
+    /// Dev package waiting for uninstall confirmation.
+    package_uninstall: Option<DevPackage>,
+    /// Log file written by the most recent logged MSI uninstall.
+    last_uninstall_log: Option<std::path::PathBuf>,
+    /// Kill request awaiting confirmation (Explorer or App Manager itself).
+    kill_confirmation: Option<(ProcessKey, kill_guard::KillGuard)>,
+    /// Restart as Administrator of a guarded process, waiting for the same
+    /// confirmation as a kill.
+    restart_elevated_confirmation: Option<(ProcessKey, kill_guard::KillGuard)>,
+    /// Completion of a background Explorer restart.
+    explorer_restart_receiver: Option<mpsc::Receiver<Result<(), String>>>,
     status: Option<StatusMessage>,
     selected_row: Option<usize>,
     hovered_row: Option<usize>,
+    /// Vertical scroll position of the active tab's table last frame.
+    scroll_offset: f32,
+    /// The last session's scroll position, applied once its tab has loaded.
+    restore_scroll: Option<(Tab, f32)>,
+    /// A full load is in progress (some collectors may already have reported).
     loading: bool,
-    load_receiver: Option<mpsc::Receiver<LoadResult>>,
+    /// Tabs still waiting for their collector during the current load.
+    loading_tabs: HashSet<Tab>,
+    load_receiver: Option<mpsc::Receiver<LoadMessage>>,
     process_refresh_receiver: Option<mpsc::Receiver<Vec<ProcessInfo>>>,
     service_properties: Option<dialogs::ServicePropertiesInfo>,
     process_properties: Option<dialogs::ProcessPropertiesInfo>,
     startup_entry_properties: Option<dialogs::StartupEntryPropertiesInfo>,
     show_about: bool,
+    show_action_help: bool,
+    /// The guided tour while it's shown (first run, or Help > Guided tour).
+    tour: Option<help::Tour>,
+    /// Where the tour's targets were drawn this frame.
+    tour_anchors: help::Anchors,
+    settings: settings::Settings,
+    /// Settings being edited in the settings dialog (saved on confirm).
+    settings_draft: Option<settings::Settings>,
+    /// Changes made during this session, shown before the app closes.
+    session_changes: Vec<dialogs::SessionChange>,
+    /// Live Running/Stopped updates for the loaded services, restarted
+    /// after each load.
+    service_watcher: Option<service_watch::ServiceWatcher>,
+    /// Services whose configuration was changed this session (by service name).
+    changed_services: HashSet<String>,
+    /// Persistent per-entry run history across sessions.
+    run_history: history::RunHistory,
+    show_session_summary: bool,
+    /// Restore dialog for deleted startup-folder files.
+    restore_backups: Option<dialogs::RestoreBackupsInfo>,
+    /// Which startup scanners ran in the last scan (Startup Apps tab tooltip).
+    scanners: Vec<collector::ScannerReport>,
+    /// Prefetch read in the background during a load.
+    prefetch_receiver: Option<mpsc::Receiver<prefetch::Update>>,
+    /// Last-ran times read from Prefetch so far, kept for entries that
+    /// arrive after them.
+    prefetch_last_ran: HashMap<String, chrono::DateTime<chrono::Local>>,
+    /// How the Prefetch read ended, once it has.
+    prefetch_outcome: Option<collector::ScanOutcome>,
+    /// Items each tab's last load found but couldn't read.
+    skipped: HashMap<Tab, SkippedItems>,
+    /// Tab whose unreadable items are shown in the details dialog.
+    skipped_dialog: Option<Tab>,
+    /// Per-vendor footprint dialog.
+    vendor_summary: Option<dialogs::VendorSummaryInfo>,
+    category_cleanup: Option<dialogs::CategoryCleanupInfo>,
+    clean_boot: Option<dialogs::CleanBootInfo>,
+    pending_clean_boot: Option<PendingCleanBoot>,
+    service_hardening: Option<dialogs::ServiceHardeningInfo>,
+    orphan_cleanup: Option<dialogs::OrphanCleanupInfo>,
+    /// Install locations of every app seen installed, for finding entries
+    /// left behind by an uninstall.
+    install_locations: orphans::InstallLocations,
+    /// Start type changes applied from the hardening suggestions.
+    hardening_changes: service_hardening::AppliedChanges,
+    audit_trail: Option<dialogs::AuditTrailInfo>,
+    /// Entries the user disabled or deleted, watched for coming back.
+    watchlist: watchlist::Watchlist,
+    /// Alert for watched entries that were re-enabled or re-created.
+    reversion_alert: Option<dialogs::ReversionAlertInfo>,
+    /// The user's notes on which items need which.
+    dependencies: dependencies::Dependencies,
+    /// A Disable or Stop held back until the user confirms it despite
+    /// other items needing the entry.
+    dependency_warning: Option<(PendingAction, dialogs::DependencyWarningInfo)>,
+    /// Keep-disabled entries found enabled by the current load, re-disabled
+    /// once it completes.
+    pending_enforcement: Vec<StartupEntry>,
+    /// Keys of entries whose last enforcement attempt this session failed;
+    /// not retried after a reload, so a failing entry can't loop rescans.
+    enforcement_failures: HashSet<String>,
+    /// Completion of a periodic enforcement pass.
+    enforcement_receiver: Option<mpsc::Receiver<Vec<enforcement::EnforcementRecord>>>,
+    last_enforcement: Instant,
+    keep_disabled_rules: Option<dialogs::KeepDisabledRulesInfo>,
+    add_startup_entry: Option<dialogs::AddStartupEntryInfo>,
+    /// Changes staged for review instead of being applied.
+    cleanup_plan: plan::CleanupPlan,
+    /// When on, Enable/Disable/Start/Stop/Uninstall add plan steps.
+    staging_plan: bool,
+    cleanup_plan_dialog: Option<dialogs::CleanupPlanInfo>,
+    /// When on, actions only report what they would change.
+    dry_run: bool,
+    /// Actions handled by the dry run this session, oldest first.
+    dry_run_log: Vec<dry_run::Simulation>,
+    show_dry_run_log: bool,
+    /// Export All requested on the command line, run when loading finishes.
+    export_all_pending: bool,
+    /// Report left by a crash in the previous session.
+    crash_report: Option<dialogs::CrashReportInfo>,
+    /// Taskbar button progress and overlay, connected on the first frame.
+    taskbar: Option<taskbar::Taskbar>,
+    /// Installers run this session and the entries they added, started on
+    /// the first frame.
+    install_tracker: Option<install_watch::InstallTracker>,
+    /// An installer or a change finished; rescan once the current load and
+    /// any running changes are done.
+    rescan_pending: bool,
+    /// Runs changes off the UI thread, started on the first frame.
+    executor: Option<executor::Executor>,
+    /// Jobs sent to the executor that haven't finished, oldest first.
+    running_jobs: Vec<RunningJob>,
+    /// Entries changed and failures of a bulk disable or delete still
+    /// running.
+    bulk_batch: Option<(usize, Vec<String>)>,
+    /// Restart Windows once the running bulk disable finishes without
+    /// failures (a clean boot with restart chosen).
+    restart_after_batch: bool,
+    /// Drive arrivals and removals, started on the first frame.
+    drive_watcher: Option<drive_watch::DriveWatcher>,
+    close_confirmed: bool,
 }
 
 impl StartupApp {
     pub fn new() -> Self {
+        let settings = settings::Settings::load();
         let (tx, rx) = mpsc::channel();
-        std::thread::spawn(move || {
-            // Run all four collectors in parallel
-            let (result, all_services, all_processes, installed) = std::thread::scope(|s| {
-                let h1 = s.spawn(|| collector::collect_all_entries());
-                let h2 = s.spawn(|| services::collect_services().unwrap_or_default());
-                let h3 = s.spawn(|| processes::collect_processes());
-                let h4 = s.spawn(|| installed_apps::collect_installed_apps());
-                (
-                    h1.join().unwrap_or(collector::CollectionResult { entries: vec![], is_admin: false }),
-                    h2.join().unwrap_or_default(),
-                    h3.join().unwrap_or_default(),
-                    h4.join().unwrap_or_default(),
-                )
-            });
-
-            let _ = tx.send(LoadResult {
-                entries: result.entries,
-                all_services,
-                all_processes,
-                installed_apps: installed,
-                is_admin: result.is_admin,
-            });
-        });
+        spawn_collectors(tx, settings.scan_scope);
+        let prefetch_receiver = settings
+            .scan_scope
+            .prefetch
+            .then(|| prefetch::load_async(prefetch::TIME_BUDGET));
 
-        Self {
+        let restore_session = settings.restore_session;
+        let mut app = Self {
             entries: Vec::new(),
             all_services: Vec::new(),
             all_processes: Vec::new(),
             installed_apps: Vec::new(),
+            execution_history: ExecutionHistory::default(),
+            security_status: Vec::new(),
+            dev_packages: DevPackages::default(),
             is_admin: false,
             active_tab: Tab::Installed,
             hide_microsoft_services: true,
             hide_windows_processes: true,
+            group_processes_by_user: false,
+            group_svchost: false,
+            group_suites: true,
+            expanded_suites: HashSet::new(),
+            group_platforms: true,
+            sort_processes_by_start: false,
+            entry_sort: table::EntrySort::Scan,
+            show_cpu_time: false,
+            quick_filters: filters::FilterState::default(),
+            column_filters: filters::ColumnFilters::default(),
             auto_refresh_processes: false,
             last_process_refresh: Instant::now(),
+            freeze_processes: false,
+            frozen_pending_processes: None,
+            process_diff: processes::ProcessDiff::default(),
             expanded_pids: HashSet::new(),
+            selected_processes: HashSet::new(),
             pending_action: None,
+            action_queue: std::collections::VecDeque::new(),
             rescan_receiver: None,
+            uninstall_preview: None,
+            package_uninstall: None,
+            last_uninstall_log: None,
+            kill_confirmation: None,
+            restart_elevated_confirmation: None,
+            explorer_restart_receiver: None,
             status: None,
             selected_row: None,
             hovered_row: None,
+            scroll_offset: 0.0,
+            restore_scroll: None,
             loading: true,
+            loading_tabs: all_tabs(&settings.scan_scope),
             load_receiver: Some(rx),
             process_refresh_receiver: None,
             service_properties: None,
             process_properties: None,
             startup_entry_properties: None,
             show_about: false,
+            show_action_help: false,
+            tour: (!help::tour_seen()).then(help::Tour::default),
+            tour_anchors: help::Anchors::default(),
+            settings,
+            settings_draft: None,
+            session_changes: Vec::new(),
+            service_watcher: None,
+            changed_services: HashSet::new(),
+            run_history: history::RunHistory::load(),
+            show_session_summary: false,
+            restore_backups: None,
+            scanners: Vec::new(),
+            prefetch_receiver,
+            prefetch_last_ran: HashMap::new(),
+            prefetch_outcome: None,
+            skipped: HashMap::new(),
+            skipped_dialog: None,
+            vendor_summary: None,
+            category_cleanup: None,
+            clean_boot: None,
+            pending_clean_boot: None,
+            service_hardening: None,
+            orphan_cleanup: None,
+            install_locations: orphans::InstallLocations::load(),
+            hardening_changes: service_hardening::AppliedChanges::load(),
+            audit_trail: None,
+            watchlist: watchlist::Watchlist::load(),
+            reversion_alert: None,
+            dependencies: dependencies::Dependencies::load(),
+            dependency_warning: None,
+            pending_enforcement: Vec::new(),
+            enforcement_failures: HashSet::new(),
+            enforcement_receiver: None,
+            last_enforcement: Instant::now(),
+            keep_disabled_rules: None,
+            add_startup_entry: None,
+            cleanup_plan: plan::CleanupPlan::default(),
+            staging_plan: false,
+            cleanup_plan_dialog: None,
+            dry_run: false,
+            dry_run_log: Vec::new(),
+            show_dry_run_log: false,
+            export_all_pending: false,
+            crash_report: crash::take_pending().map(|report| dialogs::CrashReportInfo {
+                report,
+                open_requested: false,
+                open_folder_requested: false,
+                restore_settings_requested: false,
+            }),
+            taskbar: None,
+            install_tracker: None,
+            rescan_pending: false,
+            executor: None,
+            running_jobs: Vec::new(),
+            bulk_batch: None,
+            restart_after_batch: false,
+            drive_watcher: None,
+            close_confirmed: false,
+        };
+        if restore_session {
+            if let Some(state) = settings::SessionState::load() {
+                app.restore_session(&state);
+            }
         }
+        app
     }
 
-    /// Spawn a background thread to reload all data, showing the loading overlay.
+    /// Reopen the tab and filters the last session ended on. The scroll
+    /// position waits until the tab's data has loaded.
+    fn restore_session(&mut self, state: &settings::SessionState) {
+        if let Some(tab) = tab_from_file_name(&state.tab, &self.settings.scan_scope) {
+            self.active_tab = tab;
+            self.restore_scroll = Some((tab, state.scroll_offset));
+        }
+        self.column_filters.visible = state.column_filters_visible;
+        for (tab, column, text) in &state.column_filters {
+            if let Some(tab) = tab_from_file_name(tab, &self.settings.scan_scope) {
+                self.column_filters.set(tab, column.clone(), text.clone());
+            }
+        }
+        for (tab, label) in &state.quick_filters {
+            let filter = filters::QuickFilter::ALL.into_iter().find(|f| f.label() == label);
+            if let (Some(tab), Some(filter)) = (tab_from_file_name(tab, &self.settings.scan_scope), filter) {
+                if !self.quick_filters.is_active(tab, filter) {
+                    self.quick_filters.toggle(tab, filter);
+                }
+            }
+        }
+    }
+
+    /// Remember the tab, scroll position, and filters for the next start.
+    fn save_session(&self) {
+        let state = settings::SessionState {
+            tab: tab_file_name(self.active_tab).to_string(),
+            scroll_offset: self.scroll_offset,
+            column_filters_visible: self.column_filters.visible,
+            column_filters: self
+                .column_filters
+                .filled()
+                .map(|(tab, column, text)| (tab_file_name(tab).to_string(), column.to_string(), text.to_string()))
+                .collect(),
+            quick_filters: self
+                .quick_filters
+                .all_active()
+                .map(|(tab, filter)| (tab_file_name(tab).to_string(), filter.label().to_string()))
+                .collect(),
+        };
+        let _ = state.save();
+    }
+
+    /// The last session's scroll position, once the active tab it belongs
+    /// to has loaded. Switching tabs first drops it.
+    fn take_restore_scroll(&mut self) -> Option<f32> {
+        let (tab, offset) = self.restore_scroll?;
+        if tab != self.active_tab {
+            self.restore_scroll = None;
+            return None;
+        }
+        if self.loading_tabs.contains(&tab) {
+            return None;
+        }
+        self.restore_scroll = None;
+        Some(offset)
+    }
+
+    /// Switch to the tab named `name` (its export file name, e.g. `processes`).
+    pub fn open_tab(&mut self, name: &str) {
+        if let Some(tab) = tab_from_file_name(name, &self.settings.scan_scope) {
+            self.active_tab = tab;
+        }
+    }
+
+    /// Run Export All as soon as the current load (and Prefetch read)
+    /// completes.
+    pub fn request_export_all(&mut self) {
+        self.export_all_pending = true;
+    }
+
+    pub fn set_dry_run(&mut self, on: bool) {
+        self.dry_run = on;
+    }
+
+    /// Record what an action would change instead of running it, and show
+    /// the dry-run log.
+    fn simulate(&mut self, action: String, changes: anyhow::Result<Vec<String>>) {
+        let simulation = dry_run::Simulation::new(action, changes);
+        match &simulation.changes {
+            Ok(changes) => self.set_status(
+                &format!(
+                    "Dry run: {} would make {} change{}; nothing was changed",
+                    simulation.action,
+                    changes.len(),
+                    if changes.len() == 1 { "" } else { "s" }
+                ),
+                false,
+            ),
+            Err(e) => self.set_status(&format!("Dry run: {} would fail: {}", simulation.action, e), true),
+        }
+        self.dry_run_log.push(simulation);
+        self.show_dry_run_log = true;
+    }
+
+    /// Open the Add Startup Entry dialog pre-filled for a program or
+    /// shortcut (from the Explorer context menu's command line).
+    pub fn open_add_startup_entry(&mut self, path: &str) {
+        self.active_tab = Tab::StartupApps;
+        self.add_startup_entry = Some(dialogs::AddStartupEntryInfo::for_path(path));
+    }
+
+    /// Reload all data in the background. Each tab shows a loading indicator
+    /// until its own collector reports back.
     fn start_background_load(&mut self) {
         if self.loading {
             return;
         }
         let (tx, rx) = mpsc::channel();
         self.loading = true;
+        self.loading_tabs = all_tabs(&self.settings.scan_scope);
         self.load_receiver = Some(rx);
+        spawn_collectors(tx, self.settings.scan_scope);
+        self.prefetch_receiver = self
+            .settings
+            .scan_scope
+            .prefetch
+            .then(|| prefetch::load_async(prefetch::TIME_BUDGET));
+        self.prefetch_last_ran.clear();
+        self.prefetch_outcome = None;
+    }
 
+    /// Store one collector's results.
+    fn apply_load_message(&mut self, message: LoadMessage) {
+        let tab = message.tab();
+        match message {
+            LoadMessage::Entries(result) => {
+                self.scanners = result.scanners;
+                self.skipped.insert(Tab::StartupApps, result.skipped);
+                self.entries = result.entries;
+                prefetch::fill_last_ran(&mut self.entries, &self.prefetch_last_ran);
+                self.update_prefetch_report();
+                self.is_admin = result.is_admin;
+                self.run_history.record(&self.entries);
+                self.run_history.annotate(&mut self.entries);
+                if let Some(tracker) = &mut self.install_tracker {
+                    tracker.annotate("startup", &mut self.entries);
+                }
+                self.install_locations.flag(&self.installed_apps, &mut self.entries);
+                let reversions = self.watchlist.find_reversions(&self.entries);
+                self.handle_reversions(reversions);
+            }
+            LoadMessage::Services(services, skipped) => {
+                self.all_services = services;
+                self.skipped.insert(Tab::Services, skipped);
+                self.run_history.record(&self.all_services);
+                self.run_history.annotate(&mut self.all_services);
+                if let Some(tracker) = &mut self.install_tracker {
+                    tracker.annotate("services", &mut self.all_services);
+                }
+                self.install_locations.flag(&self.installed_apps, &mut self.all_services);
+                for entry in &mut self.all_services {
+                    if let Source::Service { service_name, .. } = &entry.source {
+                        entry.config_changed = self.changed_services.contains(service_name);
+                    }
+                }
+                self.update_service_usage();
+                self.update_history_cross_refs();
+                let reversions = self.watchlist.find_reversions(&self.all_services);
+                self.handle_reversions(reversions);
+            }
+            LoadMessage::Processes(procs) => {
+                // An explicit Refresh always shows fresh data
+                self.frozen_pending_processes = None;
+                self.process_diff = processes::ProcessDiff::default();
+                self.all_processes = procs;
+                // Auto-expand all processes that have children (and all user groups)
+                self.expanded_pids = processes::parent_pids(&self.all_processes);
+                self.expanded_pids.extend(processes::user_group_pids(&self.all_processes));
+                self.last_process_refresh = Instant::now();
+                self.update_service_usage();
+                self.update_history_cross_refs();
+            }
+            LoadMessage::Installed(apps) => {
+                self.installed_apps = apps;
+                self.install_locations.update(&self.installed_apps);
+                self.install_locations.flag(&self.installed_apps, &mut self.entries);
+                self.install_locations.flag(&self.installed_apps, &mut self.all_services);
+            }
+            LoadMessage::ExecutionHistory(history) => {
+                self.execution_history = history;
+                self.update_history_cross_refs();
+            }
+            LoadMessage::Security(items) => {
+                self.security_status = items;
+            }
+            LoadMessage::DevPackages(packages) => {
+                self.dev_packages = packages;
+            }
+            LoadMessage::Failed(tab) => {
+                self.set_status(&format!("Could not load the {} tab", tab_title(tab)), true);
+            }
+        }
+        self.loading_tabs.remove(&tab);
+        if tab == self.active_tab {
+            self.selected_row = None;
+            self.hovered_row = None;
+        }
+    }
+
+    /// Mirror load progress, running uninstallers, and enforcement passes on
+    /// the taskbar button, and badge it while a reversion alert waits behind
+    /// a minimized window.
+    fn update_taskbar(&mut self, ctx: &egui::Context, frame: &eframe::Frame) {
+        if self.taskbar.is_none() {
+            self.taskbar = taskbar::Taskbar::new(frame);
+        }
+        let total = all_tabs(&self.settings.scan_scope).len() as u64;
+        let progress = if self.loading {
+            taskbar::Progress::Value(total - self.loading_tabs.len() as u64, total)
+        } else if self.rescan_receiver.is_some()
+            || self.enforcement_receiver.is_some()
+            || self.unfinished_jobs() > 0
+        {
+            taskbar::Progress::Indeterminate
+        } else {
+            taskbar::Progress::None
+        };
+        let unseen_alert = self.reversion_alert.is_some() && taskbar::is_minimized(ctx);
+        if let Some(bar) = &mut self.taskbar {
+            bar.set_progress(progress);
+            bar.set_overlay(unseen_alert, "Watched startup entries were re-enabled");
+        }
+    }
+
+    /// Re-disable entries with a keep-disabled rule (once the load completes)
+    /// and alert about the rest.
+    fn handle_reversions(&mut self, reversions: Vec<watchlist::Reversion>) {
+        for reversion in reversions {
+            if reversion.keep_disabled {
+                if self.enforcement_failures.contains(&history::entry_key(&reversion.id)) {
+                    continue;
+                }
+                if let Some(entry) = self.find_entry(&reversion.id).cloned() {
+                    self.pending_enforcement.push(entry);
+                }
+                continue;
+            }
+            let alert = self.reversion_alert.get_or_insert_with(|| dialogs::ReversionAlertInfo {
+                items: Vec::new(),
+                disable_requested: None,
+                keep_disabled_requested: None,
+                allow_requested: None,
+            });
+            if !alert.items.iter().any(|r| r.id == reversion.id) {
+                alert.items.push(reversion);
+            }
+        }
+    }
+
+    /// Apply keep-disabled rules to entries the last load found enabled, on
+    /// the enforcement thread once no timed pass is running.
+    fn run_pending_enforcement(&mut self) {
+        if self.pending_enforcement.is_empty() || self.enforcement_receiver.is_some() {
+            return;
+        }
+        let entries = std::mem::take(&mut self.pending_enforcement);
+        let rules = self.watchlist.keep_disabled_keys();
+        let dry_run = self.dry_run;
+        let (tx, rx) = mpsc::channel();
+        self.enforcement_receiver = Some(rx);
         std::thread::spawn(move || {
-            let (result, all_services, all_processes, installed) = std::thread::scope(|s| {
-                let h1 = s.spawn(|| collector::collect_all_entries());
-                let h2 = s.spawn(|| services::collect_services().unwrap_or_default());
-                let h3 = s.spawn(|| processes::collect_processes());
-                let h4 = s.spawn(|| installed_apps::collect_installed_apps());
-                (
-                    h1.join().unwrap_or(collector::CollectionResult { entries: vec![], is_admin: false }),
-                    h2.join().unwrap_or_default(),
-                    h3.join().unwrap_or_default(),
-                    h4.join().unwrap_or_default(),
-                )
+            let _ = tx.send(enforcement::enforce(&entries, &rules, dry_run));
+        });
+    }
+
+    /// Show the result of enforcement actions and reload if anything changed.
+    fn report_enforcement(&mut self, records: Vec<enforcement::EnforcementRecord>) {
+        if records.is_empty() {
+            return;
+        }
+        for record in &records {
+            if record.outcome.is_ok() {
+                self.enforcement_failures.remove(&record.key);
+            } else {
+                self.enforcement_failures.insert(record.key.clone());
+            }
+        }
+        if records.iter().any(|r| r.dry_run) {
+            for record in records {
+                let action = format!("Keep-disabled rule: disable '{}'", record.name);
+                let changes = record.outcome.map(|()| vec![format!("Disable {}", record.location)]);
+                self.simulate(action, changes.map_err(anyhow::Error::msg));
+            }
+            return;
+        }
+        for record in &records {
+            if record.outcome.is_ok() {
+                self.session_changes.push(dialogs::SessionChange {
+                    item: record.name.clone(),
+                    change: "Re-disabled (keep-disabled rule)".to_string(),
+                });
+            }
+        }
+        let failed = records.iter().filter(|r| r.outcome.is_err()).count();
+        let text = match (records.len(), failed) {
+            (1, 0) => format!("Keep-disabled rule: disabled '{}' again", records[0].name),
+            (1, _) => format!("Keep-disabled rule: {}: {}", records[0].name, records[0].outcome_text()),
+            (n, 0) => format!("Keep-disabled rules: disabled {} entries again", n),
+            (n, f) => format!("Keep-disabled rules: {} of {} entries could not be disabled", f, n),
+        };
+        self.set_status(&text, failed > 0);
+        if let Some(info) = &mut self.keep_disabled_rules {
+            info.activity = enforcement::read_log(100);
+        }
+        if failed < records.len() {
+            self.start_background_load();
+        }
+    }
+
+    /// Whether process-list updates should be held back: the Freeze toggle is
+    /// on, or the user is in a menu or mid-drag and rows must not move.
+    fn process_view_frozen(&self, ctx: &egui::Context) -> bool {
+        self.freeze_processes
+            || egui::Popup::is_any_open(ctx)
+            || ctx.dragged_id().is_some()
+            || ctx.input(|i| i.pointer.primary_down())
+    }
+
+    /// Replace the displayed process list with a freshly collected snapshot.
+    fn apply_process_snapshot(&mut self, procs: Vec<ProcessInfo>) {
+        self.process_diff = if self.auto_refresh_processes {
+            processes::ProcessDiff::new(&self.all_processes, &procs)
+        } else {
+            processes::ProcessDiff::default()
+        };
+        self.all_processes = procs;
+        self.expanded_pids = processes::parent_pids(&self.all_processes);
+        self.expanded_pids.extend(processes::user_group_pids(&self.all_processes));
+        self.update_service_usage();
+    }
+
+    /// Join running services with their hosting process's CPU and memory.
+    fn update_service_usage(&mut self) {
+        let by_pid: std::collections::HashMap<u32, &ProcessInfo> =
+            self.all_processes.iter().map(|p| (p.pid, p)).collect();
+        let mut services_per_pid: std::collections::HashMap<u32, usize> = std::collections::HashMap::new();
+        for pid in self.all_services.iter().filter_map(|e| e.pid) {
+            *services_per_pid.entry(pid).or_default() += 1;
+        }
+        for entry in &mut self.all_services {
+            entry.host_usage = entry.pid.and_then(|pid| by_pid.get(&pid)).map(|p| HostUsage {
+                pid: p.pid,
+                cpu_usage: p.cpu_usage,
+                memory_bytes: p.memory_bytes,
+                services_in_process: services_per_pid.get(&p.pid).copied().unwrap_or(1),
             });
+        }
+    }
 
-            let _ = tx.send(LoadResult {
-                entries: result.entries,
-                all_services,
-                all_processes,
-                installed_apps: installed,
-                is_admin: result.is_admin,
+    /// Fill in last-ran times from Prefetch as the background read reports
+    /// them.
+    fn apply_prefetch_updates(&mut self, ctx: &egui::Context) {
+        let Some(rx) = &self.prefetch_receiver else {
+            return;
+        };
+        let updates: Vec<prefetch::Update> = rx.try_iter().collect();
+        let mut finished = false;
+        for update in updates {
+            match update {
+                prefetch::Update::Batch(batch) => {
+                    prefetch::fill_last_ran(&mut self.entries, &batch);
+                    for (exe_name, dt) in batch {
+                        prefetch::merge(&mut self.prefetch_last_ran, exe_name, dt);
+                    }
+                }
+                prefetch::Update::Finished { executables, complete } => {
+                    self.prefetch_outcome = Some(if complete {
+                        collector::ScanOutcome::Found(executables)
+                    } else {
+                        collector::ScanOutcome::TimedOut(executables)
+                    });
+                    finished = true;
+                }
+            }
+        }
+        if finished {
+            self.prefetch_receiver = None;
+            self.update_prefetch_report();
+        } else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        }
+    }
+
+    /// Show how the Prefetch read ended in the scan summary, if both it and
+    /// the startup scan are done.
+    fn update_prefetch_report(&mut self) {
+        let Some(outcome) = self.prefetch_outcome else {
+            return;
+        };
+        for report in &mut self.scanners {
+            if report.name == collector::PREFETCH_SCANNER
+                && matches!(report.outcome, collector::ScanOutcome::InProgress)
+            {
+                report.outcome = outcome;
+            }
+        }
+    }
+
+    /// Follow the loaded services' state changes, replacing the watcher for
+    /// the previous load.
+    fn watch_services(&mut self, ctx: &egui::Context) {
+        let names: Vec<String> = self
+            .all_services
+            .iter()
+            .filter_map(|e| match &e.source {
+                Source::Service { service_name, .. } => Some(service_name.clone()),
+                _ => None,
+            })
+            .collect();
+        let ctx = ctx.clone();
+        self.service_watcher = Some(service_watch::watch(names, move || ctx.request_repaint()));
+    }
+
+    /// Show service state changes reported since the last frame.
+    fn apply_service_changes(&mut self) {
+        let Some(watcher) = &self.service_watcher else {
+            return;
+        };
+        let changes: Vec<service_watch::StateChange> = watcher.changes.try_iter().collect();
+        if changes.is_empty() {
+            return;
+        }
+        for change in changes {
+            let entry = self.all_services.iter_mut().find(|e| {
+                matches!(&e.source, Source::Service { service_name, .. } if *service_name == change.service_name)
             });
-        });
+            let Some(entry) = entry else {
+                continue;
+            };
+            let run_state = if change.running { RunState::Running } else { RunState::Stopped };
+            let pid = (change.pid > 0).then_some(change.pid);
+            if entry.run_state == run_state && entry.pid == pid {
+                continue;
+            }
+            if run_state == RunState::Running && entry.run_state != RunState::Running {
+                entry.last_ran = Some(chrono::Local::now());
+                entry.last_ran_source = Some(LastRanSource::ProcessStart);
+            }
+            entry.run_state = run_state;
+            entry.pid = pid;
+        }
+        self.update_service_usage();
     }
 
     /// Lightweight process-only refresh (no loading overlay, no status message).
@@ -173,11 +985,28 @@ impl StartupApp {
         let (tx, rx) = mpsc::channel();
         self.process_refresh_receiver = Some(rx);
         std::thread::spawn(move || {
-            let procs = processes::collect_processes();
+            let mut procs = processes::collect_processes();
+            enrichment::enrich_processes(&mut procs);
             let _ = tx.send(procs);
         });
     }
 
+    /// Add a successful change to the session summary and the audit trail.
+    /// Service configuration changes also mark the service as needing a
+    /// restart.
+    fn record_change(&mut self, entry: &StartupEntry, change: &str, values: (&str, &str), config_changed: bool) {
+        audit::append(&[audit::AuditRecord::for_entry(entry, change, values.0, values.1)]);
+        if config_changed {
+            if let Source::Service { service_name, .. } = &entry.source {
+                self.changed_services.insert(service_name.clone());
+            }
+        }
+        self.session_changes.push(dialogs::SessionChange {
+            item: entry.name.clone(),
+            change: change.to_string(),
+        });
+    }
+
     fn set_status(&mut self, text: &str, is_error: bool) {
         self.status = Some(StatusMessage {
             text: text.to_string(),
@@ -188,7 +1017,12 @@ impl StartupApp {
 
     /// Get the currently visible entries for the active tab.
     fn active_entries(&self) -> Vec<&StartupEntry> {
-        match self.active_tab {
+        self.tab_entries(self.active_tab)
+    }
+
+    /// Entries shown on `tab` after its hide toggle and quick filters.
+    fn tab_entries(&self, tab: Tab) -> Vec<&StartupEntry> {
+        let entries: Vec<&StartupEntry> = match tab {
             Tab::StartupApps => self.entries.iter().collect(),
             Tab::Services => {
                 if self.hide_microsoft_services {
@@ -202,133 +1036,1317 @@ impl StartupApp {
             }
             Tab::Processes => Vec::new(), // Processes tab uses its own data model
             Tab::Installed => Vec::new(), // Installed tab uses its own data model
+            Tab::ExecutionHistory => Vec::new(), // Execution History tab uses its own data model
+            Tab::Security => Vec::new(),  // Security tab is a status panel
+            Tab::DevPackages => Vec::new(), // Dev Packages tab uses its own data model
+        };
+        let mut entries: Vec<&StartupEntry> = entries
+            .into_iter()
+            .filter(|e| self.quick_filters.entry_matches(tab, e) && self.column_filters.entry_matches(tab, e))
+            .collect();
+        if tab == Tab::StartupApps {
+            match self.entry_sort {
+                table::EntrySort::Scan => {}
+                table::EntrySort::User => {
+                    entries.sort_by_key(|e| (e.profile_user().to_lowercase(), e.name.to_lowercase()))
+                }
+                // Newest first; entries without a registration date keep
+                // their order at the end
+                table::EntrySort::TaskRegistered => entries
+                    .sort_by_key(|e| std::cmp::Reverse(e.task_registration.as_ref().and_then(|r| r.registered))),
+            }
+        }
+        entries
+    }
+
+    /// Processes that pass the Processes tab's quick filters.
+    fn visible_processes(&self) -> Vec<ProcessInfo> {
+        self.all_processes
+            .iter()
+            .filter(|p| self.quick_filters.process_matches(p) && self.column_filters.process_matches(p))
+            .cloned()
+            .collect()
+    }
+
+    /// Installed apps that pass the Installed tab's quick filters.
+    fn visible_installed_apps(&self) -> Vec<InstalledApp> {
+        self.installed_apps
+            .iter()
+            .filter(|a| self.quick_filters.app_matches(a) && self.column_filters.app_matches(a))
+            .cloned()
+            .collect()
+    }
+
+    /// Execution history rows that pass the tab's quick filters.
+    fn visible_execution_history(&self) -> Vec<ExecutionRecord> {
+        self.execution_history
+            .records
+            .iter()
+            .filter(|r| {
+                self.quick_filters.execution_record_matches(r) && self.column_filters.execution_record_matches(r)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Dev packages that pass the tab's column filters.
+    fn visible_dev_packages(&self) -> Vec<DevPackage> {
+        self.dev_packages
+            .packages
+            .iter()
+            .filter(|p| self.column_filters.package_matches(p))
+            .cloned()
+            .collect()
+    }
+
+    /// Find a dev package by its manager and name.
+    fn find_dev_package(&self, manager: PackageManager, name: &str) -> Option<&DevPackage> {
+        self.dev_packages.packages.iter().find(|p| p.manager == manager && p.name == name)
+    }
+
+    /// Mark execution history rows whose file is also a startup entry, a
+    /// service, or a running process.
+    fn update_history_cross_refs(&mut self) {
+        let index = CrossRefIndex::build(&self.entries, &self.all_services, &self.all_processes);
+        for record in &mut self.execution_history.records {
+            record.is_startup_entry = !index.startup_entries(&record.path).is_empty();
+            record.is_service = !index.services(&record.path).is_empty();
+            record.is_running = index.is_running(&record.path);
         }
     }
 
-    /// Get mutable reference to the correct entry by tab + visible index.
-    fn get_entry_by_visible_index(&self, index: usize) -> Option<&StartupEntry> {
-        self.active_entries().get(index).copied()
+    /// Open the properties dialog for a process, with its launch origin.
+    fn show_process_properties(&mut self, proc: &ProcessInfo) {
+        let index = CrossRefIndex::build(&self.entries, &self.all_services, &self.all_processes);
+        let origin = launch_origin::explain(proc, &self.all_processes, &index);
+        self.process_properties = Some(process_properties_from(proc, origin));
+    }
+
+    /// Find a startup entry or service by its stable identity.
+    fn find_entry(&self, id: &EntryId) -> Option<&StartupEntry> {
+        let list = match id {
+            EntryId::Service { .. } => &self.all_services,
+            _ => &self.entries,
+        };
+        list.iter().find(|e| e.id() == *id)
     }
 
     fn execute_action(&mut self, action: PendingAction) {
         // Properties action
-        if let PendingAction::Properties(i) = &action {
-            if self.active_tab == Tab::Services {
-                // Services tab: show service details dialog
-                if let Some(entry) = self.get_entry_by_visible_index(*i) {
-                    let entry = entry.clone();
-                    if let Source::Service { service_name, .. } = &entry.source {
-                        let description = services::get_service_description(service_name);
-                        self.service_properties = Some(dialogs::ServicePropertiesInfo {
-                            service_name: service_name.clone(),
-                            display_name: entry.name.clone(),
-                            description,
-                            status: entry.run_state,
-                            startup_type: entry.enabled,
-                            executable_path: entry.command.clone(),
-                            log_on_as: entry.runs_as.clone(),
-                            product_name: entry.product_name.clone(),
-                        });
-                    }
-                }
+        if let PendingAction::Properties(id) = &action {
+            let entry = match self.find_entry(id) {
+                Some(e) => e.clone(),
+                None => return,
+            };
+            if let Source::Service { service_name, .. } = &entry.source {
+                // Services: show service details dialog
+                let description = services::get_service_description(service_name);
+                self.service_properties = Some(dialogs::ServicePropertiesInfo {
+                    service_name: service_name.clone(),
+                    display_name: entry.name.clone(),
+                    description,
+                    status: entry.run_state,
+                    startup_type: entry.enabled,
+                    executable_path: entry.command.clone(),
+                    binary_path: services::get_service_binary_path(service_name, &entry.command),
+                    log_on_as: entry.runs_as.clone(),
+                    product_name: entry.product_name.clone(),
+                    bitness: entry.bitness,
+                    triggers: services::get_service_triggers(service_name),
+                    security: services::get_service_security(service_name),
+                    enrichment: entry.enrichment.clone(),
+                    dependencies: self.dependency_info(&entry),
+                    open_location_requested: false,
+                    windows_properties_requested: false,
+                });
             } else {
-                // StartupApps tab: show startup entry properties dialog
-                if let Some(entry) = self.get_entry_by_visible_index(*i) {
-                    self.startup_entry_properties =
-                        Some(startup_entry_properties_from(entry));
-                }
+                // Startup entries: show startup entry properties dialog
+                let mut info = startup_entry_properties_from(&entry);
+                info.dependencies = self.dependency_info(&entry);
+                self.startup_entry_properties = Some(info);
             }
             return;
         }
 
-        let entry = match &action {
-            PendingAction::Enable(i)
-            | PendingAction::Disable(i)
-            | PendingAction::Start(i)
-            | PendingAction::Stop(i) => match self.get_entry_by_visible_index(*i) {
-                Some(e) => e.clone(),
-                None => return,
-            },
+        let id = match &action {
+            PendingAction::Enable(id)
+            | PendingAction::Disable(id)
+            | PendingAction::Start(id)
+            | PendingAction::Stop(id) => id,
             PendingAction::ConfirmDelete(_)
             | PendingAction::ConfirmUninstall(_)
             | PendingAction::Properties(_) => return,
         };
+        let entry = match self.find_entry(id) {
+            Some(e) => e.clone(),
+            None => return,
+        };
 
-        let result = match &action {
-            PendingAction::Enable(_) => {
-                actions::enable_entry(&entry).map(|_| format!("Enabled '{}'", entry.name))
+        if self.staging_plan {
+            let plan_action = match &action {
+                PendingAction::Enable(_) => plan::PlanAction::Enable,
+                PendingAction::Disable(_) => plan::PlanAction::Disable,
+                PendingAction::Start(_) => plan::PlanAction::Start,
+                _ => plan::PlanAction::Stop,
+            };
+            self.stage_step(plan_action, history::entry_key(id), entry.name);
+            return;
+        }
+
+        if matches!(action, PendingAction::Disable(_) | PendingAction::Stop(_)) {
+            let dependents = self.dependencies.dependents(&entry, &self.entries, &self.all_services);
+            if !dependents.is_empty() {
+                let verb = if matches!(action, PendingAction::Stop(_)) { "stop" } else { "disable" };
+                let info = dialogs::DependencyWarningInfo { name: entry.name, verb, dependents };
+                self.dependency_warning = Some((action, info));
+                return;
             }
-            PendingAction::Disable(_) => {
-                actions::disable_entry(&entry).map(|_| format!("Disabled '{}'", entry.name))
+        }
+        self.dispatch_entry_action(action);
+    }
+
+    /// Run an Enable/Disable/Start/Stop action now, or queue it while a
+    /// reload is in flight.
+    fn dispatch_entry_action(&mut self, action: PendingAction) {
+        let id = match &action {
+            PendingAction::Enable(id)
+            | PendingAction::Disable(id)
+            | PendingAction::Start(id)
+            | PendingAction::Stop(id) => id,
+            _ => return,
+        };
+        let entry = match self.find_entry(id) {
+            Some(e) => e.clone(),
+            None => return,
+        };
+
+        // A reload is in flight: queue the action and run it against the
+        // refreshed entry instead of running it now (and then reloading twice).
+        if self.loading {
+            let duplicate = self.action_queue.iter().any(|q| q.action == action);
+            if duplicate {
+                self.set_status(&format!("'{}' already has this action queued", entry.name), true);
+            } else {
+                self.set_status(
+                    &format!("Queued action for '{}' until the refresh completes", entry.name),
+                    false,
+                );
+                self.action_queue.push_back(QueuedAction { action, entry });
             }
-            PendingAction::Start(_) => {
-                actions::start_entry(&entry).map(|_| format!("Started '{}'", entry.name))
+            return;
+        }
+
+        self.run_entry_action(&action, &entry);
+    }
+
+    /// Run an Enable/Disable/Start/Stop action against a resolved entry.
+    fn run_entry_action(&mut self, action: &PendingAction, entry: &StartupEntry) {
+        if self.dry_run {
+            // The dry run reports its own outcome
+            self.simulate_entry_action(action, entry);
+            return;
+        }
+        if let Some(job) = entry_job(action, entry) {
+            self.submit_job(job, JobOrigin::User);
+        }
+    }
+
+    /// Log what an entry action would change. Returns whether it would
+    /// succeed.
+    fn simulate_entry_action(&mut self, action: &PendingAction, entry: &StartupEntry) -> bool {
+        let (verb, changes) = match action {
+            PendingAction::Enable(_) => ("Enable", dry_run::enable_entry(entry)),
+            PendingAction::Disable(_) => ("Disable", dry_run::disable_entry(entry)),
+            PendingAction::Start(_) => ("Start", dry_run::start_entry(entry)),
+            PendingAction::Stop(_) => ("Stop", dry_run::stop_entry(entry)),
+            _ => return false,
+        };
+        let ok = changes.is_ok();
+        self.simulate(format!("{} '{}'", verb, entry.name), changes);
+        ok
+    }
+
+    /// Queue a change on the executor; [`Self::finish_job`] handles the
+    /// result.
+    fn submit_job(&mut self, job: executor::Job, origin: JobOrigin) {
+        let Some(executor) = &mut self.executor else {
+            return;
+        };
+        let id = executor.submit(job.clone());
+        self.running_jobs.push(RunningJob {
+            id,
+            job,
+            origin,
+            started: None,
+            timed_out: false,
+        });
+    }
+
+    /// Submitted changes still queued or running, not counting ones that
+    /// timed out (they may never finish, so nothing waits on them).
+    fn unfinished_jobs(&self) -> usize {
+        self.running_jobs.iter().filter(|j| !j.timed_out).count()
+    }
+
+    /// Apply progress and results reported by the executor since the last
+    /// frame.
+    fn poll_executor(&mut self) {
+        let Some(executor) = &self.executor else {
+            return;
+        };
+        let updates: Vec<executor::Update> = executor.updates.try_iter().collect();
+        for update in updates {
+            match update {
+                executor::Update::Started(id) => {
+                    if let Some(job) = self.running_jobs.iter_mut().find(|j| j.id == id) {
+                        job.started = Some(Instant::now());
+                    }
+                }
+                executor::Update::TimedOut(id) => {
+                    let Some(job) = self.running_jobs.iter_mut().find(|j| j.id == id) else {
+                        continue;
+                    };
+                    job.timed_out = true;
+                    let label = job.job.label();
+                    self.set_status(
+                        &format!(
+                            "{} has not finished after {} seconds; moving on to the next change",
+                            label,
+                            executor::TIMEOUT.as_secs()
+                        ),
+                        true,
+                    );
+                }
+                executor::Update::Finished(id, result) => {
+                    if let Some(pos) = self.running_jobs.iter().position(|j| j.id == id) {
+                        let job = self.running_jobs.remove(pos);
+                        self.finish_job(job, result);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Record a finished change, report it to whoever asked for it, and
+    /// schedule a reload.
+    fn finish_job(&mut self, job: RunningJob, result: Result<(), String>) {
+        let result = result.map(|()| self.record_job(&job.job));
+        match &job.origin {
+            JobOrigin::User => match &result {
+                Ok(msg) if job.timed_out => self.set_status(&format!("{} (after timing out)", msg), false),
+                Ok(msg) => self.set_status(msg, false),
+                Err(e) => self.set_status(&format!("Error: {} failed: {}", job.job.label(), e), true),
+            },
+            JobOrigin::PlanStep(action, target) => {
+                let Some(index) =
+                    self.cleanup_plan.steps.iter().position(|s| s.action == *action && s.target == *target)
+                else {
+                    return;
+                };
+                let name = self.cleanup_plan.steps[index].name.clone();
+                match &result {
+                    Ok(_) => self.set_status(&format!("Ran step {}: {} '{}'", index + 1, action.label(), name), false),
+                    Err(e) => self.set_status(&format!("Step {} ('{}') failed: {}", index + 1, name, e), true),
+                }
+                self.cleanup_plan.steps[index].status = match result {
+                    Ok(msg) => plan::StepStatus::Done(msg),
+                    Err(e) => plan::StepStatus::Failed(e),
+                };
             }
-            PendingAction::Stop(_) => {
-                actions::stop_entry(&entry).map(|_| format!("Stopped '{}'", entry.name))
+            JobOrigin::BulkDisable | JobOrigin::BulkDelete => {
+                let batch = self.bulk_batch.get_or_insert_default();
+                match result {
+                    Ok(_) => batch.0 += 1,
+                    Err(e) => batch.1.push(format!("'{}': {}", job.job.entry().map_or("", |e| &e.name), e)),
+                }
+                if !self.running_jobs.iter().any(|j| j.origin == job.origin && !j.timed_out) {
+                    let (changed, failures) = self.bulk_batch.take().unwrap_or_default();
+                    let verb = if job.origin == JobOrigin::BulkDelete { "Deleted" } else { "Disabled" };
+                    self.report_bulk(verb, changed, &failures);
+                }
             }
-            _ => return,
+        }
+    }
+
+    /// Record a successful change in the watch list, session summary, and
+    /// audit trail, and mark the data for a reload. Returns the status
+    /// message.
+    fn record_job(&mut self, job: &executor::Job) -> String {
+        let msg = match job {
+            executor::Job::Enable(entry) | executor::Job::Disable(entry) => {
+                let (msg, new_value) = if matches!(job, executor::Job::Enable(_)) {
+                    self.watchlist.forget(&entry.id());
+                    (format!("Enabled '{}'", entry.name), "Enabled")
+                } else {
+                    self.watchlist.record(entry, watchlist::WatchedState::Disabled);
+                    (format!("Disabled '{}'", entry.name), "Disabled")
+                };
+                self.record_change(entry, &msg, (&entry.enabled.to_string(), new_value), true);
+                msg
+            }
+            executor::Job::Start(entry) | executor::Job::Stop(entry) => {
+                let (msg, new_value) = if matches!(job, executor::Job::Start(_)) {
+                    (format!("Started '{}'", entry.name), "Running")
+                } else {
+                    (format!("Stopped '{}'", entry.name), "Stopped")
+                };
+                self.record_change(entry, &msg, (&entry.run_state.to_string(), new_value), false);
+                // The service watcher shows a started or stopped service
+                // without a reload
+                if self.service_watcher.is_some() && matches!(entry.source, Source::Service { .. }) {
+                    return msg;
+                }
+                msg
+            }
+            executor::Job::Delete(entry) => {
+                self.watchlist.record(entry, watchlist::WatchedState::Deleted);
+                self.record_change(entry, "Deleted", (&entry.command, ""), false);
+                format!("Deleted '{}'", entry.name)
+            }
+            executor::Job::SetArguments(entry, args) => {
+                let (_, old_args) = cmdline::split(&entry.command);
+                self.record_change(entry, "Arguments changed", (old_args.trim(), args.trim()), true);
+                format!("Updated arguments for '{}'", entry.name)
+            }
+            executor::Job::SetTaskConditions(entry, conditions) => {
+                let (old_value, new_value) = match &entry.task_conditions {
+                    Some(original) => audit::condition_values(original, conditions),
+                    None => (String::new(), String::new()),
+                };
+                self.record_change(entry, "Conditions changed", (&old_value, &new_value), true);
+                format!("Updated conditions for '{}'", entry.name)
+            }
+            executor::Job::SetStartMode(entry, mode) => {
+                let old_value = service_hardening::StartMode::of(entry).map_or("", |m| m.label());
+                self.record_change(entry, "Start type changed", (old_value, mode.label()), true);
+                self.hardening_changes.record(entry, *mode);
+                format!("Set '{}' to {}", entry.name, mode.label())
+            }
+            executor::Job::Add { name, command, location } => {
+                let change = format!("Added to startup ({})", location.label());
+                audit::append(&[audit::AuditRecord::new(name, location.label(), &change, "", command)]);
+                self.session_changes.push(dialogs::SessionChange { item: name.clone(), change });
+                format!("Added '{}' to startup", name)
+            }
+            executor::Job::UninstallPackage(package) => {
+                let location = format!("{}: {}", package.manager.label(), package.location);
+                audit::append(&[audit::AuditRecord::new(&package.name, &location, "Uninstalled", &package.version, "")]);
+                self.session_changes.push(dialogs::SessionChange {
+                    item: package.name.clone(),
+                    change: format!("Uninstalled ({})", package.manager.label()),
+                });
+                // Only the package list changes, so it is updated in place
+                // instead of reloading every tab
+                self.dev_packages
+                    .packages
+                    .retain(|p| !(p.manager == package.manager && p.name == package.name));
+                return format!("Uninstalled {} package '{}'", package.manager.label(), package.name);
+            }
+        };
+        self.rescan_pending = true;
+        msg
+    }
+
+    /// The Dependencies section of an entry's properties dialog.
+    fn dependency_info(&self, entry: &StartupEntry) -> dialogs::DependencyInfo {
+        let key = history::entry_key(&entry.id());
+        let needs: Vec<(String, String)> = self
+            .dependencies
+            .needs(&key)
+            .into_iter()
+            .map(|link| (link.dependency.clone(), link.dependency_name.clone()))
+            .collect();
+        let mut choices: Vec<(String, String)> = self
+            .all_services
+            .iter()
+            .chain(&self.entries)
+            .map(|e| (history::entry_key(&e.id()), e.name.clone()))
+            .filter(|(k, _)| *k != key && !needs.iter().any(|(n, _)| n == k))
+            .collect();
+        choices.sort_by_key(|(_, name)| name.to_lowercase());
+        dialogs::DependencyInfo {
+            needed_by: self.dependencies.dependents(entry, &self.entries, &self.all_services),
+            key,
+            needs,
+            choices,
+            ..Default::default()
+        }
+    }
+
+    /// Apply an add or remove from a properties dialog's Dependencies section.
+    fn update_dependencies(&mut self, info: &mut dialogs::DependencyInfo) {
+        let name_of = |key: &str, entries: &[StartupEntry], services: &[StartupEntry]| {
+            entries
+                .iter()
+                .chain(services)
+                .find(|e| history::entry_key(&e.id()) == key)
+                .map(|e| e.name.clone())
+        };
+        if let Some(dependency) = info.add_requested.take() {
+            let dependent_name = name_of(&info.key, &self.entries, &self.all_services).unwrap_or_default();
+            let dependency_name = name_of(&dependency, &self.entries, &self.all_services).unwrap_or_default();
+            if self.dependencies.add((&info.key, &dependent_name), (&dependency, &dependency_name)) {
+                self.set_status(&format!("Noted that '{}' needs '{}'", dependent_name, dependency_name), false);
+            }
+        } else if let Some(dependency) = info.remove_requested.take() {
+            self.dependencies.remove(&info.key, &dependency);
+        } else {
+            return;
+        }
+        let entry = self
+            .entries
+            .iter()
+            .chain(&self.all_services)
+            .find(|e| history::entry_key(&e.id()) == info.key)
+            .cloned();
+        if let Some(entry) = entry {
+            *info = self.dependency_info(&entry);
+        }
+    }
+
+    /// Startup entries and non-Microsoft services grouped by category for the
+    /// categories dialog.
+    fn category_cleanup_info(&self) -> dialogs::CategoryCleanupInfo {
+        let mut groups: Vec<(categories::Category, Vec<dialogs::CategoryItem>)> =
+            categories::Category::ALL.iter().map(|c| (*c, Vec::new())).collect();
+        for entry in self.entries.iter().chain(self.all_services.iter()) {
+            let Some((category, pattern)) = categories::classify(entry) else {
+                continue;
+            };
+            let already_disabled = entry.enabled == EnabledStatus::Disabled;
+            let item = dialogs::CategoryItem {
+                key: history::entry_key(&entry.id()),
+                name: entry.name.clone(),
+                location: entry.source.display_location(),
+                pattern,
+                already_disabled,
+                dependents: self.dependencies.dependents(entry, &self.entries, &self.all_services),
+                selected: !already_disabled && category.preselected(),
+            };
+            if let Some((_, items)) = groups.iter_mut().find(|(c, _)| *c == category) {
+                items.push(item);
+            }
+        }
+        for (_, items) in &mut groups {
+            items.sort_by_key(|i| (i.already_disabled, i.name.to_lowercase()));
+        }
+        dialogs::CategoryCleanupInfo {
+            groups,
+            reviewing: false,
+            staging: self.staging_plan,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Disable (or stage) the entries reviewed in the categories dialog.
+    fn disable_categorized(&mut self, info: &dialogs::CategoryCleanupInfo) {
+        let selected: Vec<(String, String)> = info.selected().map(|i| (i.key.clone(), i.name.clone())).collect();
+        if self.staging_plan {
+            for (key, name) in selected {
+                self.stage_step(plan::PlanAction::Disable, key, name);
+            }
+            return;
+        }
+        self.disable_entries(selected);
+    }
+
+    /// Disable entries by run history key and name as one batch; the last
+    /// result reports the batch (see [`Self::report_bulk`]).
+    fn disable_entries(&mut self, selected: Vec<(String, String)>) {
+        let mut submitted = 0;
+        let mut failures = Vec::new();
+        for (key, name) in selected {
+            let entry = self
+                .entries
+                .iter()
+                .chain(self.all_services.iter())
+                .find(|e| history::entry_key(&e.id()) == key)
+                .cloned();
+            let Some(entry) = entry else {
+                failures.push(format!("'{}': entry not found", name));
+                continue;
+            };
+            let action = PendingAction::Disable(entry.id());
+            if self.dry_run {
+                if self.simulate_entry_action(&action, &entry) {
+                    submitted += 1;
+                } else {
+                    failures.push(format!("'{}': the action would fail", name));
+                }
+            } else if let Some(job) = entry_job(&action, &entry) {
+                self.submit_job(job, JobOrigin::BulkDisable);
+                submitted += 1;
+            }
+        }
+        if self.dry_run {
+            self.report_bulk("Would disable", submitted, &failures);
+        } else if submitted == 0 {
+            self.report_bulk("Disabled", 0, &failures);
+        } else {
+            // Results arrive from the executor; the last one reports the batch
+            self.bulk_batch.get_or_insert_default().1.extend(failures);
+            self.set_status(&format!("Disabling {} entries...", submitted), false);
+        }
+    }
+
+    /// Delete entries by run history key and name as one batch, like
+    /// [`Self::disable_entries`].
+    fn delete_entries(&mut self, selected: Vec<(String, String)>) {
+        let mut submitted = 0;
+        let mut failures = Vec::new();
+        for (key, name) in selected {
+            let entry = self
+                .entries
+                .iter()
+                .chain(self.all_services.iter())
+                .find(|e| history::entry_key(&e.id()) == key)
+                .cloned();
+            let Some(entry) = entry else {
+                failures.push(format!("'{}': entry not found", name));
+                continue;
+            };
+            if self.dry_run {
+                let changes = dry_run::delete_entry(&entry);
+                if changes.is_ok() {
+                    submitted += 1;
+                } else {
+                    failures.push(format!("'{}': the action would fail", name));
+                }
+                self.simulate(format!("Delete '{}'", entry.name), changes);
+            } else {
+                self.submit_job(executor::Job::Delete(entry), JobOrigin::BulkDelete);
+                submitted += 1;
+            }
+        }
+        if self.dry_run {
+            self.report_bulk("Would delete", submitted, &failures);
+        } else if submitted == 0 {
+            self.report_bulk("Deleted", 0, &failures);
+        } else {
+            self.bulk_batch.get_or_insert_default().1.extend(failures);
+            self.set_status(&format!("Deleting {} entries...", submitted), false);
+        }
+    }
+
+    fn report_bulk(&mut self, verb: &str, changed: usize, failures: &[String]) {
+        if failures.is_empty() {
+            self.set_status(&format!("{} {} entries", verb, changed), false);
+        } else {
+            self.set_status(
+                &format!("{} {} entries, {} failed: {}", verb, changed, failures.len(), failures.join("; ")),
+                true,
+            );
+        }
+        if !std::mem::take(&mut self.restart_after_batch) {
+            return;
+        }
+        if !failures.is_empty() {
+            // Leave the failures on screen instead of restarting past them
+            return;
+        }
+        match clean_boot::restart() {
+            Ok(()) => self.set_status(
+                &format!(
+                    "Disabled {} entries; Windows restarts in {} seconds (run \"shutdown /a\" to cancel)",
+                    changed,
+                    clean_boot::RESTART_DELAY_SECS
+                ),
+                false,
+            ),
+            Err(e) => self.set_status(&format!("Disabled {} entries, but {:#}", changed, e), true),
+        }
+    }
+
+    /// Startup entries, tasks, and services left behind by uninstalled apps,
+    /// all selected, for the Orphaned by Uninstall dialog.
+    fn orphan_cleanup_info(&self) -> dialogs::OrphanCleanupInfo {
+        let mut items: Vec<dialogs::OrphanItem> = self
+            .entries
+            .iter()
+            .chain(self.all_services.iter())
+            .filter_map(|entry| {
+                let app = entry.orphaned_by.clone()?;
+                let kind = match entry.source {
+                    Source::Service { .. } => "Service",
+                    Source::TaskScheduler { .. } => "Scheduled task",
+                    _ => "Startup entry",
+                };
+                Some(dialogs::OrphanItem {
+                    key: history::entry_key(&entry.id()),
+                    name: entry.name.clone(),
+                    kind,
+                    location: entry.source.display_location(),
+                    command: entry.command.clone(),
+                    app,
+                    selected: true,
+                })
+            })
+            .collect();
+        items.sort_by(|a, b| a.app.to_lowercase().cmp(&b.app.to_lowercase()).then(a.name.cmp(&b.name)));
+        dialogs::OrphanCleanupInfo {
+            items,
+            reviewing: false,
+            disable_instead: false,
+            staging: self.staging_plan,
+            dry_run: self.dry_run,
+        }
+    }
+
+    /// Delete (or disable) the orphaned entries chosen in the dialog.
+    fn clean_up_orphans(&mut self, info: &dialogs::OrphanCleanupInfo) {
+        let selected: Vec<(String, String)> = info.selected().map(|i| (i.key.clone(), i.name.clone())).collect();
+        if !info.disable_instead {
+            self.delete_entries(selected);
+        } else if self.staging_plan {
+            for (key, name) in selected {
+                self.stage_step(plan::PlanAction::Disable, key, name);
+            }
+        } else {
+            self.disable_entries(selected);
+        }
+    }
+
+    /// Enabled non-Microsoft services and startup items for the Clean Boot
+    /// Assistant, all selected.
+    fn clean_boot_info(&self) -> dialogs::CleanBootInfo {
+        let item = |entry: &StartupEntry| dialogs::CleanBootItem {
+            key: history::entry_key(&entry.id()),
+            name: entry.name.clone(),
+            location: entry.source.display_location(),
+            selected: true,
         };
+        let mut services: Vec<dialogs::CleanBootItem> = self
+            .all_services
+            .iter()
+            .filter(|s| s.enabled == EnabledStatus::Enabled && !filters::is_microsoft_entry(s))
+            .map(item)
+            .collect();
+        // msconfig's startup items: Run keys and Startup folders (scheduled
+        // tasks and RunOnce entries are left alone)
+        let mut startup_items: Vec<dialogs::CleanBootItem> = self
+            .entries
+            .iter()
+            .filter(|e| matches!(e.source, Source::RegistryRun { .. } | Source::StartupFolder { .. }))
+            .filter(|e| e.enabled == EnabledStatus::Enabled && !filters::is_microsoft_entry(e))
+            .map(item)
+            .collect();
+        services.sort_by_key(|i| i.name.to_lowercase());
+        startup_items.sort_by_key(|i| i.name.to_lowercase());
+        dialogs::CleanBootInfo {
+            services,
+            startup_items,
+            restore_point: true,
+            restart: false,
+            revert_file: clean_boot::latest_revert_plan(),
+            revert_requested: false,
+            staging: self.staging_plan,
+            dry_run: self.dry_run,
+        }
+    }
 
+    /// Apply the clean boot chosen in the assistant: save the revert file,
+    /// create the restore point if asked (on a background thread, see
+    /// [`Self::poll_clean_boot`]), then disable the selection.
+    fn apply_clean_boot(&mut self, info: &dialogs::CleanBootInfo) {
+        let selected: Vec<(String, String)> = info.selected().map(|i| (i.key.clone(), i.name.clone())).collect();
+        if self.staging_plan {
+            for (key, name) in selected {
+                self.stage_step(plan::PlanAction::Disable, key, name);
+            }
+            return;
+        }
+        if self.dry_run {
+            self.simulate("Prepare clean boot".to_string(), Ok(dry_run::prepare_clean_boot(info.restore_point)));
+            self.disable_entries(selected);
+            if info.restart {
+                self.simulate("Restart Windows".to_string(), Ok(dry_run::restart()));
+            }
+            return;
+        }
+
+        let mut revert = plan::CleanupPlan::default();
+        for (key, name) in &selected {
+            revert.add(plan::PlanAction::Enable, key.clone(), name.clone());
+        }
+        if let Err(e) = clean_boot::save_revert_plan(&revert) {
+            self.set_status(&format!("Clean boot cancelled; the revert file could not be saved: {:#}", e), true);
+            return;
+        }
+        if !info.restore_point {
+            self.restart_after_batch = info.restart;
+            self.disable_entries(selected);
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = tx.send(clean_boot::create_restore_point().map_err(|e| format!("{:#}", e)));
+        });
+        self.pending_clean_boot = Some(PendingCleanBoot {
+            items: selected,
+            restart: info.restart,
+            restore_point: rx,
+        });
+        self.set_status("Creating a restore point...", false);
+    }
+
+    /// Disable a clean boot's entries once its restore point is made. A
+    /// failed restore point cancels the clean boot. Returns whether it is
+    /// still waiting.
+    fn poll_clean_boot(&mut self) -> bool {
+        let Some(pending) = &self.pending_clean_boot else {
+            return false;
+        };
+        let result = match pending.restore_point.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::TryRecvError::Empty) => return true,
+            Err(mpsc::TryRecvError::Disconnected) => Err("the restore point thread stopped".to_string()),
+        };
+        let Some(pending) = self.pending_clean_boot.take() else {
+            return false;
+        };
         match result {
-            Ok(msg) => {
-                self.set_status(&msg, false);
-                self.start_background_load();
+            Ok(()) => {
+                self.restart_after_batch = pending.restart;
+                self.disable_entries(pending.items);
+            }
+            Err(e) => self.set_status(&format!("Clean boot cancelled; nothing was disabled: {}", e), true),
+        }
+        false
+    }
+
+    /// Load the last clean boot's revert file into the cleanup plan and
+    /// open it, so the entries can be enabled again step by step.
+    fn revert_clean_boot(&mut self, path: &std::path::Path) {
+        match plan::CleanupPlan::load(path) {
+            Ok(plan) => {
+                self.set_status(
+                    &format!("Loaded {} Enable steps to revert the clean boot; run them from the plan", plan.steps.len()),
+                    false,
+                );
+                self.cleanup_plan = plan;
+                self.cleanup_plan_dialog = Some(dialogs::CleanupPlanInfo::default());
+            }
+            Err(e) => self.set_status(&format!("Failed to load the revert file: {:#}", e), true),
+        }
+    }
+
+    /// "Orphaned..." on the Startup Apps and Services toolbars, with the
+    /// count across both.
+    fn orphans_button(&mut self, ui: &mut egui::Ui) -> egui::Response {
+        let count = self
+            .entries
+            .iter()
+            .chain(self.all_services.iter())
+            .filter(|e| e.orphaned_by.is_some())
+            .count();
+        let label = if count > 0 { format!("Orphaned ({})...", count) } else { "Orphaned...".to_string() };
+        let r = ui
+            .add_enabled(!self.loading, egui::Button::new(label))
+            .on_hover_text("Startup entries, tasks, and services left in the folders of uninstalled apps, for cleaning up in bulk");
+        if r.clicked() {
+            self.orphan_cleanup = Some(self.orphan_cleanup_info());
+        }
+        r
+    }
+
+    /// Set a service's start type from the hardening dialog, or put it back
+    /// to what it was before a suggestion was applied.
+    fn set_service_start_mode(&mut self, entry: StartupEntry, mode: service_hardening::StartMode) {
+        if self.dry_run {
+            let action = format!("Set '{}' to {}", entry.name, mode.label());
+            self.simulate(action, dry_run::set_start_mode(&entry, mode));
+            return;
+        }
+        self.submit_job(executor::Job::SetStartMode(entry, mode), JobOrigin::User);
+    }
+
+    fn revert_hardening(&mut self, index: usize) {
+        let Some(change) = self.hardening_changes.changes.get(index).cloned() else {
+            return;
+        };
+        let service = self
+            .all_services
+            .iter()
+            .find(|s| matches!(&s.source, Source::Service { service_name, .. } if *service_name == change.service_name))
+            .cloned();
+        match service {
+            Some(service) => self.set_service_start_mode(service, change.previous),
+            None => {
+                self.hardening_changes.forget(&change.service_name);
+                self.set_status(&format!("'{}' is no longer installed; removed it from the list", change.name), false);
+            }
+        }
+    }
+
+    /// Re-check the targets of startup entries and services on drives that
+    /// just arrived or were removed, so a tool on a USB drive isn't shown as
+    /// broken while the drive is unplugged.
+    fn revalidate_drive(&mut self, change: drive_watch::DriveChange) {
+        let mut rechecked = 0;
+        for entry in self.entries.iter_mut().chain(self.all_services.iter_mut()) {
+            if !change.affects(&cmdline::target_path(&entry.command)) {
+                continue;
+            }
+            (entry.target_missing, entry.volatile_drive) = drives::check_target(&entry.command);
+            rechecked += 1;
+        }
+        if rechecked > 0 {
+            self.set_status(
+                &format!(
+                    "Drive {} {}; re-checked {} entries",
+                    change.letter_names().join(", "),
+                    if change.arrived { "connected" } else { "removed" },
+                    rechecked
+                ),
+                false,
+            );
+        }
+    }
+
+    /// Add a step to the cleanup plan instead of applying it.
+    fn stage_step(&mut self, action: plan::PlanAction, target: String, name: String) {
+        let label = action.label();
+        if self.cleanup_plan.add(action, target, name.clone()) {
+            self.set_status(
+                &format!(
+                    "Staged {} '{}' ({} steps in plan)",
+                    label.to_lowercase(),
+                    name,
+                    self.cleanup_plan.steps.len()
+                ),
+                false,
+            );
+        } else {
+            self.set_status(&format!("'{}' already has this step in the plan", name), true);
+        }
+    }
+
+    /// Run one plan step against the current data and record its result.
+    /// Uninstall steps open the usual confirmation rather than running
+    /// unattended.
+    fn run_plan_step(&mut self, index: usize) {
+        let Some(step) = self.cleanup_plan.steps.get(index).cloned() else {
+            return;
+        };
+        let status = if step.action == plan::PlanAction::Uninstall {
+            match self.find_installed_app(&step.target) {
+                Some(app) => {
+                    self.uninstall_preview = Some(uninstall_preview_from(app));
+                    self.pending_action = Some(PendingAction::ConfirmUninstall(step.target.clone()));
+                    // Finished by the confirmation dialog and the uninstaller
+                    plan::StepStatus::Running
+                }
+                None => plan::StepStatus::Failed("App is no longer installed".to_string()),
+            }
+        } else {
+            let entry = self
+                .entries
+                .iter()
+                .chain(self.all_services.iter())
+                .find(|e| history::entry_key(&e.id()) == step.target)
+                .cloned();
+            match entry {
+                Some(entry) => {
+                    let id = entry.id();
+                    let action = match step.action {
+                        plan::PlanAction::Enable => PendingAction::Enable(id),
+                        plan::PlanAction::Disable => PendingAction::Disable(id),
+                        plan::PlanAction::Start => PendingAction::Start(id),
+                        _ => PendingAction::Stop(id),
+                    };
+                    if self.dry_run {
+                        self.simulate_entry_action(&action, &entry);
+                        return;
+                    }
+                    match entry_job(&action, &entry) {
+                        Some(job) => {
+                            self.submit_job(job, JobOrigin::PlanStep(step.action, step.target.clone()));
+                            plan::StepStatus::Running
+                        }
+                        None => plan::StepStatus::Failed("Not an entry action".to_string()),
+                    }
+                }
+                None => plan::StepStatus::Failed("Entry not found".to_string()),
+            }
+        };
+        // A dry run reported its own outcome and leaves the step to be run
+        // for real
+        if self.dry_run {
+            return;
+        }
+        match &status {
+            plan::StepStatus::Failed(e) => {
+                self.set_status(&format!("Step {} ('{}') failed: {}", index + 1, step.name, e), true)
+            }
+            // Reported when the executor or the uninstaller finishes it
+            plan::StepStatus::Running => {}
+            _ => self.set_status(
+                &format!("Ran step {}: {} '{}'", index + 1, step.action.label(), step.name),
+                false,
+            ),
+        }
+        if let Some(s) = self.cleanup_plan.steps.get_mut(index) {
+            s.status = status;
+        }
+    }
+
+    fn export_cleanup_plan(&mut self) {
+        let stamp = chrono::Local::now().format("%Y-%m-%d_%H%M%S");
+        let path = rfd::FileDialog::new()
+            .set_file_name(format!("cleanup-plan-{}.txt", stamp))
+            .add_filter("Cleanup Plans", &["txt"])
+            .save_file();
+
+        let path = match path {
+            Some(p) => p,
+            None => return, // User cancelled
+        };
+
+        match self.cleanup_plan.save(&path) {
+            Ok(()) => self.set_status(
+                &format!("Exported {} plan steps to {}", self.cleanup_plan.steps.len(), path.display()),
+                false,
+            ),
+            Err(e) => self.set_status(&format!("Export failed: {:#}", e), true),
+        }
+    }
+
+    fn import_cleanup_plan(&mut self) {
+        let path = rfd::FileDialog::new()
+            .add_filter("Cleanup Plans", &["txt"])
+            .pick_file();
+
+        let path = match path {
+            Some(p) => p,
+            None => return, // User cancelled
+        };
+
+        match plan::CleanupPlan::load(&path) {
+            Ok(plan) => {
+                self.set_status(
+                    &format!("Imported {} plan steps from {}", plan.steps.len(), path.display()),
+                    false,
+                );
+                self.cleanup_plan = plan;
+            }
+            Err(e) => self.set_status(&format!("Import failed: {:#}", e), true),
+        }
+    }
+
+    /// Run actions that were queued while a reload was in flight, resolving
+    /// each against the freshly loaded entries.
+    fn drain_action_queue(&mut self) {
+        while let Some(queued) = self.action_queue.pop_front() {
+            match self.find_entry(&queued.entry.id()).cloned() {
+                Some(entry) => self.run_entry_action(&queued.action, &entry),
+                None => self.set_status(
+                    &format!("'{}' no longer exists; skipped its queued action", queued.entry.name),
+                    true,
+                ),
+            }
+        }
+    }
+
+    /// Apply edited arguments and task conditions from the properties dialog.
+    fn apply_entry_properties(&mut self, info: &dialogs::StartupEntryPropertiesInfo) {
+        if self.loading {
+            self.set_status("Wait for the refresh to finish before applying changes", true);
+            return;
+        }
+        let mut entry = StartupEntry::new(info.name.clone(), info.command.clone(), info.source.clone());
+        entry.task_conditions = info.original_task_conditions;
+        if self.dry_run {
+            let mut changes = Vec::new();
+            if info.arguments.trim() != info.original_arguments.trim() {
+                match dry_run::set_entry_arguments(&entry, &info.arguments) {
+                    Ok(c) => changes.extend(c),
+                    Err(e) => return self.simulate(format!("Change '{}'", info.name), Err(e)),
+                }
             }
-            Err(e) => {
-                self.set_status(&format!("Error: {}", e), true);
+            if let (Some(conditions), Some(original), Source::TaskScheduler { task_path }) =
+                (&info.task_conditions, &info.original_task_conditions, &info.source)
+            {
+                changes.extend(dry_run::set_task_conditions(task_path, original, conditions));
+            }
+            if !changes.is_empty() {
+                self.simulate(format!("Change '{}'", info.name), Ok(changes));
+            }
+            return;
+        }
+        if info.arguments.trim() != info.original_arguments.trim() {
+            self.submit_job(executor::Job::SetArguments(entry.clone(), info.arguments.clone()), JobOrigin::User);
+        }
+        if let (Some(conditions), Source::TaskScheduler { .. }) = (info.task_conditions, &info.source) {
+            if info.task_conditions != info.original_task_conditions {
+                self.submit_job(executor::Job::SetTaskConditions(entry, conditions), JobOrigin::User);
             }
         }
     }
 
-    fn delete_confirmed(&mut self, visible_index: usize) {
-        let entry = match self.get_entry_by_visible_index(visible_index) {
+    fn delete_confirmed(&mut self, id: &EntryId) {
+        if self.loading {
+            self.set_status("Wait for the refresh to finish before deleting", true);
+            return;
+        }
+        let entry = match self.find_entry(id) {
             Some(e) => e.clone(),
             None => return,
         };
-        let name = entry.name.clone();
-        match actions::delete_entry(&entry) {
-            Ok(_) => {
-                self.set_status(&format!("Deleted '{}'", name), false);
-                self.start_background_load();
+        if self.dry_run {
+            self.simulate(format!("Delete '{}'", entry.name), dry_run::delete_entry(&entry));
+            return;
+        }
+        self.submit_job(executor::Job::Delete(entry), JobOrigin::User);
+    }
+
+    fn add_startup_entry_confirmed(&mut self, info: &dialogs::AddStartupEntryInfo) {
+        let name = info.name.trim().to_string();
+        if self.dry_run {
+            let changes = dry_run::add_entry(&name, &info.command, info.location);
+            self.simulate(format!("Add '{}' to startup", name), changes);
+            return;
+        }
+        let job = executor::Job::Add {
+            name,
+            command: info.command.clone(),
+            location: info.location,
+        };
+        self.submit_job(job, JobOrigin::User);
+    }
+
+    fn restore_backup(&mut self, item: &backup::BackupItem) {
+        let name = item.file_name();
+        if self.dry_run {
+            self.simulate(format!("Restore '{}'", name), dry_run::restore_backup(item));
+            return;
+        }
+        match backup::restore(item) {
+            Ok(()) => {
+                // Restored on purpose, so it coming back isn't a surprise
+                self.watchlist.forget(&EntryId::StartupFile {
+                    path: item.original_path.to_string_lossy().into_owned(),
+                });
+                let original_path = item.original_path.display().to_string();
+                audit::append(&[audit::AuditRecord::new(&name, &original_path, "Restored", "", &original_path)]);
+                self.session_changes.push(dialogs::SessionChange {
+                    item: name.clone(),
+                    change: "Restored".to_string(),
+                });
+                self.set_status(&format!("Restored '{}'", name), false);
+                if !self.loading {
+                    self.start_background_load();
+                }
             }
             Err(e) => {
-                self.set_status(&format!("Error deleting '{}': {}", name, e), true);
+                self.set_status(&format!("Error restoring '{}': {}", name, e), true);
             }
         }
     }
 
-    fn uninstall_confirmed(&mut self, index: usize) {
-        let app = match self.installed_apps.get(index) {
+    fn find_installed_app(&self, registry_key: &str) -> Option<&InstalledApp> {
+        self.installed_apps.iter().find(|a| a.registry_key == registry_key)
+    }
+
+    /// Launch the command confirmed in the uninstall preview (the registered
+    /// uninstall string, possibly with edited arguments or msiexec logging).
+    /// Remove a dev package through its package manager on the executor.
+    fn package_uninstall_confirmed(&mut self, package: DevPackage) {
+        if self.dry_run {
+            let action = format!("Uninstall {} package '{}'", package.manager.label(), package.name);
+            self.simulate(action, Ok(dry_run::uninstall_package(&package)));
+            return;
+        }
+        self.submit_job(executor::Job::UninstallPackage(package), JobOrigin::User);
+    }
+
+    fn uninstall_confirmed(&mut self, registry_key: &str, preview: &dialogs::UninstallPreview) {
+        let app = match self.find_installed_app(registry_key) {
             Some(a) => a.clone(),
-            None => return,
+            None => {
+                self.finish_uninstall_step(registry_key, plan::StepStatus::Failed("App is no longer installed".to_string()));
+                return;
+            }
         };
         let name = app.display_name.clone();
-        match run_shell_command(&app.uninstall_string) {
-            Ok(()) => {
+        if self.dry_run {
+            let changes = dry_run::launch_elevated(&preview.command());
+            self.simulate(format!("Uninstall '{}'", name), Ok(changes));
+            self.finish_uninstall_step(registry_key, plan::StepStatus::Pending);
+            return;
+        }
+        match uninstaller::launch_elevated(&preview.command()) {
+            Ok(launched) => {
                 self.set_status(&format!("Uninstalling '{}'...", name), false);
-                // Poll the registry for the app to disappear (every 2s, up to 10 min)
+                if let Some(log_path) = preview.active_log_path() {
+                    self.last_uninstall_log = Some(log_path.to_path_buf());
+                }
                 let (tx, rx) = mpsc::channel();
                 self.rescan_receiver = Some(rx);
-                let display_name = name.clone();
+                let registry_key = app.registry_key.clone();
                 std::thread::spawn(move || {
-                    for _ in 0..300 {
-                        std::thread::sleep(std::time::Duration::from_secs(2));
-                        let apps = crate::installed_apps::collect_installed_apps();
-                        let still_installed = apps.iter().any(|a| a.display_name == display_name);
-                        if !still_installed {
-                            break;
+                    let outcome = match launched {
+                        // Follow the uninstaller's process tree until it finishes
+                        Some(process) => uninstaller::wait_for_tree(process),
+                        None => {
+                            // No process handle: poll the registry for the app
+                            // to disappear (every 2s, up to 10 min)
+                            for _ in 0..300 {
+                                std::thread::sleep(std::time::Duration::from_secs(2));
+                                let apps = crate::installed_apps::collect_installed_apps();
+                                if !apps.iter().any(|a| a.registry_key == registry_key) {
+                                    break;
+                                }
+                            }
+                            uninstaller::UninstallOutcome::Untracked
                         }
-                    }
+                    };
                     // Brief pause for any remaining registry cleanup
                     std::thread::sleep(std::time::Duration::from_secs(1));
-                    let _ = tx.send(());
+                    let _ = tx.send((registry_key, name, outcome));
                 });
             }
             Err(e) => {
                 self.set_status(&format!("Failed to uninstall '{}': {}", name, e), true);
+                self.finish_uninstall_step(registry_key, plan::StepStatus::Failed(e));
+            }
+        }
+    }
+
+    /// Set the status of a running cleanup plan Uninstall step for the app
+    /// with `registry_key`, if the uninstall came from the plan.
+    fn finish_uninstall_step(&mut self, registry_key: &str, status: plan::StepStatus) {
+        if let Some(step) = self.cleanup_plan.steps.iter_mut().find(|s| {
+            s.action == plan::PlanAction::Uninstall
+                && s.target == registry_key
+                && s.status == plan::StepStatus::Running
+        }) {
+            step.status = status;
+        }
+    }
+
+    fn kill_process_by_key(&mut self, key: &ProcessKey) {
+        let Some(proc) = self.all_processes.iter().find(|p| p.key() == *key) else {
+            self.set_status("Process is no longer running", true);
+            return;
+        };
+        let pid = proc.pid;
+        let name = proc.name.clone();
+        if self.dry_run {
+            let changes = vec![dry_run::kill_process(pid, &name)];
+            self.simulate(format!("Kill '{}'", name), Ok(changes));
+            return;
+        }
+        match kill_process(pid) {
+            Ok(_) => {
+                self.set_status(&format!("Killed '{}' (PID {})", name, pid), false);
+                self.start_background_load();
+            }
+            Err(e) => {
+                self.set_status(&format!("Failed to kill PID {}: {}", pid, e), true);
+            }
+        }
+    }
+
+    /// Relaunch a process elevated: capture its command line, start the same
+    /// command through the UAC prompt, and end the original only once the
+    /// elevated copy has launched. If elevation is declined the original is
+    /// left running. Explorer needs confirming first, and App Manager itself
+    /// is refused (ending it would leave nothing to finish the restart).
+    fn restart_process_elevated(&mut self, key: &ProcessKey, confirmed: bool) {
+        let Some(proc) = self.all_processes.iter().find(|p| p.key() == *key) else {
+            self.set_status("Process is no longer running", true);
+            return;
+        };
+        let (pid, name) = (proc.pid, proc.name.clone());
+        match kill_guard::guard_for(pid, &name) {
+            Some(kill_guard::KillGuard::SelfProcess) => {
+                self.set_status(
+                    "App Manager can't restart itself this way; use the Refresh as Administrator jump list task",
+                    true,
+                );
+                return;
             }
+            Some(guard) if !confirmed => {
+                self.restart_elevated_confirmation = Some((*key, guard));
+                return;
+            }
+            _ => {}
+        }
+        if proc.exe_path.is_empty() {
+            self.set_status(&format!("Cannot read the executable path of '{}' (PID {})", name, pid), true);
+            return;
+        }
+        let exe = proc.exe_path.clone();
+        let (_, args) = cmdline::split(&proc.command_line);
+        let working_dir = std::path::Path::new(&exe).parent().map(|p| p.to_path_buf());
+
+        if self.dry_run {
+            let changes = vec![
+                dry_run::launch_elevated(&cmdline::join(&exe, &args)).remove(0),
+                dry_run::kill_process(pid, &name),
+            ];
+            self.simulate(format!("Restart '{}' as administrator", name), Ok(changes));
+            return;
+        }
+        if let Err(e) = shell::run_elevated(&exe, &args, working_dir.as_deref()) {
+            self.set_status(&format!("{}; '{}' was left running", e, name), true);
+            return;
+        }
+        match kill_process(pid) {
+            Ok(()) => self.set_status(&format!("Restarted '{}' as administrator", name), false),
+            Err(e) => self.set_status(
+                &format!("Started '{}' as administrator, but failed to end the original (PID {}): {}", name, pid, e),
+                true,
+            ),
+        }
+        self.start_background_load();
+    }
+
+    /// Open the native Windows properties sheet for a file.
+    fn open_windows_properties(&mut self, path: &str) {
+        if let Err(e) = shell::show_file_properties(path) {
+            self.set_status(&format!("Failed to open properties: {}", e), true);
+        }
+    }
+
+    fn attach_debugger(&mut self, pid: u32, name: &str) {
+        let Some(command) = self.settings.debugger_command_for(pid) else {
+            self.set_status("No debugger found; set one in Settings", true);
+            return;
+        };
+        let (exe, args) = cmdline::split(&command);
+        let result = std::process::Command::new(cmdline::expand_env_vars(&exe))
+            .raw_arg(args)
+            .spawn();
+        match result {
+            Ok(_) => self.set_status(&format!("Attaching debugger to '{}' (PID {})", name, pid), false),
+            Err(e) => self.set_status(&format!("Failed to start debugger: {}", e), true),
+        }
+    }
+
+    /// Restart Explorer on a background thread; the result is reported in the
+    /// status bar.
+    fn start_explorer_restart(&mut self) {
+        if self.explorer_restart_receiver.is_some() {
+            return;
         }
+        if self.dry_run {
+            let mut changes: Vec<String> = self
+                .all_processes
+                .iter()
+                .filter(|p| p.name.eq_ignore_ascii_case("explorer.exe"))
+                .map(|p| dry_run::kill_process(p.pid, &p.name))
+                .collect();
+            changes.push("Start explorer.exe unless Windows restarts it".to_string());
+            self.simulate("Restart Explorer".to_string(), Ok(changes));
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        self.explorer_restart_receiver = Some(rx);
+        self.set_status("Restarting Explorer...", false);
+        std::thread::spawn(move || {
+            let _ = tx.send(kill_guard::restart_explorer());
+        });
     }
 
     fn filtered_process_count(&self) -> usize {
@@ -354,14 +2372,12 @@ impl StartupApp {
     }
 
     fn export_csv(&mut self) {
-        let tab_name = match self.active_tab {
-            Tab::StartupApps => "startup-apps",
-            Tab::Services => "services",
-            Tab::Processes => "processes",
-            Tab::Installed => "installed-apps",
-        };
         let now = chrono::Local::now();
-        let default_name = format!("{}-{}.csv", tab_name, now.format("%Y-%m-%d_%H%M%S"));
+        let default_name = format!(
+            "{}-{}.csv",
+            tab_file_name(self.active_tab),
+            now.format("%Y-%m-%d_%H%M%S")
+        );
 
         let path = rfd::FileDialog::new()
             .set_file_name(&default_name)
@@ -373,12 +2389,9 @@ impl StartupApp {
             None => return, // User cancelled
         };
 
-        let result = match self.active_tab {
-            Tab::StartupApps => self.write_startup_apps_csv(&path),
-            Tab::Services => self.write_services_csv(&path),
-            Tab::Processes => self.write_processes_csv(&path),
-            Tab::Installed => self.write_installed_apps_csv(&path),
-        };
+        let result = std::fs::File::create(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|mut file| self.write_tab_csv(self.active_tab, &mut file));
 
         match result {
             Ok(count) => {
@@ -393,12 +2406,267 @@ impl StartupApp {
         }
     }
 
-    fn write_startup_apps_csv(&self, path: &std::path::Path) -> Result<usize, String> {
-        let entries = self.active_entries();
-        let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    /// Draw every row of the current tab into an image and copy it to the
+    /// clipboard, or save it as a PNG.
+    fn export_view_image(&mut self, ctx: &egui::Context, save: bool) {
+        let mut csv = Vec::new();
+        let count = match self.write_tab_csv(self.active_tab, &mut csv) {
+            Ok(count) => count,
+            Err(e) => {
+                self.set_status(&format!("Image failed: {}", e), true);
+                return;
+            }
+        };
+        let now = chrono::Local::now();
+        let title = format!(
+            "{} \u{2014} {} \u{2014} {} \u{2014} {} rows",
+            tab_title(self.active_tab),
+            std::env::var("COMPUTERNAME").unwrap_or_default(),
+            now.format("%Y-%m-%d %H:%M:%S"),
+            count
+        );
+        let image = match table_image::render(ctx, &title, &String::from_utf8_lossy(&csv)) {
+            Ok(image) => image,
+            Err(e) => {
+                self.set_status(&format!("Image failed: {}", e), true);
+                return;
+            }
+        };
+        if !save {
+            ctx.copy_image(image);
+            self.set_status(&format!("Copied an image of {} rows to the clipboard", count), false);
+            return;
+        }
 
-        writeln!(file, "Name,Product Name,Command,Source,Status,State,Runs As,Visible As,Last Ran")
-            .map_err(|e| e.to_string())?;
+        let path = rfd::FileDialog::new()
+            .set_file_name(format!("{}-{}.png", tab_file_name(self.active_tab), now.format("%Y-%m-%d_%H%M%S")))
+            .add_filter("PNG Images", &["png"])
+            .save_file();
+        let Some(path) = path else {
+            return; // User cancelled
+        };
+        let result = table_image::encode_png(&image)
+            .and_then(|png| std::fs::write(&path, png).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => self.set_status(&format!("Saved an image of {} rows to {}", count, path.display()), false),
+            Err(e) => self.set_status(&format!("Image failed: {}", e), true),
+        }
+    }
+
+    /// Save the audit trail (newest first) as CSV or JSON Lines.
+    fn export_audit_trail(&mut self, records: &[audit::AuditRecord], format: audit::ExportFormat) {
+        let stamp = chrono::Local::now().format("%Y-%m-%d_%H%M%S");
+        let path = rfd::FileDialog::new()
+            .set_file_name(format!("audit-trail-{}.{}", stamp, format.extension()))
+            .add_filter(format.filter_name(), &[format.extension()])
+            .save_file();
+
+        let path = match path {
+            Some(p) => p,
+            None => return, // User cancelled
+        };
+
+        let mut text = String::new();
+        match format {
+            audit::ExportFormat::Csv => {
+                let language = export_locale::resolve(self.settings.export_language);
+                text.push_str(&export_locale::header_row(audit::COLUMNS, language));
+                text.push('\n');
+                for record in records {
+                    let fields: Vec<String> = record.fields().iter().map(|f| csv_escape(f)).collect();
+                    text.push_str(&fields.join(","));
+                    text.push('\n');
+                }
+            }
+            audit::ExportFormat::JsonLines => {
+                for record in records {
+                    text.push_str(&record.to_json());
+                    text.push('\n');
+                }
+            }
+        }
+
+        match std::fs::write(&path, text) {
+            Ok(()) => self.set_status(&format!("Exported {} changes to {}", records.len(), path.display()), false),
+            Err(e) => self.set_status(&format!("Export failed: {}", e), true),
+        }
+    }
+
+    /// Export every tab's CSV (with the current filters) into one timestamped
+    /// `.zip` for a full machine inventory.
+    fn export_all(&mut self) {
+        let now = chrono::Local::now();
+        let stamp = now.format("%Y-%m-%d_%H%M%S");
+        let path = rfd::FileDialog::new()
+            .set_file_name(format!("app-manager-export-{}.zip", stamp))
+            .add_filter("ZIP Archives", &["zip"])
+            .save_file();
+
+        let path = match path {
+            Some(p) => p,
+            None => return, // User cancelled
+        };
+
+        let mut files = Vec::new();
+        let mut total = 0;
+        let dev_packages = self.settings.scan_scope.dev_packages.then_some(Tab::DevPackages);
+        for tab in [
+            Tab::Installed,
+            Tab::StartupApps,
+            Tab::Processes,
+            Tab::Services,
+            Tab::ExecutionHistory,
+            Tab::Security,
+        ]
+        .into_iter()
+        .chain(dev_packages)
+        {
+            let mut csv = Vec::new();
+            match self.write_tab_csv(tab, &mut csv) {
+                Ok(count) => total += count,
+                Err(e) => {
+                    self.set_status(&format!("Export failed: {}", e), true);
+                    return;
+                }
+            }
+            files.push((format!("{}-{}.csv", tab_file_name(tab), stamp), csv));
+        }
+
+        match zip_file::write_zip(&path, &files, now) {
+            Ok(()) => {
+                self.set_status(
+                    &format!(
+                        "Exported {} rows from {} tabs to {}",
+                        total,
+                        files.len(),
+                        path.display()
+                    ),
+                    false,
+                );
+            }
+            Err(e) => {
+                self.set_status(&format!("Export failed: {}", e), true);
+            }
+        }
+    }
+
+    fn write_tab_csv(&self, tab: Tab, file: &mut dyn Write) -> Result<usize, String> {
+        match tab {
+            Tab::StartupApps => self.write_startup_apps_csv(file),
+            Tab::Services => self.write_services_csv(file),
+            Tab::Processes => self.write_processes_csv(file),
+            Tab::Installed => self.write_installed_apps_csv(file),
+            Tab::ExecutionHistory => self.write_execution_history_csv(file),
+            Tab::Security => self.write_security_csv(file),
+            Tab::DevPackages => self.write_dev_packages_csv(file),
+        }
+    }
+
+    /// Write the metadata block (if turned on in Settings) and the header
+    /// row, in the export language, for a tab's CSV. `columns` is the
+    /// English header row.
+    fn write_csv_header(&self, tab: Tab, file: &mut dyn Write, columns: &str) -> Result<(), String> {
+        let language = export_locale::resolve(self.settings.export_language);
+        if self.settings.export_metadata {
+            let t = |text| export_locale::translate(text, language);
+            let mut filters: Vec<String> = match tab {
+                Tab::Services if self.hide_microsoft_services => vec!["Hide Windows Services".to_string()],
+                Tab::Processes if self.hide_windows_processes => vec!["Hide Windows Processes".to_string()],
+                _ => Vec::new(),
+            };
+            filters.extend(self.quick_filters.active_labels(tab).into_iter().map(String::from));
+            filters.extend(self.column_filters.describe(tab));
+            let filters = if filters.is_empty() { t("None").to_string() } else { filters.join("; ") };
+            let env = |name| std::env::var(name).unwrap_or_default();
+            let lines = [
+                (t("Tab"), t(tab_title(tab)).to_string()),
+                (t("Machine"), env("COMPUTERNAME")),
+                (t("User"), env("USERNAME")),
+                (t("Exported"), chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()),
+                (t("App Version"), env!("CARGO_PKG_VERSION").to_string()),
+                (t("Administrator"), t(if self.is_admin { "Yes" } else { "No" }).to_string()),
+                (t("Filters"), filters),
+            ];
+            for (label, value) in lines {
+                writeln!(file, "# {}: {}", label, value).map_err(|e| e.to_string())?;
+            }
+        }
+        writeln!(file, "{}", export_locale::header_row(columns, language)).map_err(|e| e.to_string())
+    }
+
+    /// Export the visible registry-sourced startup entries as a `.reg` file.
+    fn export_all_reg(&mut self) {
+        let entries: Vec<StartupEntry> = self
+            .active_entries()
+            .into_iter()
+            .filter(|e| reg_file::is_exportable(e))
+            .cloned()
+            .collect();
+        if entries.is_empty() {
+            self.set_status("No registry entries to export", true);
+            return;
+        }
+        let default_name = format!(
+            "startup-entries-{}.reg",
+            chrono::Local::now().format("%Y-%m-%d_%H%M%S")
+        );
+        self.save_reg_file(&entries.iter().collect::<Vec<_>>(), &default_name);
+    }
+
+    /// Export the entry shown in the properties dialog as a `.reg` file.
+    fn export_entry_reg(&mut self, info: &dialogs::StartupEntryPropertiesInfo) {
+        let id = match &info.source {
+            Source::RegistryRun { hive, key_path } | Source::RegistryRunOnce { hive, key_path } => {
+                EntryId::Registry {
+                    hive: *hive,
+                    key_path: key_path.clone(),
+                    value_name: info.name.clone(),
+                }
+            }
+            _ => return,
+        };
+        let Some(entry) = self.find_entry(&id).cloned() else {
+            self.set_status("Entry no longer exists", true);
+            return;
+        };
+        let default_name = format!("{}.reg", sanitize_file_name(&entry.name));
+        self.save_reg_file(&[&entry], &default_name);
+    }
+
+    fn save_reg_file(&mut self, entries: &[&StartupEntry], default_name: &str) {
+        let path = rfd::FileDialog::new()
+            .set_file_name(default_name)
+            .add_filter("Registry Files", &["reg"])
+            .save_file();
+
+        let path = match path {
+            Some(p) => p,
+            None => return, // User cancelled
+        };
+
+        let (contents, count) = reg_file::build_reg_file(entries);
+        match reg_file::write_reg_file(&path, &contents) {
+            Ok(()) => {
+                self.set_status(
+                    &format!("Exported {} entries to {}", count, path.display()),
+                    false,
+                );
+            }
+            Err(e) => {
+                self.set_status(&format!("Export failed: {}", e), true);
+            }
+        }
+    }
+
+    fn write_startup_apps_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        let entries = self.tab_entries(Tab::StartupApps);
+
+        let extra = enrichment::columns();
+        self.write_csv_header(
+            Tab::StartupApps,
+            file,
+            &format!("Name,Product Name,Command,Source,User,Status,State,Runs As,Visible As,Last Ran,Seen Running,Task Author,Task Registered,Script Payload,Bitness,Orphaned By{}", csv_extra_header(&extra)),
+        )?;
 
         for entry in &entries {
             let source = entry.source.display_location();
@@ -407,18 +2675,36 @@ impl StartupApp {
                 Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
                 None => String::new(),
             };
+            let seen_running = entry.run_history.map(|h| h.ratio()).unwrap_or_default();
+            let (task_author, task_registered) = match &entry.task_registration {
+                Some(reg) => (reg.author.clone(), reg.registered_text()),
+                None => (String::new(), String::new()),
+            };
+            let script_payload = entry
+                .script_payload
+                .as_ref()
+                .map(|s| s.payload.as_str())
+                .unwrap_or_default();
             writeln!(
                 file,
-                "{},{},{},{},{},{},{},{},{}",
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}{}",
                 csv_escape(&entry.name),
                 csv_escape(&entry.product_name),
                 csv_escape(&entry.command),
                 csv_escape(&source),
+                csv_escape(entry.profile_user()),
                 entry.enabled,
                 entry.run_state,
                 csv_escape(&entry.runs_as),
                 visible_as,
                 last_ran,
+                seen_running,
+                csv_escape(&task_author),
+                task_registered,
+                csv_escape(script_payload),
+                entry.bitness.map(|b| b.to_string()).unwrap_or_default(),
+                csv_escape(entry.orphaned_by.as_deref().unwrap_or_default()),
+                csv_extra_values(&extra, &entry.enrichment),
             )
             .map_err(|e| e.to_string())?;
         }
@@ -426,12 +2712,18 @@ impl StartupApp {
         Ok(entries.len())
     }
 
-    fn write_services_csv(&self, path: &std::path::Path) -> Result<usize, String> {
-        let entries = self.active_entries();
-        let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    fn write_services_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        let entries = self.tab_entries(Tab::Services);
 
-        writeln!(file, "Name,Product Name,Command,Status,State,Runs As,Visible As,Last Started")
-            .map_err(|e| e.to_string())?;
+        let extra = enrichment::columns();
+        self.write_csv_header(
+            Tab::Services,
+            file,
+            &format!(
+                "Name,Product Name,Command,Status,State,PID,CPU %,Memory,Runs As,Visible As,Last Started,Installed,Bitness,Orphaned By{}",
+                csv_extra_header(&extra)
+            ),
+        )?;
 
         for entry in &entries {
             let visible_as = if entry.requires_admin { "Admin" } else { "User" };
@@ -439,17 +2731,29 @@ impl StartupApp {
                 Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
                 None => String::new(),
             };
+            let installed = match entry.installed {
+                Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
+                None => String::new(),
+            };
+            let usage = entry.host_usage;
             writeln!(
                 file,
-                "{},{},{},{},{},{},{},{}",
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}{}",
                 csv_escape(&entry.name),
                 csv_escape(&entry.product_name),
                 csv_escape(&entry.command),
                 entry.enabled,
                 entry.run_state,
+                entry.pid.map(|p| p.to_string()).unwrap_or_default(),
+                usage.map(|u| format!("{:.1}", u.cpu_usage)).unwrap_or_default(),
+                usage.map(|u| format_memory_csv(u.memory_bytes)).unwrap_or_default(),
                 csv_escape(&entry.runs_as),
                 visible_as,
                 last_started,
+                installed,
+                entry.bitness.map(|b| b.to_string()).unwrap_or_default(),
+                csv_escape(entry.orphaned_by.as_deref().unwrap_or_default()),
+                csv_extra_values(&extra, &entry.enrichment),
             )
             .map_err(|e| e.to_string())?;
         }
@@ -457,24 +2761,37 @@ impl StartupApp {
         Ok(entries.len())
     }
 
-    fn write_processes_csv(&self, path: &std::path::Path) -> Result<usize, String> {
-        let rows = processes::build_visible_tree(
-            &self.all_processes,
-            &self.expanded_pids,
-            self.hide_windows_processes,
-        );
-        let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    fn write_processes_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        let procs = self.visible_processes();
+        let rows = if self.sort_processes_by_start {
+            processes::build_start_order_list(&procs, self.hide_windows_processes)
+        } else {
+            processes::build_visible_tree(
+                &procs,
+                &self.expanded_pids,
+                self.hide_windows_processes,
+                false,
+                false,
+            )
+        };
 
-        writeln!(file, "PID,Parent PID,Name,Product Name,Path,CPU %,Memory,Disk Read,Disk Write,Start Time")
-            .map_err(|e| e.to_string())?;
+        let extra = enrichment::columns();
+        self.write_csv_header(
+            Tab::Processes,
+            file,
+            &format!("PID,Parent PID,Name,Product Name,Path,CPU %,Kernel Time,User Time,CPU Time,Memory,Disk Read,Disk Write,Start Time,Since Boot,Built,Bitness,Platform,Image Mismatch{}", csv_extra_header(&extra)),
+        )?;
 
         for row in &rows {
-            let proc = row.process;
+            let proc = &row.process;
             let ppid = proc
                 .parent_pid
                 .map(|p| p.to_string())
                 .unwrap_or_default();
             let cpu = format!("{:.1}", proc.cpu_usage);
+            let cpu_time = |time: Option<std::time::Duration>| {
+                time.map(processes::format_cpu_time).unwrap_or_default()
+            };
             let memory = format_memory_csv(proc.memory_bytes);
             let disk_read = format_memory_csv(proc.disk_read_bytes);
             let disk_write = format_memory_csv(proc.disk_write_bytes);
@@ -482,99 +2799,302 @@ impl StartupApp {
                 Some(dt) => dt.format("%Y-%m-%d %H:%M:%S").to_string(),
                 None => String::new(),
             };
+            let since_boot = proc
+                .since_boot
+                .map(processes::format_since_boot)
+                .unwrap_or_default();
             writeln!(
                 file,
-                "{},{},{},{},{},{},{},{},{},{}",
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{},{}{}",
                 proc.pid,
                 ppid,
                 csv_escape(&proc.name),
                 csv_escape(&proc.product_name),
                 csv_escape(&proc.exe_path),
                 cpu,
+                cpu_time(proc.cpu_times.map(|t| t.kernel)),
+                cpu_time(proc.cpu_times.map(|t| t.user)),
+                cpu_time(proc.cpu_times.map(|t| t.total())),
                 memory,
                 disk_read,
                 disk_write,
                 start_time,
+                since_boot,
+                proc.link_time.map(|t| t.to_string()).unwrap_or_default(),
+                proc.bitness.map(|b| b.to_string()).unwrap_or_default(),
+                proc.platform.map(|p| p.label()).unwrap_or_default(),
+                csv_escape(&match &proc.image_mismatch {
+                    Some(ImageMismatch::Unmapped) => "Unmapped".to_string(),
+                    Some(ImageMismatch::OtherFile(path)) => path.clone(),
+                    None => String::new(),
+                }),
+                csv_extra_values(&extra, &proc.enrichment),
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(rows.len())
+    }
+
+    fn write_installed_apps_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        self.write_csv_header(
+            Tab::Installed,
+            file,
+            "Name,Publisher,Version,Install Date,Size (KB),Uninstall Command,Modify Path,Install Location,MSI Product Code",
+        )?;
+
+        let apps = self.visible_installed_apps();
+        for app in &apps {
+            let modify = app.modify_path.as_deref().unwrap_or("");
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{}",
+                csv_escape(&app.display_name),
+                csv_escape(&app.publisher),
+                csv_escape(&app.display_version),
+                csv_escape(&app.install_date),
+                app.estimated_size_kb,
+                csv_escape(&app.uninstall_string),
+                csv_escape(modify),
+                csv_escape(&app.install_location),
+                app.msi_product_code.as_deref().unwrap_or(""),
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(apps.len())
+    }
+
+    fn write_execution_history_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        self.write_csv_header(
+            Tab::ExecutionHistory,
+            file,
+            "Name,Publisher,Product Name,Version,First Seen,File Modified,Shimcache Position,SHA-1,Startup Entry,Service,Running,Path",
+        )?;
+
+        let records = self.visible_execution_history();
+        let time = |dt: Option<chrono::DateTime<chrono::Local>>| {
+            dt.map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string()).unwrap_or_default()
+        };
+        for record in &records {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                csv_escape(&record.name),
+                csv_escape(&record.publisher),
+                csv_escape(&record.product_name),
+                csv_escape(&record.version),
+                time(record.first_seen),
+                time(record.file_modified),
+                record.shimcache_position.map(|p| (p + 1).to_string()).unwrap_or_default(),
+                record.sha1.as_deref().unwrap_or(""),
+                record.is_startup_entry,
+                record.is_service,
+                record.is_running,
+                csv_escape(&record.path),
+            )
+            .map_err(|e| e.to_string())?;
+        }
+
+        Ok(records.len())
+    }
+
+    fn write_dev_packages_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        self.write_csv_header(Tab::DevPackages, file, "Name,Version,Manager,Location,Uninstall Command")?;
+
+        let packages = self.visible_dev_packages();
+        for package in &packages {
+            writeln!(
+                file,
+                "{},{},{},{},{}",
+                csv_escape(&package.name),
+                csv_escape(&package.version),
+                package.manager.label(),
+                csv_escape(&package.location),
+                csv_escape(&dev_packages::command_line(package)),
             )
             .map_err(|e| e.to_string())?;
         }
 
-        Ok(rows.len())
+        Ok(packages.len())
     }
 
-    fn write_installed_apps_csv(&self, path: &std::path::Path) -> Result<usize, String> {
-        let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
-
-        writeln!(
-            file,
-            "Name,Publisher,Version,Install Date,Size (KB),Uninstall Command,Modify Path,Install Location"
-        )
-        .map_err(|e| e.to_string())?;
+    fn write_security_csv(&self, file: &mut dyn Write) -> Result<usize, String> {
+        self.write_csv_header(Tab::Security, file, "Component,Status,Details")?;
 
-        for app in &self.installed_apps {
-            let modify = app.modify_path.as_deref().unwrap_or("");
+        for item in &self.security_status {
             writeln!(
                 file,
-                "{},{},{},{},{},{},{},{}",
-                csv_escape(&app.display_name),
-                csv_escape(&app.publisher),
-                csv_escape(&app.display_version),
-                csv_escape(&app.install_date),
-                app.estimated_size_kb,
-                csv_escape(&app.uninstall_string),
-                csv_escape(modify),
-                csv_escape(&app.install_location),
+                "{},{},{}",
+                csv_escape(&item.name),
+                item.state,
+                csv_escape(&item.detail),
             )
             .map_err(|e| e.to_string())?;
         }
 
-        Ok(self.installed_apps.len())
+        Ok(self.security_status.len())
     }
 }
 
 impl eframe::App for StartupApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         // Force dark mode every frame (overrides any persisted theme)
         ctx.set_visuals(egui::Visuals::dark());
+        self.tour_anchors = help::Anchors::default();
+
+        // Results of changes made on the executor thread
+        let repaint_ctx = ctx.clone();
+        self.executor
+            .get_or_insert_with(|| executor::Executor::start(move || repaint_ctx.request_repaint()));
+        self.poll_executor();
+        if self.poll_clean_boot() {
+            // Keep polling while the restore point is created
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+        if self.running_jobs.iter().any(|j| j.started.is_some()) {
+            // Keep the elapsed time in the status bar ticking
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+
+        // Rescan when an installer finishes, so the entries it added show up
+        let repaint_ctx = ctx.clone();
+        let tracker = self
+            .install_tracker
+            .get_or_insert_with(|| install_watch::InstallTracker::start(move || repaint_ctx.request_repaint()));
+        if let Some(run) = tracker.poll().pop() {
+            self.rescan_pending = true;
+            self.set_status(
+                &format!("{} finished installing; rescanning for new startup entries", run.description),
+                false,
+            );
+        }
+        if self.rescan_pending && !self.loading && self.unfinished_jobs() == 0 {
+            self.rescan_pending = false;
+            self.start_background_load();
+        }
+
+        // Re-check entries on drives that were just plugged in or removed
+        let repaint_ctx = ctx.clone();
+        let watcher = self
+            .drive_watcher
+            .get_or_insert_with(|| drive_watch::watch(move || repaint_ctx.request_repaint()));
+        let drive_changes: Vec<drive_watch::DriveChange> = watcher.changes.try_iter().collect();
+        for change in drive_changes {
+            self.revalidate_drive(change);
+        }
 
-        // Check for background load completion
+        // Apply collector results as they arrive
         if let Some(rx) = &self.load_receiver {
-            if let Ok(result) = rx.try_recv() {
-                self.entries = result.entries;
-                self.all_services = result.all_services;
-                self.all_processes = result.all_processes;
-                self.installed_apps = result.installed_apps;
-                // Auto-expand all processes that have children
-                self.expanded_pids = processes::parent_pids(&self.all_processes);
-                self.is_admin = result.is_admin;
+            let messages: Vec<LoadMessage> = rx.try_iter().collect();
+            let services_loaded = messages.iter().any(|m| matches!(m, LoadMessage::Services(..)));
+            for message in messages {
+                self.apply_load_message(message);
+            }
+            if services_loaded {
+                self.watch_services(ctx);
+            }
+            if self.loading_tabs.is_empty() {
                 self.loading = false;
                 self.load_receiver = None;
-                self.last_process_refresh = Instant::now();
-                self.selected_row = None;
-                self.hovered_row = None;
+                self.drain_action_queue();
+            } else {
+                // Show each tab as soon as its collector finishes
+                ctx.request_repaint_after(std::time::Duration::from_millis(100));
             }
         }
 
+        self.apply_service_changes();
+        self.apply_prefetch_updates(ctx);
+        // Exports wait for Prefetch too, so they include its last-ran times
+        if !self.loading && self.prefetch_receiver.is_none() && std::mem::take(&mut self.export_all_pending) {
+            self.export_all();
+        }
+
         // Fire rescan after uninstaller process exits
         if let Some(rx) = &self.rescan_receiver {
-            if rx.try_recv().is_ok() {
+            if let Ok((registry_key, name, outcome)) = rx.try_recv() {
                 self.rescan_receiver = None;
                 self.start_background_load();
+                let result = format!("Uninstaller {}", outcome.describe());
+                let step_status = if outcome.is_error() || outcome == uninstaller::UninstallOutcome::TimedOut {
+                    plan::StepStatus::Failed(result)
+                } else {
+                    plan::StepStatus::Done(result)
+                };
+                self.finish_uninstall_step(&registry_key, step_status);
+                if outcome != uninstaller::UninstallOutcome::Untracked {
+                    self.set_status(
+                        &format!("Uninstaller for '{}' {}", name, outcome.describe()),
+                        outcome.is_error(),
+                    );
+                }
             } else {
                 // Keep polling while waiting for the uninstaller to finish
                 ctx.request_repaint_after(std::time::Duration::from_millis(500));
             }
         }
 
+        // Report a finished Explorer restart
+        if let Some(rx) = &self.explorer_restart_receiver {
+            if let Ok(result) = rx.try_recv() {
+                self.explorer_restart_receiver = None;
+                match result {
+                    Ok(()) => self.set_status("Explorer restarted", false),
+                    Err(e) => self.set_status(&format!("Failed to restart Explorer: {}", e), true),
+                }
+                self.start_process_refresh();
+            } else {
+                ctx.request_repaint_after(std::time::Duration::from_millis(250));
+            }
+        }
+
+        // Periodic keep-disabled enforcement
+        if let Some(rx) = &self.enforcement_receiver {
+            if let Ok(records) = rx.try_recv() {
+                self.enforcement_receiver = None;
+                self.report_enforcement(records);
+            }
+        }
+        self.run_pending_enforcement();
+        let interval = self.settings.enforce_interval_minutes;
+        if interval > 0 {
+            if !self.loading
+                && self.enforcement_receiver.is_none()
+                && self.last_enforcement.elapsed().as_secs() >= u64::from(interval) * 60
+            {
+                self.last_enforcement = Instant::now();
+                if !self.watchlist.keep_disabled_keys().is_empty() {
+                    let (tx, rx) = mpsc::channel();
+                    self.enforcement_receiver = Some(rx);
+                    let settings = self.settings.clone();
+                    let dry_run = self.dry_run;
+                    std::thread::spawn(move || {
+                        let _ = tx.send(enforcement::run_pass(&settings, dry_run));
+                    });
+                }
+            }
+            ctx.request_repaint_after(std::time::Duration::from_secs(30));
+        }
+
         // Check for process-only refresh completion (auto-refresh, no overlay)
         if let Some(rx) = &self.process_refresh_receiver {
             if let Ok(new_procs) = rx.try_recv() {
-                self.all_processes = new_procs;
-                self.expanded_pids = processes::parent_pids(&self.all_processes);
+                // While frozen, keep collecting but hold the snapshot back
+                if self.process_view_frozen(ctx) {
+                    self.frozen_pending_processes = Some(new_procs);
+                } else {
+                    self.apply_process_snapshot(new_procs);
+                }
                 self.last_process_refresh = Instant::now();
                 self.process_refresh_receiver = None;
             }
         }
+        if self.frozen_pending_processes.is_some() && !self.process_view_frozen(ctx) {
+            if let Some(procs) = self.frozen_pending_processes.take() {
+                self.apply_process_snapshot(procs);
+            }
+        }
 
         // Auto-refresh processes every 3 seconds when enabled and on the Processes tab
         if self.auto_refresh_processes && self.active_tab == Tab::Processes {
@@ -585,6 +3105,8 @@ impl eframe::App for StartupApp {
             ctx.request_repaint_after(std::time::Duration::from_secs(1));
         }
 
+        self.update_taskbar(ctx, frame);
+
         // Draw a border around the entire window
         let window_rect = ctx.input(|i| i.viewport_rect());
         let painter = ctx.layer_painter(egui::LayerId::new(
@@ -670,26 +3192,46 @@ impl eframe::App for StartupApp {
             let any_widget_hovered = ui.horizontal(|ui| {
                 let mut hovered = false;
 
-                // Disable tabs and action buttons while loading (window controls stay enabled)
-                if self.loading {
-                    ui.disable();
-                }
-
                 // Tab definitions
                 let svc_count = self.filtered_service_count();
                 let proc_count = self.filtered_process_count();
-                let tabs: &[(Tab, String)] = &[
-                    (Tab::Installed, format!("Installed Apps: {}", self.installed_apps.len())),
-                    (Tab::StartupApps, format!("Startup Apps: {}", self.entries.len())),
-                    (Tab::Processes, format!("Processes: {}", proc_count)),
-                    (Tab::Services, format!("Services: {}", svc_count)),
+                // Tabs still loading show an ellipsis instead of a count
+                let count = |tab: Tab, n: usize| {
+                    if self.loading_tabs.contains(&tab) {
+                        "\u{2026}".to_string()
+                    } else {
+                        n.to_string()
+                    }
+                };
+                let mut tabs: Vec<(Tab, String)> = vec![
+                    (Tab::Installed, format!("Installed Apps: {}", count(Tab::Installed, self.installed_apps.len()))),
+                    (Tab::StartupApps, format!("Startup Apps: {}", count(Tab::StartupApps, self.entries.len()))),
+                    (Tab::Processes, format!("Processes: {}", count(Tab::Processes, proc_count))),
+                    (Tab::Services, format!("Services: {}", count(Tab::Services, svc_count))),
+                    (
+                        Tab::ExecutionHistory,
+                        format!(
+                            "Execution History: {}",
+                            count(Tab::ExecutionHistory, self.execution_history.records.len())
+                        ),
+                    ),
+                    (Tab::Security, "Security".to_string()),
                 ];
+                if self.settings.scan_scope.dev_packages {
+                    tabs.insert(
+                        1,
+                        (
+                            Tab::DevPackages,
+                            format!("Dev Packages: {}", count(Tab::DevPackages, self.dev_packages.packages.len())),
+                        ),
+                    );
+                }
 
                 let selected_bg = egui::Color32::from_rgb(50, 50, 55);
                 let hover_bg = egui::Color32::from_rgb(45, 45, 50);
                 let accent = egui::Color32::from_rgb(100, 140, 200);
 
-                for (tab, label) in tabs {
+                for (tab, label) in &tabs {
                     let is_selected = self.active_tab == *tab;
                     let text_color = if is_selected {
                         egui::Color32::WHITE
@@ -742,7 +3284,11 @@ impl eframe::App for StartupApp {
                         resp
                     });
 
-                    let resp = r.inner;
+                    let mut resp = r.inner;
+                    self.tour_anchors.extend(help::Target::Tabs, resp.rect);
+                    if *tab == Tab::StartupApps && !self.scanners.is_empty() {
+                        resp = resp.on_hover_text(collector::summary(&self.scanners));
+                    }
                     hovered |= resp.hovered();
                     if resp.clicked() && self.active_tab != *tab {
                         self.active_tab = *tab;
@@ -762,6 +3308,30 @@ impl eframe::App for StartupApp {
                         self.selected_row = None;
                         self.hovered_row = None;
                     }
+                    let r = ui
+                        .add_enabled(!self.loading, egui::Button::new("Hardening..."))
+                        .on_hover_text("Suggest manual or delayed start for third-party services, from their run history and memory use");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.service_hardening = Some(dialogs::ServiceHardeningInfo {
+                            staging: self.staging_plan,
+                            dry_run: self.dry_run,
+                            ..Default::default()
+                        });
+                    }
+                    hovered |= self.orphans_button(ui).hovered();
+                    ui.separator();
+                }
+
+                // Checkbox for installed apps tab
+                if self.active_tab == Tab::Installed {
+                    let r = ui.checkbox(&mut self.group_suites, "Group Suites")
+                        .on_hover_text("Collapse entries of the same product family (Visual C++ Redistributables, SQL Server, SDKs) into one row with their combined size");
+                    hovered |= r.hovered();
+                    if r.changed() {
+                        self.selected_row = None;
+                        self.hovered_row = None;
+                    }
                     ui.separator();
                 }
 
@@ -773,8 +3343,60 @@ impl eframe::App for StartupApp {
                         self.selected_row = None;
                         self.hovered_row = None;
                     }
+                    let r = ui.checkbox(&mut self.group_processes_by_user, "Group by User");
+                    hovered |= r.hovered();
+                    if r.changed() {
+                        self.selected_row = None;
+                        self.hovered_row = None;
+                    }
+                    let r = ui.checkbox(&mut self.group_svchost, "Group svchost")
+                        .on_hover_text("Collapse all svchost.exe instances into a single node");
+                    hovered |= r.hovered();
+                    if r.changed() {
+                        self.selected_row = None;
+                        self.hovered_row = None;
+                    }
+                    let r = ui.checkbox(&mut self.group_platforms, "Group WSL/Containers")
+                        .on_hover_text("Collapse WSL, Docker Desktop, and Windows Sandbox processes (including their vmmem VM) into one node per platform");
+                    hovered |= r.hovered();
+                    if r.changed() {
+                        self.selected_row = None;
+                        self.hovered_row = None;
+                    }
+                    let r = ui.checkbox(&mut self.show_cpu_time, "CPU Time")
+                        .on_hover_text("Show total CPU time used since each process started (kernel + user)");
+                    hovered |= r.hovered();
                     let r = ui.checkbox(&mut self.auto_refresh_processes, "Auto-Refresh");
                     hovered |= r.hovered();
+                    if r.changed() && !self.auto_refresh_processes {
+                        self.process_diff = processes::ProcessDiff::default();
+                    }
+                    let freeze_label = if self.freeze_processes && self.frozen_pending_processes.is_some() {
+                        "Frozen (new data)"
+                    } else if self.freeze_processes {
+                        "Frozen"
+                    } else {
+                        "Freeze"
+                    };
+                    let r = ui
+                        .selectable_label(self.freeze_processes, freeze_label)
+                        .on_hover_text(
+                            "Stop updating the list while you read it; data keeps being collected and is shown when you unfreeze",
+                        );
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.freeze_processes = !self.freeze_processes;
+                    }
+                    let r = ui
+                        .add_enabled(
+                            self.explorer_restart_receiver.is_none(),
+                            egui::Button::new("Restart Explorer"),
+                        )
+                        .on_hover_text("Restart the Windows shell (taskbar and desktop) in this session");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.start_explorer_restart();
+                    }
                     ui.separator();
                 }
 
@@ -789,6 +3411,90 @@ impl eframe::App for StartupApp {
                 if r.clicked() {
                     self.export_csv();
                 }
+                let r = ui
+                    .add_enabled(!self.loading, egui::Button::new("Export All"))
+                    .on_hover_text("Export every tab as CSV files in one .zip");
+                hovered |= r.hovered();
+                if r.clicked() {
+                    self.export_all();
+                }
+                let menu = ui
+                    .add_enabled_ui(!self.loading, |ui| {
+                        ui.menu_button("Image", |ui| {
+                            if ui.button("Copy to Clipboard").clicked() {
+                                self.export_view_image(ui.ctx(), false);
+                            }
+                            if ui.button("Save as PNG...").clicked() {
+                                self.export_view_image(ui.ctx(), true);
+                            }
+                        })
+                    })
+                    .inner;
+                let r = menu
+                    .response
+                    .on_hover_text("Draw every row of this tab (as exported to CSV) into an image for a ticket or report");
+                hovered |= r.hovered();
+                if self.active_tab == Tab::StartupApps {
+                    let r = ui
+                        .add_enabled(!self.loading, egui::Button::new("Export .reg"))
+                        .on_hover_text("Save the visible registry entries as a .reg restoration file");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.export_all_reg();
+                    }
+                    let r = ui
+                        .add_enabled(!self.loading, egui::Button::new("Categories..."))
+                        .on_hover_text("Updaters, telemetry, and helpers grouped for disabling in bulk");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.category_cleanup = Some(self.category_cleanup_info());
+                    }
+                    let r = ui
+                        .add_enabled(
+                            !self.loading && self.pending_clean_boot.is_none(),
+                            egui::Button::new("Clean Boot..."),
+                        )
+                        .on_hover_text("Disable all non-Microsoft services and startup items to troubleshoot, msconfig style");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.clean_boot = Some(self.clean_boot_info());
+                    }
+                    hovered |= self.orphans_button(ui).hovered();
+                    let r = ui
+                        .button("Restore...")
+                        .on_hover_text("Restore deleted startup folder shortcuts from the backup store");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.restore_backups = Some(dialogs::RestoreBackupsInfo {
+                            items: backup::list(),
+                            restore_requested: None,
+                            discard_requested: None,
+                        });
+                    }
+                    let r = ui
+                        .button("Add...")
+                        .on_hover_text("Add a program to a Run key or the Startup folder");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.add_startup_entry = Some(dialogs::AddStartupEntryInfo {
+                            name: String::new(),
+                            command: String::new(),
+                            location: actions::NewEntryLocation::UserRun,
+                        });
+                    }
+                    let r = ui
+                        .button("Rules...")
+                        .on_hover_text("Entries kept disabled automatically, and what was enforced");
+                    hovered |= r.hovered();
+                    if r.clicked() {
+                        self.keep_disabled_rules = Some(dialogs::KeepDisabledRulesInfo {
+                            rules: self.watchlist.keep_disabled_rules(),
+                            activity: enforcement::read_log(100),
+                            remove_requested: None,
+                            copy_monitor_command_requested: false,
+                        });
+                    }
+                }
 
                 ui.separator();
 
@@ -801,6 +3507,7 @@ impl eframe::App for StartupApp {
                         )
                         .sense(egui::Sense::click_and_drag()),
                     );
+                    self.tour_anchors.set(help::Target::AdminIndicator, r.rect);
                     if r.drag_started() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
                     } else if r.double_clicked() {
@@ -815,6 +3522,7 @@ impl eframe::App for StartupApp {
                         )
                         .sense(egui::Sense::click_and_drag()),
                     );
+                    self.tour_anchors.set(help::Target::AdminIndicator, r.rect);
                     if r.drag_started() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::StartDrag);
                     } else if r.double_clicked() {
@@ -822,6 +3530,7 @@ impl eframe::App for StartupApp {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Maximized(!is_max));
                     }
                     let r = ui.button("Restart as Admin");
+                    self.tour_anchors.extend(help::Target::AdminIndicator, r.rect);
                     hovered |= r.hovered();
                     if r.clicked() {
                         // Save current task paths so admin mode can detect truly new entries
@@ -871,9 +3580,75 @@ impl eframe::App for StartupApp {
             }
         });
 
+        // Quick filter chips for the active tab (the Security tab has none)
+        if filters::QuickFilter::ALL.iter().any(|f| f.applies_to(self.active_tab)) {
+            egui::TopBottomPanel::top("filter_chips").show(ctx, |ui| {
+                self.tour_anchors.set(help::Target::Filters, ui.max_rect());
+                if self.loading_tabs.contains(&self.active_tab) {
+                    ui.disable();
+                }
+                ui.horizontal(|ui| {
+                    let tab = self.active_tab;
+                    ui.label(egui::RichText::new("Filters:").color(egui::Color32::GRAY));
+                    for filter in filters::QuickFilter::ALL {
+                        if !filter.applies_to(tab) {
+                            continue;
+                        }
+                        let active = self.quick_filters.is_active(tab, filter);
+                        let r = ui
+                            .selectable_label(active, filter.label())
+                            .on_hover_text(filter.tooltip(tab));
+                        if r.clicked() {
+                            self.quick_filters.toggle(tab, filter);
+                            self.selected_row = None;
+                            self.hovered_row = None;
+                        }
+                    }
+                    ui.separator();
+                    let r = ui
+                        .selectable_label(self.column_filters.visible, "Column filters")
+                        .on_hover_text(
+                            "Show a filter box under each column header; \
+                             filled-in boxes must all match (e.g. > 100 MB, = Running)",
+                        );
+                    if r.clicked() {
+                        self.column_filters.visible = !self.column_filters.visible;
+                        self.selected_row = None;
+                        self.hovered_row = None;
+                    }
+                    if self.quick_filters.any_active(tab) || self.column_filters.any_active(tab) {
+                        ui.separator();
+                        if ui.small_button("Clear").clicked() {
+                            self.quick_filters.clear(tab);
+                            self.column_filters.clear(tab);
+                            self.selected_row = None;
+                            self.hovered_row = None;
+                        }
+                    }
+                });
+            });
+        }
+
         // Bottom panel: status bar
         egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
             ui.horizontal(|ui| {
+                // The change the executor is working on, and how many wait
+                if let Some(job) = self.running_jobs.iter().find(|j| j.started.is_some()) {
+                    ui.spinner();
+                    let elapsed = job.started.map_or(0, |t| t.elapsed().as_secs());
+                    let mut text = format!("{} ({} s)", job.job.label(), elapsed);
+                    let queued = self.running_jobs.len() - 1;
+                    if queued > 0 {
+                        text.push_str(&format!(", {} more queued", queued));
+                    }
+                    let color = if job.timed_out {
+                        egui::Color32::from_rgb(230, 160, 50)
+                    } else {
+                        egui::Color32::from_rgb(100, 160, 230)
+                    };
+                    ui.colored_label(color, text);
+                    ui.separator();
+                }
                 if let Some(status) = &self.status {
                     // Auto-expire after 8 seconds
                     if status.when.elapsed().as_secs() < 8 {
@@ -885,6 +3660,23 @@ impl eframe::App for StartupApp {
                         ui.colored_label(color, &status.text);
                     }
                 }
+                // "3 services could not be read (access denied)" for the active tab
+                let skipped_lines = self.skipped.get(&self.active_tab).map(|s| s.summary_lines()).unwrap_or_default();
+                if let Some(first) = skipped_lines.first() {
+                    let text = if skipped_lines.len() > 1 {
+                        format!("\u{26A0} {} (+{} more)", first, skipped_lines.len() - 1)
+                    } else {
+                        format!("\u{26A0} {}", first)
+                    };
+                    let link = ui
+                        .add(egui::Link::new(
+                            egui::RichText::new(text).small().color(egui::Color32::from_rgb(230, 160, 50)),
+                        ))
+                        .on_hover_text("The list may be incomplete; click for details");
+                    if link.clicked() {
+                        self.skipped_dialog = Some(self.active_tab);
+                    }
+                }
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     let link = ui.add(
                         egui::Link::new(
@@ -894,27 +3686,103 @@ impl eframe::App for StartupApp {
                     if link.clicked() {
                         self.show_about = true;
                     }
+                    let menu = ui.menu_button(egui::RichText::new("Help").small(), |ui| {
+                        if ui.button("Guided tour").clicked() {
+                            self.tour = Some(help::Tour::default());
+                        }
+                        if ui.button("What do the actions do?").clicked() {
+                            self.show_action_help = true;
+                        }
+                        if ui.button("About App Manager").clicked() {
+                            self.show_about = true;
+                        }
+                    });
+                    self.tour_anchors.set(help::Target::HelpMenu, menu.response.rect);
+                    let link = ui.add(egui::Link::new(egui::RichText::new("Settings").small()));
+                    if link.clicked() {
+                        self.settings_draft = Some(self.settings.clone());
+                    }
+                    let link = ui
+                        .add(egui::Link::new(egui::RichText::new("Vendors").small()))
+                        .on_hover_text("Installed apps, services, startup entries, and memory per vendor");
+                    if link.clicked() {
+                        self.vendor_summary = Some(dialogs::VendorSummaryInfo {
+                            vendors: vendors::summarize(
+                                &self.installed_apps,
+                                &self.entries,
+                                &self.all_services,
+                                &self.all_processes,
+                            ),
+                            hide_microsoft: false,
+                        });
+                    }
+                    let link = ui
+                        .add(egui::Link::new(egui::RichText::new("Audit").small()))
+                        .on_hover_text("Every change made with App Manager on this machine, for export");
+                    if link.clicked() {
+                        self.audit_trail = Some(dialogs::AuditTrailInfo {
+                            records: audit::read_log(),
+                            export_requested: None,
+                        });
+                    }
+                    let dry_run_label = if self.dry_run {
+                        egui::RichText::new(format!("Dry run ({})", self.dry_run_log.len()))
+                            .small()
+                            .color(egui::Color32::from_rgb(230, 160, 50))
+                    } else {
+                        egui::RichText::new("Dry run").small()
+                    };
+                    let link = ui
+                        .add(egui::Link::new(dry_run_label))
+                        .on_hover_text("Report what actions would change instead of changing anything");
+                    if link.clicked() {
+                        self.show_dry_run_log = true;
+                    }
+                    let plan_label = if self.staging_plan {
+                        format!("Plan ({}, staging)", self.cleanup_plan.steps.len())
+                    } else {
+                        format!("Plan ({})", self.cleanup_plan.steps.len())
+                    };
+                    let link = ui
+                        .add(egui::Link::new(egui::RichText::new(plan_label).small()))
+                        .on_hover_text("Stage changes into a cleanup plan to export, review, and run step by step");
+                    if link.clicked() {
+                        self.cleanup_plan_dialog = Some(dialogs::CleanupPlanInfo::default());
+                    }
+                    if let Some(log) = self.last_uninstall_log.clone() {
+                        let link = ui
+                            .add(egui::Link::new(egui::RichText::new("Open uninstall log").small()))
+                            .on_hover_text(log.display().to_string());
+                        if link.clicked() {
+                            if let Err(e) = std::process::Command::new("notepad.exe").arg(&log).spawn() {
+                                self.set_status(&format!("Failed to open log: {}", e), true);
+                            }
+                        }
+                    }
                 });
             });
         });
 
         // Central panel: table with horizontal + vertical scrolling
         egui::CentralPanel::default().show(ctx, |ui| {
-            // Disable content interaction while loading/scanning
-            if self.loading {
-                ui.disable();
+            self.tour_anchors.set(help::Target::Table, ui.max_rect());
+            // Show a loading indicator until this tab's collector reports back
+            if self.loading_tabs.contains(&self.active_tab) {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(40.0);
+                    ui.spinner();
+                    ui.add_space(8.0);
+                    ui.label("Loading...");
+                });
+                ctx.request_repaint();
+                return;
             }
 
             // Use solid (non-floating) horizontal scrollbar so it has
             // dedicated space just above the status bar.
             ui.style_mut().spacing.scroll.floating = false;
 
-            // Hide scrollbars until data is loaded
-            let scroll_visibility = if self.loading {
-                egui::scroll_area::ScrollBarVisibility::AlwaysHidden
-            } else {
-                egui::scroll_area::ScrollBarVisibility::VisibleWhenNeeded
-            };
+            let scroll_visibility = egui::scroll_area::ScrollBarVisibility::VisibleWhenNeeded;
 
             match self.active_tab {
                 Tab::StartupApps | Tab::Services => {
@@ -925,17 +3793,38 @@ impl eframe::App for StartupApp {
                         _ => unreachable!(),
                     };
 
+                    let scroll_to = self.take_restore_scroll();
                     egui::ScrollArea::horizontal()
                         .scroll_bar_visibility(scroll_visibility)
                         .auto_shrink(false)
                         .show(ui, |ui| {
                         let show_delete = self.active_tab == Tab::StartupApps;
                         let show_properties = true;
-                        let result = table::render_table(ui, &visible_entries, self.selected_row, self.hovered_row, col3_header, last_time_header, show_delete, show_properties);
+                        let usage_thresholds = (self.active_tab == Tab::Services).then_some(&self.settings.thresholds);
+                        let result = table::render_table(
+                            ui,
+                            &visible_entries,
+                            self.selected_row,
+                            self.hovered_row,
+                            col3_header,
+                            last_time_header,
+                            show_delete,
+                            show_properties,
+                            usage_thresholds,
+                            scroll_to,
+                            self.column_filters.row(self.active_tab),
+                            self.entry_sort,
+                        );
                         self.hovered_row = result.hovered_row;
+                        self.scroll_offset = result.scroll_offset;
                         if let Some(clicked) = result.clicked_row {
                             self.selected_row = Some(clicked);
                         }
+                        if let Some(sort) = result.sort_clicked {
+                            self.entry_sort = if self.entry_sort == sort { table::EntrySort::Scan } else { sort };
+                            self.selected_row = None;
+                            self.hovered_row = None;
+                        }
                         if let Some(action) = result.action {
                             match &action {
                                 PendingAction::ConfirmDelete(_) => {
@@ -945,61 +3834,217 @@ impl eframe::App for StartupApp {
                                     self.execute_action(action);
                                 }
                             }
-                        }
-                        // Double-click opens properties dialog
-                        if let Some(index) = result.double_clicked_row {
-                            self.execute_action(PendingAction::Properties(index));
+                        }
+                        // Double-click opens properties dialog
+                        if let Some(entry) = result.double_clicked_row.and_then(|i| visible_entries.get(i)) {
+                            self.execute_action(PendingAction::Properties(entry.id()));
+                        }
+                    });
+                }
+                Tab::Installed => {
+                    let apps = self.visible_installed_apps();
+                    let suites = if self.group_suites { suites::group(&apps) } else { Vec::new() };
+                    let rows = installed_table::table_rows(&apps, &suites, &self.expanded_suites);
+                    let scroll_to = self.take_restore_scroll();
+                    egui::ScrollArea::horizontal()
+                        .scroll_bar_visibility(scroll_visibility)
+                        .auto_shrink(false)
+                        .show(ui, |ui| {
+                        let result = installed_table::render_installed_table(
+                            ui,
+                            &rows,
+                            self.selected_row,
+                            self.hovered_row,
+                            scroll_to,
+                            self.column_filters.row(Tab::Installed),
+                        );
+                        self.hovered_row = result.hovered_row;
+                        self.scroll_offset = result.scroll_offset;
+                        if let Some(clicked) = result.clicked_row {
+                            self.selected_row = Some(clicked);
+                        }
+                        if let Some(action) = result.action {
+                            match action {
+                                installed_table::InstalledAppAction::Modify(key) => {
+                                    if let Some(app) = self.find_installed_app(&key) {
+                                        if let Some(ref path) = app.modify_path {
+                                            let name = app.display_name.clone();
+                                            if self.dry_run {
+                                                let changes = dry_run::launch_elevated(path);
+                                                self.simulate(format!("Modify '{}'", name), Ok(changes));
+                                                return;
+                                            }
+                                            match run_shell_command(path) {
+                                                Ok(()) => self.set_status(
+                                                    &format!("Launched modify for '{}'", name),
+                                                    false,
+                                                ),
+                                                Err(e) => self.set_status(
+                                                    &format!("Failed to modify '{}': {}", name, e),
+                                                    true,
+                                                ),
+                                            }
+                                        }
+                                    }
+                                }
+                                installed_table::InstalledAppAction::ToggleSuite(key) => {
+                                    if !self.expanded_suites.remove(&key) {
+                                        self.expanded_suites.insert(key);
+                                    }
+                                    self.selected_row = None;
+                                    self.hovered_row = None;
+                                }
+                                installed_table::InstalledAppAction::Uninstall(key) => {
+                                    if self.staging_plan {
+                                        let name = self.find_installed_app(&key).map(|a| a.display_name.clone());
+                                        if let Some(name) = name {
+                                            self.stage_step(plan::PlanAction::Uninstall, key, name);
+                                        }
+                                    } else if let Some(app) = self.find_installed_app(&key) {
+                                        self.uninstall_preview = Some(uninstall_preview_from(app));
+                                        self.pending_action = Some(PendingAction::ConfirmUninstall(key));
+                                    }
+                                }
+                            }
+                        }
+                    });
+                }
+                Tab::ExecutionHistory => {
+                    let records = self.visible_execution_history();
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(
+                            egui::RichText::new(
+                                "Programs Windows recorded for application compatibility (Amcache and Shimcache). \
+                                 A row shows Windows saw the file, not that it ran.",
+                            )
+                            .color(egui::Color32::GRAY),
+                        );
+                        for note in &self.execution_history.notes {
+                            ui.label(egui::RichText::new(note).color(egui::Color32::from_rgb(230, 160, 50)));
+                        }
+                    });
+                    let scroll_to = self.take_restore_scroll();
+                    egui::ScrollArea::horizontal()
+                        .scroll_bar_visibility(scroll_visibility)
+                        .auto_shrink(false)
+                        .show(ui, |ui| {
+                        let result = history_table::render_history_table(
+                            ui,
+                            &records,
+                            self.selected_row,
+                            self.hovered_row,
+                            scroll_to,
+                            self.column_filters.row(Tab::ExecutionHistory),
+                        );
+                        self.hovered_row = result.hovered_row;
+                        self.scroll_offset = result.scroll_offset;
+                        if let Some(clicked) = result.clicked_row {
+                            self.selected_row = Some(clicked);
+                        }
+                        match result.action {
+                            Some(history_table::HistoryAction::OpenLocation(path)) => {
+                                if let Err(e) = shell::open_file_location(&path) {
+                                    self.set_status(&format!("Failed to open location: {}", e), true);
+                                }
+                            }
+                            Some(history_table::HistoryAction::CopySha1(hash)) => {
+                                ctx.copy_text(hash);
+                                self.set_status("SHA-1 copied to clipboard", false);
+                            }
+                            None => {}
                         }
                     });
                 }
-                Tab::Installed => {
+                Tab::DevPackages => {
+                    let packages = self.visible_dev_packages();
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(
+                            egui::RichText::new(
+                                "Packages installed globally by pip, npm, cargo, Scoop, and Chocolatey. \
+                                 Uninstall runs the package manager's own uninstall command.",
+                            )
+                            .color(egui::Color32::GRAY),
+                        );
+                        if !self.dev_packages.missing.is_empty() {
+                            let missing: Vec<&str> = self.dev_packages.missing.iter().map(|m| m.label()).collect();
+                            ui.label(
+                                egui::RichText::new(format!("Not found: {}", missing.join(", ")))
+                                    .color(egui::Color32::GRAY),
+                            );
+                        }
+                        for note in &self.dev_packages.notes {
+                            ui.label(egui::RichText::new(note).color(egui::Color32::from_rgb(230, 160, 50)));
+                        }
+                    });
+                    let scroll_to = self.take_restore_scroll();
                     egui::ScrollArea::horizontal()
                         .scroll_bar_visibility(scroll_visibility)
                         .auto_shrink(false)
                         .show(ui, |ui| {
-                        let result = installed_table::render_installed_table(
+                        let result = packages_table::render_packages_table(
                             ui,
-                            &self.installed_apps,
+                            &packages,
                             self.selected_row,
                             self.hovered_row,
+                            scroll_to,
+                            self.column_filters.row(Tab::DevPackages),
                         );
                         self.hovered_row = result.hovered_row;
+                        self.scroll_offset = result.scroll_offset;
                         if let Some(clicked) = result.clicked_row {
                             self.selected_row = Some(clicked);
                         }
-                        if let Some(action) = result.action {
-                            match action {
-                                installed_table::InstalledAppAction::Modify(i) => {
-                                    if let Some(app) = self.installed_apps.get(i) {
-                                        if let Some(ref path) = app.modify_path {
-                                            let name = app.display_name.clone();
-                                            match run_shell_command(path) {
-                                                Ok(()) => self.set_status(
-                                                    &format!("Launched modify for '{}'", name),
-                                                    false,
-                                                ),
-                                                Err(e) => self.set_status(
-                                                    &format!("Failed to modify '{}': {}", name, e),
-                                                    true,
-                                                ),
-                                            }
-                                        }
-                                    }
-                                }
-                                installed_table::InstalledAppAction::Uninstall(i) => {
-                                    self.pending_action = Some(PendingAction::ConfirmUninstall(i));
+                        match result.action {
+                            Some(packages_table::PackageAction::Uninstall(manager, name)) => {
+                                self.package_uninstall = self.find_dev_package(manager, &name).cloned();
+                            }
+                            Some(packages_table::PackageAction::OpenLocation(path)) => {
+                                if let Err(e) = shell::open_file(std::path::Path::new(&path)) {
+                                    self.set_status(&format!("Failed to open location: {}", e), true);
                                 }
                             }
+                            None => {}
                         }
                     });
                 }
+                Tab::Security => {
+                    egui::ScrollArea::vertical()
+                        .auto_shrink(false)
+                        .show(ui, |ui| {
+                            security_panel::render_security_panel(ui, &self.security_status);
+                        });
+                }
                 Tab::Processes => {
-                    let procs = self.all_processes.clone();
-                    let rows = processes::build_visible_tree(
-                        &procs,
-                        &self.expanded_pids,
-                        self.hide_windows_processes,
+                    let mut procs = self.visible_processes();
+                    // Processes that just exited stay listed until the next refresh
+                    procs.extend(
+                        self.process_diff
+                            .exited
+                            .iter()
+                            .filter(|p| self.quick_filters.process_matches(p) && self.column_filters.process_matches(p))
+                            .cloned(),
                     );
+                    let rows = if self.sort_processes_by_start {
+                        // Flat launch-order view; grouping doesn't apply
+                        processes::build_start_order_list(&procs, self.hide_windows_processes)
+                    } else if self.group_processes_by_user {
+                        processes::build_user_grouped_tree(
+                            &procs,
+                            &self.expanded_pids,
+                            self.hide_windows_processes,
+                            self.group_svchost,
+                            self.group_platforms,
+                        )
+                    } else {
+                        processes::build_visible_tree(
+                            &procs,
+                            &self.expanded_pids,
+                            self.hide_windows_processes,
+                            self.group_svchost,
+                            self.group_platforms,
+                        )
+                    };
+                    let scroll_to = self.take_restore_scroll();
                     egui::ScrollArea::horizontal()
                         .scroll_bar_visibility(scroll_visibility)
                         .auto_shrink(false)
@@ -1008,16 +4053,41 @@ impl eframe::App for StartupApp {
                             ui,
                             &rows,
                             self.selected_row,
+                            &self.selected_processes,
                             self.hovered_row,
+                            self.sort_processes_by_start,
+                            self.show_cpu_time,
+                            &self.settings.thresholds,
+                            &self.process_diff,
+                            scroll_to,
+                            self.column_filters.row(Tab::Processes),
                         );
                         self.hovered_row = result.hovered_row;
+                        self.scroll_offset = result.scroll_offset;
                         if let Some(clicked) = result.clicked_row {
+                            // Ctrl+click adds to (or removes from) the selection,
+                            // starting from the row already selected
+                            let key_at = |index: usize| {
+                                rows.get(index).filter(|r| !r.is_group).map(|r| r.process.key())
+                            };
+                            if result.ctrl_clicked {
+                                if self.selected_processes.is_empty() {
+                                    self.selected_processes.extend(self.selected_row.and_then(key_at));
+                                }
+                                if let Some(key) = key_at(clicked) {
+                                    if !self.selected_processes.remove(&key) {
+                                        self.selected_processes.insert(key);
+                                    }
+                                }
+                            } else {
+                                self.selected_processes.clear();
+                            }
                             self.selected_row = Some(clicked);
                         }
                         // Double-click on Processes tab opens process properties dialog
                         if let Some(index) = result.double_clicked_row {
-                            if let Some(row) = rows.get(index) {
-                                self.process_properties = Some(process_properties_from(row.process));
+                            if let Some(row) = rows.get(index).filter(|r| !r.is_group) {
+                                self.show_process_properties(&row.process);
                             }
                         }
                         if let Some(action) = result.action {
@@ -1027,31 +4097,60 @@ impl eframe::App for StartupApp {
                                         self.expanded_pids.insert(pid);
                                     }
                                 }
-                                process_table::ProcessAction::Kill(index) => {
-                                    if let Some(row) = rows.get(index) {
-                                        let pid = row.process.pid;
-                                        let name = row.process.name.clone();
-                                        match kill_process(pid) {
-                                            Ok(_) => {
-                                                self.set_status(
-                                                    &format!("Killed '{}' (PID {})", name, pid),
-                                                    false,
-                                                );
-                                                self.start_background_load();
-                                            }
-                                            Err(e) => {
-                                                self.set_status(
-                                                    &format!("Failed to kill PID {}: {}", pid, e),
-                                                    true,
-                                                );
-                                            }
+                                process_table::ProcessAction::ToggleStartTimeSort => {
+                                    self.sort_processes_by_start = !self.sort_processes_by_start;
+                                    self.selected_row = None;
+                                    self.hovered_row = None;
+                                }
+                                process_table::ProcessAction::Kill(key) => {
+                                    if let Some(proc) = self.all_processes.iter().find(|p| p.key() == key) {
+                                        match kill_guard::guard_for(proc.pid, &proc.name) {
+                                            Some(guard) => self.kill_confirmation = Some((key, guard)),
+                                            None => self.kill_process_by_key(&key),
                                         }
+                                    } else {
+                                        self.set_status("Process is no longer running", true);
                                     }
                                 }
-                                process_table::ProcessAction::Properties(index) => {
-                                    if let Some(row) = rows.get(index) {
-                                        self.process_properties =
-                                            Some(process_properties_from(row.process));
+                                process_table::ProcessAction::AttachDebugger(key) => {
+                                    if let Some(proc) = self.all_processes.iter().find(|p| p.key() == key) {
+                                        let (pid, name) = (proc.pid, proc.name.clone());
+                                        self.attach_debugger(pid, &name);
+                                    } else {
+                                        self.set_status("Process is no longer running", true);
+                                    }
+                                }
+                                process_table::ProcessAction::RestartElevated(key) => {
+                                    self.restart_process_elevated(&key, false);
+                                }
+                                process_table::ProcessAction::CopyKillCommand(key, style) => {
+                                    // The whole selection when the row is part of it,
+                                    // otherwise just the row
+                                    let keys: Vec<ProcessKey> = if self.selected_processes.contains(&key) {
+                                        self.selected_processes.iter().copied().collect()
+                                    } else {
+                                        vec![key]
+                                    };
+                                    let mut pids: Vec<u32> = self
+                                        .all_processes
+                                        .iter()
+                                        .filter(|p| keys.contains(&p.key()))
+                                        .map(|p| p.pid)
+                                        .collect();
+                                    pids.sort_unstable();
+                                    if !pids.is_empty() {
+                                        let command = processes::kill_command(&pids, style);
+                                        ctx.copy_text(command.clone());
+                                        self.set_status(&format!("Copied: {}", command), false);
+                                    } else {
+                                        self.set_status("Process is no longer running", true);
+                                    }
+                                }
+                                process_table::ProcessAction::Properties(key) => {
+                                    if let Some(proc) = self.all_processes.iter().find(|p| p.key() == key).cloned() {
+                                        self.show_process_properties(&proc);
+                                    } else {
+                                        self.set_status("Process is no longer running", true);
                                     }
                                 }
                             }
@@ -1061,19 +4160,97 @@ impl eframe::App for StartupApp {
             }
         });
 
+        // Kill confirmation for Explorer / App Manager itself
+        if let Some((key, guard)) = self.kill_confirmation {
+            let target = self
+                .all_processes
+                .iter()
+                .find(|p| p.key() == key)
+                .map(|p| (p.name.clone(), p.pid));
+            match target {
+                Some((name, pid)) => match dialogs::show_kill_confirmation(ctx, &name, pid, guard) {
+                    dialogs::KillGuardResult::Kill => {
+                        self.kill_confirmation = None;
+                        self.kill_process_by_key(&key);
+                    }
+                    dialogs::KillGuardResult::RestartExplorer => {
+                        self.kill_confirmation = None;
+                        self.start_explorer_restart();
+                    }
+                    dialogs::KillGuardResult::Cancelled => {
+                        self.kill_confirmation = None;
+                    }
+                    dialogs::KillGuardResult::Open => {}
+                },
+                None => self.kill_confirmation = None,
+            }
+        }
+
+        // The same confirmation before restarting Explorer as administrator
+        if let Some((key, guard)) = self.restart_elevated_confirmation {
+            let target = self
+                .all_processes
+                .iter()
+                .find(|p| p.key() == key)
+                .map(|p| (p.name.clone(), p.pid));
+            match target {
+                Some((name, pid)) => match dialogs::show_kill_confirmation(ctx, &name, pid, guard) {
+                    dialogs::KillGuardResult::Kill => {
+                        self.restart_elevated_confirmation = None;
+                        self.restart_process_elevated(&key, true);
+                    }
+                    dialogs::KillGuardResult::RestartExplorer => {
+                        self.restart_elevated_confirmation = None;
+                        self.start_explorer_restart();
+                    }
+                    dialogs::KillGuardResult::Cancelled => {
+                        self.restart_elevated_confirmation = None;
+                    }
+                    dialogs::KillGuardResult::Open => {}
+                },
+                None => self.restart_elevated_confirmation = None,
+            }
+        }
+
+        // Restore dialog for deleted startup-folder files
+        if let Some(mut info) = self.restore_backups.take() {
+            match dialogs::show_restore_backups(ctx, &mut info) {
+                dialogs::DialogResult::Cancelled => {}
+                _ => {
+                    if let Some(item) = info.restore_requested.take().and_then(|i| info.items.get(i).cloned()) {
+                        self.restore_backup(&item);
+                        info.items = backup::list();
+                    }
+                    if let Some(item) = info.discard_requested.take().and_then(|i| info.items.get(i).cloned()) {
+                        if self.dry_run {
+                            let changes = dry_run::discard_backup(&item);
+                            self.simulate(format!("Delete backup of '{}'", item.file_name()), Ok(changes));
+                        } else {
+                            match backup::discard(&item) {
+                                Ok(()) => {
+                                    self.set_status(&format!("Deleted backup of '{}'", item.file_name()), false)
+                                }
+                                Err(e) => self.set_status(&format!("{}", e), true),
+                            }
+                        }
+                        info.items = backup::list();
+                    }
+                    self.restore_backups = Some(info);
+                }
+            }
+        }
+
         // Delete confirmation dialog
-        if let Some(PendingAction::ConfirmDelete(index)) = self.pending_action.clone() {
-            let visible = self.active_entries();
-            let name = if index < visible.len() {
-                visible[index].name.clone()
-            } else {
-                "Unknown".to_string()
-            };
+        if let Some(PendingAction::ConfirmDelete(id)) = self.pending_action.clone() {
+            let (name, backed_up) = self
+                .find_entry(&id)
+                .map(|e| (e.name.clone(), matches!(e.source, Source::StartupFolder { .. })))
+                .unwrap_or_else(|| ("Unknown".to_string(), false));
 
-            match dialogs::show_delete_confirmation(ctx, &name) {
+            match dialogs::show_delete_confirmation(ctx, &name, backed_up) {
                 dialogs::DialogResult::Confirmed => {
                     self.pending_action = None;
-                    self.delete_confirmed(index);
+                    self.delete_confirmed(&id);
                 }
                 dialogs::DialogResult::Cancelled => {
                     self.pending_action = None;
@@ -1084,58 +4261,337 @@ impl eframe::App for StartupApp {
             }
         }
 
+        // Warning before disabling or stopping something other items need
+        if let Some((action, info)) = self.dependency_warning.take() {
+            match dialogs::show_dependency_warning(ctx, &info) {
+                dialogs::DialogResult::Confirmed => self.dispatch_entry_action(action),
+                dialogs::DialogResult::Cancelled => {}
+                dialogs::DialogResult::Open => self.dependency_warning = Some((action, info)),
+            }
+        }
+
         // Uninstall confirmation dialog
-        if let Some(PendingAction::ConfirmUninstall(index)) = self.pending_action.clone() {
-            let name = if let Some(app) = self.installed_apps.get(index) {
-                app.display_name.clone()
-            } else {
-                "Unknown".to_string()
-            };
+        if let Some(PendingAction::ConfirmUninstall(key)) = self.pending_action.clone() {
+            match self.uninstall_preview.take() {
+                Some(mut preview) => match dialogs::show_uninstall_confirmation(ctx, &mut preview) {
+                    dialogs::DialogResult::Confirmed => {
+                        self.pending_action = None;
+                        self.uninstall_confirmed(&key, &preview);
+                    }
+                    dialogs::DialogResult::Cancelled => {
+                        self.pending_action = None;
+                        self.finish_uninstall_step(&key, plan::StepStatus::Pending);
+                    }
+                    dialogs::DialogResult::Open => {
+                        self.uninstall_preview = Some(preview);
+                    }
+                },
+                None => {
+                    self.pending_action = None;
+                    self.finish_uninstall_step(&key, plan::StepStatus::Pending);
+                }
+            }
+        }
+
+        // Dev package uninstall confirmation dialog
+        if let Some(package) = self.package_uninstall.take() {
+            match dialogs::show_package_uninstall_confirmation(ctx, &package) {
+                dialogs::DialogResult::Confirmed => self.package_uninstall_confirmed(package),
+                dialogs::DialogResult::Cancelled => {}
+                dialogs::DialogResult::Open => self.package_uninstall = Some(package),
+            }
+        }
 
-            match dialogs::show_uninstall_confirmation(ctx, &name) {
+        // Service properties dialog
+        if let Some(mut info) = self.service_properties.take() {
+            match dialogs::show_service_properties(ctx, &mut info) {
+                dialogs::DialogResult::Cancelled => {}
+                _ => {
+                    if info.open_location_requested {
+                        info.open_location_requested = false;
+                        if let Err(e) = shell::open_file_location(&info.binary_path) {
+                            self.set_status(&format!("Failed to open location: {}", e), true);
+                        }
+                    }
+                    if info.windows_properties_requested {
+                        info.windows_properties_requested = false;
+                        self.open_windows_properties(&info.binary_path);
+                    }
+                    self.update_dependencies(&mut info.dependencies);
+                    self.service_properties = Some(info);
+                }
+            }
+        }
+
+        // Process properties dialog
+        if let Some(mut info) = self.process_properties.take() {
+            match dialogs::show_process_properties(ctx, &mut info, &self.settings.thresholds) {
+                dialogs::DialogResult::Cancelled => {}
+                _ => {
+                    if info.windows_properties_requested {
+                        info.windows_properties_requested = false;
+                        self.open_windows_properties(&info.exe_path);
+                    }
+                    if let Some(conn) = info.close_connection_requested.take() {
+                        match connections::close_tcp(&conn) {
+                            Ok(()) => self.set_status(
+                                &format!(
+                                    "Closed connection {} -> {}",
+                                    conn.local,
+                                    conn.remote.map(|r| r.to_string()).unwrap_or_default()
+                                ),
+                                false,
+                            ),
+                            Err(e) => self.set_status(&format!("Failed to close connection: {}", e), true),
+                        }
+                        info.refresh_connections_requested = true;
+                    }
+                    if info.refresh_connections_requested {
+                        info.refresh_connections_requested = false;
+                        info.connections = connections::for_process(info.pid);
+                    }
+                    self.process_properties = Some(info);
+                }
+            }
+        }
+
+        // Startup entry properties dialog
+        if let Some(mut info) = self.startup_entry_properties.take() {
+            match dialogs::show_startup_entry_properties(ctx, &mut info) {
                 dialogs::DialogResult::Confirmed => {
-                    self.pending_action = None;
-                    self.uninstall_confirmed(index);
+                    self.apply_entry_properties(&info);
                 }
-                dialogs::DialogResult::Cancelled => {
-                    self.pending_action = None;
+                dialogs::DialogResult::Cancelled => {}
+                dialogs::DialogResult::Open => {
+                    if info.export_requested {
+                        info.export_requested = false;
+                        self.export_entry_reg(&info);
+                    }
+                    if info.windows_properties_requested {
+                        info.windows_properties_requested = false;
+                        self.open_windows_properties(&cmdline::target_path(&info.command));
+                    }
+                    self.update_dependencies(&mut info.dependencies);
+                    self.startup_entry_properties = Some(info);
                 }
+            }
+        }
+
+        // Settings dialog
+        if let Some(mut draft) = self.settings_draft.take() {
+            match dialogs::show_settings(ctx, &mut draft) {
+                dialogs::DialogResult::Confirmed => match draft.save() {
+                    Ok(()) => {
+                        let rescan = draft.scan_scope != self.settings.scan_scope;
+                        if rescan {
+                            if let Some(tracker) = &mut self.install_tracker {
+                                tracker.forget_baselines();
+                            }
+                        }
+                        self.settings = draft;
+                        if self.active_tab == Tab::DevPackages && !self.settings.scan_scope.dev_packages {
+                            self.active_tab = Tab::Installed;
+                            self.selected_row = None;
+                            self.hovered_row = None;
+                        }
+                        allowlist::reload();
+                        if rescan && !self.loading {
+                            self.start_background_load();
+                        }
+                        self.set_status("Settings saved", false);
+                    }
+                    Err(e) => {
+                        self.set_status(&format!("Failed to save settings: {}", e), true);
+                        self.settings_draft = Some(draft);
+                    }
+                },
+                dialogs::DialogResult::Cancelled => {}
                 dialogs::DialogResult::Open => {
-                    // Still showing
+                    self.settings_draft = Some(draft);
                 }
             }
+            crash::set_unsaved_settings(self.settings_draft.as_ref());
         }
 
-        // Service properties dialog
-        if let Some(info) = &self.service_properties.clone() {
-            match dialogs::show_service_properties(ctx, info) {
-                dialogs::DialogResult::Cancelled => {
-                    self.service_properties = None;
+        // Report of a crash in the previous session
+        if let Some(mut info) = self.crash_report.take() {
+            let result = dialogs::show_crash_report(ctx, &mut info);
+            if std::mem::take(&mut info.open_requested) {
+                if let Err(e) = shell::open_file(&info.report.path) {
+                    self.set_status(&e, true);
+                }
+            }
+            if std::mem::take(&mut info.open_folder_requested) {
+                if let Err(e) = shell::open_file_location(&info.report.path.to_string_lossy()) {
+                    self.set_status(&e, true);
+                }
+            }
+            if std::mem::take(&mut info.restore_settings_requested) {
+                if let Some(settings) = info.report.unsaved_settings.take() {
+                    self.settings_draft = Some(settings);
                 }
+            }
+            if result == dialogs::DialogResult::Open {
+                self.crash_report = Some(info);
+            }
+        }
+
+        // Alert for disabled entries that came back
+        if let Some(mut info) = self.reversion_alert.take() {
+            let result = dialogs::show_reversion_alert(ctx, &mut info);
+            if let Some(item) = info.disable_requested.take().map(|i| info.items.remove(i)) {
+                self.execute_action(PendingAction::Disable(item.id));
+            }
+            if let Some(item) = info.keep_disabled_requested.take().map(|i| info.items.remove(i)) {
+                self.watchlist.set_keep_disabled(&item.id, true);
+                self.execute_action(PendingAction::Disable(item.id));
+            }
+            if let Some(item) = info.allow_requested.take().map(|i| info.items.remove(i)) {
+                self.watchlist.forget(&item.id);
+                self.set_status(&format!("No longer watching '{}'", item.name), false);
+            }
+            if result == dialogs::DialogResult::Open && !info.items.is_empty() {
+                self.reversion_alert = Some(info);
+            }
+        }
+
+        // Add Startup Entry dialog
+        if let Some(mut info) = self.add_startup_entry.take() {
+            match dialogs::show_add_startup_entry(ctx, &mut info) {
+                dialogs::DialogResult::Confirmed => self.add_startup_entry_confirmed(&info),
+                dialogs::DialogResult::Cancelled => {}
+                dialogs::DialogResult::Open => self.add_startup_entry = Some(info),
+            }
+        }
+
+        // Keep-disabled rules dialog
+        if let Some(mut info) = self.keep_disabled_rules.take() {
+            let result = dialogs::show_keep_disabled_rules(ctx, &mut info);
+            if let Some(rule) = info.remove_requested.take().and_then(|i| info.rules.get(i).cloned()) {
+                self.watchlist.remove_rule(&rule.key);
+                self.set_status(&format!("Removed keep-disabled rule for '{}'", rule.name), false);
+                info.rules = self.watchlist.keep_disabled_rules();
+            }
+            if std::mem::take(&mut info.copy_monitor_command_requested) {
+                let exe = std::env::current_exe().unwrap_or_default();
+                let command = format!("\"{}\" {}", exe.display(), enforcement::MONITOR_FLAG);
+                ctx.copy_text(command.clone());
+                self.set_status(&format!("Copied: {}", command), false);
+            }
+            if result == dialogs::DialogResult::Open {
+                self.keep_disabled_rules = Some(info);
+            }
+        }
+
+        // Dry-run log
+        if self.show_dry_run_log {
+            match dialogs::show_dry_run_log(ctx, &self.dry_run_log, &mut self.dry_run) {
+                dialogs::DialogResult::Confirmed => self.dry_run_log.clear(),
+                dialogs::DialogResult::Cancelled => self.show_dry_run_log = false,
                 dialogs::DialogResult::Open => {}
-                _ => {}
             }
         }
 
-        // Process properties dialog
-        if let Some(info) = &self.process_properties.clone() {
-            match dialogs::show_process_properties(ctx, info) {
-                dialogs::DialogResult::Cancelled => {
-                    self.process_properties = None;
+        // Cleanup plan dialog
+        if let Some(mut info) = self.cleanup_plan_dialog.take() {
+            let busy = self.loading;
+            let result =
+                dialogs::show_cleanup_plan(ctx, &self.cleanup_plan, &mut self.staging_plan, busy, &mut info);
+            if let Some(i) = info.remove_requested.take() {
+                if i < self.cleanup_plan.steps.len() {
+                    self.cleanup_plan.steps.remove(i);
                 }
-                dialogs::DialogResult::Open => {}
-                _ => {}
+            }
+            if let Some(i) = info.run_requested.take() {
+                self.run_plan_step(i);
+            }
+            if std::mem::take(&mut info.export_requested) {
+                self.export_cleanup_plan();
+            }
+            if std::mem::take(&mut info.import_requested) {
+                self.import_cleanup_plan();
+            }
+            if std::mem::take(&mut info.clear_requested) {
+                self.cleanup_plan.steps.clear();
+                self.set_status("Cleared the cleanup plan", false);
+            }
+            if result == dialogs::DialogResult::Open {
+                self.cleanup_plan_dialog = Some(info);
             }
         }
 
-        // Startup entry properties dialog
-        if let Some(info) = &self.startup_entry_properties.clone() {
-            match dialogs::show_startup_entry_properties(ctx, info) {
-                dialogs::DialogResult::Cancelled => {
-                    self.startup_entry_properties = None;
+        // Categories dialog
+        if let Some(mut info) = self.category_cleanup.take() {
+            match dialogs::show_category_cleanup(ctx, &mut info) {
+                dialogs::DialogResult::Confirmed => self.disable_categorized(&info),
+                dialogs::DialogResult::Cancelled => {}
+                dialogs::DialogResult::Open => self.category_cleanup = Some(info),
+            }
+        }
+
+        // Clean Boot Assistant
+        if let Some(mut info) = self.clean_boot.take() {
+            let result = dialogs::show_clean_boot(ctx, &mut info);
+            if info.revert_requested {
+                if let Some(path) = &info.revert_file {
+                    self.revert_clean_boot(path);
                 }
-                dialogs::DialogResult::Open => {}
-                _ => {}
+            } else {
+                match result {
+                    dialogs::DialogResult::Confirmed => self.apply_clean_boot(&info),
+                    dialogs::DialogResult::Cancelled => {}
+                    dialogs::DialogResult::Open => self.clean_boot = Some(info),
+                }
+            }
+        }
+
+        // Orphaned by Uninstall dialog
+        if let Some(mut info) = self.orphan_cleanup.take() {
+            match dialogs::show_orphan_cleanup(ctx, &mut info) {
+                dialogs::DialogResult::Confirmed => self.clean_up_orphans(&info),
+                dialogs::DialogResult::Cancelled => {}
+                dialogs::DialogResult::Open => self.orphan_cleanup = Some(info),
+            }
+        }
+
+        // Service hardening dialog
+        if let Some(mut info) = self.service_hardening.take() {
+            let suggestions = service_hardening::suggest(&self.all_services);
+            let busy = self.loading || self.unfinished_jobs() > 0;
+            let result = dialogs::show_service_hardening(
+                ctx,
+                &suggestions,
+                &self.hardening_changes.changes,
+                busy,
+                &mut info,
+            );
+            if let Some(index) = info.apply_requested.take() {
+                let suggestion = &suggestions[index];
+                self.set_service_start_mode(suggestion.service.clone(), suggestion.suggested);
+            }
+            if let Some(index) = info.revert_requested.take() {
+                self.revert_hardening(index);
+            }
+            if result == dialogs::DialogResult::Open {
+                self.service_hardening = Some(info);
+            }
+        }
+
+        // Audit trail dialog
+        if let Some(mut info) = self.audit_trail.take() {
+            let result = dialogs::show_audit_trail(ctx, &mut info);
+            if let Some(format) = info.export_requested.take() {
+                self.export_audit_trail(&info.records, format);
+            }
+            if result == dialogs::DialogResult::Open {
+                self.audit_trail = Some(info);
+            }
+        }
+
+        // Vendors dialog
+        if let Some(mut info) = self.vendor_summary.take() {
+            if dialogs::show_vendor_summary(ctx, &mut info) == dialogs::DialogResult::Open {
+                self.vendor_summary = Some(info);
             }
         }
 
@@ -1150,10 +4606,120 @@ impl eframe::App for StartupApp {
             }
         }
 
+        // Action help dialog
+        if self.show_action_help && dialogs::show_action_help(ctx) != dialogs::DialogResult::Open {
+            self.show_action_help = false;
+        }
+
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.save_session();
+        }
+
+        // Don't abandon changes the executor hasn't made yet (ones that
+        // timed out may never finish, so they don't hold the window open)
+        let unfinished = self.unfinished_jobs();
+        if ctx.input(|i| i.viewport().close_requested()) && unfinished > 0 {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.set_status(&format!("Wait for {} changes to finish before closing", unfinished), true);
+        }
+
+        // Intercept window close to summarize changes made this session
+        if ctx.input(|i| i.viewport().close_requested())
+            && unfinished == 0
+            && !self.close_confirmed
+            && !self.session_changes.is_empty()
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.show_session_summary = true;
+        }
+
+        // Unreadable items dialog
+        if let Some(tab) = self.skipped_dialog {
+            let result = match self.skipped.get(&tab) {
+                Some(skipped) => dialogs::show_skipped_items(ctx, skipped, self.is_admin),
+                None => dialogs::DialogResult::Cancelled,
+            };
+            match result {
+                dialogs::DialogResult::Confirmed => {
+                    self.skipped_dialog = None;
+                    collector::save_nonadmin_task_paths(&self.entries);
+                    restart_as_admin();
+                }
+                dialogs::DialogResult::Cancelled => {
+                    self.skipped_dialog = None;
+                }
+                dialogs::DialogResult::Open => {}
+            }
+        }
+
+        // Session summary dialog
+        if self.show_session_summary {
+            match dialogs::show_session_summary(ctx, &self.session_changes) {
+                dialogs::DialogResult::Confirmed => {
+                    self.show_session_summary = false;
+                    self.close_confirmed = true;
+                    ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                }
+                dialogs::DialogResult::Cancelled => {
+                    self.show_session_summary = false;
+                }
+                dialogs::DialogResult::Open => {}
+            }
+        }
+
+        // Guided tour, over everything else
+        if let Some(tour) = &mut self.tour {
+            if tour.show(ctx, &self.tour_anchors) != dialogs::DialogResult::Open {
+                self.tour = None;
+                help::mark_tour_seen();
+            }
+        }
+
         // Escape key closes open dialogs
         if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            if self.show_about {
+            if self.tour.is_some() {
+                self.tour = None;
+                help::mark_tour_seen();
+            } else if self.show_session_summary {
+                self.show_session_summary = false;
+            } else if self.skipped_dialog.is_some() {
+                self.skipped_dialog = None;
+            } else if self.dependency_warning.is_some() {
+                self.dependency_warning = None;
+            } else if self.package_uninstall.is_some() {
+                self.package_uninstall = None;
+            } else if self.show_about {
                 self.show_about = false;
+            } else if self.show_action_help {
+                self.show_action_help = false;
+            } else if self.show_dry_run_log {
+                self.show_dry_run_log = false;
+            } else if self.crash_report.is_some() {
+                self.crash_report = None;
+            } else if self.settings_draft.is_some() {
+                self.settings_draft = None;
+            } else if self.restore_backups.is_some() {
+                self.restore_backups = None;
+            } else if self.vendor_summary.is_some() {
+                self.vendor_summary = None;
+            } else if self.audit_trail.is_some() {
+                self.audit_trail = None;
+            } else if self.category_cleanup.is_some() {
+                self.category_cleanup = None;
+            } else if self.clean_boot.is_some() {
+                self.clean_boot = None;
+            } else if self.service_hardening.is_some() {
+                self.service_hardening = None;
+            } else if self.orphan_cleanup.is_some() {
+                self.orphan_cleanup = None;
+            } else if self.reversion_alert.is_some() {
+                self.reversion_alert = None;
+            } else if self.keep_disabled_rules.is_some() {
+                self.keep_disabled_rules = None;
+            } else if self.cleanup_plan_dialog.is_some() {
+                self.cleanup_plan_dialog = None;
+            } else if self.add_startup_entry.is_some() {
+                self.add_startup_entry = None;
             } else if self.startup_entry_properties.is_some() {
                 self.startup_entry_properties = None;
             } else if self.process_properties.is_some() {
@@ -1162,30 +4728,11 @@ impl eframe::App for StartupApp {
                 self.service_properties = None;
             }
         }
-
-        // Loading overlay
-        if self.loading {
-            egui::Area::new(egui::Id::new("loading_overlay"))
-                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
-                .order(egui::Order::Foreground)
-                .show(ctx, |ui| {
-                    egui::Frame::popup(&ctx.style())
-                        .inner_margin(egui::Margin::symmetric(24, 16))
-                        .show(ui, |ui| {
-                            ui.vertical_centered(|ui| {
-                                ui.spinner();
-                                ui.add_space(8.0);
-                                ui.label(egui::RichText::new("Loading...").color(egui::Color32::WHITE));
-                            });
-                        });
-                });
-
-            ctx.request_repaint();
-        }
     }
 }
 
-fn restart_as_admin() {
+/// Start this executable again through the UAC prompt and exit.
+pub fn restart_as_admin() {
     let exe = std::env::current_exe().unwrap_or_default();
     let exe_wide: Vec<u16> = exe.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
     let verb: Vec<u16> = "runas\0".encode_utf16().collect();
@@ -1203,6 +4750,44 @@ fn restart_as_admin() {
     std::process::exit(0);
 }
 
+/// Replace characters that aren't allowed in Windows file names.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if r#"<>:"/\|?*"#.contains(c) || c.is_control() { '_' } else { c })
+        .collect()
+}
+
+/// A tab's name in status messages and export metadata.
+fn tab_title(tab: Tab) -> &'static str {
+    match tab {
+        Tab::StartupApps => "Startup Apps",
+        Tab::Services => "Services",
+        Tab::Processes => "Processes",
+        Tab::Installed => "Installed Apps",
+        Tab::ExecutionHistory => "Execution History",
+        Tab::Security => "Security",
+        Tab::DevPackages => "Dev Packages",
+    }
+}
+
+/// File name prefix for a tab's exported CSV.
+fn tab_file_name(tab: Tab) -> &'static str {
+    match tab {
+        Tab::StartupApps => "startup-apps",
+        Tab::Services => "services",
+        Tab::Processes => "processes",
+        Tab::Installed => "installed-apps",
+        Tab::ExecutionHistory => "execution-history",
+        Tab::Security => "security",
+        Tab::DevPackages => "dev-packages",
+    }
+}
+
+/// The tab exported as `name`, if it is shown with `scope`.
+fn tab_from_file_name(name: &str, scope: &settings::ScanScope) -> Option<Tab> {
+    all_tabs(scope).into_iter().find(|t| tab_file_name(*t).eq_ignore_ascii_case(name))
+}
+
 fn csv_escape(field: &str) -> String {
     if field.contains(',') || field.contains('"') || field.contains('\n') {
         format!("\"{}\"", field.replace('"', "\"\""))
@@ -1211,6 +4796,19 @@ fn csv_escape(field: &str) -> String {
     }
 }
 
+/// Enrichment provider columns appended to a CSV header, e.g. ",Signature,Signer".
+fn csv_extra_header(columns: &[&str]) -> String {
+    columns.iter().map(|c| format!(",{}", csv_escape(c))).collect()
+}
+
+/// A row's values for the columns from [`csv_extra_header`].
+fn csv_extra_values(columns: &[&str], enrichment: &Enrichment) -> String {
+    columns
+        .iter()
+        .map(|c| format!(",{}", csv_escape(enrichment.value(c).unwrap_or_default())))
+        .collect()
+}
+
 fn format_memory_csv(bytes: u64) -> String {
     if bytes >= 1_073_741_824 {
         format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
@@ -1223,85 +4821,10 @@ fn format_memory_csv(bytes: u64) -> String {
     }
 }
 
-/// Parse a command string into (executable, arguments).
-///
-/// Handles three forms commonly found in Windows uninstall strings:
-/// 1. Quoted: `"C:\Program Files\app.exe" /S` → split at closing quote
-/// 2. Unquoted with `.exe`: `C:\Program Files\app.exe /S` → split after `.exe`
-/// 3. Fallback: split on first whitespace
-fn split_command(command: &str) -> (String, String) {
-    let cmd = command.trim();
-
-    // Case 1: Quoted executable
-    if cmd.starts_with('"') {
-        if let Some(end) = cmd[1..].find('"') {
-            let exe = &cmd[1..1 + end];
-            let args = cmd[1 + end + 1..].trim();
-            return (exe.to_string(), args.to_string());
-        }
-    }
-
-    // Case 2: Find .exe boundary (case-insensitive) — handles unquoted paths
-    // with spaces like C:\Program Files (x86)\App\uninstall.exe /silent
-    let lower = cmd.to_lowercase();
-    if let Some(pos) = lower.find(".exe") {
-        let end = pos + 4;
-        let exe = &cmd[..end];
-        let args = cmd[end..].trim();
-        return (exe.to_string(), args.to_string());
-    }
-
-    // Case 3: No .exe found — split on first whitespace
-    if let Some(pos) = cmd.find(char::is_whitespace) {
-        let exe = &cmd[..pos];
-        let args = cmd[pos..].trim();
-        (exe.to_string(), args.to_string())
-    } else {
-        (cmd.to_string(), String::new())
-    }
-}
-
-/// Run a shell command string (like an uninstall or modify path) via ShellExecuteW
-/// with "runas" verb so UAC elevation is requested when needed.
+/// Run a shell command string (like an uninstall or modify path) elevated,
+/// without waiting for it.
 fn run_shell_command(command: &str) -> Result<(), String> {
-    use std::os::windows::ffi::OsStrExt;
-    use windows::Win32::UI::Shell::ShellExecuteW;
-    use windows::core::PCWSTR;
-
-    let (exe, args) = split_command(command);
-
-    let exe_wide: Vec<u16> = std::ffi::OsStr::new(&exe)
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
-    let args_wide: Vec<u16> = std::ffi::OsStr::new(&args)
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
-    let verb_wide: Vec<u16> = std::ffi::OsStr::new("runas")
-        .encode_wide()
-        .chain(std::iter::once(0))
-        .collect();
-
-    let result = unsafe {
-        ShellExecuteW(
-            None,
-            PCWSTR(verb_wide.as_ptr()),
-            PCWSTR(exe_wide.as_ptr()),
-            PCWSTR(args_wide.as_ptr()),
-            PCWSTR::null(),
-            windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL,
-        )
-    };
-
-    if result.0 as usize > 32 {
-        Ok(())
-    } else {
-        Err(format!(
-            "ShellExecute failed (code {}): {}",
-            result.0 as usize, exe
-        ))
-    }
+    uninstaller::launch_elevated(command).map(|_| ())
 }
 
 fn kill_process(pid: u32) -> Result<(), String> {
@@ -1319,21 +4842,71 @@ fn kill_process(pid: u32) -> Result<(), String> {
     }
 }
 
+/// The executor job for an Enable/Disable/Start/Stop action.
+fn entry_job(action: &PendingAction, entry: &StartupEntry) -> Option<executor::Job> {
+    let entry = entry.clone();
+    match action {
+        PendingAction::Enable(_) => Some(executor::Job::Enable(entry)),
+        PendingAction::Disable(_) => Some(executor::Job::Disable(entry)),
+        PendingAction::Start(_) => Some(executor::Job::Start(entry)),
+        PendingAction::Stop(_) => Some(executor::Job::Stop(entry)),
+        _ => None,
+    }
+}
+
 fn startup_entry_properties_from(entry: &StartupEntry) -> dialogs::StartupEntryPropertiesInfo {
+    let (_, arguments) = cmdline::split(&entry.command);
     dialogs::StartupEntryPropertiesInfo {
         name: entry.name.clone(),
         product_name: entry.product_name.clone(),
         command: entry.command.clone(),
         source: entry.source.clone(),
+        expand_sz: entry.expand_sz,
         enabled: entry.enabled,
         run_state: entry.run_state,
         runs_as: entry.runs_as.clone(),
         requires_admin: entry.requires_admin,
         last_ran: entry.last_ran,
+        last_ran_source: entry.last_ran_source,
+        run_history: entry.run_history,
+        session_origin: entry.session_origin.clone(),
+        task_registration: entry.task_registration.clone(),
+        task_conditions: entry.task_conditions,
+        original_task_conditions: entry.task_conditions,
+        script_payload: entry.script_payload.clone(),
+        bitness: entry.bitness,
+        arguments: arguments.clone(),
+        original_arguments: arguments,
+        enrichment: entry.enrichment.clone(),
+        dependencies: Default::default(),
+        export_requested: false,
+        windows_properties_requested: false,
     }
 }
 
-fn process_properties_from(proc: &ProcessInfo) -> dialogs::ProcessPropertiesInfo {
+fn uninstall_preview_from(app: &InstalledApp) -> dialogs::UninstallPreview {
+    let log_path = std::env::temp_dir().join(format!(
+        "AppManager-uninstall-{}-{}.log",
+        sanitize_file_name(&app.display_name),
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+    let mut preview = dialogs::UninstallPreview {
+        app_name: app.display_name.clone(),
+        product_code: app.msi_product_code.clone(),
+        uninstall_string: app.uninstall_string.clone(),
+        log_path,
+        with_log: false,
+        executable: String::new(),
+        arguments: String::new(),
+    };
+    preview.reset_command();
+    preview
+}
+
+fn process_properties_from(
+    proc: &ProcessInfo,
+    launch_origin: launch_origin::LaunchOrigin,
+) -> dialogs::ProcessPropertiesInfo {
     dialogs::ProcessPropertiesInfo {
         pid: proc.pid,
         parent_pid: proc.parent_pid,
@@ -1348,6 +4921,18 @@ fn process_properties_from(proc: &ProcessInfo) -> dialogs::ProcessPropertiesInfo
         product_name: proc.product_name.clone(),
         user_name: proc.user_name.clone(),
         is_elevated: proc.is_elevated,
+        bitness: proc.bitness,
+        link_time: proc.link_time,
+        cpu_times: proc.cpu_times,
+        image_mismatch: proc.image_mismatch.clone(),
+        dpi_awareness: process_ui::dpi_awareness(proc.pid),
+        ui_framework: process_ui::detect_framework(proc.pid, &proc.exe_path),
+        enrichment: proc.enrichment.clone(),
+        launch_origin,
+        connections: connections::for_process(proc.pid),
+        refresh_connections_requested: false,
+        close_connection_requested: None,
+        windows_properties_requested: false,
     }
 }
 
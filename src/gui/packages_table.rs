@@ -0,0 +1,193 @@
+use super::column_fit::{self, ColumnFit};
+use super::filters::{self, ColumnFilterRow};
+use super::table::{details_tooltip, truncated_label};
+use crate::dev_packages;
+use crate::models::{DevPackage, PackageManager};
+use eframe::egui;
+use egui_extras::{Column, TableBuilder};
+
+/// Dev package actions, addressed by the package's manager and name.
+pub enum PackageAction {
+    Uninstall(PackageManager, String),
+    OpenLocation(String),
+}
+
+pub struct PackagesTableResult {
+    pub action: Option<PackageAction>,
+    pub clicked_row: Option<usize>,
+    pub hovered_row: Option<usize>,
+    /// Vertical scroll position, to restore in a later session.
+    pub scroll_offset: f32,
+}
+
+pub fn render_packages_table(
+    ui: &mut egui::Ui,
+    packages: &[DevPackage],
+    selected_row: Option<usize>,
+    prev_hovered_row: Option<usize>,
+    scroll_to: Option<f32>,
+    mut filters: Option<&mut ColumnFilterRow>,
+) -> PackagesTableResult {
+    let mut action = None;
+    let mut clicked_row = None;
+    let mut hovered_row = None;
+
+    let available_height = ui.available_height();
+
+    let fit = ColumnFit::new(ui, "packages_table", || {
+        let measure = |header: &str, cell: fn(&DevPackage) -> String| {
+            Some(column_fit::text_width(ui, std::iter::once(header.to_string()).chain(packages.iter().map(cell))))
+        };
+        vec![
+            measure("Name", |p| p.name.clone()),
+            measure("Version", |p| p.version.clone()),
+            measure("Manager", |p| p.manager.label().to_string()),
+            measure("Location", |p| p.location.clone()),
+            None, // Actions
+        ]
+    });
+    let mut table = TableBuilder::new(ui)
+        .id_salt("packages_table")
+        .striped(true)
+        .resizable(true)
+        .sense(egui::Sense::click())
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .column(fit.column(220.0, 100.0)) // Name
+        .column(fit.column(100.0, 60.0)) // Version
+        .column(fit.column(90.0, 60.0)) // Manager
+        .column(fit.column(300.0, 100.0)) // Location
+        .column(Column::remainder().at_least(150.0)) // Actions
+        .min_scrolled_height(0.0)
+        .max_scroll_height(available_height);
+    if fit.pending() {
+        table.reset();
+    }
+
+    if let Some(offset) = scroll_to {
+        table = table.vertical_scroll_offset(offset);
+    }
+
+    let scroll = table
+        .header(filters::header_height(&filters), |mut header| {
+            for column in ["Name", "Version", "Manager", "Location"] {
+                header.col(|ui| {
+                    ui.vertical(|ui| {
+                        ui.strong(column);
+                        filters::filter_box(ui, &mut filters, column, "contains");
+                    });
+                });
+            }
+            header.col(|ui| { ui.strong("Actions"); });
+            fit.context_menu(&header.response());
+        })
+        .body(|body| {
+            fit.record(body.widths());
+            body.rows(24.0, packages.len(), |mut row| {
+                let index = row.index();
+                let package = &packages[index];
+                let is_selected = selected_row == Some(index);
+                let was_hovered = prev_hovered_row == Some(index);
+
+                if is_selected || was_hovered {
+                    row.set_selected(true);
+                }
+
+                let mut row_hovered = false;
+                let mut row_clicked = false;
+
+                // Name
+                let (_, cell_resp) = row.col(|ui| {
+                    let resp = ui.add(truncated_label(&package.name));
+                    let resp = details_tooltip(ui, resp, &package.name, || package_details(package));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Version
+                let (_, cell_resp) = row.col(|ui| {
+                    let text = if package.version.is_empty() { "--" } else { &package.version };
+                    let resp = ui.add(truncated_label(text));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Manager
+                let (_, cell_resp) = row.col(|ui| {
+                    let text = egui::RichText::new(package.manager.label()).color(egui::Color32::from_rgb(100, 160, 230));
+                    let resp = ui.add(truncated_label(text));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Location
+                let (_, cell_resp) = row.col(|ui| {
+                    let text = if package.location.is_empty() { "--" } else { &package.location };
+                    let resp = ui.add(truncated_label(text));
+                    let resp = details_tooltip(ui, resp, text, || package_details(package));
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                // Actions
+                let (_, cell_resp) = row.col(|ui| {
+                    ui.horizontal(|ui| {
+                        let btn_size = egui::vec2(65.0, 18.0);
+
+                        if ui
+                            .add_sized(btn_size, egui::Button::new("Uninstall"))
+                            .on_hover_text(format!(
+                                "Remove with {}: {}",
+                                package.manager.label(),
+                                dev_packages::command_line(package)
+                            ))
+                            .clicked()
+                        {
+                            action = Some(PackageAction::Uninstall(package.manager, package.name.clone()));
+                        }
+
+                        if ui
+                            .add_enabled(!package.location.is_empty(), egui::Button::new("Location").min_size(btn_size))
+                            .clicked()
+                        {
+                            action = Some(PackageAction::OpenLocation(package.location.clone()));
+                        }
+                    });
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+
+                if row_hovered {
+                    hovered_row = Some(index);
+                }
+                if row_clicked {
+                    clicked_row = Some(index);
+                }
+            });
+        });
+
+    PackagesTableResult {
+        action,
+        clicked_row,
+        hovered_row,
+        scroll_offset: scroll.state.offset.y,
+    }
+}
+
+/// Full details of a dev package for [`details_tooltip`].
+fn package_details(package: &DevPackage) -> Vec<(&'static str, String)> {
+    vec![
+        ("Name", package.name.clone()),
+        ("Version", package.version.clone()),
+        ("Manager", package.manager.label().to_string()),
+        ("Location", package.location.clone()),
+        ("Uninstall Command", dev_packages::command_line(package)),
+    ]
+}
@@ -1,32 +1,60 @@
-use crate::processes::TreeRow;
+use super::column_fit::{self, ColumnFit};
+use super::filters::{self, ColumnFilterRow};
+use super::help::Action;
+use super::table::{details_tooltip, truncated_label};
+use crate::models::{Bitness, ProcessInfo, ProcessKey, VirtPlatform};
+use crate::processes::{self, ProcessDiff, TreeRow};
+use crate::settings::{UsageLevel, UsageThresholds};
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
+use std::collections::HashSet;
 
 pub enum ProcessAction {
-    Kill(usize),
-    Properties(usize),
+    Kill(ProcessKey),
+    Properties(ProcessKey),
+    /// Launch the configured debugger against the process.
+    AttachDebugger(ProcessKey),
+    /// Kill the process and launch the same command line elevated.
+    RestartElevated(ProcessKey),
+    /// Put a command that kills the process on the clipboard.
+    CopyKillCommand(ProcessKey, processes::KillCommandStyle),
     ToggleExpand(u32),
+    /// Switch between the process tree and the flat start-time order.
+    ToggleStartTimeSort,
 }
 
 pub struct ProcessTableResult {
     pub action: Option<ProcessAction>,
     pub clicked_row: Option<usize>,
+    /// Whether Ctrl was held for `clicked_row`.
+    pub ctrl_clicked: bool,
     pub double_clicked_row: Option<usize>,
     pub hovered_row: Option<usize>,
+    /// Vertical scroll position, to restore in a later session.
+    pub scroll_offset: f32,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_process_table(
     ui: &mut egui::Ui,
     rows: &[TreeRow<'_>],
     selected_row: Option<usize>,
+    selected_processes: &HashSet<ProcessKey>,
     prev_hovered_row: Option<usize>,
+    sorted_by_start: bool,
+    show_cpu_time: bool,
+    thresholds: &UsageThresholds,
+    diff: &ProcessDiff,
+    scroll_to: Option<f32>,
+    mut filters: Option<&mut ColumnFilterRow>,
 ) -> ProcessTableResult {
     let mut action = None;
     let mut clicked_row = None;
     let mut double_clicked_row = None;
     let mut hovered_row = None;
 
-    if rows.is_empty() {
+    // Keep the header while the filter row is shown so its boxes stay editable
+    if rows.is_empty() && filters.is_none() {
         ui.vertical_centered(|ui| {
             ui.add_space(40.0);
             ui.label("No processes. Click \"Refresh\" to reload.");
@@ -34,55 +62,194 @@ pub fn render_process_table(
         return ProcessTableResult {
             action: None,
             clicked_row: None,
+            ctrl_clicked: false,
             double_clicked_row: None,
             hovered_row: None,
+            scroll_offset: 0.0,
         };
     }
 
     let available_height = ui.available_height();
 
-    let table = TableBuilder::new(ui)
+    // Separate width state per layout so toggling CPU Time doesn't shift widths
+    let salt = if show_cpu_time { "process_table_cpu_time" } else { "process_table" };
+    let fit = ColumnFit::new(ui, salt, || {
+        let measure = |header: &str, cell: fn(&ProcessInfo) -> String| {
+            Some(column_fit::text_width(
+                ui,
+                std::iter::once(header.to_string()).chain(rows.iter().map(|r| cell(&r.process))),
+            ))
+        };
+        // Names are indented by their depth in the tree, after the expand box
+        let name = rows
+            .iter()
+            .map(|r| column_fit::text_width(ui, [r.process.name.as_str()]) + r.depth as f32 * 18.0 + 13.0)
+            .fold(column_fit::text_width(ui, ["Name"]), f32::max);
+        let mut widths = vec![
+            measure("PID", |p| p.pid.to_string()),
+            Some(name),
+            measure("Product Name", |p| p.product_name.clone()),
+            measure("Command Line", |p| p.command_line.clone()),
+            measure("CPU %", |p| format!("{:.1}%", p.cpu_usage)),
+        ];
+        if show_cpu_time {
+            widths.push(measure("CPU Time", |p| {
+                p.cpu_times.map(|t| processes::format_cpu_time(t.total())).unwrap_or_default()
+            }));
+        }
+        widths.extend([
+            measure("Memory", |p| format_memory(p.memory_bytes)),
+            measure("Disk Read", |p| format_bytes(p.disk_read_bytes)),
+            measure("Disk Write", |p| format_bytes(p.disk_write_bytes)),
+            measure("Runs As", |p| p.user_name.clone()),
+            Some(column_fit::text_width(ui, ["Visible As", "Admin"])),
+            Some(column_fit::text_width(ui, ["Start Time \u{25B2}", "0000-00-00 00:00:00"])),
+            measure("Since Boot", |p| p.since_boot.map(processes::format_since_boot).unwrap_or_default()),
+            measure("Built / Signed", processes::format_binary_dates),
+            None, // Actions
+        ]);
+        widths
+    });
+    let mut table = TableBuilder::new(ui)
+        .id_salt(salt)
         .striped(true)
         .resizable(true)
         .sense(egui::Sense::click())
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-        .column(Column::initial(70.0).at_least(50.0))    // PID
-        .column(Column::initial(200.0).at_least(120.0))  // Name (with tree indent)
-        .column(Column::initial(180.0).at_least(80.0))   // Product Name
-        .column(Column::initial(400.0).at_least(150.0))  // Command Line
-        .column(Column::initial(60.0).at_least(45.0))    // CPU %
-        .column(Column::initial(80.0).at_least(60.0))    // Memory
-        .column(Column::initial(90.0).at_least(60.0))    // Disk Read
-        .column(Column::initial(90.0).at_least(60.0))    // Disk Write
-        .column(Column::initial(90.0).at_least(60.0))    // Runs As
-        .column(Column::initial(75.0).at_least(55.0))    // Visible As
-        .column(Column::initial(140.0).at_least(100.0))  // Start Time
-        .column(Column::remainder().at_least(160.0))      // Actions
+        .column(fit.column(70.0, 50.0))    // PID
+        .column(fit.column(200.0, 120.0))  // Name (with tree indent)
+        .column(fit.column(180.0, 80.0))   // Product Name
+        .column(fit.column(400.0, 150.0))  // Command Line
+        .column(fit.column(60.0, 45.0));   // CPU %
+    if show_cpu_time {
+        table = table.column(fit.column(80.0, 60.0)); // CPU Time
+    }
+    let mut table = table
+        .column(fit.column(80.0, 60.0))    // Memory
+        .column(fit.column(90.0, 60.0))    // Disk Read
+        .column(fit.column(90.0, 60.0))    // Disk Write
+        .column(fit.column(90.0, 60.0))    // Runs As
+        .column(fit.column(75.0, 55.0))    // Visible As
+        .column(fit.column(140.0, 100.0))  // Start Time
+        .column(fit.column(110.0, 80.0))   // Since Boot
+        .column(fit.column(170.0, 90.0))   // Built / Signed
+        .column(Column::remainder().at_least(220.0))      // Actions
         .min_scrolled_height(0.0)
         .max_scroll_height(available_height);
+    if fit.pending() {
+        table.reset();
+    }
 
-    table
-        .header(20.0, |mut header| {
-            header.col(|ui| { ui.strong("PID"); });
-            header.col(|ui| { ui.strong("Name"); });
-            header.col(|ui| { ui.strong("Product Name"); });
-            header.col(|ui| { ui.strong("Command Line"); });
-            header.col(|ui| { ui.strong("CPU %"); });
-            header.col(|ui| { ui.strong("Memory"); });
-            header.col(|ui| { ui.strong("Disk Read"); });
-            header.col(|ui| { ui.strong("Disk Write"); });
-            header.col(|ui| { ui.strong("Runs As"); });
-            header.col(|ui| { ui.strong("Visible As"); });
-            header.col(|ui| { ui.strong("Start Time"); });
+    if let Some(offset) = scroll_to {
+        table = table.vertical_scroll_offset(offset);
+    }
+
+    let scroll = table
+        .header(filters::header_height(&filters), |mut header| {
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("PID");
+                    filters::filter_box(ui, &mut filters, "PID", "> 1000");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Name");
+                    filters::filter_box(ui, &mut filters, "Name", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Product Name");
+                    filters::filter_box(ui, &mut filters, "Product Name", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Command Line");
+                    filters::filter_box(ui, &mut filters, "Command Line", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("CPU %");
+                    filters::filter_box(ui, &mut filters, "CPU %", "> 5%");
+                });
+            });
+            if show_cpu_time {
+                header.col(|ui| {
+                    ui.strong("CPU Time")
+                        .on_hover_text("Total kernel + user CPU time used since the process started");
+                });
+            }
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Memory");
+                    filters::filter_box(ui, &mut filters, "Memory", "> 100 MB");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Disk Read");
+                    filters::filter_box(ui, &mut filters, "Disk Read", "> 10 MB");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Disk Write");
+                    filters::filter_box(ui, &mut filters, "Disk Write", "> 10 MB");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Runs As");
+                    filters::filter_box(ui, &mut filters, "Runs As", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Visible As");
+                    filters::filter_box(ui, &mut filters, "Visible As", "= Admin");
+                });
+            });
+            header.col(|ui| {
+                let text = if sorted_by_start { "Start Time \u{25B2}" } else { "Start Time" };
+                let resp = ui
+                    .add(egui::Label::new(egui::RichText::new(text).strong()).sense(egui::Sense::click()))
+                    .on_hover_text(if sorted_by_start {
+                        "Click to return to the process tree"
+                    } else {
+                        "Click to list processes in launch order"
+                    });
+                if resp.clicked() {
+                    action = Some(ProcessAction::ToggleStartTimeSort);
+                }
+            });
+            header.col(|ui| { ui.strong("Since Boot"); });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Built / Signed").on_hover_text(
+                        "When the executable was linked (from its PE header) and signed (from its \
+                         signature's timestamp). A recent date on a binary that should be long \
+                         installed is worth a look",
+                    );
+                    filters::filter_box(ui, &mut filters, "Built / Signed", "contains");
+                });
+            });
             header.col(|ui| { ui.strong("Actions"); });
+            fit.context_menu(&header.response());
         })
         .body(|body| {
+            fit.record(body.widths());
             body.rows(24.0, rows.len(), |mut row| {
                 let index = row.index();
                 let tree_row = &rows[index];
-                let proc = tree_row.process;
-                let is_selected = selected_row == Some(index);
+                let proc = &*tree_row.process;
+                let is_selected = selected_row == Some(index)
+                    || (!tree_row.is_group && selected_processes.contains(&proc.key()));
                 let was_hovered = prev_hovered_row == Some(index);
+                let exited = !tree_row.is_group && diff.has_exited(&proc.key());
+                let appeared = !tree_row.is_group && diff.appeared.contains(&proc.key());
 
                 if is_selected || was_hovered {
                     row.set_selected(true);
@@ -92,10 +259,11 @@ pub fn render_process_table(
                 let mut row_clicked = false;
                 let mut row_double_clicked = false;
 
-                // PID
+                // PID (blank for group header rows)
                 let (_, cell_resp) = row.col(|ui| {
+                    let pid_text = if tree_row.is_group { String::new() } else { proc.pid.to_string() };
                     let label = egui::Label::new(
-                        egui::RichText::new(proc.pid.to_string())
+                        egui::RichText::new(pid_text)
                             .color(egui::Color32::from_rgb(180, 180, 180)),
                     )
                     .sense(egui::Sense::click());
@@ -227,11 +395,61 @@ pub fn render_process_table(
                             }
                         }
 
-                        // Name label
-                        let label = egui::Label::new(&proc.name)
-                            .truncate()
-                            .sense(egui::Sense::click());
-                        let resp = ui.add(label);
+                        if let Some(mismatch) = &proc.image_mismatch {
+                            let resp = ui
+                                .add(super::table::image_mismatch_badge())
+                                .on_hover_text(mismatch.description());
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                        }
+                        if let Some(kind) = proc.volatile_drive {
+                            let resp = ui
+                                .add(super::table::drive_badge())
+                                .on_hover_text(kind.warning());
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                        }
+                        if let Some(platform) = proc.platform {
+                            let resp = ui
+                                .add(platform_badge(platform))
+                                .on_hover_text(platform.description());
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                        }
+                        if proc.bitness == Some(Bitness::Bit32) {
+                            let resp = ui
+                                .add(super::table::bitness_badge())
+                                .on_hover_text("32-bit process");
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                        }
+                        for badge in &proc.enrichment.badges {
+                            let resp = ui
+                                .add(super::table::enrichment_badge(badge))
+                                .on_hover_text(&badge.tooltip);
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                        }
+
+                        // Name label (bold for group header rows; green or
+                        // struck through when it started or exited since the
+                        // last refresh)
+                        let name_text = if tree_row.is_group {
+                            egui::RichText::new(&proc.name).strong()
+                        } else if exited {
+                            egui::RichText::new(&proc.name).strikethrough().color(egui::Color32::GRAY)
+                        } else if appeared {
+                            egui::RichText::new(&proc.name).color(egui::Color32::from_rgb(80, 200, 80))
+                        } else {
+                            egui::RichText::new(&proc.name)
+                        };
+                        let resp = ui.add(truncated_label(name_text));
+                        let mut resp = details_tooltip(ui, resp, &proc.name, || process_details(proc));
+                        if exited {
+                            resp = resp.on_hover_text("Exited since the last refresh");
+                        } else if appeared {
+                            resp = resp.on_hover_text("Started since the last refresh");
+                        }
                         row_hovered |= resp.hovered();
                         row_clicked |= resp.clicked();
                         row_double_clicked |= resp.double_clicked();
@@ -249,10 +467,8 @@ pub fn render_process_table(
                     } else {
                         egui::Color32::from_rgb(200, 200, 200)
                     };
-                    let label = egui::Label::new(egui::RichText::new(text).color(color))
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let resp = ui.add(truncated_label(egui::RichText::new(text).color(color)));
+                    let resp = details_tooltip(ui, resp, text, || process_details(proc));
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
@@ -273,10 +489,8 @@ pub fn render_process_table(
                     } else {
                         egui::Color32::from_rgb(200, 200, 200)
                     };
-                    let label = egui::Label::new(egui::RichText::new(text).color(color))
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let resp = ui.add(truncated_label(egui::RichText::new(text).color(color)));
+                    let resp = details_tooltip(ui, resp, text, || process_details(proc));
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
@@ -292,13 +506,7 @@ pub fn render_process_table(
                     } else {
                         "0%".to_string()
                     };
-                    let color = if proc.cpu_usage > 50.0 {
-                        egui::Color32::from_rgb(230, 80, 80)
-                    } else if proc.cpu_usage > 10.0 {
-                        egui::Color32::from_rgb(230, 160, 50)
-                    } else {
-                        ui.visuals().text_color()
-                    };
+                    let color = usage_color(ui, thresholds.cpu_level(proc.cpu_usage));
                     let label = egui::Label::new(egui::RichText::new(&text).color(color))
                         .sense(egui::Sense::click());
                     let resp = ui.add(label);
@@ -310,10 +518,41 @@ pub fn render_process_table(
                 row_clicked |= cell_resp.clicked();
                 row_double_clicked |= cell_resp.double_clicked();
 
+                // CPU Time (optional)
+                if show_cpu_time {
+                    let (_, cell_resp) = row.col(|ui| {
+                        let Some(times) = proc.cpu_times else {
+                            let resp = ui.add(
+                                egui::Label::new(egui::RichText::new("\u{2014}").color(egui::Color32::GRAY))
+                                    .sense(egui::Sense::click()),
+                            );
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                            row_double_clicked |= resp.double_clicked();
+                            return;
+                        };
+                        let label = egui::Label::new(processes::format_cpu_time(times.total()))
+                            .sense(egui::Sense::click());
+                        let resp = ui.add(label).on_hover_text(format!(
+                            "Kernel: {}\nUser: {}",
+                            processes::format_cpu_time(times.kernel),
+                            processes::format_cpu_time(times.user)
+                        ));
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                        row_double_clicked |= resp.double_clicked();
+                    });
+                    row_hovered |= cell_resp.hovered();
+                    row_clicked |= cell_resp.clicked();
+                    row_double_clicked |= cell_resp.double_clicked();
+                }
+
                 // Memory
                 let (_, cell_resp) = row.col(|ui| {
                     let text = format_memory(proc.memory_bytes);
-                    let label = egui::Label::new(&text).sense(egui::Sense::click());
+                    let color = usage_color(ui, thresholds.memory_level(proc.memory_bytes));
+                    let label = egui::Label::new(egui::RichText::new(&text).color(color))
+                        .sense(egui::Sense::click());
                     let resp = ui.add(label);
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
@@ -399,8 +638,49 @@ pub fn render_process_table(
                 row_clicked |= cell_resp.clicked();
                 row_double_clicked |= cell_resp.double_clicked();
 
-                // Actions: Kill + Properties
+                // Since Boot
                 let (_, cell_resp) = row.col(|ui| {
+                    let text = match proc.since_boot {
+                        Some(offset) => processes::format_since_boot(offset),
+                        None => "\u{2014}".to_string(),
+                    };
+                    let label = egui::Label::new(&text).sense(egui::Sense::click());
+                    let resp = ui.add(label);
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                    row_double_clicked |= resp.double_clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+                row_double_clicked |= cell_resp.double_clicked();
+
+                // Built / Signed
+                let (_, cell_resp) = row.col(|ui| {
+                    let text = processes::format_binary_dates(proc);
+                    let text = if text.is_empty() { "\u{2014}".to_string() } else { text };
+                    let mut resp = ui.add(egui::Label::new(&text).sense(egui::Sense::click()));
+                    if !tree_row.is_group {
+                        let signed = proc.enrichment.value("Signed On").filter(|s| !s.is_empty());
+                        resp = resp.on_hover_text(format!(
+                            "Built: {}\nSigned: {}",
+                            proc.link_time.map(|t| t.to_string()).unwrap_or_else(|| "unknown".to_string()),
+                            signed.unwrap_or("not timestamped"),
+                        ));
+                    }
+                    row_hovered |= resp.hovered();
+                    row_clicked |= resp.clicked();
+                    row_double_clicked |= resp.double_clicked();
+                });
+                row_hovered |= cell_resp.hovered();
+                row_clicked |= cell_resp.clicked();
+                row_double_clicked |= cell_resp.double_clicked();
+
+                // Actions: Kill + Properties + More menu (none for group header
+                // rows or exited processes)
+                let (_, cell_resp) = row.col(|ui| {
+                    if tree_row.is_group || exited {
+                        return;
+                    }
                     ui.horizontal(|ui| {
                         let btn_size = egui::vec2(65.0, 18.0);
 
@@ -409,9 +689,10 @@ pub fn render_process_table(
                         if can_kill {
                             if ui
                                 .add_sized(btn_size, egui::Button::new("Kill"))
+                                .on_hover_text(Action::Kill.help())
                                 .clicked()
                             {
-                                action = Some(ProcessAction::Kill(index));
+                                action = Some(ProcessAction::Kill(proc.key()));
                             }
                         } else {
                             ui.add_space(btn_size.x + ui.spacing().item_spacing.x);
@@ -419,9 +700,35 @@ pub fn render_process_table(
 
                         if ui
                             .add_sized(btn_size, egui::Button::new("Properties"))
+                            .on_hover_text(Action::Properties.help())
                             .clicked()
                         {
-                            action = Some(ProcessAction::Properties(index));
+                            action = Some(ProcessAction::Properties(proc.key()));
+                        }
+
+                        if can_kill {
+                            ui.menu_button("More", |ui| {
+                                if ui.button("Attach Debugger").clicked() {
+                                    action = Some(ProcessAction::AttachDebugger(proc.key()));
+                                    ui.close();
+                                }
+                                if !proc.is_elevated
+                                    && ui
+                                        .button("Restart as Administrator")
+                                        .on_hover_text("End the process and start it again with the same command line, elevated")
+                                        .clicked()
+                                {
+                                    action = Some(ProcessAction::RestartElevated(proc.key()));
+                                    ui.close();
+                                }
+                                ui.separator();
+                                for style in processes::KillCommandStyle::ALL {
+                                    if ui.button(style.label()).clicked() {
+                                        action = Some(ProcessAction::CopyKillCommand(proc.key(), style));
+                                        ui.close();
+                                    }
+                                }
+                            });
                         }
                     });
                 });
@@ -443,12 +750,50 @@ pub fn render_process_table(
     ProcessTableResult {
         action,
         clicked_row,
+        ctrl_clicked: clicked_row.is_some() && ui.input(|i| i.modifiers.command),
         double_clicked_row,
         hovered_row,
+        scroll_offset: scroll.state.offset.y,
+    }
+}
+
+/// Full details of a process for [`details_tooltip`].
+fn process_details(proc: &ProcessInfo) -> Vec<(&'static str, String)> {
+    let mut details = vec![
+        ("Name", proc.name.clone()),
+        ("PID", proc.pid.to_string()),
+        ("Product Name", proc.product_name.clone()),
+        ("Company", proc.company_name.clone()),
+        ("Command Line", proc.command_line.clone()),
+        ("Path", proc.exe_path.clone()),
+        ("Runs As", proc.user_name.clone()),
+    ];
+    if let Some(mismatch) = &proc.image_mismatch {
+        details.push(("Image", mismatch.description()));
+    }
+    details
+}
+
+/// Small platform tag (WSL, Docker, Sandbox) shown before the process name.
+fn platform_badge(platform: VirtPlatform) -> egui::Label {
+    egui::Label::new(
+        egui::RichText::new(platform.label())
+            .small()
+            .color(egui::Color32::from_rgb(160, 130, 220)),
+    )
+    .sense(egui::Sense::click())
+}
+
+/// Text color for a CPU or memory value at the given threshold level.
+pub fn usage_color(ui: &egui::Ui, level: UsageLevel) -> egui::Color32 {
+    match level {
+        UsageLevel::High => egui::Color32::from_rgb(230, 80, 80),
+        UsageLevel::Warning => egui::Color32::from_rgb(230, 160, 50),
+        UsageLevel::Normal => ui.visuals().text_color(),
     }
 }
 
-fn format_memory(bytes: u64) -> String {
+pub fn format_memory(bytes: u64) -> String {
     if bytes >= 1_073_741_824 {
         format!("{:.1} GB", bytes as f64 / 1_073_741_824.0)
     } else if bytes >= 1_048_576 {
@@ -0,0 +1,41 @@
+use crate::models::{SecurityItem, SecurityState};
+use eframe::egui;
+
+/// Render the Security Center status panel.
+pub fn render_security_panel(ui: &mut egui::Ui, items: &[SecurityItem]) {
+    ui.add_space(8.0);
+    ui.label(egui::RichText::new("Windows Security Center").strong().size(15.0));
+    ui.label(
+        egui::RichText::new("Context for auditing startup entries and services")
+            .color(egui::Color32::GRAY),
+    );
+    ui.add_space(8.0);
+
+    egui::Frame::group(ui.style())
+        .inner_margin(egui::Margin::symmetric(12, 8))
+        .show(ui, |ui| {
+            egui::Grid::new("security_status_grid")
+                .num_columns(3)
+                .spacing([24.0, 8.0])
+                .show(ui, |ui| {
+                    for item in items {
+                        ui.label(egui::RichText::new(&item.name).strong());
+                        ui.label(
+                            egui::RichText::new(item.state.to_string())
+                                .color(state_color(item.state)),
+                        );
+                        ui.label(&item.detail);
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn state_color(state: SecurityState) -> egui::Color32 {
+    match state {
+        SecurityState::Good => egui::Color32::from_rgb(80, 200, 80),
+        SecurityState::Warning => egui::Color32::from_rgb(230, 160, 50),
+        SecurityState::Bad => egui::Color32::from_rgb(230, 80, 80),
+        SecurityState::Unknown => egui::Color32::GRAY,
+    }
+}
@@ -1,5 +1,12 @@
+use super::column_fit::{self, ColumnFit};
+use super::filters::{self, ColumnFilterRow};
+use super::help::{self, Action};
+use super::process_table;
+use crate::cmdline;
+use crate::enrichment;
 use crate::gui::PendingAction;
 use crate::models::*;
+use crate::settings::UsageThresholds;
 use eframe::egui;
 use egui_extras::{Column, TableBuilder};
 
@@ -8,8 +15,25 @@ pub struct TableResult {
     pub clicked_row: Option<usize>,
     pub double_clicked_row: Option<usize>,
     pub hovered_row: Option<usize>,
+    /// Vertical scroll position, to restore in a later session.
+    pub scroll_offset: f32,
+    /// A sortable header was clicked.
+    pub sort_clicked: Option<EntrySort>,
 }
 
+/// Order of the Startup Apps rows, picked by clicking a sortable header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntrySort {
+    /// The order the scan found them in.
+    #[default]
+    Scan,
+    /// By profile user, then name.
+    User,
+    /// By scheduled task registration date, newest first.
+    TaskRegistered,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_table(
     ui: &mut egui::Ui,
     entries: &[StartupEntry],
@@ -19,8 +43,13 @@ pub fn render_table(
     last_time_header: &str,
     show_delete: bool,
     show_properties: bool,
+    usage_thresholds: Option<&UsageThresholds>,
+    scroll_to: Option<f32>,
+    mut filters: Option<&mut ColumnFilterRow>,
+    sort: EntrySort,
 ) -> TableResult {
     let mut action = None;
+    let mut sort_clicked = None;
     let mut clicked_row = None;
     let mut double_clicked_row = None;
     let mut hovered_row = None;
@@ -28,43 +57,217 @@ pub fn render_table(
     let available_height = ui.available_height();
     let show_col3 = col3_header.is_some();
 
+    let salt = if show_col3 { "startup_table" } else { "services_table" };
+    let extra_columns = enrichment::columns();
+    let fit = ColumnFit::new(ui, salt, || {
+        let measure = |header: &str, cell: &dyn Fn(&StartupEntry) -> String| {
+            Some(column_fit::text_width(ui, std::iter::once(header.to_string()).chain(entries.iter().map(cell))))
+        };
+        let mut widths = vec![
+            // Room for the drive, 32-bit, and NEW badges
+            measure("Name", &|e| e.name.clone()).map(|w| w + 48.0),
+            measure("Product Name", &|e| e.product_name.clone()),
+            measure("Command", &|e| match &e.script_payload {
+                Some(script) => format!("{} {}", script.host, script.payload),
+                None => e.command.clone(),
+            }),
+        ];
+        if let Some(header) = col3_header {
+            widths.push(measure(header, &|e| e.source.display_location()));
+            let users = entries.iter().map(|e| e.profile_user().to_string());
+            widths.push(Some(column_fit::text_width(ui, std::iter::once("User \u{25B2}".to_string()).chain(users))));
+            widths.push(measure("Task Author", &|e| {
+                e.task_registration.as_ref().map(|r| r.author.clone()).unwrap_or_default()
+            }));
+            widths.push(Some(column_fit::text_width(ui, ["Task Registered \u{25BC}", "0000-00-00 00:00:00"])));
+        }
+        widths.push(measure("Status", &|e| format!("{:?}", e.enabled)));
+        widths.push(measure("State", &|e| match e.run_history {
+            Some(history) => format!("{:?} {}", e.run_state, history.ratio()),
+            None => format!("{:?}", e.run_state),
+        }));
+        if usage_thresholds.is_some() {
+            widths.push(measure("CPU %", &|e| {
+                e.host_usage.map(|u| format!("{:.1}%", u.cpu_usage)).unwrap_or_default()
+            }));
+            widths.push(measure("Memory", &|e| {
+                e.host_usage.map(|u| process_table::format_memory(u.memory_bytes)).unwrap_or_default()
+            }));
+        }
+        widths.extend([
+            measure("Runs As", &|e| e.runs_as.clone()),
+            Some(column_fit::text_width(ui, ["Visible As", "Admin"])),
+            Some(column_fit::text_width(ui, [last_time_header, "0000-00-00 00:00:00"])),
+        ]);
+        for column in &extra_columns {
+            widths.push(measure(column, &|e| e.enrichment.value(column).unwrap_or_default().to_string()));
+        }
+        widths.push(None); // Actions
+        widths
+    });
     let mut builder = TableBuilder::new(ui)
+        .id_salt(salt)
         .striped(true)
         .resizable(true)
         .sense(egui::Sense::click())
         .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-        .column(Column::initial(160.0).at_least(80.0)) // Name
-        .column(Column::initial(180.0).at_least(80.0)) // Product Name
-        .column(Column::initial(300.0).at_least(100.0)); // Command
+        .column(fit.column(160.0, 80.0)) // Name
+        .column(fit.column(180.0, 80.0)) // Product Name
+        .column(fit.column(300.0, 100.0)); // Command
     if show_col3 {
-        builder = builder.column(Column::initial(220.0).at_least(80.0)); // Source
+        builder = builder
+            .column(fit.column(220.0, 80.0)) // Source
+            .column(fit.column(100.0, 60.0)) // User
+            .column(fit.column(120.0, 60.0)) // Task Author
+            .column(fit.column(140.0, 100.0)); // Task Registered
+    }
+    builder = builder
+        .column(fit.column(70.0, 60.0)) // Status
+        .column(fit.column(100.0, 55.0)); // State (+ run history)
+    if usage_thresholds.is_some() {
+        builder = builder
+            .column(fit.column(60.0, 45.0)) // CPU %
+            .column(fit.column(80.0, 60.0)); // Memory
     }
-    let table = builder
-        .column(Column::initial(70.0).at_least(60.0)) // Status
-        .column(Column::initial(65.0).at_least(55.0)) // State
-        .column(Column::initial(90.0).at_least(60.0)) // Runs As
-        .column(Column::initial(75.0).at_least(55.0)) // Visible As
-        .column(Column::initial(140.0).at_least(100.0)) // Last Ran / Last Started
+    builder = builder
+        .column(fit.column(90.0, 60.0)) // Runs As
+        .column(fit.column(75.0, 55.0)) // Visible As
+        .column(fit.column(140.0, 100.0)); // Last Ran / Last Started
+    for _ in &extra_columns {
+        builder = builder.column(fit.column(120.0, 60.0)); // Enrichment provider columns
+    }
+    let mut table = builder
         .column(Column::remainder().at_least(200.0)) // Actions
         .min_scrolled_height(0.0)
         .max_scroll_height(available_height);
+    if fit.pending() {
+        table.reset();
+    }
 
-    table
-        .header(20.0, |mut header| {
-            header.col(|ui| { ui.strong("Name"); });
-            header.col(|ui| { ui.strong("Product Name"); });
-            header.col(|ui| { ui.strong("Command"); });
+    if let Some(offset) = scroll_to {
+        table = table.vertical_scroll_offset(offset);
+    }
+
+    let scroll = table
+        .header(filters::header_height(&filters), |mut header| {
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Name");
+                    filters::filter_box(ui, &mut filters, "Name", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Product Name");
+                    filters::filter_box(ui, &mut filters, "Product Name", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Command");
+                    filters::filter_box(ui, &mut filters, "Command", "contains");
+                });
+            });
             if show_col3 {
-                header.col(|ui| { ui.strong(col3_header.unwrap()); });
+                header.col(|ui| {
+                    ui.vertical(|ui| {
+                        ui.strong(col3_header.unwrap());
+                        filters::filter_box(ui, &mut filters, "Source", "contains");
+                    });
+                });
+                header.col(|ui| {
+                    ui.vertical(|ui| {
+                        let sorted = sort == EntrySort::User;
+                        let text = if sorted { "User \u{25B2}" } else { "User" };
+                        let resp = ui
+                            .add(egui::Label::new(egui::RichText::new(text).strong()).sense(egui::Sense::click()))
+                            .on_hover_text(if sorted {
+                                "Whose profile the entry is in. Click to return to the scan order"
+                            } else {
+                                "Whose profile the entry is in. Click to sort by user"
+                            });
+                        if resp.clicked() {
+                            sort_clicked = Some(EntrySort::User);
+                        }
+                        filters::filter_box(ui, &mut filters, "User", "contains");
+                    });
+                });
+                header.col(|ui| {
+                    ui.vertical(|ui| {
+                        ui.strong("Task Author");
+                        filters::filter_box(ui, &mut filters, "Task Author", "contains");
+                    });
+                });
+                header.col(|ui| {
+                    ui.vertical(|ui| {
+                        let sorted = sort == EntrySort::TaskRegistered;
+                        let text = if sorted { "Task Registered \u{25BC}" } else { "Task Registered" };
+                        let resp = ui
+                            .add(egui::Label::new(egui::RichText::new(text).strong()).sense(egui::Sense::click()))
+                            .on_hover_text(if sorted {
+                                "Click to return to the scan order"
+                            } else {
+                                "Click to list scheduled tasks newest first"
+                            });
+                        if resp.clicked() {
+                            sort_clicked = Some(EntrySort::TaskRegistered);
+                        }
+                        filters::filter_box(ui, &mut filters, "Task Registered", "contains");
+                    });
+                });
             }
-            header.col(|ui| { ui.strong("Status"); });
-            header.col(|ui| { ui.strong("State"); });
-            header.col(|ui| { ui.strong("Runs As"); });
-            header.col(|ui| { ui.strong("Visible As"); });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Status");
+                    filters::filter_box(ui, &mut filters, "Status", "= Enabled");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("State");
+                    filters::filter_box(ui, &mut filters, "State", "= Running");
+                });
+            });
+            if usage_thresholds.is_some() {
+                header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("CPU %");
+                    filters::filter_box(ui, &mut filters, "CPU %", "> 5%");
+                });
+            });
+                header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Memory");
+                    filters::filter_box(ui, &mut filters, "Memory", "> 100 MB");
+                });
+            });
+            }
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Runs As");
+                    filters::filter_box(ui, &mut filters, "Runs As", "contains");
+                });
+            });
+            header.col(|ui| {
+                ui.vertical(|ui| {
+                    ui.strong("Visible As");
+                    filters::filter_box(ui, &mut filters, "Visible As", "= Admin");
+                });
+            });
             header.col(|ui| { ui.strong(last_time_header); });
+            for column in &extra_columns {
+                header.col(|ui| {
+                    ui.vertical(|ui| {
+                        ui.strong(*column);
+                        filters::filter_box(ui, &mut filters, column, "contains");
+                    });
+                });
+            }
             header.col(|ui| { ui.strong("Actions"); });
+            fit.context_menu(&header.response());
         })
         .body(|body| {
+            fit.record(body.widths());
             body.rows(24.0, entries.len(), |mut row| {
                 let index = row.index();
                 let entry = &entries[index];
@@ -80,12 +283,50 @@ pub fn render_table(
                 let mut row_clicked = false;
                 let mut row_double_clicked = false;
 
-                // Name
+                // Name (with badges for entries added this session, binaries on
+                // removable/network drives, 32-bit entries, recently installed
+                // services, entries left behind by an uninstall, and enrichment
+                // providers)
                 let (_, cell_resp) = row.col(|ui| {
-                    let label = egui::Label::new(&entry.name)
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    if let Some(origin) = &entry.session_origin {
+                        let resp = ui.add(new_badge()).on_hover_text(origin.to_string());
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    } else if let Some(installed) = entry.installed.filter(|_| entry.installed_within(30)) {
+                        let resp = ui.add(new_badge()).on_hover_text(format!(
+                            "Service installed or reconfigured on {}",
+                            installed.format("%Y-%m-%d %H:%M")
+                        ));
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    }
+                    if let Some(app) = &entry.orphaned_by {
+                        let resp = ui.add(orphaned_badge()).on_hover_text(format!(
+                            "Orphaned by uninstall: it is in the install folder of {}, which is no longer installed",
+                            app
+                        ));
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    }
+                    if let Some(kind) = entry.volatile_drive {
+                        let resp = ui
+                            .add(drive_badge())
+                            .on_hover_text(kind.warning());
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    }
+                    if let Some(note) = entry.bitness_note() {
+                        let resp = ui.add(bitness_badge()).on_hover_text(note);
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    }
+                    for badge in &entry.enrichment.badges {
+                        let resp = ui.add(enrichment_badge(badge)).on_hover_text(&badge.tooltip);
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    }
+                    let resp = ui.add(truncated_label(&entry.name));
+                    let resp = details_tooltip(ui, resp, &entry.name, || entry_details(entry));
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
@@ -102,10 +343,8 @@ pub fn render_table(
                     } else {
                         egui::Color32::from_rgb(200, 200, 200)
                     };
-                    let label = egui::Label::new(egui::RichText::new(text).color(color))
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let resp = ui.add(truncated_label(egui::RichText::new(text).color(color)));
+                    let resp = details_tooltip(ui, resp, text, || entry_details(entry));
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
@@ -114,12 +353,28 @@ pub fn render_table(
                 row_clicked |= cell_resp.clicked();
                 row_double_clicked |= cell_resp.double_clicked();
 
-                // Command
+                // Command (script-host commands show the effective payload)
                 let (_, cell_resp) = row.col(|ui| {
-                    let label = egui::Label::new(&entry.command)
-                        .truncate()
-                        .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let resp = match &entry.script_payload {
+                        Some(script) => {
+                            ui.label(
+                                egui::RichText::new(&script.host)
+                                    .small()
+                                    .color(egui::Color32::from_rgb(100, 160, 230)),
+                            );
+                            let label = egui::Label::new(&script.payload)
+                                .truncate()
+                                .sense(egui::Sense::click());
+                            ui.add(label).on_hover_text(format!(
+                                "Runs via {}: {}\n\n{}",
+                                script.host, script.payload, entry.command
+                            ))
+                        }
+                        None => {
+                            let resp = ui.add(truncated_label(&entry.command));
+                            details_tooltip(ui, resp, &entry.command, || entry_details(entry))
+                        }
+                    };
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
@@ -132,15 +387,35 @@ pub fn render_table(
                 if show_col3 {
                     let (_, cell_resp) = row.col(|ui| {
                         let loc = entry.source.display_location();
-                        let label = egui::Label::new(&loc)
-                            .truncate()
-                            .sense(egui::Sense::click());
-                        let resp = ui.add(label);
+                        let resp = ui.add(truncated_label(&loc));
+                        let mut resp = details_tooltip(ui, resp, &loc, || entry_details(entry));
+                        // Who/what registered a scheduled task
+                        if let Some(reg) = &entry.task_registration {
+                            resp = resp.on_hover_text(reg.summary());
+                        }
                         row_hovered |= resp.hovered();
                         row_clicked |= resp.clicked();
                     });
                     row_hovered |= cell_resp.hovered();
                     row_clicked |= cell_resp.clicked();
+
+                    // User, then Task Author and Task Registered (scheduled
+                    // tasks only)
+                    let registration = entry.task_registration.as_ref();
+                    let author = registration.map(|r| r.author.clone()).unwrap_or_default();
+                    let registered = registration.map(|r| r.registered_text()).unwrap_or_default();
+                    for text in [entry.profile_user().to_string(), author, registered] {
+                        let (_, cell_resp) = row.col(|ui| {
+                            let resp = ui.add(truncated_label(&text));
+                            let resp = details_tooltip(ui, resp, &text, || entry_details(entry));
+                            row_hovered |= resp.hovered();
+                            row_clicked |= resp.clicked();
+                            row_double_clicked |= resp.double_clicked();
+                        });
+                        row_hovered |= cell_resp.hovered();
+                        row_clicked |= cell_resp.clicked();
+                        row_double_clicked |= cell_resp.double_clicked();
+                    }
                 }
 
                 // Status (color-coded)
@@ -170,6 +445,28 @@ pub fn render_table(
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
+
+                    // Badge for configuration changed this session
+                    if entry.config_changed {
+                        let badge = if entry.run_state == RunState::Running {
+                            "\u{21bb}"
+                        } else {
+                            "*"
+                        };
+                        let tip = if entry.run_state == RunState::Running {
+                            "Changed this session \u{2014} restart required"
+                        } else {
+                            "Changed this session"
+                        };
+                        let resp = ui.add(
+                            egui::Label::new(
+                                egui::RichText::new(badge).color(egui::Color32::from_rgb(230, 160, 50)),
+                            )
+                            .sense(egui::Sense::click()),
+                        ).on_hover_text(tip);
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                    }
                 });
                 row_hovered |= cell_resp.hovered();
                 row_clicked |= cell_resp.clicked();
@@ -194,11 +491,81 @@ pub fn render_table(
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
+
+                    // Sessions seen running, e.g. "12/14"
+                    if let Some(history) = entry.run_history {
+                        let label = egui::Label::new(
+                            egui::RichText::new(history.ratio())
+                                .small()
+                                .color(egui::Color32::GRAY),
+                        ).sense(egui::Sense::click());
+                        let resp = ui.add(label).on_hover_text(history.to_string());
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                        row_double_clicked |= resp.double_clicked();
+                    }
                 });
                 row_hovered |= cell_resp.hovered();
                 row_clicked |= cell_resp.clicked();
                 row_double_clicked |= cell_resp.double_clicked();
 
+                // CPU % and Memory of the hosting process (services)
+                if let Some(thresholds) = usage_thresholds {
+                    let host_note = entry.host_usage.filter(|u| u.services_in_process > 1).map(|u| {
+                        format!(
+                            "Shared process (PID {}) hosting {} services; figures are for the whole process",
+                            u.pid, u.services_in_process
+                        )
+                    });
+                    let (_, cell_resp) = row.col(|ui| {
+                        let (text, color) = match entry.host_usage {
+                            Some(u) => (
+                                if u.cpu_usage > 0.05 { format!("{:.1}%", u.cpu_usage) } else { "0%".to_string() },
+                                process_table::usage_color(ui, thresholds.cpu_level(u.cpu_usage)),
+                            ),
+                            None => ("--".to_string(), egui::Color32::GRAY),
+                        };
+                        let label = egui::Label::new(egui::RichText::new(text).color(color))
+                            .sense(egui::Sense::click());
+                        let mut resp = ui.add(label);
+                        if let Some(note) = &host_note {
+                            resp = resp.on_hover_text(note);
+                        }
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                        row_double_clicked |= resp.double_clicked();
+                    });
+                    row_hovered |= cell_resp.hovered();
+                    row_clicked |= cell_resp.clicked();
+                    row_double_clicked |= cell_resp.double_clicked();
+
+                    let (_, cell_resp) = row.col(|ui| {
+                        let (text, color) = match entry.host_usage {
+                            Some(u) => (
+                                process_table::format_memory(u.memory_bytes),
+                                process_table::usage_color(ui, thresholds.memory_level(u.memory_bytes)),
+                            ),
+                            None => ("--".to_string(), egui::Color32::GRAY),
+                        };
+                        let shared = entry.host_usage.is_some_and(|u| u.services_in_process > 1);
+                        let mut rich = egui::RichText::new(text).color(color);
+                        if shared {
+                            rich = rich.italics();
+                        }
+                        let label = egui::Label::new(rich).sense(egui::Sense::click());
+                        let mut resp = ui.add(label);
+                        if let Some(note) = &host_note {
+                            resp = resp.on_hover_text(note);
+                        }
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                        row_double_clicked |= resp.double_clicked();
+                    });
+                    row_hovered |= cell_resp.hovered();
+                    row_clicked |= cell_resp.clicked();
+                    row_double_clicked |= cell_resp.double_clicked();
+                }
+
                 // Runs As
                 let (_, cell_resp) = row.col(|ui| {
                     let text = if entry.runs_as.is_empty() { "--" } else { &entry.runs_as };
@@ -241,7 +608,10 @@ pub fn render_table(
                     };
                     let label = egui::Label::new(&text)
                         .sense(egui::Sense::click());
-                    let resp = ui.add(label);
+                    let mut resp = ui.add(label);
+                    if let Some(source) = entry.last_ran_source.filter(|_| entry.last_ran.is_some()) {
+                        resp = resp.on_hover_text(source.description());
+                    }
                     row_hovered |= resp.hovered();
                     row_clicked |= resp.clicked();
                     row_double_clicked |= resp.double_clicked();
@@ -250,43 +620,79 @@ pub fn render_table(
                 row_clicked |= cell_resp.clicked();
                 row_double_clicked |= cell_resp.double_clicked();
 
+                // Enrichment provider columns
+                for column in &extra_columns {
+                    let (_, cell_resp) = row.col(|ui| {
+                        let text = entry.enrichment.value(column).filter(|v| !v.is_empty()).unwrap_or("--");
+                        let label = egui::Label::new(text)
+                            .truncate()
+                            .sense(egui::Sense::click());
+                        let resp = ui.add(label);
+                        row_hovered |= resp.hovered();
+                        row_clicked |= resp.clicked();
+                        row_double_clicked |= resp.double_clicked();
+                    });
+                    row_hovered |= cell_resp.hovered();
+                    row_clicked |= cell_resp.clicked();
+                    row_double_clicked |= cell_resp.double_clicked();
+                }
+
                 // Actions (fixed-width buttons for alignment)
                 let (_, cell_resp) = row.col(|ui| {
                     ui.horizontal(|ui| {
                         let btn_size = egui::vec2(55.0, 18.0);
 
-                        let is_run_once = matches!(entry.source, Source::RegistryRunOnce { .. });
-                        if !is_run_once {
-                            let (label, act) = match entry.enabled {
-                                EnabledStatus::Enabled => ("Disable", PendingAction::Disable(index)),
-                                EnabledStatus::Disabled => ("Enable", PendingAction::Enable(index)),
-                                EnabledStatus::Manual => ("Disable", PendingAction::Disable(index)),
-                                EnabledStatus::Unknown => ("Disable", PendingAction::Disable(index)),
+                        let can_toggle = !matches!(
+                            entry.source,
+                            Source::RegistryRunOnce { .. } | Source::Ifeo { .. }
+                        );
+                        if can_toggle {
+                            let (help_action, act) = match entry.enabled {
+                                EnabledStatus::Enabled => (Action::Disable, PendingAction::Disable(entry.id())),
+                                EnabledStatus::Disabled => (Action::Enable, PendingAction::Enable(entry.id())),
+                                EnabledStatus::Manual => (Action::Disable, PendingAction::Disable(entry.id())),
+                                EnabledStatus::Unknown => (Action::Disable, PendingAction::Disable(entry.id())),
                             };
-                            if ui.add_sized(btn_size, egui::Button::new(label)).clicked() {
+                            if ui
+                                .add_sized(btn_size, egui::Button::new(help_action.label()))
+                                .on_hover_text(help::entry_action_help(help_action, &entry.source))
+                                .clicked()
+                            {
                                 action = Some(act);
                             }
                         } else {
                             ui.add_space(btn_size.x + ui.spacing().item_spacing.x);
                         }
 
-                        let (label, act) = match entry.run_state {
-                            RunState::Running => ("Stop", PendingAction::Stop(index)),
-                            RunState::Stopped => ("Start", PendingAction::Start(index)),
+                        let (help_action, act) = match entry.run_state {
+                            RunState::Running => (Action::Stop, PendingAction::Stop(entry.id())),
+                            RunState::Stopped => (Action::Start, PendingAction::Start(entry.id())),
                         };
-                        if ui.add_sized(btn_size, egui::Button::new(label)).clicked() {
+                        if ui
+                            .add_sized(btn_size, egui::Button::new(help_action.label()))
+                            .on_hover_text(help::entry_action_help(help_action, &entry.source))
+                            .clicked()
+                        {
                             action = Some(act);
                         }
 
                         if show_delete {
-                            if ui.add_sized(btn_size, egui::Button::new("Delete")).clicked() {
-                                action = Some(PendingAction::ConfirmDelete(index));
+                            if ui
+                                .add_sized(btn_size, egui::Button::new("Delete"))
+                                .on_hover_text(help::entry_action_help(Action::Delete, &entry.source))
+                                .clicked()
+                            {
+                                action = Some(PendingAction::ConfirmDelete(entry.id()));
                             }
                         }
 
                         if show_properties {
-                            if ui.add_sized(btn_size, egui::Button::new("Properties")).clicked() {
-                                action = Some(PendingAction::Properties(index));
+                            if ui
+                                .add_sized(btn_size, egui::Button::new("Properties"))
+                                .on_hover_text(Action::Properties.help())
+                                .clicked()
+                            {
+                                action = Some(PendingAction::Properties(entry.id()));
                             }
                         }
                     });
@@ -306,5 +712,135 @@ pub fn render_table(
             });
         });
 
-    TableResult { action, clicked_row, double_clicked_row, hovered_row }
+    TableResult {
+        action,
+        clicked_row,
+        double_clicked_row,
+        hovered_row,
+        scroll_offset: scroll.state.offset.y,
+        sort_clicked,
+    }
+}
+
+/// A cell label cut off with "…" when the column is too narrow. Unlike a
+/// plain truncated label it has no full-text tooltip of its own; pass the
+/// response to [`details_tooltip`] to show the whole row instead.
+pub fn truncated_label(text: impl Into<egui::WidgetText>) -> egui::Label {
+    egui::Label::new(text)
+        .truncate()
+        .show_tooltip_when_elided(false)
+        .sense(egui::Sense::click())
+}
+
+/// When `text` didn't fit in its [`truncated_label`], show the row's full,
+/// untruncated `details` (label and value; empty values are left out) on
+/// hover. They are only built while the tooltip is showing.
+pub fn details_tooltip(
+    ui: &egui::Ui,
+    resp: egui::Response,
+    text: &str,
+    details: impl FnOnce() -> Vec<(&'static str, String)>,
+) -> egui::Response {
+    if !resp.hovered() {
+        return resp;
+    }
+    let font = egui::TextStyle::Body.resolve(ui.style());
+    let full_width = ui.fonts_mut(|fonts| {
+        fonts
+            .layout_no_wrap(text.to_owned(), font, egui::Color32::WHITE)
+            .size()
+            .x
+    });
+    if full_width <= resp.rect.width() + 0.5 {
+        return resp;
+    }
+    resp.on_hover_ui(|ui| {
+        ui.set_max_width(640.0);
+        egui::Grid::new("cell_details_tooltip")
+            .num_columns(2)
+            .spacing([12.0, 4.0])
+            .show(ui, |ui| {
+                for (label, value) in details().into_iter().filter(|(_, v)| !v.is_empty()) {
+                    ui.label(egui::RichText::new(label).strong());
+                    ui.add(egui::Label::new(value).wrap());
+                    ui.end_row();
+                }
+            });
+    })
+}
+
+/// Full details of a startup entry for [`details_tooltip`].
+fn entry_details(entry: &StartupEntry) -> Vec<(&'static str, String)> {
+    vec![
+        ("Name", entry.name.clone()),
+        ("Product Name", entry.product_name.clone()),
+        ("Company", entry.company_name.clone()),
+        ("Command", entry.command.clone()),
+        ("Path", cmdline::target_path(&entry.command)),
+        ("Location", entry.source.display_location()),
+        ("Runs As", entry.runs_as.clone()),
+        ("User", entry.profile_user().to_string()),
+        ("Task Author", entry.task_registration.as_ref().map(|r| r.author.clone()).unwrap_or_default()),
+        ("Task Registered", entry.task_registration.as_ref().map(|r| r.registered_text()).unwrap_or_default()),
+    ]
+}
+
+/// Orange warning badge for binaries on removable or network drives.
+/// Small "32" marker for 32-bit binaries and `Wow6432Node` registry entries.
+pub fn bitness_badge() -> egui::Label {
+    egui::Label::new(
+        egui::RichText::new("32")
+            .small()
+            .color(egui::Color32::from_rgb(100, 160, 230)),
+    )
+    .sense(egui::Sense::click())
+}
+
+/// Green "NEW" marker for entries added this session and services installed
+/// in the last 30 days.
+pub fn new_badge() -> egui::Label {
+    egui::Label::new(
+        egui::RichText::new("NEW")
+            .small()
+            .color(egui::Color32::from_rgb(80, 200, 80)),
+    )
+    .sense(egui::Sense::click())
+}
+
+/// Orange marker for entries in the install folder of an uninstalled app.
+pub fn orphaned_badge() -> egui::Label {
+    egui::Label::new(
+        egui::RichText::new("ORPHANED")
+            .small()
+            .color(egui::Color32::from_rgb(230, 160, 50)),
+    )
+    .sense(egui::Sense::click())
+}
+
+/// Badge from an enrichment provider, e.g. "UNSIGNED".
+pub fn enrichment_badge(badge: &Badge) -> egui::Label {
+    let color = if badge.warning {
+        egui::Color32::from_rgb(230, 160, 50)
+    } else {
+        egui::Color32::from_rgb(100, 160, 230)
+    };
+    egui::Label::new(egui::RichText::new(&badge.text).small().color(color)).sense(egui::Sense::click())
+}
+
+/// Red marker for a process whose mapped image doesn't match its
+/// executable.
+pub fn image_mismatch_badge() -> egui::Label {
+    egui::Label::new(
+        egui::RichText::new("HOLLOWED?")
+            .small()
+            .color(egui::Color32::from_rgb(230, 80, 80)),
+    )
+    .sense(egui::Sense::click())
+}
+
+pub fn drive_badge() -> egui::Label {
+    egui::Label::new(
+        egui::RichText::new("\u{26A0}").color(egui::Color32::from_rgb(230, 160, 50)),
+    )
+    .sense(egui::Sense::click())
 }
@@ -0,0 +1,211 @@
+//! "Copy/Save View as Image": every row of the current tab (not just the
+//! ones scrolled into view) drawn off-screen, for attaching to a ticket. The
+//! rows and columns come from the tab's CSV export, so the image honors the
+//! same filters. egui lays out and tessellates the text; a small rasterizer
+//! fills in the triangles, since egui only renders to the window.
+
+use eframe::egui::{self, epaint, Color32, Pos2};
+
+const FONT_SIZE: f32 = 12.0;
+const ROW_HEIGHT: f32 = 20.0;
+const CELL_PADDING: f32 = 6.0;
+/// Longer text is cut off with "…".
+const MAX_COLUMN_WIDTH: f32 = 320.0;
+/// Larger images are refused; filter the table first.
+const MAX_PIXELS: usize = 80_000_000;
+
+/// Draw `csv` (a tab's CSV export) as a table under a `title` line.
+pub fn render(ctx: &egui::Context, title: &str, csv: &str) -> Result<egui::ColorImage, String> {
+    let rows = parse_csv(csv);
+    let Some((header, body)) = rows.split_first() else {
+        return Err("Nothing to draw".to_string());
+    };
+    let visuals = ctx.style().visuals.clone();
+    let font = egui::FontId::proportional(FONT_SIZE);
+    let layout = |text: &str, color: Color32, max_width: f32| {
+        let mut job = egui::text::LayoutJob::single_section(
+            text.replace(['\r', '\n'], " "),
+            egui::TextFormat::simple(font.clone(), color),
+        );
+        job.wrap = egui::text::TextWrapping {
+            max_width,
+            max_rows: 1,
+            break_anywhere: true,
+            overflow_character: Some('\u{2026}'),
+        };
+        ctx.fonts_mut(|f| f.layout_job(job))
+    };
+
+    // Lay out every cell once, then size the columns to their widest cell
+    let header_galleys: Vec<_> = header
+        .iter()
+        .map(|text| layout(text, visuals.strong_text_color(), MAX_COLUMN_WIDTH))
+        .collect();
+    let body_galleys: Vec<Vec<_>> = body
+        .iter()
+        .map(|row| row.iter().map(|text| layout(text, visuals.text_color(), MAX_COLUMN_WIDTH)).collect())
+        .collect();
+    let mut widths: Vec<f32> = header_galleys.iter().map(|g| g.size().x).collect();
+    for row in &body_galleys {
+        for (i, galley) in row.iter().enumerate() {
+            if i < widths.len() {
+                widths[i] = widths[i].max(galley.size().x);
+            }
+        }
+    }
+    let widths: Vec<f32> = widths.iter().map(|w| w.ceil() + CELL_PADDING * 2.0).collect();
+    let table_width: f32 = widths.iter().sum();
+    let title_galley = layout(title, visuals.strong_text_color(), f32::INFINITY);
+    let width_pts = table_width.max(title_galley.size().x + CELL_PADDING * 2.0);
+    let table_top = ROW_HEIGHT + CELL_PADDING;
+    let height_pts = table_top + ROW_HEIGHT * rows.len() as f32;
+
+    let ppp = ctx.pixels_per_point();
+    let size = [(width_pts * ppp).ceil() as usize, (height_pts * ppp).ceil() as usize];
+    if size[0] * size[1] > MAX_PIXELS {
+        return Err(format!(
+            "The table is too large for an image ({} x {} pixels); filter it first",
+            size[0], size[1]
+        ));
+    }
+
+    let full = egui::Rect::from_min_size(Pos2::ZERO, egui::vec2(width_pts, height_pts));
+    let mut shapes = vec![
+        egui::Shape::rect_filled(full, 0.0, visuals.panel_fill),
+        egui::Shape::galley(
+            egui::pos2(CELL_PADDING, (ROW_HEIGHT - title_galley.size().y) / 2.0),
+            title_galley,
+            visuals.strong_text_color(),
+        ),
+    ];
+    let grid = visuals.widgets.noninteractive.bg_stroke;
+    for (index, galleys) in std::iter::once(&header_galleys).chain(&body_galleys).enumerate() {
+        let top = table_top + ROW_HEIGHT * index as f32;
+        let row_rect = egui::Rect::from_min_size(egui::pos2(0.0, top), egui::vec2(table_width, ROW_HEIGHT));
+        if index == 0 {
+            shapes.push(egui::Shape::rect_filled(row_rect, 0.0, visuals.extreme_bg_color));
+        } else if index % 2 == 0 {
+            shapes.push(egui::Shape::rect_filled(row_rect, 0.0, visuals.faint_bg_color));
+        }
+        let mut left = 0.0;
+        for (galley, width) in galleys.iter().zip(&widths) {
+            let pos = egui::pos2(left + CELL_PADDING, top + (ROW_HEIGHT - galley.size().y) / 2.0);
+            shapes.push(egui::Shape::galley(pos, galley.clone(), visuals.text_color()));
+            left += width;
+        }
+        shapes.push(egui::Shape::hline(0.0..=table_width, top + ROW_HEIGHT, grid));
+    }
+    let mut left = 0.0;
+    for width in &widths[..widths.len().saturating_sub(1)] {
+        left += width;
+        shapes.push(egui::Shape::vline(left, table_top..=height_pts, grid));
+    }
+
+    let clipped = shapes
+        .into_iter()
+        .map(|shape| epaint::ClippedShape { clip_rect: full, shape })
+        .collect();
+    let primitives = ctx.tessellate(clipped, ppp);
+    // Read the atlas after tessellating: laying out new text can add glyphs
+    let atlas = ctx.fonts(|f| f.image());
+    let mut pixels = vec![Color32::TRANSPARENT; size[0] * size[1]];
+    for primitive in primitives {
+        if let epaint::Primitive::Mesh(mesh) = primitive.primitive {
+            if mesh.texture_id == egui::TextureId::default() {
+                draw_mesh(&mut pixels, size, ppp, &mesh, &atlas);
+            }
+        }
+    }
+    Ok(egui::ColorImage::new(size, pixels))
+}
+
+/// Encode an image as PNG.
+pub fn encode_png(image: &egui::ColorImage) -> Result<Vec<u8>, String> {
+    let rgba: Vec<u8> = image.pixels.iter().flat_map(|p| p.to_srgba_unmultiplied()).collect();
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, image.size[0] as u32, image.size[1] as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer.write_image_data(&rgba).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(out)
+}
+
+/// Fill a mesh's triangles, textured by the font atlas (solid shapes use its
+/// white texel), blending premultiplied colors over what is already there.
+fn draw_mesh(pixels: &mut [Color32], size: [usize; 2], ppp: f32, mesh: &epaint::Mesh, atlas: &egui::ColorImage) {
+    let edge = |a: Pos2, b: Pos2, p: Pos2| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+    for triangle in mesh.indices.chunks_exact(3) {
+        let v = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+        let p = v.map(|v| (v.pos.to_vec2() * ppp).to_pos2());
+        let area = edge(p[0], p[1], p[2]);
+        if area.abs() < f32::EPSILON {
+            continue;
+        }
+        let min_x = p.iter().map(|p| p.x).fold(f32::INFINITY, f32::min).floor().max(0.0) as usize;
+        let min_y = p.iter().map(|p| p.y).fold(f32::INFINITY, f32::min).floor().max(0.0) as usize;
+        let max_x = (p.iter().map(|p| p.x).fold(0.0, f32::max).ceil() as usize).min(size[0]);
+        let max_y = (p.iter().map(|p| p.y).fold(0.0, f32::max).ceil() as usize).min(size[1]);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let center = egui::pos2(x as f32 + 0.5, y as f32 + 0.5);
+                let w = [
+                    edge(p[1], p[2], center) / area,
+                    edge(p[2], p[0], center) / area,
+                    edge(p[0], p[1], center) / area,
+                ];
+                if w.iter().any(|w| *w < 0.0) {
+                    continue;
+                }
+                let u = w[0] * v[0].uv.x + w[1] * v[1].uv.x + w[2] * v[2].uv.x;
+                let t = w[0] * v[0].uv.y + w[1] * v[1].uv.y + w[2] * v[2].uv.y;
+                let tx = ((u * atlas.size[0] as f32) as usize).min(atlas.size[0] - 1);
+                let ty = ((t * atlas.size[1] as f32) as usize).min(atlas.size[1] - 1);
+                let texel = atlas.pixels[ty * atlas.size[0] + tx].to_array();
+                let src: [f32; 4] = std::array::from_fn(|c| {
+                    let color = w[0] * v[0].color[c] as f32 + w[1] * v[1].color[c] as f32 + w[2] * v[2].color[c] as f32;
+                    color * texel[c] as f32 / 255.0
+                });
+                let dst = &mut pixels[y * size[0] + x];
+                let keep = 1.0 - src[3] / 255.0;
+                let out: [u8; 4] = std::array::from_fn(|c| (src[c] + dst[c] as f32 * keep).round().min(255.0) as u8);
+                *dst = Color32::from_rgba_premultiplied(out[0], out[1], out[2], out[3]);
+            }
+        }
+    }
+}
+
+/// Split CSV text into rows of fields, skipping the `#` metadata lines at
+/// the top.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut lines = text.lines().peekable();
+    while lines.next_if(|line| line.starts_with('#')).is_some() {}
+    let text: String = lines.collect::<Vec<_>>().join("\n");
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
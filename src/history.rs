@@ -0,0 +1,128 @@
+//! Per-entry run history across App Manager sessions.
+//!
+//! Each time the app is started counts as one session. An entry is recorded as
+//! "seen running" for a session if any scan during that session found it
+//! running. Only the most recent sessions are kept, giving evidence like
+//! "seen running in 12 of last 14 sessions" when deciding what to disable.
+//!
+//! Stored as a tab-separated text file in the app data directory:
+//! `<entry key>\t<observations>`, where observations are `1`/`0` per session,
+//! oldest first.
+
+use crate::app_data;
+use crate::models::{EntryId, RunHistoryStats, RunState, StartupEntry};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Number of sessions kept per entry.
+const MAX_SESSIONS: usize = 30;
+
+const FILE_NAME: &str = "run-history.tsv";
+const HEADER: &str = "# App Manager run history v1";
+
+pub struct RunHistory {
+    path: Option<PathBuf>,
+    observations: HashMap<String, Vec<bool>>,
+    /// Entries that already have an observation slot for this session.
+    seen_this_session: HashSet<String>,
+}
+
+impl RunHistory {
+    /// Load the history file, starting empty if it doesn't exist or can't be read.
+    pub fn load() -> Self {
+        let path = app_data::data_dir().map(|dir| dir.join(FILE_NAME));
+        let mut observations = HashMap::new();
+        if let Some(text) = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for line in text.lines() {
+                if line.starts_with('#') {
+                    continue;
+                }
+                let Some((key, obs)) = line.rsplit_once('\t') else {
+                    continue;
+                };
+                let obs: Vec<bool> = obs.chars().map(|c| c == '1').collect();
+                observations.insert(key.to_string(), obs);
+            }
+        }
+        Self {
+            path,
+            observations,
+            seen_this_session: HashSet::new(),
+        }
+    }
+
+    /// Record the run state of each entry from a scan and save the file.
+    pub fn record(&mut self, entries: &[StartupEntry]) {
+        for entry in entries {
+            let key = entry_key(&entry.id());
+            let running = entry.run_state == RunState::Running;
+            let obs = self.observations.entry(key.clone()).or_default();
+            if self.seen_this_session.insert(key) {
+                obs.push(running);
+                if obs.len() > MAX_SESSIONS {
+                    obs.drain(..obs.len() - MAX_SESSIONS);
+                }
+            } else if let Some(last) = obs.last_mut() {
+                *last |= running;
+            }
+        }
+        self.save();
+    }
+
+    /// Run-history stats for an entry, if it has been observed before.
+    pub fn stats(&self, id: &EntryId) -> Option<RunHistoryStats> {
+        let obs = self.observations.get(&entry_key(id))?;
+        if obs.is_empty() {
+            return None;
+        }
+        Some(RunHistoryStats {
+            sessions_running: obs.iter().filter(|r| **r).count(),
+            sessions_observed: obs.len(),
+        })
+    }
+
+    /// Fill in `run_history` on each entry.
+    pub fn annotate(&self, entries: &mut [StartupEntry]) {
+        for entry in entries {
+            entry.run_history = self.stats(&entry.id());
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut keys: Vec<&String> = self.observations.keys().collect();
+        keys.sort();
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for key in keys {
+            let obs: String = self.observations[key]
+                .iter()
+                .map(|r| if *r { '1' } else { '0' })
+                .collect();
+            out.push_str(key);
+            out.push('\t');
+            out.push_str(&obs);
+            out.push('\n');
+        }
+        // Best effort: history is advisory, so a failed write isn't reported
+        let _ = app_data::write_atomic(path, out);
+    }
+}
+
+/// Stable text key for an entry. Tabs and newlines are replaced so the key
+/// fits on one line of the history file.
+pub fn entry_key(id: &EntryId) -> String {
+    let key = match id {
+        EntryId::Registry {
+            hive,
+            key_path,
+            value_name,
+        } => format!("reg:{}\\{}\\{}", hive, key_path, value_name),
+        EntryId::StartupFile { path } => format!("file:{}", path),
+        EntryId::Task { task_path } => format!("task:{}", task_path),
+        EntryId::Service { service_name } => format!("service:{}", service_name),
+    };
+    key.replace(['\t', '\n', '\r'], " ").to_lowercase()
+}
@@ -0,0 +1,87 @@
+//! Process hollowing check. A hollowed process is started suspended from a
+//! legitimate executable, its image is unmapped and replaced with other
+//! code, then resumed, so it keeps the original name and path. Windows goes
+//! on reporting the original file (`QueryFullProcessImageName`), so compare
+//! that with the file actually mapped at the image base recorded in the PEB
+//! (found through `NtQueryInformationProcess`).
+//!
+//! Reading the PEB needs `PROCESS_VM_READ`: only the current user's
+//! processes are checked, or every unprotected one when running elevated.
+
+use crate::models::ImageMismatch;
+use windows::core::PWSTR;
+use windows::Wdk::System::Threading::{NtQueryInformationProcess, ProcessBasicInformation};
+use windows::Win32::Foundation::{CloseHandle, GetLastError, ERROR_FILE_INVALID, HANDLE};
+use windows::Win32::System::Diagnostics::Debug::ReadProcessMemory;
+use windows::Win32::System::ProcessStatus::GetMappedFileNameW;
+use windows::Win32::System::Threading::{
+    OpenProcess, QueryFullProcessImageNameW, PEB, PROCESS_BASIC_INFORMATION, PROCESS_NAME_NATIVE,
+    PROCESS_QUERY_INFORMATION, PROCESS_VM_READ,
+};
+
+/// Compare a process's image file with what is mapped at its image base.
+/// `None` when they match or the process can't be read.
+pub fn check(pid: u32) -> Option<ImageMismatch> {
+    if pid <= 4 {
+        return None;
+    }
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION | PROCESS_VM_READ, false, pid) }.ok()?;
+    let result = check_handle(handle);
+    let _ = unsafe { CloseHandle(handle) };
+    result
+}
+
+fn check_handle(handle: HANDLE) -> Option<ImageMismatch> {
+    let image_file = image_file_name(handle)?;
+    let base = image_base(handle)?;
+    let mut buf = vec![0u16; 1024];
+    let len = unsafe { GetMappedFileNameW(handle, base, &mut buf) } as usize;
+    if len == 0 {
+        // Private memory at the image base; other failures (the process
+        // exiting, say) prove nothing
+        return (unsafe { GetLastError() } == ERROR_FILE_INVALID).then_some(ImageMismatch::Unmapped);
+    }
+    // Both are kernel paths (\Device\HarddiskVolume3\...)
+    let mapped = String::from_utf16_lossy(&buf[..len]);
+    (!mapped.eq_ignore_ascii_case(&image_file)).then_some(ImageMismatch::OtherFile(mapped))
+}
+
+/// The process's executable as a kernel path.
+fn image_file_name(handle: HANDLE) -> Option<String> {
+    let mut buf = vec![0u16; 1024];
+    let mut size = buf.len() as u32;
+    unsafe { QueryFullProcessImageNameW(handle, PROCESS_NAME_NATIVE, PWSTR(buf.as_mut_ptr()), &mut size) }.ok()?;
+    Some(String::from_utf16_lossy(&buf[..size as usize]))
+}
+
+/// `ImageBaseAddress` from the process's PEB.
+fn image_base(handle: HANDLE) -> Option<*const core::ffi::c_void> {
+    let mut info = PROCESS_BASIC_INFORMATION::default();
+    let mut returned = 0u32;
+    let status = unsafe {
+        NtQueryInformationProcess(
+            handle,
+            ProcessBasicInformation,
+            &mut info as *mut _ as *mut core::ffi::c_void,
+            std::mem::size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+            &mut returned,
+        )
+    };
+    if status.is_err() || info.PebBaseAddress.is_null() {
+        return None;
+    }
+    let mut peb = PEB::default();
+    unsafe {
+        ReadProcessMemory(
+            handle,
+            info.PebBaseAddress as *const core::ffi::c_void,
+            &mut peb as *mut _ as *mut core::ffi::c_void,
+            std::mem::size_of::<PEB>(),
+            None,
+        )
+    }
+    .ok()?;
+    // Reserved3[1] is ImageBaseAddress in the documented layout
+    let base = peb.Reserved3[1];
+    (!base.is_null()).then_some(base as *const core::ffi::c_void)
+}
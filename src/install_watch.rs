@@ -0,0 +1,224 @@
+//! Provenance for startup entries and services that appear while App Manager
+//! is open. A background thread polls for installer processes (msiexec with a
+//! package, setup.exe and the like); entries missing from the session's first
+//! scan are attributed to the installer that ran most recently before they
+//! were found, e.g. "Installed by 7-Zip during this session".
+
+use crate::history::entry_key;
+use crate::models::{SessionOrigin, StartupEntry};
+use crate::version_info;
+use chrono::{DateTime, Local, TimeDelta};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How long after an installer exits an entry found by a scan can still be
+/// credited to it.
+const ATTRIBUTION_WINDOW: TimeDelta = TimeDelta::minutes(30);
+
+/// An installer process seen this session.
+#[derive(Debug, Clone)]
+pub struct InstallerRun {
+    pub pid: u32,
+    /// The product or package being installed, e.g. "7-Zip" or "node-v20.msi".
+    pub description: String,
+    pub started: DateTime<Local>,
+    /// When it exited, if it has.
+    pub exited: Option<DateTime<Local>>,
+}
+
+enum Event {
+    Started(InstallerRun),
+    Exited { pid: u32, at: DateTime<Local> },
+}
+
+/// Tracks installers and new entries for the session. Stops polling when
+/// dropped.
+pub struct InstallTracker {
+    stop: Arc<AtomicBool>,
+    events: mpsc::Receiver<Event>,
+    installers: Vec<InstallerRun>,
+    /// Keys of the entries found by the first scan of each list.
+    baselines: HashMap<&'static str, HashSet<String>>,
+    /// Where each entry found after its list's first scan came from, kept
+    /// across reloads.
+    origins: HashMap<String, SessionOrigin>,
+}
+
+impl Drop for InstallTracker {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl InstallTracker {
+    /// Start polling. `wake` is called when an installer starts or exits
+    /// (e.g. to repaint the window).
+    pub fn start(wake: impl Fn() + Send + 'static) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        let thread_stop = stop.clone();
+        std::thread::spawn(move || poll_installers(&thread_stop, &tx, &wake));
+        Self {
+            stop,
+            events: rx,
+            installers: Vec::new(),
+            baselines: HashMap::new(),
+            origins: HashMap::new(),
+        }
+    }
+
+    /// Take in installer starts and exits since the last call, returning the
+    /// installers that finished.
+    pub fn poll(&mut self) -> Vec<InstallerRun> {
+        let mut finished = Vec::new();
+        for event in self.events.try_iter() {
+            match event {
+                Event::Started(run) => self.installers.push(run),
+                Event::Exited { pid, at } => {
+                    if let Some(run) = self.installers.iter_mut().find(|r| r.pid == pid && r.exited.is_none()) {
+                        run.exited = Some(at);
+                        finished.push(run.clone());
+                    }
+                }
+            }
+        }
+        finished
+    }
+
+    /// Set `session_origin` on entries that weren't in the first scan of
+    /// `list` ("startup" or "services"). The first scan only records what
+    /// was already there.
+    pub fn annotate(&mut self, list: &'static str, entries: &mut [StartupEntry]) {
+        let Some(baseline) = self.baselines.get(list) else {
+            let keys = entries.iter().map(|e| entry_key(&e.id())).collect();
+            self.baselines.insert(list, keys);
+            return;
+        };
+        let now = Local::now();
+        for entry in entries {
+            let key = entry_key(&entry.id());
+            if baseline.contains(&key) {
+                continue;
+            }
+            let installers = &self.installers;
+            let origin = self.origins.entry(key).or_insert_with(|| SessionOrigin {
+                first_seen: now,
+                installer: attribute(installers, now),
+            });
+            entry.session_origin = Some(origin.clone());
+        }
+    }
+
+    /// Start over from the next scan, e.g. after the scan scope changed and
+    /// whole sources would otherwise look new.
+    pub fn forget_baselines(&mut self) {
+        self.baselines.clear();
+    }
+}
+
+/// The installer that ran most recently before `found`, within the window.
+fn attribute(installers: &[InstallerRun], found: DateTime<Local>) -> Option<String> {
+    installers
+        .iter()
+        .filter(|r| r.started <= found)
+        .filter(|r| r.exited.is_none_or(|t| found - t <= ATTRIBUTION_WINDOW))
+        .max_by_key(|r| r.exited.unwrap_or(found))
+        .map(|r| r.description.clone())
+}
+
+fn poll_installers(stop: &AtomicBool, tx: &mpsc::Sender<Event>, wake: &dyn Fn()) {
+    let mut sys = System::new();
+    let refresh_kind = ProcessRefreshKind::nothing()
+        .with_exe(UpdateKind::OnlyIfNotSet)
+        .with_cmd(UpdateKind::OnlyIfNotSet);
+    // Running installers by PID, with the start time that tells a reused PID
+    // apart
+    let mut running: HashMap<u32, u64> = HashMap::new();
+    // Installers already running at startup aren't reported; their entries
+    // are in the first scan anyway
+    let mut first = true;
+
+    while !stop.load(Ordering::Relaxed) {
+        sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+        let now = Local::now();
+        let mut sent = false;
+
+        running.retain(|pid, started| {
+            let alive = sys
+                .process(sysinfo::Pid::from_u32(*pid))
+                .is_some_and(|p| p.start_time() == *started);
+            if !alive {
+                sent |= tx.send(Event::Exited { pid: *pid, at: now }).is_ok();
+            }
+            alive
+        });
+
+        let found: Vec<(u32, &sysinfo::Process, Option<String>)> = sys
+            .processes()
+            .iter()
+            .filter(|(pid, _)| !running.contains_key(&pid.as_u32()))
+            .filter_map(|(pid, process)| {
+                let name = process.name().to_string_lossy().to_lowercase();
+                let args: Vec<String> = process.cmd().iter().map(|a| a.to_string_lossy().to_string()).collect();
+                installer_package(&name, &args).map(|package| (pid.as_u32(), process, package))
+            })
+            .collect();
+        for (pid, process, _) in &found {
+            running.insert(*pid, process.start_time());
+        }
+        for (pid, process, package) in found {
+            // Installers start helpers of their own (setup.exe running
+            // setup.tmp, msiexec running msiexec); report only the outermost
+            if first || process.parent().is_some_and(|p| running.contains_key(&p.as_u32())) {
+                continue;
+            }
+            let exe_path = process.exe().map(|p| p.to_string_lossy().to_string()).unwrap_or_default();
+            let description = package
+                .or_else(|| version_info::get_product_name(&exe_path).filter(|p| !p.is_empty()))
+                .unwrap_or_else(|| process.name().to_string_lossy().to_string());
+            let run = InstallerRun {
+                pid,
+                description,
+                started: now,
+                exited: None,
+            };
+            if tx.send(Event::Started(run)).is_err() {
+                return;
+            }
+            sent = true;
+        }
+        if sent {
+            wake();
+        }
+
+        first = false;
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Whether a process looks like an installer, and if so the package it
+/// installs when the command line names one (`Some(None)` otherwise).
+fn installer_package(name: &str, args: &[String]) -> Option<Option<String>> {
+    if name == "msiexec.exe" {
+        // The Windows Installer service and its custom action servers run
+        // msiexec without a package
+        let package = args
+            .iter()
+            .skip(1)
+            .map(|a| a.trim_matches('"'))
+            .find(|a| a.to_lowercase().ends_with(".msi"))?;
+        let file_name = package.rsplit(['\\', '/']).next().unwrap_or(package);
+        return Some(Some(file_name.to_string()));
+    }
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    // Uninstallers (unins000.exe, uninstall.exe) don't add entries, and
+    // TrustedInstaller services Windows Update rather than apps
+    if stem.starts_with("unins") || stem == "trustedinstaller" {
+        return None;
+    }
+    (stem.starts_with("setup") || stem.ends_with("setup") || stem.contains("install")).then_some(None)
+}
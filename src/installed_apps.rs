@@ -1,3 +1,4 @@
+use crate::cmdline;
 use crate::models::InstalledApp;
 use std::collections::HashSet;
 use winreg::enums::*;
@@ -28,6 +29,49 @@ fn read_dword(key: &RegKey, name: &str) -> u64 {
         .unwrap_or(0)
 }
 
+/// Whether `s` looks like a braced GUID: `{8-4-4-4-12}` hex digits.
+fn is_braced_guid(s: &str) -> bool {
+    let Some(inner) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) else {
+        return false;
+    };
+    let groups: Vec<&str> = inner.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// The MSI ProductCode for a Windows Installer entry. MSI registers the
+/// product under its ProductCode; otherwise fall back to an
+/// `msiexec /X{GUID}` (or `/I{GUID}`) uninstall command.
+fn msi_product_code(subkey_name: &str, subkey: &RegKey, uninstall_string: &str) -> Option<String> {
+    if read_dword(subkey, "WindowsInstaller") == 1 && is_braced_guid(subkey_name) {
+        return Some(subkey_name.to_uppercase());
+    }
+    let lower = uninstall_string.to_lowercase();
+    if !lower.contains("msiexec") {
+        return None;
+    }
+    let start = uninstall_string.find('{')?;
+    let end = start + uninstall_string[start..].find('}')?;
+    let guid = &uninstall_string[start..=end];
+    is_braced_guid(guid).then(|| guid.to_uppercase())
+}
+
+/// Command that uninstalls an MSI product with a verbose log written to `log_path`.
+pub fn msi_uninstall_with_log_command(product_code: &str, log_path: &std::path::Path) -> String {
+    format!("msiexec.exe /x {} /l*v \"{}\"", product_code, log_path.display())
+}
+
+fn hive_name(hive: HKEY) -> &'static str {
+    if hive == HKEY_CURRENT_USER {
+        "HKCU"
+    } else {
+        "HKLM"
+    }
+}
+
 pub fn collect_installed_apps() -> Vec<InstalledApp> {
     let mut apps = Vec::new();
     let mut seen_names: HashSet<String> = HashSet::new();
@@ -72,15 +116,20 @@ pub fn collect_installed_apps() -> Vec<InstalledApp> {
                 if val.is_empty() { None } else { Some(val) }
             };
 
+            let msi_product_code = msi_product_code(&subkey_name, &subkey, &uninstall_string);
+
             apps.push(InstalledApp {
                 display_name,
                 publisher: read_string(&subkey, "Publisher"),
                 display_version: read_string(&subkey, "DisplayVersion"),
                 install_date: read_string(&subkey, "InstallDate"),
                 estimated_size_kb: read_dword(&subkey, "EstimatedSize"),
+                uninstaller_missing: cmdline::is_target_missing(&uninstall_string),
+                msi_product_code,
                 uninstall_string,
                 modify_path,
                 install_location: read_string(&subkey, "InstallLocation"),
+                registry_key: format!("{}\\{}\\{}", hive_name(hive), path, subkey_name),
             });
         }
     }
@@ -0,0 +1,84 @@
+//! Tasks on the taskbar icon's jump list (right-click menu), each a shortcut
+//! back to this executable with one of the deep-link arguments in `main`.
+
+use anyhow::{Context, Result};
+use windows::core::{HSTRING, Interface, PCWSTR};
+use windows::Win32::Storage::EnhancedStorage::PKEY_Title;
+use windows::Win32::System::Com::StructuredStorage::PROPVARIANT;
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED,
+};
+use windows::Win32::System::Variant::VT_LPWSTR;
+use windows::Win32::UI::Shell::Common::{IObjectArray, IObjectCollection};
+use windows::Win32::UI::Shell::PropertiesSystem::IPropertyStore;
+use windows::Win32::UI::Shell::{
+    DestinationList, EnumerableObjectCollection, ICustomDestinationList, IShellLinkW, SHStrDupW, ShellLink,
+};
+
+/// (title, arguments, description) of each task.
+const TASKS: &[(&str, &str, &str)] = &[
+    (
+        "Refresh as Administrator",
+        crate::ELEVATE_FLAG,
+        "Start App Manager elevated to see every entry, service, and process",
+    ),
+    (
+        "Open Processes Tab",
+        "--tab processes",
+        "Start App Manager on the Processes tab",
+    ),
+    (
+        "Export Report",
+        crate::EXPORT_ALL_FLAG,
+        "Export every tab to a .zip of CSV files once loading finishes",
+    ),
+];
+
+/// Replace the jump list's tasks. Best-effort: runs on its own thread at
+/// startup, and failures only mean the jump list keeps its previous tasks.
+pub fn register() {
+    std::thread::spawn(|| {
+        let _ = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+        let _ = unsafe { register_tasks() };
+        unsafe { CoUninitialize() };
+    });
+}
+
+unsafe fn register_tasks() -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the executable")?;
+    let exe = HSTRING::from(exe.as_os_str());
+
+    let list: ICustomDestinationList = CoCreateInstance(&DestinationList, None, CLSCTX_INPROC_SERVER)
+        .context("Failed to create the destination list")?;
+    let mut min_slots = 0u32;
+    let _removed: IObjectArray = list.BeginList(&mut min_slots)?;
+
+    let tasks: IObjectCollection = CoCreateInstance(&EnumerableObjectCollection, None, CLSCTX_INPROC_SERVER)?;
+    for (title, args, description) in TASKS {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        link.SetPath(&exe)?;
+        link.SetArguments(&HSTRING::from(*args))?;
+        link.SetDescription(&HSTRING::from(*description))?;
+        link.SetIconLocation(&exe, 0)?;
+
+        // Jump list items take their label from the link's title property
+        let store: IPropertyStore = link.cast()?;
+        let title = lpwstr_propvariant(title)?;
+        store.SetValue(&PKEY_Title, &title)?;
+        store.Commit()?;
+        tasks.AddObject(&link)?;
+    }
+
+    list.AddUserTasks(&tasks.cast::<IObjectArray>()?)?;
+    list.CommitList()?;
+    Ok(())
+}
+
+/// A `VT_LPWSTR` PROPVARIANT (freed by its `Drop` via `PropVariantClear`).
+unsafe fn lpwstr_propvariant(text: &str) -> Result<PROPVARIANT> {
+    let mut value = PROPVARIANT::default();
+    let inner = &mut *value.Anonymous.Anonymous;
+    inner.vt = VT_LPWSTR;
+    inner.Anonymous.pwszVal = SHStrDupW(PCWSTR(HSTRING::from(text).as_ptr()))?;
+    Ok(value)
+}
@@ -0,0 +1,90 @@
+//! Protections around killing processes that take the user's session down
+//! with them: Explorer (the taskbar and desktop) and App Manager itself.
+
+use std::os::windows::process::CommandExt;
+use std::process::Command;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// How long to wait for Winlogon to restart the shell by itself before
+/// launching it ourselves.
+const SHELL_RESTART_WAIT: Duration = Duration::from_secs(3);
+
+/// Why killing a process needs extra confirmation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillGuard {
+    /// The Windows shell; killing it removes the taskbar and desktop.
+    Explorer,
+    /// App Manager's own process.
+    SelfProcess,
+}
+
+/// Whether killing `pid` should be confirmed first.
+pub fn guard_for(pid: u32, name: &str) -> Option<KillGuard> {
+    if pid == std::process::id() {
+        Some(KillGuard::SelfProcess)
+    } else if name.eq_ignore_ascii_case("explorer.exe") {
+        Some(KillGuard::Explorer)
+    } else {
+        None
+    }
+}
+
+/// Restart Explorer in the current session: end every `explorer.exe` in this
+/// session, then start a new shell unless Winlogon already restarted it.
+/// Blocks for a few seconds; call from a background thread.
+pub fn restart_explorer() -> Result<(), String> {
+    let session = session_id(std::process::id())
+        .ok_or_else(|| "Could not determine the current session".to_string())?;
+
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    let targets: Vec<u32> = explorer_pids(&sys, session);
+
+    for pid in &targets {
+        let output = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map_err(|e| e.to_string())?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(stderr.trim().to_string());
+        }
+    }
+
+    let started = Instant::now();
+    while started.elapsed() < SHELL_RESTART_WAIT {
+        std::thread::sleep(Duration::from_millis(250));
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+        if explorer_pids(&sys, session)
+            .iter()
+            .any(|pid| !targets.contains(pid))
+        {
+            return Ok(());
+        }
+    }
+
+    Command::new("explorer.exe")
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to start explorer.exe: {}", e))
+}
+
+fn explorer_pids(sys: &System, session: u32) -> Vec<u32> {
+    sys.processes()
+        .iter()
+        .filter(|(_, p)| p.name().eq_ignore_ascii_case("explorer.exe"))
+        .map(|(pid, _)| pid.as_u32())
+        .filter(|pid| session_id(*pid) == Some(session))
+        .collect()
+}
+
+fn session_id(pid: u32) -> Option<u32> {
+    let mut session = 0u32;
+    unsafe { ProcessIdToSessionId(pid, &mut session) }.ok()?;
+    Some(session)
+}
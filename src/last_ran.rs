@@ -0,0 +1,151 @@
+//! Last-ran times from sources other than Prefetch, used when Prefetch is
+//! turned off (common on SSD-tuned systems) or unreadable without admin
+//! rights:
+//!
+//! - UserAssist (`HKCU\...\Explorer\UserAssist`): programs the current user
+//!   launched through Explorer, with ROT13-encoded names. Readable without
+//!   admin rights.
+//! - Background Activity Moderator (`HKLM\SYSTEM\...\Services\bam`): the last
+//!   execution time of every program, per user. Windows 10 1709 and later;
+//!   requires admin rights.
+//!
+//! Shimcache records file modification times and Amcache first-seen times
+//! rather than runs, so both are shown on the Execution History tab instead
+//! (see `execution_history`). SRUM is an ESE database locked while Windows is
+//! running and is not read.
+
+use crate::models::LastRanSource;
+use crate::status::filetime_to_datetime;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
+use winreg::enums::*;
+use winreg::RegKey;
+
+const USER_ASSIST_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Explorer\UserAssist";
+/// Windows 10 1809 moved the BAM state under `State`.
+const BAM_KEYS: &[&str] = &[
+    r"SYSTEM\CurrentControlSet\Services\bam\State\UserSettings",
+    r"SYSTEM\CurrentControlSet\Services\bam\UserSettings",
+];
+
+/// Size of a Windows 7+ UserAssist value; the last-run FILETIME is at offset 60.
+const USER_ASSIST_VALUE_LEN: usize = 72;
+const USER_ASSIST_TIME_OFFSET: usize = 60;
+
+pub struct AlternativeSources {
+    user_assist: HashMap<String, DateTime<Local>>,
+    background_activity: HashMap<String, DateTime<Local>>,
+}
+
+impl AlternativeSources {
+    pub fn load() -> Self {
+        Self {
+            user_assist: load_user_assist(),
+            background_activity: load_background_activity(),
+        }
+    }
+
+    /// Number of executables with UserAssist data.
+    pub fn user_assist_len(&self) -> usize {
+        self.user_assist.len()
+    }
+
+    /// Number of executables with Background Activity Moderator data.
+    pub fn background_activity_len(&self) -> usize {
+        self.background_activity.len()
+    }
+
+    /// The most recent run of `exe_name` in either source.
+    pub fn last_ran(&self, exe_name: &str) -> Option<(DateTime<Local>, LastRanSource)> {
+        let key = exe_name.to_uppercase();
+        let user_assist = self.user_assist.get(&key).map(|dt| (*dt, LastRanSource::UserAssist));
+        let bam = self
+            .background_activity
+            .get(&key)
+            .map(|dt| (*dt, LastRanSource::BackgroundActivity));
+        match (user_assist, bam) {
+            (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+fn load_user_assist() -> HashMap<String, DateTime<Local>> {
+    let mut result = HashMap::new();
+    let Ok(root) = RegKey::predef(HKEY_CURRENT_USER).open_subkey_with_flags(USER_ASSIST_KEY, KEY_READ) else {
+        return result;
+    };
+    for guid in root.enum_keys().flatten() {
+        let Ok(count) = root.open_subkey_with_flags(format!(r"{}\Count", guid), KEY_READ) else {
+            continue;
+        };
+        for (name, value) in count.enum_values().flatten() {
+            if value.bytes.len() < USER_ASSIST_VALUE_LEN {
+                continue;
+            }
+            let path = rot13(&name);
+            let ft_bytes: [u8; 8] = value.bytes[USER_ASSIST_TIME_OFFSET..USER_ASSIST_TIME_OFFSET + 8]
+                .try_into()
+                .unwrap();
+            if let Some(dt) = filetime_to_datetime(u64::from_le_bytes(ft_bytes)) {
+                insert_latest(&mut result, &path, dt);
+            }
+        }
+    }
+    result
+}
+
+fn load_background_activity() -> HashMap<String, DateTime<Local>> {
+    let mut result = HashMap::new();
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let Some(root) = BAM_KEYS
+        .iter()
+        .find_map(|path| hklm.open_subkey_with_flags(path, KEY_READ).ok())
+    else {
+        return result;
+    };
+    for sid in root.enum_keys().flatten() {
+        let Ok(user) = root.open_subkey_with_flags(&sid, KEY_READ) else {
+            continue;
+        };
+        // Values are named by device path (\Device\HarddiskVolume3\...);
+        // the data starts with the last execution FILETIME
+        for (name, value) in user.enum_values().flatten() {
+            if value.vtype != REG_BINARY || value.bytes.len() < 8 {
+                continue;
+            }
+            let ft_bytes: [u8; 8] = value.bytes[..8].try_into().unwrap();
+            if let Some(dt) = filetime_to_datetime(u64::from_le_bytes(ft_bytes)) {
+                insert_latest(&mut result, &name, dt);
+            }
+        }
+    }
+    result
+}
+
+/// Record `dt` under the upper-cased file name of `path` if it is an
+/// executable and newer than what is already there.
+fn insert_latest(map: &mut HashMap<String, DateTime<Local>>, path: &str, dt: DateTime<Local>) {
+    let file_name = path.rsplit(['\\', '/']).next().unwrap_or(path).to_uppercase();
+    if !file_name.ends_with(".EXE") {
+        return;
+    }
+    map.entry(file_name)
+        .and_modify(|existing| {
+            if dt > *existing {
+                *existing = dt;
+            }
+        })
+        .or_insert(dt);
+}
+
+/// UserAssist value names are ROT13-encoded.
+fn rot13(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'a'..='m' | 'A'..='M' => (c as u8 + 13) as char,
+            'n'..='z' | 'N'..='Z' => (c as u8 - 13) as char,
+            _ => c,
+        })
+        .collect()
+}
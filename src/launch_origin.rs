@@ -0,0 +1,129 @@
+//! "Why is this running?": the probable launch origin of a process, from its
+//! parent chain and the startup entries, services, and scheduled tasks that
+//! point at its executable.
+
+use crate::cross_ref::CrossRefIndex;
+use crate::models::{ProcessInfo, Source, StartupEntry, VirtPlatform};
+use std::collections::HashMap;
+
+/// Ancestors walked before giving up (guards against PID reuse cycles).
+const MAX_CHAIN_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOrigin {
+    /// One-sentence best guess.
+    pub summary: String,
+    /// The process and its ancestors, oldest first, as "name (PID n)".
+    pub parent_chain: Vec<String>,
+    /// Supporting evidence: matching services, startup entries, and tasks.
+    pub details: Vec<String>,
+}
+
+pub fn explain(proc: &ProcessInfo, processes: &[ProcessInfo], index: &CrossRefIndex) -> LaunchOrigin {
+    let by_pid: HashMap<u32, &ProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+
+    // Walk up while each parent is still running and started before its child
+    // (a later start means the parent's PID was reused)
+    let mut chain = vec![format!("{} (PID {})", proc.name, proc.pid)];
+    let mut parent_name: Option<String> = None;
+    let mut exited_parent = None;
+    let mut current = proc;
+    while chain.len() <= MAX_CHAIN_DEPTH {
+        let Some(ppid) = current.parent_pid else {
+            break;
+        };
+        let started_before = |p: &&&ProcessInfo| match (p.start_time, current.start_time) {
+            (Some(parent), Some(child)) => parent <= child,
+            _ => true,
+        };
+        match by_pid.get(&ppid).filter(started_before) {
+            Some(parent) => {
+                if parent_name.is_none() {
+                    parent_name = Some(parent.name.clone());
+                }
+                chain.push(format!("{} (PID {})", parent.name, parent.pid));
+                current = parent;
+            }
+            None => {
+                chain.push(format!("PID {} (exited)", ppid));
+                if current.pid == proc.pid {
+                    exited_parent = Some(ppid);
+                }
+                break;
+            }
+        }
+    }
+    chain.reverse();
+
+    let hosted = index.services_in_process(proc.pid);
+    let entries = index.startup_entries(&proc.exe_path);
+    let (tasks, startup): (Vec<&StartupEntry>, Vec<&StartupEntry>) =
+        entries.iter().copied().partition(|e| matches!(e.source, Source::TaskScheduler { .. }));
+    let quoted = |names: Vec<&str>| names.iter().map(|n| format!("'{}'", n)).collect::<Vec<_>>().join(", ");
+
+    let mut details = Vec::new();
+    if let Some(platform) = proc.platform {
+        details.push(format!("Part of {}", match platform {
+            VirtPlatform::Wsl => "the Windows Subsystem for Linux",
+            VirtPlatform::Docker => "Docker Desktop",
+            VirtPlatform::Sandbox => "Windows Sandbox",
+        }));
+    }
+    if !hosted.is_empty() {
+        details.push(format!("Hosts services: {}", quoted(hosted.iter().map(|s| s.name.as_str()).collect())));
+    }
+    for entry in &startup {
+        details.push(format!("Startup entry '{}' ({})", entry.name, entry.source.display_location()));
+    }
+    for task in &tasks {
+        details.push(format!("Scheduled task '{}' ({})", task.name, task.source.display_location()));
+    }
+    let services = index.services(&proc.exe_path);
+    if hosted.is_empty() && !services.is_empty() && services.len() <= 3 {
+        details.push(format!(
+            "Same executable as services: {}",
+            quoted(services.iter().map(|s| s.name.as_str()).collect())
+        ));
+    }
+
+    let parent = parent_name.as_deref().map(str::to_lowercase);
+    let summary = if !hosted.is_empty() {
+        format!(
+            "Started by the Service Control Manager to run {}",
+            quoted(hosted.iter().map(|s| s.name.as_str()).collect())
+        )
+    } else if !tasks.is_empty()
+        && matches!(parent.as_deref(), Some("svchost.exe" | "taskeng.exe" | "taskhostw.exe"))
+    {
+        format!("Started by Task Scheduler for {}", quoted(tasks.iter().map(|t| t.name.as_str()).collect()))
+    } else if !startup.is_empty() {
+        format!(
+            "Probably started at sign-in by {}",
+            quoted(startup.iter().map(|e| e.name.as_str()).collect())
+        )
+    } else if !tasks.is_empty() {
+        format!(
+            "Probably started by scheduled task {}",
+            quoted(tasks.iter().map(|t| t.name.as_str()).collect())
+        )
+    } else {
+        match parent.as_deref() {
+            Some("services.exe") => "Started by the Service Control Manager".to_string(),
+            Some("explorer.exe") => {
+                "Started from Explorer (Start menu, taskbar, desktop, or a file you opened)".to_string()
+            }
+            Some("svchost.exe") => "Started by a Windows service host (e.g. COM, WMI, or a broker)".to_string(),
+            Some(_) => format!("Started by {}", parent_name.as_deref().unwrap_or_default()),
+            None => match exited_parent {
+                Some(ppid) => format!("Its parent (PID {}) has exited, so the launcher is unknown", ppid),
+                None => "Started by Windows during boot".to_string(),
+            },
+        }
+    };
+
+    LaunchOrigin {
+        summary,
+        parent_chain: chain,
+        details,
+    }
+}
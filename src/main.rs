@@ -1,21 +1,95 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod actions;
+mod allowlist;
+mod app_data;
+mod audit;
+mod backup;
+mod bench;
+mod categories;
+mod clean_boot;
+mod cmdline;
 mod collector;
+mod connections;
+mod crash;
+mod cross_ref;
+mod dependencies;
+mod dev_packages;
+mod drive_watch;
+mod drives;
+mod dry_run;
+mod enrichment;
+mod enforcement;
+mod execution_history;
+mod executor;
+mod export_locale;
 mod gui;
+mod history;
+mod image_integrity;
+mod install_watch;
 mod installed_apps;
+mod jump_list;
+mod kill_guard;
+mod last_ran;
+mod launch_origin;
 mod models;
+mod orphans;
+mod parallel;
+mod plan;
 mod prefetch;
 mod process;
-mod registry;
+mod process_ui;
 mod processes;
+mod reg_file;
+mod registry;
+mod security_center;
+mod service_hardening;
+mod service_watch;
+mod settings;
+mod shell;
 mod services;
+mod skipped;
 mod startup_folders;
 mod status;
+mod suites;
 mod task_scheduler;
+mod taskbar;
+mod uninstaller;
+mod vendors;
 mod version_info;
+mod watchlist;
+mod zip_file;
+
+/// `--add-startup <path>`: open the Add Startup Entry dialog pre-filled for a
+/// program or shortcut (used by the Explorer context menu).
+const ADD_STARTUP_FLAG: &str = "--add-startup";
+
+/// `--tab <name>`: open on a tab, named as in export file names (e.g.
+/// `processes`, `startup-apps`).
+const TAB_FLAG: &str = "--tab";
+
+/// `--export-all`: run Export All once the first load completes.
+const EXPORT_ALL_FLAG: &str = "--export-all";
+
+/// `--elevate`: restart through the UAC prompt (jump list task).
+const ELEVATE_FLAG: &str = "--elevate";
 
 fn main() -> eframe::Result {
+    crash::install_hook();
+
+    // Headless keep-disabled enforcement (no window)
+    if std::env::args().any(|a| a == enforcement::MONITOR_FLAG) {
+        enforcement::run_monitor(std::env::args().any(|a| a == dry_run::DRY_RUN_FLAG));
+        return Ok(());
+    }
+
+    // Headless collector benchmark (no window)
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(i) = args.iter().position(|a| a == bench::BENCH_FLAG) {
+        bench::run(&args[i + 1..]);
+        return Ok(());
+    }
+
     let icon_rgba = include_bytes!(concat!(env!("OUT_DIR"), "/icon_rgba.bin")).to_vec();
     let icon = eframe::egui::IconData {
         rgba: icon_rgba,
@@ -23,6 +97,21 @@ fn main() -> eframe::Result {
         height: 48,
     };
 
+    if args.iter().any(|a| a == ELEVATE_FLAG) {
+        gui::restart_as_admin();
+    }
+    let flag_value = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1).cloned())
+    };
+    let add_startup_path = flag_value(ADD_STARTUP_FLAG);
+    let start_tab = flag_value(TAB_FLAG);
+    let export_all = args.iter().any(|a| a == EXPORT_ALL_FLAG);
+    let dry_run = args.iter().any(|a| a == dry_run::DRY_RUN_FLAG);
+
+    jump_list::register();
+
     let win_w: f32 = 1200.0;
     let win_h: f32 = 700.0;
 
@@ -54,7 +143,20 @@ fn main() -> eframe::Result {
         options,
         Box::new(|cc| {
             cc.egui_ctx.set_visuals(eframe::egui::Visuals::dark());
-            Ok(Box::new(gui::StartupApp::new()))
+            let mut app = gui::StartupApp::new();
+            if let Some(name) = start_tab.as_deref() {
+                app.open_tab(name);
+            }
+            if let Some(path) = add_startup_path.as_deref() {
+                app.open_add_startup_entry(path);
+            }
+            if export_all {
+                app.request_export_all();
+            }
+            if dry_run {
+                app.set_dry_run(true);
+            }
+            Ok(Box::new(app))
         }),
     )
 }
@@ -1,8 +1,9 @@
+use crate::cmdline;
 use chrono::{DateTime, Local};
 use std::fmt;
 use std::path::Path;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum RegistryHive {
     HKCU,
@@ -18,6 +19,16 @@ impl fmt::Display for RegistryHive {
     }
 }
 
+/// Another account on this computer, for entries found in its profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherUser {
+    pub sid: String,
+    pub name: String,
+}
+
+/// Value name that holds an IFEO debugger command.
+pub const IFEO_DEBUGGER_VALUE: &str = "Debugger";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Source {
     RegistryRun {
@@ -31,6 +42,8 @@ pub enum Source {
     StartupFolder {
         path: String,
         is_common: bool,
+        /// Set for another user's folder (found when running elevated).
+        other_user: Option<OtherUser>,
     },
     TaskScheduler {
         task_path: String,
@@ -39,6 +52,11 @@ pub enum Source {
         service_name: String,
         command_line: String,
     },
+    /// `Debugger` value under an Image File Execution Options key (HKLM),
+    /// which launches the debugger in place of the named executable.
+    Ifeo {
+        key_path: String,
+    },
 }
 
 impl Source {
@@ -46,15 +64,29 @@ impl Source {
         match self {
             Source::RegistryRun { hive, key_path } => format!("{}\\{}", hive, key_path),
             Source::RegistryRunOnce { hive, key_path } => format!("{}\\{}", hive, key_path),
-            Source::StartupFolder { is_common, .. } => {
+            Source::StartupFolder { is_common, other_user, .. } => {
                 if *is_common {
                     "Common Startup Folder".to_string()
+                } else if let Some(user) = other_user {
+                    format!("User Startup Folder ({})", user.name)
                 } else {
                     "User Startup Folder".to_string()
                 }
             }
             Source::TaskScheduler { task_path } => format!("Task: {}", task_path),
             Source::Service { command_line, .. } => command_line.clone(),
+            Source::Ifeo { key_path } => format!("HKLM\\{}", key_path),
+        }
+    }
+
+    /// Registry entries under `Wow6432Node`, the 32-bit registry view that
+    /// 32-bit programs read and write.
+    pub fn is_wow64_registry(&self) -> bool {
+        match self {
+            Source::RegistryRun { key_path, .. } | Source::RegistryRunOnce { key_path, .. } => {
+                key_path.to_lowercase().contains("wow6432node")
+            }
+            _ => false,
         }
     }
 
@@ -65,10 +97,32 @@ impl Source {
             Source::StartupFolder { .. } => 2,
             Source::TaskScheduler { .. } => 3,
             Source::Service { .. } => 4,
+            Source::Ifeo { .. } => 5,
         }
     }
 }
 
+/// Stable identity of a startup entry or service, independent of its position
+/// in any list. Used to address actions so they can't hit the wrong row after
+/// the lists are reloaded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EntryId {
+    Registry {
+        hive: RegistryHive,
+        key_path: String,
+        value_name: String,
+    },
+    StartupFile {
+        path: String,
+    },
+    Task {
+        task_path: String,
+    },
+    Service {
+        service_name: String,
+    },
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnabledStatus {
     Enabled,
@@ -103,6 +157,186 @@ impl fmt::Display for RunState {
     }
 }
 
+/// Registration details of a scheduled task, for attributing who or what
+/// created it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskRegistration {
+    pub author: String,
+    pub registered: Option<DateTime<Local>>,
+    pub description: String,
+}
+
+impl TaskRegistration {
+    /// The registration date as shown in the table and exports; empty when
+    /// unknown.
+    pub fn registered_text(&self) -> String {
+        self.registered
+            .map(|dt| dt.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default()
+    }
+
+    /// Multi-line summary for tooltips; empty when nothing is known.
+    pub fn summary(&self) -> String {
+        let mut lines = Vec::new();
+        if !self.author.is_empty() {
+            lines.push(format!("Author: {}", self.author));
+        }
+        if self.registered.is_some() {
+            lines.push(format!("Registered: {}", self.registered_text()));
+        }
+        if !self.description.is_empty() {
+            lines.push(self.description.clone());
+        }
+        lines.join("\n")
+    }
+}
+
+/// Conditions a scheduled task must meet before Task Scheduler starts it,
+/// the usual reason a task "didn't run".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskConditions {
+    /// Don't start the task while on battery power.
+    pub disallow_start_on_batteries: bool,
+    /// Stop the task if the computer switches to battery power.
+    pub stop_on_batteries: bool,
+    pub run_only_if_idle: bool,
+    /// How long the computer must be idle first, in minutes.
+    pub idle_minutes: u32,
+    pub run_only_if_network: bool,
+    /// Wake the computer to run the task.
+    pub wake_to_run: bool,
+}
+
+/// The script or DLL a script-host command (wscript, cscript, powershell,
+/// mshta, rundll32) actually runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptPayload {
+    /// Host executable name, lowercase and without extension (e.g. "wscript").
+    pub host: String,
+    /// Script path, inline command, URL, or DLL the host is told to run.
+    pub payload: String,
+}
+
+/// Whether a binary (or registry view) is 32-bit or 64-bit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bitness {
+    Bit32,
+    Bit64,
+}
+
+impl fmt::Display for Bitness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bitness::Bit32 => write!(f, "32-bit"),
+            Bitness::Bit64 => write!(f, "64-bit"),
+        }
+    }
+}
+
+/// When a binary was linked, from the `TimeDateStamp` in its COFF header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTime {
+    At(DateTime<Local>),
+    /// Built reproducibly (as Windows' own binaries are): the field holds a
+    /// hash of the contents rather than a time.
+    Reproducible,
+}
+
+impl fmt::Display for LinkTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LinkTime::At(dt) => write!(f, "{}", dt.format("%Y-%m-%d %H:%M:%S")),
+            LinkTime::Reproducible => write!(f, "Reproducible build"),
+        }
+    }
+}
+
+/// Kind of drive that can disappear out from under a binary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DriveKind {
+    Removable,
+    Network,
+    /// The drive letter has no volume right now (an unplugged device or an
+    /// empty card reader).
+    Disconnected,
+}
+
+impl DriveKind {
+    /// Tooltip for the warning badge shown next to affected rows.
+    pub fn warning(&self) -> &'static str {
+        match self {
+            DriveKind::Removable => {
+                "Binary is on a removable drive; it won't start while the device is unplugged"
+            }
+            DriveKind::Network => {
+                "Binary is on a network drive; it won't start while the share is unavailable"
+            }
+            DriveKind::Disconnected => {
+                "Binary is on a drive that isn't connected; it isn't counted as missing, but it won't \
+                 start until the drive is back"
+            }
+        }
+    }
+}
+
+impl fmt::Display for DriveKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriveKind::Removable => write!(f, "Removable"),
+            DriveKind::Network => write!(f, "Network"),
+            DriveKind::Disconnected => write!(f, "Disconnected"),
+        }
+    }
+}
+
+/// Values and badges added by enrichment providers (see [`crate::enrichment`]).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Enrichment {
+    /// (column, value) pairs, in provider column order.
+    pub values: Vec<(&'static str, String)>,
+    pub badges: Vec<Badge>,
+}
+
+impl Enrichment {
+    pub fn value(&self, column: &str) -> Option<&str> {
+        self.values.iter().find(|(c, _)| *c == column).map(|(_, v)| v.as_str())
+    }
+}
+
+/// A short label shown next to a row's name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Badge {
+    pub text: String,
+    pub tooltip: String,
+    /// Drawn in the warning color instead of the informational one.
+    pub warning: bool,
+}
+
+/// Number of recent sessions in which an entry was seen running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RunHistoryStats {
+    pub sessions_running: usize,
+    pub sessions_observed: usize,
+}
+
+impl RunHistoryStats {
+    /// Short form for table cells and CSV, e.g. "12/14".
+    pub fn ratio(&self) -> String {
+        format!("{}/{}", self.sessions_running, self.sessions_observed)
+    }
+}
+
+impl fmt::Display for RunHistoryStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.sessions_observed == 1 { "" } else { "s" };
+        write!(
+            f,
+            "Seen running in {} of last {} session{}",
+            self.sessions_running, self.sessions_observed, plural
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StartupEntry {
     pub name: String,
@@ -111,9 +345,107 @@ pub struct StartupEntry {
     pub enabled: EnabledStatus,
     pub run_state: RunState,
     pub last_ran: Option<DateTime<Local>>,
+    /// Where `last_ran` came from (shown as a tooltip).
+    pub last_ran_source: Option<LastRanSource>,
     pub requires_admin: bool,
     pub runs_as: String,
     pub product_name: String,
+    /// Company name from the target binary's version info (the vendor).
+    pub company_name: String,
+    /// Configuration was changed by the user during this session
+    /// (services need a restart for the change to take effect).
+    pub config_changed: bool,
+    /// The registry value is REG_EXPAND_SZ rather than REG_SZ, so the
+    /// `%VARIABLES%` in `command` are expanded each time it runs.
+    pub expand_sz: bool,
+    /// The executable (or rundll32 DLL) the command points at no longer exists.
+    pub target_missing: bool,
+    /// How often the entry was seen running in recent App Manager sessions.
+    pub run_history: Option<RunHistoryStats>,
+    /// The target binary lives on a removable or network drive.
+    pub volatile_drive: Option<DriveKind>,
+    /// Author, registration date, and description (Task Scheduler entries only).
+    pub task_registration: Option<TaskRegistration>,
+    /// Power, idle, and network conditions (Task Scheduler entries only).
+    pub task_conditions: Option<TaskConditions>,
+    /// Effective payload when the command runs a script host.
+    pub script_payload: Option<ScriptPayload>,
+    /// 32-bit for `Wow6432Node` registry entries, otherwise the bitness of
+    /// the target binary.
+    pub bitness: Option<Bitness>,
+    /// Process ID of a running service, from the SCM.
+    pub pid: Option<u32>,
+    /// CPU and memory of the process hosting a running service.
+    pub host_usage: Option<HostUsage>,
+    /// Last write time of a service's registry key: when it was installed,
+    /// or last reconfigured (services only).
+    pub installed: Option<DateTime<Local>>,
+    /// An automatic start is delayed until shortly after boot (services only).
+    pub delayed_start: bool,
+    /// Name of the uninstalled app whose install folder the target is in.
+    pub orphaned_by: Option<String>,
+    /// Set when the entry appeared while App Manager was open.
+    pub session_origin: Option<SessionOrigin>,
+    pub enrichment: Enrichment,
+}
+
+/// Where an entry that appeared during this session came from.
+#[derive(Debug, Clone)]
+pub struct SessionOrigin {
+    /// The scan that first found it.
+    pub first_seen: DateTime<Local>,
+    /// The installer that ran just before, if there was one.
+    pub installer: Option<String>,
+}
+
+impl fmt::Display for SessionOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.installer {
+            Some(app) => write!(f, "Installed by {} during this session", app)?,
+            None => write!(f, "Added during this session")?,
+        }
+        write!(f, " (found {})", self.first_seen.format("%H:%M"))
+    }
+}
+
+/// Resource usage of the process hosting a service. Services sharing an
+/// `svchost.exe` process share its figures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostUsage {
+    pub pid: u32,
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+    /// Number of services running in the same process (1 = own process).
+    pub services_in_process: usize,
+}
+
+/// Where an entry's Last Ran time came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LastRanSource {
+    /// Start time of the running process.
+    ProcessStart,
+    /// The task's last run time, from Task Scheduler.
+    TaskScheduler,
+    Prefetch,
+    UserAssist,
+    BackgroundActivity,
+    /// When the entry was disabled in Task Manager / Settings.
+    DisabledTime,
+}
+
+impl LastRanSource {
+    pub fn description(&self) -> &'static str {
+        match self {
+            LastRanSource::ProcessStart => "Start time of the running process",
+            LastRanSource::TaskScheduler => "Last run time reported by Task Scheduler",
+            LastRanSource::Prefetch => "Prefetch file last written (C:\\Windows\\Prefetch)",
+            LastRanSource::UserAssist => "UserAssist: last launched by you through Explorer (Prefetch unavailable)",
+            LastRanSource::BackgroundActivity => {
+                "Background Activity Moderator: last execution recorded by Windows (Prefetch unavailable)"
+            }
+            LastRanSource::DisabledTime => "Time the entry was disabled (no run time available)",
+        }
+    }
 }
 
 impl StartupEntry {
@@ -125,55 +457,170 @@ impl StartupEntry {
             enabled: EnabledStatus::Unknown,
             run_state: RunState::Stopped,
             last_ran: None,
+            last_ran_source: None,
             requires_admin: false,
             runs_as: String::new(),
             product_name: String::new(),
+            company_name: String::new(),
+            config_changed: false,
+            expand_sz: false,
+            target_missing: false,
+            run_history: None,
+            volatile_drive: None,
+            task_registration: None,
+            task_conditions: None,
+            script_payload: None,
+            bitness: None,
+            pid: None,
+            host_usage: None,
+            installed: None,
+            delayed_start: false,
+            orphaned_by: None,
+            session_origin: None,
+            enrichment: Enrichment::default(),
+        }
+    }
+
+    /// Whether a service's registry key was written in the last `days` days.
+    pub fn installed_within(&self, days: i64) -> bool {
+        self.installed.is_some_and(|t| Local::now() - t < chrono::TimeDelta::days(days))
+    }
+
+    /// Whose profile the entry belongs to: the account for per-user
+    /// locations (HKCU, a user's Startup folder), "All users" otherwise.
+    pub fn profile_user(&self) -> &str {
+        let per_user = match &self.source {
+            Source::RegistryRun { hive, .. } | Source::RegistryRunOnce { hive, .. } => *hive == RegistryHive::HKCU,
+            Source::StartupFolder { is_common, .. } => !is_common,
+            _ => false,
+        };
+        if per_user { &self.runs_as } else { "All users" }
+    }
+
+    /// Tooltip for the 32-bit badge, or `None` if the entry isn't 32-bit.
+    pub fn bitness_note(&self) -> Option<&'static str> {
+        if self.source.is_wow64_registry() {
+            Some("Registered under WOW6432Node, the 32-bit registry view that 32-bit programs use")
+        } else if self.bitness == Some(Bitness::Bit32) {
+            Some("32-bit binary")
+        } else {
+            None
         }
     }
 
     pub fn exe_name(&self) -> Option<String> {
         extract_exe_name(&self.command)
     }
+
+    pub fn id(&self) -> EntryId {
+        match &self.source {
+            Source::RegistryRun { hive, key_path } | Source::RegistryRunOnce { hive, key_path } => {
+                EntryId::Registry {
+                    hive: *hive,
+                    key_path: key_path.clone(),
+                    value_name: self.name.clone(),
+                }
+            }
+            Source::StartupFolder { path, .. } => EntryId::StartupFile { path: path.clone() },
+            Source::TaskScheduler { task_path } => EntryId::Task {
+                task_path: task_path.clone(),
+            },
+            Source::Service { service_name, .. } => EntryId::Service {
+                service_name: service_name.clone(),
+            },
+            Source::Ifeo { key_path } => EntryId::Registry {
+                hive: RegistryHive::HKLM,
+                key_path: key_path.clone(),
+                value_name: IFEO_DEBUGGER_VALUE.to_string(),
+            },
+        }
+    }
 }
 
 pub fn extract_exe_name(command: &str) -> Option<String> {
-    let command = command.trim();
-    if command.is_empty() {
+    let path = cmdline::executable_path(command);
+    if path.is_empty() {
         return None;
     }
 
-    let path_str = if let Some(stripped) = command.strip_prefix('"') {
-        stripped.split('"').next()?
-    } else {
-        command.split_whitespace().next()?
-    };
-
-    // Expand common environment variables
-    let expanded = expand_env_vars(path_str);
-
-    Path::new(&expanded)
+    Path::new(&path)
         .file_name()?
         .to_str()
         .map(|s| s.to_lowercase())
 }
 
-fn expand_env_vars(s: &str) -> String {
-    let mut result = s.to_string();
-    // Find all %VAR% patterns and expand them
-    while let Some(start) = result.find('%') {
-        if let Some(end) = result[start + 1..].find('%') {
-            let var_name = &result[start + 1..start + 1 + end];
-            if let Ok(value) = std::env::var(var_name) {
-                result = format!("{}{}{}", &result[..start], value, &result[start + 2 + end..]);
-            } else {
-                // Can't expand, skip this one
-                break;
-            }
-        } else {
-            break;
+// ── Service Models ──────────────────────────────────────────────────
+
+/// One trigger condition of a trigger-start service (SERVICE_TRIGGER).
+#[derive(Debug, Clone)]
+pub struct ServiceTrigger {
+    /// "Start" or "Stop" — what the trigger does to the service.
+    pub action: String,
+    /// Human-readable condition, e.g. "Device interface arrival {GUID}".
+    pub condition: String,
+    /// Trigger-specific data items (device IDs, ports, ETW keywords, ...).
+    pub data: Vec<String>,
+}
+
+/// SID type of a service (SERVICE_SID_INFO).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceSidType {
+    None,
+    Unrestricted,
+    Restricted,
+    Other(u32),
+}
+
+impl fmt::Display for ServiceSidType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceSidType::None => write!(f, "None"),
+            ServiceSidType::Unrestricted => write!(f, "Unrestricted"),
+            ServiceSidType::Restricted => write!(f, "Restricted"),
+            ServiceSidType::Other(v) => write!(f, "Unknown ({})", v),
         }
     }
-    result
+}
+
+/// Least-privilege configuration of a service: its SID type and the
+/// privileges it declares it needs.
+#[derive(Debug, Clone)]
+pub struct ServiceSecurity {
+    pub sid_type: ServiceSidType,
+    /// Required privileges (e.g. "SeChangeNotifyPrivilege"). Empty means the
+    /// service keeps every privilege of its account.
+    pub required_privileges: Vec<String>,
+}
+
+// ── Security Models ─────────────────────────────────────────────────
+
+/// Overall health of one Security Center component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityState {
+    Good,
+    Warning,
+    Bad,
+    Unknown,
+}
+
+impl fmt::Display for SecurityState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SecurityState::Good => write!(f, "On"),
+            SecurityState::Warning => write!(f, "Attention"),
+            SecurityState::Bad => write!(f, "Off"),
+            SecurityState::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// One row of the Security Center status panel.
+#[derive(Debug, Clone)]
+pub struct SecurityItem {
+    pub name: String,
+    pub state: SecurityState,
+    /// Supporting details (e.g. per-profile firewall state, UAC prompt level).
+    pub detail: String,
 }
 
 // ── Installed App Models ────────────────────────────────────────────
@@ -189,6 +636,102 @@ pub struct InstalledApp {
     pub uninstall_string: String,
     pub modify_path: Option<String>,
     pub install_location: String,
+    /// Full path of the Uninstall subkey (e.g. `HKLM\...\Uninstall\{GUID}`),
+    /// used as the app's stable identity.
+    pub registry_key: String,
+    /// The uninstall command points at an executable that no longer exists.
+    pub uninstaller_missing: bool,
+    /// Windows Installer ProductCode (`{GUID}`) for MSI-based installs.
+    pub msi_product_code: Option<String>,
+}
+
+// ── Execution History Models ────────────────────────────────────────
+
+/// A program Windows recorded as present or executed, from Amcache and/or
+/// the Shimcache (AppCompatCache).
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionRecord {
+    /// Full path, lower-cased by Amcache.
+    pub path: String,
+    pub name: String,
+    pub publisher: String,
+    pub product_name: String,
+    pub version: String,
+    /// SHA-1 of the file (Amcache only).
+    pub sha1: Option<String>,
+    /// When Amcache first recorded the file (its key's last write time).
+    pub first_seen: Option<DateTime<Local>>,
+    /// File modification time recorded by the Shimcache.
+    pub file_modified: Option<DateTime<Local>>,
+    /// Position in the Shimcache (0 = most recently added).
+    pub shimcache_position: Option<usize>,
+    pub in_amcache: bool,
+    /// Cross-references to the other tabs, refreshed as they load.
+    pub is_startup_entry: bool,
+    pub is_service: bool,
+    pub is_running: bool,
+}
+
+/// Execution history rows plus why any source could not be read.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionHistory {
+    pub records: Vec<ExecutionRecord>,
+    pub notes: Vec<String>,
+}
+
+// ── Dev Package Models ──────────────────────────────────────────────
+
+/// A developer package manager whose global installs are listed on the Dev
+/// Packages tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageManager {
+    Pip,
+    Npm,
+    Cargo,
+    Scoop,
+    Chocolatey,
+}
+
+impl PackageManager {
+    pub const ALL: [PackageManager; 5] = [
+        PackageManager::Pip,
+        PackageManager::Npm,
+        PackageManager::Cargo,
+        PackageManager::Scoop,
+        PackageManager::Chocolatey,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PackageManager::Pip => "pip",
+            PackageManager::Npm => "npm",
+            PackageManager::Cargo => "cargo",
+            PackageManager::Scoop => "Scoop",
+            PackageManager::Chocolatey => "Chocolatey",
+        }
+    }
+}
+
+/// A package installed globally by a developer package manager.
+#[derive(Debug, Clone)]
+pub struct DevPackage {
+    pub manager: PackageManager,
+    pub name: String,
+    pub version: String,
+    /// Where the manager keeps its global installs (site-packages, the npm
+    /// prefix, the Scoop or Chocolatey root).
+    pub location: String,
+    /// The package manager command that removes it, as program and
+    /// arguments.
+    pub uninstall_command: Vec<String>,
+}
+
+/// Dev packages plus which managers were not found or could not be read.
+#[derive(Debug, Clone, Default)]
+pub struct DevPackages {
+    pub packages: Vec<DevPackage>,
+    pub missing: Vec<PackageManager>,
+    pub notes: Vec<String>,
 }
 
 // ── Process Models ──────────────────────────────────────────────────
@@ -206,7 +749,117 @@ pub struct ProcessInfo {
     pub disk_read_bytes: u64,
     pub disk_write_bytes: u64,
     pub start_time: Option<DateTime<Local>>,
+    /// How long after system boot the process was started.
+    pub since_boot: Option<chrono::TimeDelta>,
     pub product_name: String,
+    pub company_name: String,
     pub user_name: String,
     pub is_elevated: bool,
+    /// The executable lives on a removable or network drive.
+    pub volatile_drive: Option<DriveKind>,
+    pub bitness: Option<Bitness>,
+    /// When the executable was built; with the signing time, this tells a
+    /// freshly dropped binary from long-installed software.
+    pub link_time: Option<LinkTime>,
+    /// Accumulated CPU time since the process started.
+    pub cpu_times: Option<CpuTimes>,
+    /// WSL, Docker Desktop, or Windows Sandbox, for the platform's own
+    /// processes and everything they started.
+    pub platform: Option<VirtPlatform>,
+    /// What is mapped at the image base doesn't match the executable, a
+    /// sign of process hollowing. `None` when it matches or couldn't be
+    /// checked.
+    pub image_mismatch: Option<ImageMismatch>,
+    pub enrichment: Enrichment,
+}
+
+/// How a process's mapped image differs from its executable file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageMismatch {
+    /// No file is mapped at the image base: the image was replaced with
+    /// private memory.
+    Unmapped,
+    /// Another file is mapped at the image base (kernel path).
+    OtherFile(String),
+}
+
+impl ImageMismatch {
+    pub fn description(&self) -> String {
+        match self {
+            ImageMismatch::Unmapped => {
+                "The image in memory isn't backed by the executable file; its code was replaced after \
+                 the process started (process hollowing)"
+                    .to_string()
+            }
+            ImageMismatch::OtherFile(path) => format!(
+                "The image in memory was loaded from a different file than the executable shown: {}",
+                path
+            ),
+        }
+    }
+}
+
+/// A virtualization platform whose host processes are grouped and badged on
+/// the Processes tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum VirtPlatform {
+    Wsl,
+    Docker,
+    Sandbox,
+}
+
+impl VirtPlatform {
+    pub fn label(&self) -> &'static str {
+        match self {
+            VirtPlatform::Wsl => "WSL",
+            VirtPlatform::Docker => "Docker",
+            VirtPlatform::Sandbox => "Sandbox",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            VirtPlatform::Wsl => {
+                "Windows Subsystem for Linux. vmmem / vmmemWSL holds the memory of the WSL 2 \
+                 virtual machine, including every running Linux distribution"
+            }
+            VirtPlatform::Docker => {
+                "Docker Desktop. Containers run inside its WSL 2 or Hyper-V virtual machine, \
+                 whose memory is shown under vmmem"
+            }
+            VirtPlatform::Sandbox => {
+                "Windows Sandbox. vmmem holds the memory of the sandbox virtual machine"
+            }
+        }
+    }
+}
+
+/// Kernel- and user-mode CPU time a process has used (`GetProcessTimes`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub kernel: std::time::Duration,
+    pub user: std::time::Duration,
+}
+
+impl CpuTimes {
+    pub fn total(&self) -> std::time::Duration {
+        self.kernel + self.user
+    }
+}
+
+/// Stable identity of a running process: PIDs are reused by Windows, so the
+/// start time is included to tell a new process apart from an exited one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProcessKey {
+    pub pid: u32,
+    pub start_time: Option<DateTime<Local>>,
+}
+
+impl ProcessInfo {
+    pub fn key(&self) -> ProcessKey {
+        ProcessKey {
+            pid: self.pid,
+            start_time: self.start_time,
+        }
+    }
 }
@@ -0,0 +1,186 @@
+//! Entries left behind by uninstalled apps. An uninstaller that misses a
+//! service, Run value, or scheduled task leaves it pointing into a folder
+//! that no longer belongs to anything. Once an app drops off the Installed
+//! list its Uninstall key (and with it `InstallLocation`) is gone, so every
+//! install location seen is remembered across sessions; a startup entry,
+//! task, or service whose binary is in the folder of an app that is no
+//! longer installed is "Orphaned by uninstall".
+//!
+//! Only apps App Manager has seen installed are known. Stored as a
+//! tab-separated text file in the app data directory:
+//! `<install location>\t<last seen date>\t<app name>`.
+
+use crate::app_data;
+use crate::cmdline;
+use crate::execution_history::normalize_path;
+use crate::models::{InstalledApp, StartupEntry};
+use chrono::{Local, NaiveDate};
+use std::path::PathBuf;
+
+const FILE_NAME: &str = "install-locations.tsv";
+const HEADER: &str = "# App Manager install locations v1";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Folders shared by many apps. An app that gives one of these (or a folder
+/// above one) as its install location doesn't own what is in it.
+const SHARED_FOLDERS: &[&str] = &[
+    "ProgramFiles",
+    "ProgramFiles(x86)",
+    "ProgramW6432",
+    "CommonProgramFiles",
+    "CommonProgramFiles(x86)",
+    "ProgramData",
+    "SystemRoot",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "USERPROFILE",
+    "PUBLIC",
+];
+
+/// An install location from the Installed list.
+#[derive(Debug, Clone)]
+struct KnownLocation {
+    /// Normalized, ending in a backslash.
+    folder: String,
+    app: String,
+    last_seen: NaiveDate,
+}
+
+/// Every install location seen on the Installed tab.
+pub struct InstallLocations {
+    path: Option<PathBuf>,
+    known: Vec<KnownLocation>,
+}
+
+impl InstallLocations {
+    /// Load the file, starting empty if it doesn't exist or can't be read.
+    pub fn load() -> Self {
+        let path = app_data::data_dir().map(|dir| dir.join(FILE_NAME));
+        let mut known = Vec::new();
+        if let Some(text) = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for line in text.lines() {
+                if line.starts_with('#') {
+                    continue;
+                }
+                let mut fields = line.splitn(3, '\t');
+                let (Some(folder), Some(last_seen), Some(app)) = (fields.next(), fields.next(), fields.next()) else {
+                    continue;
+                };
+                let Ok(last_seen) = NaiveDate::parse_from_str(last_seen, DATE_FORMAT) else {
+                    continue;
+                };
+                known.push(KnownLocation {
+                    folder: folder.to_string(),
+                    app: app.to_string(),
+                    last_seen,
+                });
+            }
+        }
+        Self { path, known }
+    }
+
+    /// Remember the install locations of the apps installed now.
+    pub fn update(&mut self, apps: &[InstalledApp]) {
+        let today = Local::now().date_naive();
+        let mut changed = false;
+        for app in apps {
+            let Some(folder) = app_folder(&app.install_location) else {
+                continue;
+            };
+            let name = app.display_name.replace(['\t', '\n', '\r'], " ");
+            match self.known.iter_mut().find(|k| k.folder == folder) {
+                Some(known) => {
+                    if known.last_seen != today || known.app != name {
+                        known.last_seen = today;
+                        known.app = name;
+                        changed = true;
+                    }
+                }
+                None => {
+                    self.known.push(KnownLocation {
+                        folder,
+                        app: name,
+                        last_seen: today,
+                    });
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.save();
+        }
+    }
+
+    /// Set `orphaned_by` on entries whose binary is in the install folder of
+    /// an app that isn't in `apps` any more, unless an installed app claims
+    /// the file too.
+    pub fn flag(&self, apps: &[InstalledApp], entries: &mut [StartupEntry]) {
+        // Without an Installed list every known app would look uninstalled
+        if apps.is_empty() {
+            return;
+        }
+        let installed: Vec<String> = apps.iter().filter_map(|a| app_folder(&a.install_location)).collect();
+        let uninstalled: Vec<&KnownLocation> = self
+            .known
+            .iter()
+            .filter(|k| !installed.contains(&k.folder))
+            .collect();
+        for entry in entries {
+            let target = normalize_path(&cmdline::target_path(&entry.command));
+            entry.orphaned_by = uninstalled
+                .iter()
+                .filter(|k| target.starts_with(&k.folder))
+                // The most specific folder names the app best
+                .max_by_key(|k| k.folder.len())
+                .filter(|_| !installed.iter().any(|folder| target.starts_with(folder)))
+                .map(|k| k.app.clone());
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for known in &self.known {
+            out.push_str(&format!("{}\t{}\t{}\n", known.folder, known.last_seen.format(DATE_FORMAT), known.app));
+        }
+        // Best effort, like the watch list
+        let _ = app_data::write_atomic(path, out);
+    }
+}
+
+/// An `InstallLocation` normalized for prefix matching, or `None` when it is
+/// empty or a folder the app can't own (a drive root, Program Files, the
+/// Windows folder and anything in it).
+fn app_folder(location: &str) -> Option<String> {
+    let folder = normalize_folder(location)?;
+    let (_, rest) = folder.split_once(":\\")?;
+    if rest.is_empty() {
+        return None;
+    }
+    let system_root = std::env::var("SystemRoot").ok().and_then(|p| normalize_folder(&p));
+    if system_root.is_some_and(|root| folder.starts_with(&root)) {
+        return None;
+    }
+    let shared = SHARED_FOLDERS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .chain(std::env::var("LOCALAPPDATA").ok().map(|p| format!("{}\\Programs", p)))
+        .filter_map(|p| normalize_folder(&p));
+    for shared in shared {
+        if shared.starts_with(&folder) {
+            return None;
+        }
+    }
+    Some(folder)
+}
+
+fn normalize_folder(path: &str) -> Option<String> {
+    let path = path.trim().trim_matches('"').trim_end_matches(['\\', '/']).replace('/', "\\");
+    if path.is_empty() {
+        return None;
+    }
+    Some(format!("{}\\", normalize_path(&path)))
+}
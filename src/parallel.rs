@@ -0,0 +1,36 @@
+//! Data-parallel helpers for the collectors.
+//!
+//! Per-item enrichment (PE version resources, process tokens, file checks) is
+//! dominated by blocking I/O, so spreading it over a few scoped threads cuts
+//! the cold-start load time roughly by the thread count.
+
+/// Upper bound on worker threads; the work is I/O-bound, so more doesn't help.
+const MAX_THREADS: usize = 8;
+
+/// Map `f` over `items` on scoped worker threads, preserving order.
+pub fn map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = std::thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(MAX_THREADS);
+    if threads <= 1 || items.len() < 2 {
+        return items.iter().map(&f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|s| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| s.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    })
+}
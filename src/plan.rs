@@ -0,0 +1,158 @@
+//! Cleanup plans: changes staged without being applied (disable this entry,
+//! stop that service, uninstall an app), saved as a text file that can be
+//! reviewed or handed to someone else, then imported and run one step at a
+//! time.
+//!
+//! File format, one step per line after the header:
+//! `<action>\t<target>\t<name>\t<status>\t<result>`. The target is the run
+//! history key of a startup entry or service (see [`entry_key`]), or the
+//! Uninstall registry key of an installed app. Status and result record how a
+//! run went; imported plans start over with every step pending.
+//!
+//! [`entry_key`]: crate::history::entry_key
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+const HEADER: &str = "# App Manager cleanup plan v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanAction {
+    Disable,
+    Enable,
+    Start,
+    Stop,
+    Uninstall,
+}
+
+impl PlanAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PlanAction::Disable => "disable",
+            PlanAction::Enable => "enable",
+            PlanAction::Start => "start",
+            PlanAction::Stop => "stop",
+            PlanAction::Uninstall => "uninstall",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "disable" => Some(PlanAction::Disable),
+            "enable" => Some(PlanAction::Enable),
+            "start" => Some(PlanAction::Start),
+            "stop" => Some(PlanAction::Stop),
+            "uninstall" => Some(PlanAction::Uninstall),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlanAction::Disable => "Disable",
+            PlanAction::Enable => "Enable",
+            PlanAction::Start => "Start",
+            PlanAction::Stop => "Stop",
+            PlanAction::Uninstall => "Uninstall",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepStatus {
+    Pending,
+    /// Sent to the executor and not finished yet.
+    Running,
+    Done(String),
+    Failed(String),
+}
+
+impl StepStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            StepStatus::Pending => "pending",
+            StepStatus::Running => "running",
+            StepStatus::Done(_) => "done",
+            StepStatus::Failed(_) => "failed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PlanStep {
+    pub action: PlanAction,
+    pub target: String,
+    pub name: String,
+    pub status: StepStatus,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CleanupPlan {
+    pub steps: Vec<PlanStep>,
+}
+
+impl CleanupPlan {
+    /// Stage a step. Returns `false` if the same action on the same target is
+    /// already in the plan.
+    pub fn add(&mut self, action: PlanAction, target: String, name: String) -> bool {
+        if self.steps.iter().any(|s| s.action == action && s.target == target) {
+            return false;
+        }
+        self.steps.push(PlanStep {
+            action,
+            target,
+            name,
+            status: StepStatus::Pending,
+        });
+        true
+    }
+
+    /// Index of the first step that hasn't run yet.
+    pub fn next_pending(&self) -> Option<usize> {
+        self.steps.iter().position(|s| s.status == StepStatus::Pending)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let clean = |s: &str| s.replace(['\t', '\n', '\r'], " ");
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for step in &self.steps {
+            let result = match &step.status {
+                StepStatus::Pending | StepStatus::Running => "",
+                StepStatus::Done(msg) | StepStatus::Failed(msg) => msg,
+            };
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                step.action.as_str(),
+                clean(&step.target),
+                clean(&step.name),
+                step.status.as_str(),
+                clean(result)
+            ));
+        }
+        std::fs::write(path, out).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if text.lines().next().map(str::trim) != Some(HEADER) {
+            anyhow::bail!("{} is not an App Manager cleanup plan", path.display());
+        }
+        let mut plan = CleanupPlan::default();
+        for (n, line) in text.lines().enumerate().skip(1) {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split('\t');
+            let (Some(action), Some(target), Some(name)) = (fields.next(), fields.next(), fields.next())
+            else {
+                anyhow::bail!("Line {}: expected action, target, and name", n + 1);
+            };
+            let action = PlanAction::parse(action)
+                .with_context(|| format!("Line {}: unknown action '{}'", n + 1, action))?;
+            plan.add(action, target.to_string(), name.to_string());
+        }
+        Ok(plan)
+    }
+}
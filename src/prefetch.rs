@@ -1,60 +1,140 @@
+use crate::models::{LastRanSource, RunState, StartupEntry};
 use chrono::{DateTime, Local};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use winreg::enums::*;
+use winreg::RegKey;
 
 const PREFETCH_DIR: &str = r"C:\Windows\Prefetch";
+const PREFETCH_PARAMETERS_KEY: &str =
+    r"SYSTEM\CurrentControlSet\Control\Session Manager\Memory Management\PrefetchParameters";
 
-pub struct PrefetchCache {
-    last_ran: HashMap<String, DateTime<Local>>,
-    pub accessible: bool,
+/// How long a load may spend reading the Prefetch folder. On machines with
+/// thousands of .pf files, the times read by then are kept and the rest are
+/// left out.
+pub const TIME_BUDGET: Duration = Duration::from_secs(10);
+/// Files read between updates.
+const BATCH_SIZE: usize = 256;
+
+/// Progress of a background Prefetch read.
+pub enum Update {
+    /// Last-ran times from the files read since the previous update, keyed by
+    /// upper-case exe name.
+    Batch(HashMap<String, DateTime<Local>>),
+    /// Reading ended. `complete` is false if the time budget ran out first.
+    Finished { executables: usize, complete: bool },
 }
 
-impl PrefetchCache {
-    pub fn new() -> Self {
-        let mut last_ran = HashMap::new();
-        let prefetch_path = Path::new(PREFETCH_DIR);
+/// Whether the Prefetch folder can be read (it requires administrator rights).
+pub fn is_accessible() -> bool {
+    std::fs::read_dir(PREFETCH_DIR).is_ok()
+}
 
-        let accessible = match std::fs::read_dir(prefetch_path) {
-            Ok(entries) => {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    let ext = path
-                        .extension()
-                        .and_then(|e| e.to_str())
-                        .unwrap_or("");
-                    if !ext.eq_ignore_ascii_case("pf") {
-                        continue;
-                    }
+/// Whether Prefetch is turned off (`EnablePrefetcher` = 0), as some SSD
+/// tuning guides and policies do. The folder then stops being updated.
+pub fn is_disabled() -> bool {
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey_with_flags(PREFETCH_PARAMETERS_KEY, KEY_READ)
+        .and_then(|key| key.get_value::<u32, _>("EnablePrefetcher"))
+        .is_ok_and(|v| v == 0)
+}
 
-                    if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                        if let Some(exe_name) = parse_prefetch_filename(filename) {
-                            if let Ok(metadata) = entry.metadata() {
-                                if let Ok(modified) = metadata.modified() {
-                                    let dt: DateTime<Local> = modified.into();
-                                    last_ran
-                                        .entry(exe_name)
-                                        .and_modify(|existing: &mut DateTime<Local>| {
-                                            if dt > *existing {
-                                                *existing = dt;
-                                            }
-                                        })
-                                        .or_insert(dt);
-                                }
-                            }
-                        }
-                    }
+/// Read the Prefetch folder on a background thread, sending last-ran times in
+/// batches as they are read.
+pub fn load_async(budget: Duration) -> mpsc::Receiver<Update> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let deadline = Instant::now() + budget;
+        let mut seen = HashSet::new();
+        let mut batch = HashMap::new();
+        let mut files = 0;
+        let mut complete = true;
+        if let Ok(entries) = std::fs::read_dir(PREFETCH_DIR) {
+            for entry in entries.flatten() {
+                if Instant::now() >= deadline {
+                    complete = false;
+                    break;
+                }
+                if let Some((exe_name, dt)) = read_entry(&entry) {
+                    seen.insert(exe_name.clone());
+                    merge(&mut batch, exe_name, dt);
+                }
+                files += 1;
+                if files % BATCH_SIZE == 0
+                    && !batch.is_empty()
+                    && tx.send(Update::Batch(std::mem::take(&mut batch))).is_err()
+                {
+                    return;
                 }
-                true
             }
-            Err(_) => false,
-        };
+        }
+        if !batch.is_empty() {
+            let _ = tx.send(Update::Batch(batch));
+        }
+        let _ = tx.send(Update::Finished {
+            executables: seen.len(),
+            complete,
+        });
+    });
+    rx
+}
 
-        Self { last_ran, accessible }
+/// Keep the newer time when an exe has several .pf files.
+pub fn merge(last_ran: &mut HashMap<String, DateTime<Local>>, exe_name: String, dt: DateTime<Local>) {
+    last_ran
+        .entry(exe_name)
+        .and_modify(|existing| {
+            if dt > *existing {
+                *existing = dt;
+            }
+        })
+        .or_insert(dt);
+}
+
+/// Fill in last-ran times from Prefetch on stopped entries. Prefetch is
+/// preferred over UserAssist and Background Activity data, but not over
+/// times from Task Scheduler or the time an entry was disabled.
+pub fn fill_last_ran(entries: &mut [StartupEntry], last_ran: &HashMap<String, DateTime<Local>>) {
+    if last_ran.is_empty() {
+        return;
     }
+    for entry in entries {
+        if entry.run_state == RunState::Running {
+            continue;
+        }
+        let replaceable = match entry.last_ran_source {
+            None => entry.last_ran.is_none(),
+            Some(LastRanSource::Prefetch | LastRanSource::UserAssist | LastRanSource::BackgroundActivity) => true,
+            Some(_) => false,
+        };
+        if !replaceable {
+            continue;
+        }
+        let Some(exe) = entry.exe_name() else {
+            continue;
+        };
+        let Some(&dt) = last_ran.get(&exe.to_uppercase()) else {
+            continue;
+        };
+        if entry.last_ran_source == Some(LastRanSource::Prefetch) && entry.last_ran >= Some(dt) {
+            continue;
+        }
+        entry.last_ran = Some(dt);
+        entry.last_ran_source = Some(LastRanSource::Prefetch);
+    }
+}
 
-    pub fn last_ran(&self, exe_name: &str) -> Option<DateTime<Local>> {
-        self.last_ran.get(&exe_name.to_uppercase()).copied()
+/// Exe name and last-written time of one .pf file.
+fn read_entry(entry: &std::fs::DirEntry) -> Option<(String, DateTime<Local>)> {
+    let path = entry.path();
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if !ext.eq_ignore_ascii_case("pf") {
+        return None;
     }
+    let exe_name = parse_prefetch_filename(path.file_name()?.to_str()?)?;
+    let modified = entry.metadata().ok()?.modified().ok()?;
+    Some((exe_name, modified.into()))
 }
 
 /// Extract exe name from prefetch filename: "CHROME.EXE-AB12CD34.pf" -> "CHROME.EXE"
@@ -0,0 +1,157 @@
+//! DPI awareness and UI framework of a process, for developers checking how
+//! their own apps will render on mixed-DPI setups.
+
+use std::fmt;
+use std::path::Path;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Module32FirstW, Module32NextW, MODULEENTRY32W, TH32CS_SNAPMODULE,
+    TH32CS_SNAPMODULE32,
+};
+use windows::Win32::System::Threading::{OpenProcess, PROCESS_QUERY_INFORMATION};
+use windows::Win32::UI::HiDpi::{
+    AreDpiAwarenessContextsEqual, GetDpiAwarenessContextForProcess, GetProcessDpiAwareness,
+    DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, DPI_AWARENESS_CONTEXT_UNAWARE_GDISCALED,
+    PROCESS_PER_MONITOR_DPI_AWARE, PROCESS_SYSTEM_DPI_AWARE,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DpiAwareness {
+    Unaware,
+    /// Unaware, but GDI text and shapes are redrawn at the monitor's DPI.
+    UnawareGdiScaled,
+    SystemAware,
+    PerMonitor,
+    PerMonitorV2,
+}
+
+impl DpiAwareness {
+    /// How Windows scales the process's windows.
+    pub fn description(&self) -> &'static str {
+        match self {
+            DpiAwareness::Unaware => "Bitmap-stretched by Windows on any display above 100%, so it looks blurry",
+            DpiAwareness::UnawareGdiScaled => "Bitmap-stretched, except GDI text and shapes, which stay sharp",
+            DpiAwareness::SystemAware => {
+                "Sharp at the primary display's scale; stretched on monitors with a different scale"
+            }
+            DpiAwareness::PerMonitor => "Rescales itself when moved between monitors (top-level windows only)",
+            DpiAwareness::PerMonitorV2 => {
+                "Rescales itself when moved between monitors, including dialogs and non-client areas"
+            }
+        }
+    }
+}
+
+impl fmt::Display for DpiAwareness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DpiAwareness::Unaware => "Unaware",
+            DpiAwareness::UnawareGdiScaled => "Unaware (GDI scaled)",
+            DpiAwareness::SystemAware => "System aware",
+            DpiAwareness::PerMonitor => "Per-monitor",
+            DpiAwareness::PerMonitorV2 => "Per-monitor v2",
+        })
+    }
+}
+
+/// Loaded modules that identify a UI framework, matched by file name prefix.
+const FRAMEWORK_MODULES: &[(&str, &[&str])] = &[
+    ("WinUI 3", &["microsoft.ui.xaml.dll"]),
+    ("UWP XAML", &["windows.ui.xaml.dll"]),
+    ("WPF", &["presentationframework", "wpfgfx_"]),
+    ("Windows Forms", &["system.windows.forms"]),
+    ("Qt 6", &["qt6core"]),
+    ("Qt 5", &["qt5core"]),
+    ("Qt 4", &["qtcore4"]),
+    ("Flutter", &["flutter_windows.dll"]),
+    ("Chromium Embedded Framework", &["libcef.dll"]),
+    ("WebView2", &["embeddedbrowserwebview.dll"]),
+    ("GTK", &["libgtk-"]),
+    ("wxWidgets", &["wxmsw"]),
+    ("Java", &["jvm.dll"]),
+    // Versioned names only: mfcore.dll is Media Foundation
+    ("MFC", &["mfc42", "mfc7", "mfc8", "mfc9", "mfc1"]),
+];
+
+/// DPI awareness of a running process. `None` if it can't be opened.
+pub fn dpi_awareness(pid: u32) -> Option<DpiAwareness> {
+    if pid <= 4 {
+        return None;
+    }
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION, false, pid) }.ok()?;
+    let awareness = unsafe { query_dpi_awareness(handle) };
+    let _ = unsafe { CloseHandle(handle) };
+    awareness
+}
+
+unsafe fn query_dpi_awareness(handle: HANDLE) -> Option<DpiAwareness> {
+    // GetProcessDpiAwareness predates per-monitor v2 and GDI scaling, which
+    // only the process's awareness context tells apart
+    let awareness = GetProcessDpiAwareness(Some(handle)).ok()?;
+    let context = GetDpiAwarenessContextForProcess(handle);
+    Some(match awareness {
+        PROCESS_PER_MONITOR_DPI_AWARE
+            if AreDpiAwarenessContextsEqual(context, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2).as_bool() =>
+        {
+            DpiAwareness::PerMonitorV2
+        }
+        PROCESS_PER_MONITOR_DPI_AWARE => DpiAwareness::PerMonitor,
+        PROCESS_SYSTEM_DPI_AWARE => DpiAwareness::SystemAware,
+        _ if AreDpiAwarenessContextsEqual(context, DPI_AWARENESS_CONTEXT_UNAWARE_GDISCALED).as_bool() => {
+            DpiAwareness::UnawareGdiScaled
+        }
+        _ => DpiAwareness::Unaware,
+    })
+}
+
+/// UI frameworks a process has loaded, e.g. "WPF, WebView2", from its module
+/// list; Electron apps are recognized by the `app.asar` next to the
+/// executable. `None` if the module list can't be read (other users'
+/// processes without admin rights, protected processes).
+pub fn detect_framework(pid: u32, exe_path: &str) -> Option<String> {
+    let modules = loaded_modules(pid)?;
+    let mut found: Vec<&str> = Vec::new();
+    let electron = Path::new(exe_path)
+        .parent()
+        .is_some_and(|dir| dir.join("resources").join("app.asar").exists());
+    if electron {
+        found.push("Electron");
+    }
+    for (framework, prefixes) in FRAMEWORK_MODULES {
+        if modules.iter().any(|m| prefixes.iter().any(|p| m.starts_with(p))) {
+            found.push(framework);
+        }
+    }
+    if !found.is_empty() {
+        Some(found.join(", "))
+    } else if modules.iter().any(|m| m == "user32.dll") {
+        Some("Win32 (no framework recognized)".to_string())
+    } else {
+        Some("None (no windowing libraries loaded)".to_string())
+    }
+}
+
+/// Lowercased file names of the modules loaded in a process.
+fn loaded_modules(pid: u32) -> Option<Vec<String>> {
+    if pid <= 4 {
+        return None;
+    }
+    // The snapshot fails with ERROR_BAD_LENGTH while the process is loading
+    // or unloading a module; one retry covers that
+    let snapshot = (0..2).find_map(|_| unsafe {
+        CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, pid).ok()
+    })?;
+    let mut modules = Vec::new();
+    let mut entry = MODULEENTRY32W {
+        dwSize: std::mem::size_of::<MODULEENTRY32W>() as u32,
+        ..Default::default()
+    };
+    let mut next = unsafe { Module32FirstW(snapshot, &mut entry) };
+    while next.is_ok() {
+        let len = entry.szModule.iter().position(|&c| c == 0).unwrap_or(entry.szModule.len());
+        modules.push(String::from_utf16_lossy(&entry.szModule[..len]).to_lowercase());
+        next = unsafe { Module32NextW(snapshot, &mut entry) };
+    }
+    let _ = unsafe { CloseHandle(snapshot) };
+    Some(modules)
+}
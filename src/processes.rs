@@ -1,13 +1,22 @@
-use crate::models::ProcessInfo;
+use crate::allowlist;
+use crate::drives;
+use crate::image_integrity;
+use crate::models::{CpuTimes, Enrichment, LinkTime, ProcessInfo, ProcessKey, VirtPlatform};
+use crate::parallel;
+use crate::status;
 use crate::version_info;
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
-use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Foundation::{CloseHandle, FILETIME, HANDLE};
 use windows::Win32::Security::{
     GetTokenInformation, LookupAccountSidW, TokenElevation, TokenUser, SID_NAME_USE,
     TOKEN_ELEVATION, TOKEN_QUERY, TOKEN_USER,
 };
-use windows::Win32::System::Threading::{OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION};
+use windows::Win32::System::SystemInformation::GetTickCount64;
+use windows::Win32::System::Threading::{
+    GetProcessTimes, OpenProcess, OpenProcessToken, PROCESS_QUERY_LIMITED_INFORMATION,
+};
 
 /// Collect all running processes.
 /// Performs a double-refresh with a short delay to get accurate CPU usage values.
@@ -27,55 +36,73 @@ pub fn collect_processes() -> Vec<ProcessInfo> {
     // Second refresh: CPU usage is now computed from the delta
     sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
 
-    let mut processes: Vec<ProcessInfo> = sys
-        .processes()
-        .iter()
-        .map(|(pid, process)| {
-            let start_time = {
-                let secs = process.start_time();
-                if secs > 0 {
-                    chrono::DateTime::from_timestamp(secs as i64, 0)
-                        .map(|dt| dt.with_timezone(&chrono::Local))
-                } else {
-                    None
-                }
-            };
-            let exe_path = process
-                .exe()
-                .map(|p| p.to_string_lossy().to_string())
-                .unwrap_or_default();
-            let command_line = {
-                let args = process.cmd();
-                if args.is_empty() {
-                    String::new()
-                } else {
-                    args.iter()
-                        .map(|a| a.to_string_lossy().to_string())
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                }
-            };
-            let product_name = version_info::get_product_name(&exe_path).unwrap_or_default();
-            let disk = process.disk_usage();
-            let pid_u32 = pid.as_u32();
-            let (user_name, is_elevated) = get_process_user_and_elevation(pid_u32);
-            ProcessInfo {
-                pid: pid_u32,
-                parent_pid: process.parent().map(|p| p.as_u32()),
-                name: process.name().to_string_lossy().to_string(),
-                exe_path,
-                command_line,
-                memory_bytes: process.memory(),
-                cpu_usage: process.cpu_usage(),
-                disk_read_bytes: disk.total_read_bytes,
-                disk_write_bytes: disk.total_written_bytes,
-                start_time,
-                product_name,
-                user_name,
-                is_elevated,
+    // Version-info and token queries dominate the cost; run them in parallel
+    let boot = boot_time();
+    let procs: Vec<(&sysinfo::Pid, &sysinfo::Process)> = sys.processes().iter().collect();
+    let mut processes: Vec<ProcessInfo> = parallel::map(&procs, |&(pid, process)| {
+        let (creation_time, cpu_times) = get_process_times(pid.as_u32()).unzip();
+        // Prefer the exact creation time; sysinfo only has whole seconds
+        let start_time = creation_time.flatten().or_else(|| {
+            let secs = process.start_time();
+            if secs > 0 {
+                chrono::DateTime::from_timestamp(secs as i64, 0)
+                    .map(|dt| dt.with_timezone(&chrono::Local))
+            } else {
+                None
             }
-        })
-        .collect();
+        });
+        // Kernel processes report times slightly before boot; clamp to zero
+        let since_boot = start_time.map(|t| (t - boot).max(chrono::TimeDelta::zero()));
+        let exe_path = process
+            .exe()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let command_line = {
+            let args = process.cmd();
+            if args.is_empty() {
+                String::new()
+            } else {
+                args.iter()
+                    .map(|a| a.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            }
+        };
+        let product_name = version_info::get_product_name(&exe_path).unwrap_or_default();
+        let company_name = version_info::get_company_name(&exe_path).unwrap_or_default();
+        let volatile_drive = drives::volatile_drive_kind(&exe_path);
+        let bitness = version_info::get_bitness(&exe_path);
+        let link_time = version_info::get_link_time(&exe_path);
+        let disk = process.disk_usage();
+        let pid_u32 = pid.as_u32();
+        let (user_name, is_elevated) = get_process_user_and_elevation(pid_u32);
+        let image_mismatch = image_integrity::check(pid_u32);
+        ProcessInfo {
+            pid: pid_u32,
+            parent_pid: process.parent().map(|p| p.as_u32()),
+            name: process.name().to_string_lossy().to_string(),
+            exe_path,
+            command_line,
+            memory_bytes: process.memory(),
+            cpu_usage: process.cpu_usage(),
+            disk_read_bytes: disk.total_read_bytes,
+            disk_write_bytes: disk.total_written_bytes,
+            start_time,
+            since_boot,
+            product_name,
+            company_name,
+            user_name,
+            is_elevated,
+            volatile_drive,
+            bitness,
+            link_time,
+            cpu_times,
+            platform: None,
+            image_mismatch,
+            enrichment: Enrichment::default(),
+        }
+    });
+    assign_platforms(&mut processes);
 
     processes.sort_by(|a, b| {
         a.name
@@ -87,6 +114,177 @@ pub fn collect_processes() -> Vec<ProcessInfo> {
     processes
 }
 
+/// Executables that belong to a virtualization platform (lowercase).
+static PLATFORM_PROCESS_NAMES: &[(&str, VirtPlatform)] = &[
+    ("wsl.exe", VirtPlatform::Wsl),
+    ("wslhost.exe", VirtPlatform::Wsl),
+    ("wslservice.exe", VirtPlatform::Wsl),
+    ("wslrelay.exe", VirtPlatform::Wsl),
+    ("wslg.exe", VirtPlatform::Wsl),
+    ("vmmemwsl", VirtPlatform::Wsl),
+    ("docker desktop.exe", VirtPlatform::Docker),
+    ("com.docker.backend.exe", VirtPlatform::Docker),
+    ("com.docker.build.exe", VirtPlatform::Docker),
+    ("com.docker.proxy.exe", VirtPlatform::Docker),
+    ("com.docker.service", VirtPlatform::Docker),
+    ("com.docker.service.exe", VirtPlatform::Docker),
+    ("com.docker.dev-envs.exe", VirtPlatform::Docker),
+    ("vpnkit.exe", VirtPlatform::Docker),
+    ("dockerd.exe", VirtPlatform::Docker),
+    ("windowssandbox.exe", VirtPlatform::Sandbox),
+    ("windowssandboxclient.exe", VirtPlatform::Sandbox),
+    ("windowssandboxremotesession.exe", VirtPlatform::Sandbox),
+    ("vmmemcmfirstboot", VirtPlatform::Sandbox),
+    ("vmmemcmzygote", VirtPlatform::Sandbox),
+];
+
+/// Mark each platform's own processes, attribute the shared `vmmem` process
+/// to whichever platform is running, and let child processes inherit their
+/// ancestor's platform.
+fn assign_platforms(processes: &mut [ProcessInfo]) {
+    let mut direct: HashMap<u32, VirtPlatform> = HashMap::new();
+    for proc in processes.iter() {
+        let name = proc.name.to_lowercase();
+        if let Some((_, platform)) = PLATFORM_PROCESS_NAMES.iter().find(|(n, _)| *n == name) {
+            direct.insert(proc.pid, *platform);
+        }
+    }
+
+    // Older builds name every VM's memory process plain "vmmem". Docker
+    // Desktop's backend is itself a WSL 2 distribution, so WSL wins.
+    let present: HashSet<VirtPlatform> = direct.values().copied().collect();
+    let vmmem_owner = [VirtPlatform::Wsl, VirtPlatform::Docker, VirtPlatform::Sandbox]
+        .into_iter()
+        .find(|p| present.contains(p));
+    if let Some(owner) = vmmem_owner {
+        for proc in processes.iter() {
+            if proc.name.eq_ignore_ascii_case("vmmem") {
+                direct.insert(proc.pid, owner);
+            }
+        }
+    }
+
+    let parents: HashMap<u32, u32> = processes
+        .iter()
+        .filter_map(|p| p.parent_pid.map(|ppid| (p.pid, ppid)))
+        .collect();
+    for proc in processes.iter_mut() {
+        let mut pid = proc.pid;
+        // Bounded walk: PIDs are reused, so parent links can form cycles
+        for _ in 0..64 {
+            if let Some(platform) = direct.get(&pid) {
+                proc.platform = Some(*platform);
+                break;
+            }
+            match parents.get(&pid) {
+                Some(&ppid) if ppid != pid => pid = ppid,
+                _ => break,
+            }
+        }
+    }
+}
+
+/// System boot time, derived from the millisecond uptime counter.
+pub fn boot_time() -> chrono::DateTime<chrono::Local> {
+    let uptime_ms = unsafe { GetTickCount64() };
+    chrono::Local::now() - chrono::TimeDelta::milliseconds(uptime_ms as i64)
+}
+
+/// Format a since-boot offset as `+H:MM:SS.mmm` (with a day prefix past 24h).
+pub fn format_since_boot(offset: chrono::TimeDelta) -> String {
+    let total_ms = offset.num_milliseconds().max(0);
+    let ms = total_ms % 1000;
+    let secs = total_ms / 1000;
+    let (days, hours, mins, secs) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("+{}d {:02}:{:02}:{:02}.{:03}", days, hours, mins, secs, ms)
+    } else {
+        format!("+{}:{:02}:{:02}.{:03}", hours, mins, secs, ms)
+    }
+}
+
+/// Format accumulated CPU time as `H:MM:SS` (Task Manager's CPU time style),
+/// with tenths of a second under a minute so short bursts still register.
+/// The Built / Signed cell: the link date, then the signing date when the
+/// signature is timestamped.
+pub fn format_binary_dates(proc: &ProcessInfo) -> String {
+    let built = match proc.link_time {
+        Some(LinkTime::At(dt)) => dt.format("%Y-%m-%d").to_string(),
+        Some(LinkTime::Reproducible) => "Reproducible".to_string(),
+        None => String::new(),
+    };
+    // The date part of the full timestamp
+    let signed = proc.enrichment.value("Signed On").unwrap_or_default().split(' ').next().unwrap_or_default();
+    match (built.is_empty(), signed.is_empty()) {
+        (_, true) => built,
+        (true, false) => format!("\u{2014} / {}", signed),
+        (false, false) => format!("{} / {}", built, signed),
+    }
+}
+
+pub fn format_cpu_time(time: std::time::Duration) -> String {
+    let secs = time.as_secs();
+    if secs < 60 {
+        format!("{:.1}s", time.as_secs_f64())
+    } else {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+}
+
+/// Shell flavor for a copied kill command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KillCommandStyle {
+    PowerShell,
+    Taskkill,
+}
+
+impl KillCommandStyle {
+    pub const ALL: [KillCommandStyle; 2] = [KillCommandStyle::PowerShell, KillCommandStyle::Taskkill];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KillCommandStyle::PowerShell => "Copy Stop-Process command",
+            KillCommandStyle::Taskkill => "Copy taskkill command",
+        }
+    }
+}
+
+/// A command that force-kills the given processes, for repeating the action
+/// in a script or on a remote shell.
+pub fn kill_command(pids: &[u32], style: KillCommandStyle) -> String {
+    let ids: Vec<String> = pids.iter().map(|p| p.to_string()).collect();
+    match style {
+        KillCommandStyle::PowerShell => format!("Stop-Process -Id {} -Force", ids.join(",")),
+        KillCommandStyle::Taskkill => {
+            let args: Vec<String> = ids.iter().map(|id| format!("/PID {}", id)).collect();
+            format!("taskkill {} /F", args.join(" "))
+        }
+    }
+}
+
+/// Exact process creation time via GetProcessTimes (100ns resolution).
+/// Creation time and accumulated kernel/user CPU time of a process.
+fn get_process_times(pid: u32) -> Option<(Option<chrono::DateTime<chrono::Local>>, CpuTimes)> {
+    if pid <= 4 {
+        return None;
+    }
+    let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) }.ok()?;
+    let mut creation = FILETIME::default();
+    let mut exit = FILETIME::default();
+    let mut kernel = FILETIME::default();
+    let mut user = FILETIME::default();
+    let ok = unsafe { GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user) };
+    let _ = unsafe { CloseHandle(handle) };
+    ok.ok()?;
+    let ticks = |ft: FILETIME| ((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64;
+    // Kernel and user times are durations in 100 ns units
+    let cpu_times = CpuTimes {
+        kernel: std::time::Duration::from_nanos(ticks(kernel).saturating_mul(100)),
+        user: std::time::Duration::from_nanos(ticks(user).saturating_mul(100)),
+    };
+    Some((status::filetime_to_datetime(ticks(creation)), cpu_times))
+}
+
 /// Get the user name and elevation status for a process by PID.
 /// Returns (user_name, is_elevated). On failure, returns empty string / false.
 fn get_process_user_and_elevation(pid: u32) -> (String, bool) {
@@ -201,10 +399,39 @@ pub fn parent_pids(processes: &[ProcessInfo]) -> HashSet<u32> {
     parents
 }
 
+/// Processes that started or exited between two auto-refreshes, highlighted
+/// until the next one.
+#[derive(Default)]
+pub struct ProcessDiff {
+    pub appeared: HashSet<ProcessKey>,
+    /// Still listed (struck through) for one cycle.
+    pub exited: Vec<ProcessInfo>,
+    exited_keys: HashSet<ProcessKey>,
+}
+
+impl ProcessDiff {
+    pub fn new(old: &[ProcessInfo], new: &[ProcessInfo]) -> Self {
+        let old_keys: HashSet<ProcessKey> = old.iter().map(|p| p.key()).collect();
+        let new_keys: HashSet<ProcessKey> = new.iter().map(|p| p.key()).collect();
+        Self {
+            appeared: new_keys.difference(&old_keys).copied().collect(),
+            exited: old.iter().filter(|p| !new_keys.contains(&p.key())).cloned().collect(),
+            exited_keys: old_keys.difference(&new_keys).copied().collect(),
+        }
+    }
+
+    pub fn has_exited(&self, key: &ProcessKey) -> bool {
+        self.exited_keys.contains(key)
+    }
+}
+
 /// A flattened tree row: depth level + reference to the process.
 pub struct TreeRow<'a> {
     pub depth: usize,
-    pub process: &'a ProcessInfo,
+    /// The process, or a synthetic aggregate for group header rows.
+    pub process: Cow<'a, ProcessInfo>,
+    /// True for synthetic group header rows (no real process behind them).
+    pub is_group: bool,
     pub has_children: bool,
     pub is_expanded: bool,
     /// Whether this node is the last sibling at its depth level.
@@ -219,17 +446,308 @@ pub struct TreeRow<'a> {
 /// - `expanded_pids`: PIDs whose children are visible.
 /// - `hide_windows`: if true, skip known Windows processes (and their subtrees
 ///   unless they have non-Windows descendants).
+/// - `collapse_svchost`: if true, fold svchost.exe instances into one synthetic
+///   node per parent (see [`collapse_svchost`]).
+/// - `group_platforms`: if true, fold WSL, Docker Desktop, and Windows Sandbox
+///   processes into one synthetic node per platform (see [`group_platforms`]).
 pub fn build_visible_tree<'a>(
     processes: &'a [ProcessInfo],
     expanded_pids: &HashSet<u32>,
     hide_windows: bool,
+    collapse_svchost: bool,
+    group_platforms: bool,
+) -> Vec<TreeRow<'a>> {
+    let refs: Vec<&ProcessInfo> = processes.iter().collect();
+    build_tree_rows(&refs, expanded_pids, hide_windows, collapse_svchost, group_platforms)
+}
+
+/// Build the visible tree grouped under one synthetic header row per user account.
+/// Header rows carry the aggregated CPU, memory, and disk usage of their processes.
+pub fn build_user_grouped_tree<'a>(
+    processes: &'a [ProcessInfo],
+    expanded_pids: &HashSet<u32>,
+    hide_windows: bool,
+    collapse_svchost: bool,
+    group_platforms: bool,
 ) -> Vec<TreeRow<'a>> {
+    let mut groups: BTreeMap<String, Vec<&ProcessInfo>> = BTreeMap::new();
+    for proc in processes {
+        groups.entry(user_group_key(proc)).or_default().push(proc);
+    }
+
+    // Collect the groups with visible rows first, so the last of them is the
+    // last sibling
+    let visible: Vec<_> = groups
+        .into_iter()
+        .filter_map(|(user, members)| {
+            let children = build_tree_rows(&members, expanded_pids, hide_windows, collapse_svchost, group_platforms);
+            (!children.is_empty()).then_some((user, members, children))
+        })
+        .collect();
+
+    let mut result = Vec::new();
+    let group_count = visible.len();
+    for (i, (user, members, children)) in visible.into_iter().enumerate() {
+        let header = group_header(&user, &user, &members, hide_windows);
+        let gid = header.pid;
+        let is_expanded = expanded_pids.contains(&gid);
+        result.push(TreeRow {
+            depth: 0,
+            process: Cow::Owned(header),
+            is_group: true,
+            has_children: true,
+            is_expanded,
+            is_last_sibling: i == group_count - 1,
+            connector_lines: Vec::new(),
+        });
+        if is_expanded {
+            result.extend(nest_rows(children));
+        }
+    }
+    result
+}
+
+/// Build a flat list of processes ordered by start time (oldest first), to show
+/// the order in which processes were launched. Processes without a known start
+/// time go last.
+pub fn build_start_order_list<'a>(
+    processes: &'a [ProcessInfo],
+    hide_windows: bool,
+) -> Vec<TreeRow<'a>> {
+    let mut sorted: Vec<&'a ProcessInfo> = processes
+        .iter()
+        .filter(|p| !hide_windows || !is_windows_process(p))
+        .collect();
+    sorted.sort_by(|a, b| match (a.start_time, b.start_time) {
+        (Some(x), Some(y)) => x.cmp(&y).then(a.pid.cmp(&b.pid)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.pid.cmp(&b.pid),
+    });
+    let count = sorted.len();
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, proc)| TreeRow {
+            depth: 0,
+            process: Cow::Borrowed(proc),
+            is_group: false,
+            has_children: false,
+            is_expanded: false,
+            is_last_sibling: i == count - 1,
+            connector_lines: Vec::new(),
+        })
+        .collect()
+}
+
+/// Synthetic PIDs for the user group headers, so they start out expanded.
+pub fn user_group_pids(processes: &[ProcessInfo]) -> HashSet<u32> {
+    processes
+        .iter()
+        .map(|p| group_pid(&user_group_key(p)))
+        .collect()
+}
+
+fn user_group_key(proc: &ProcessInfo) -> String {
+    if proc.user_name.is_empty() {
+        "(Unknown)".to_string()
+    } else {
+        proc.user_name.clone()
+    }
+}
+
+/// Stable synthetic PID for a group key. The high bit is set so it never
+/// collides with a real PID (Windows PIDs are small multiples of 4).
+pub fn group_pid(key: &str) -> u32 {
+    // FNV-1a
+    let mut hash: u32 = 0x811c_9dc5;
+    for b in key.to_lowercase().bytes() {
+        hash ^= b as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash | 0x8000_0000
+}
+
+/// Whether a PID belongs to a synthetic group row rather than a real process.
+pub fn is_group_pid(pid: u32) -> bool {
+    pid & 0x8000_0000 != 0
+}
+
+/// Fold all svchost.exe instances that share a parent (normally services.exe)
+/// into a single synthetic node with aggregated stats. The instances become
+/// children of that node, so they are still reachable by expanding it.
+fn collapse_svchost<'a>(processes: &[&'a ProcessInfo]) -> Vec<Cow<'a, ProcessInfo>> {
+    let mut by_parent: BTreeMap<Option<u32>, Vec<&'a ProcessInfo>> = BTreeMap::new();
+    for proc in processes {
+        if proc.name.eq_ignore_ascii_case("svchost.exe") {
+            by_parent.entry(proc.parent_pid).or_default().push(*proc);
+        }
+    }
+
+    let mut result: Vec<Cow<'a, ProcessInfo>> = processes
+        .iter()
+        .filter(|p| !p.name.eq_ignore_ascii_case("svchost.exe"))
+        .map(|p| Cow::Borrowed(*p))
+        .collect();
+    for (parent, members) in by_parent {
+        // A lone instance gains nothing from a group node
+        if members.len() < 2 {
+            result.extend(members.into_iter().map(Cow::Borrowed));
+            continue;
+        }
+        let key = format!("svchost.exe/{}", parent.unwrap_or(0));
+        let mut header = group_header(&key, "svchost.exe", &members, false);
+        header.parent_pid = parent;
+        let gid = header.pid;
+        result.push(Cow::Owned(header));
+        for member in members {
+            let mut member = member.clone();
+            member.parent_pid = Some(gid);
+            result.push(Cow::Owned(member));
+        }
+    }
+    result
+}
+
+/// Build an aggregate row for a group of processes.
+fn group_header(
+    key: &str,
+    label: &str,
+    members: &[&ProcessInfo],
+    hide_windows: bool,
+) -> ProcessInfo {
+    let counted: Vec<&&ProcessInfo> = members
+        .iter()
+        .filter(|p| !hide_windows || !is_windows_process(p))
+        .collect();
+    ProcessInfo {
+        pid: group_pid(key),
+        parent_pid: None,
+        name: label.to_string(),
+        exe_path: String::new(),
+        command_line: String::new(),
+        memory_bytes: counted.iter().map(|p| p.memory_bytes).sum(),
+        cpu_usage: counted.iter().map(|p| p.cpu_usage).sum(),
+        disk_read_bytes: counted.iter().map(|p| p.disk_read_bytes).sum(),
+        disk_write_bytes: counted.iter().map(|p| p.disk_write_bytes).sum(),
+        start_time: None,
+        since_boot: None,
+        product_name: format!("{} processes", counted.len()),
+        company_name: String::new(),
+        user_name: members.first().map(|p| p.user_name.clone()).unwrap_or_default(),
+        is_elevated: false,
+        volatile_drive: None,
+        bitness: None,
+        link_time: None,
+        cpu_times: counted.iter().filter_map(|p| p.cpu_times).reduce(|a, b| CpuTimes {
+            kernel: a.kernel + b.kernel,
+            user: a.user + b.user,
+        }),
+        platform: None,
+        image_mismatch: None,
+        enrichment: Enrichment::default(),
+    }
+}
+
+/// Fold each virtualization platform's top-level processes into one
+/// synthetic node per platform, so a platform's memory (notably the `vmmem`
+/// VM process) shows up as one figure. The node totals every process of the
+/// platform; its subtree keeps the original parent/child structure.
+fn group_platforms<'a>(processes: Vec<Cow<'a, ProcessInfo>>) -> Vec<Cow<'a, ProcessInfo>> {
+    let pids: HashMap<u32, Option<VirtPlatform>> =
+        processes.iter().map(|p| (p.pid, p.platform)).collect();
+    let mut members: BTreeMap<VirtPlatform, Vec<ProcessInfo>> = BTreeMap::new();
+    let mut roots: HashMap<VirtPlatform, usize> = HashMap::new();
+    for proc in &processes {
+        if let Some(platform) = proc.platform {
+            members.entry(platform).or_default().push(proc.as_ref().clone());
+            let parent_same = proc
+                .parent_pid
+                .is_some_and(|ppid| ppid != proc.pid && pids.get(&ppid) == Some(&Some(platform)));
+            if !parent_same {
+                *roots.entry(platform).or_default() += 1;
+            }
+        }
+    }
+
+    let mut headers = HashMap::new();
+    for (platform, procs) in &members {
+        // A single top-level process already carries the whole tree
+        if roots.get(platform).copied().unwrap_or(0) < 2 {
+            continue;
+        }
+        let refs: Vec<&ProcessInfo> = procs.iter().collect();
+        let mut header = group_header(&format!("platform/{}", platform.label()), platform.label(), &refs, false);
+        header.platform = Some(*platform);
+        headers.insert(*platform, header);
+    }
+    if headers.is_empty() {
+        return processes;
+    }
+
+    let mut result: Vec<Cow<'a, ProcessInfo>> = processes
+        .into_iter()
+        .map(|proc| {
+            let Some(header) = proc.platform.and_then(|p| headers.get(&p)) else {
+                return proc;
+            };
+            let parent_same = proc
+                .parent_pid
+                .is_some_and(|ppid| ppid != proc.pid && pids.get(&ppid) == Some(&proc.platform));
+            if parent_same {
+                return proc;
+            }
+            let mut proc = proc.into_owned();
+            proc.parent_pid = Some(header.pid);
+            Cow::Owned(proc)
+        })
+        .collect();
+    result.extend(headers.into_values().map(Cow::Owned));
+    result
+}
+
+/// Shift rows one level deeper so they nest under a group header.
+fn nest_rows(rows: Vec<TreeRow<'_>>) -> Vec<TreeRow<'_>> {
+    let mut root_is_last = true;
+    rows.into_iter()
+        .map(|mut row| {
+            if row.depth == 0 {
+                root_is_last = row.is_last_sibling;
+            } else {
+                // New column 0 is the connector from the group header down
+                // through the subtree of a root that has more siblings below.
+                row.connector_lines.insert(0, !root_is_last);
+            }
+            row.depth += 1;
+            row
+        })
+        .collect()
+}
+
+fn build_tree_rows<'a>(
+    processes: &[&'a ProcessInfo],
+    expanded_pids: &HashSet<u32>,
+    hide_windows: bool,
+    collapse_svchost_nodes: bool,
+    group_platform_nodes: bool,
+) -> Vec<TreeRow<'a>> {
+    let processes: Vec<Cow<'a, ProcessInfo>> = if collapse_svchost_nodes {
+        collapse_svchost(processes)
+    } else {
+        processes.iter().map(|p| Cow::Borrowed(*p)).collect()
+    };
+    let processes = if group_platform_nodes {
+        group_platforms(processes)
+    } else {
+        processes
+    };
     let pid_set: HashSet<u32> = processes.iter().map(|p| p.pid).collect();
-    let proc_map: HashMap<u32, &ProcessInfo> = processes.iter().map(|p| (p.pid, p)).collect();
+    let proc_map: HashMap<u32, &Cow<'a, ProcessInfo>> =
+        processes.iter().map(|p| (p.pid, p)).collect();
 
     // Build children map
     let mut children_map: HashMap<u32, Vec<u32>> = HashMap::new();
-    for proc in processes {
+    for proc in &processes {
         if let Some(ppid) = proc.parent_pid {
             if pid_set.contains(&ppid) && ppid != proc.pid {
                 children_map.entry(ppid).or_default().push(proc.pid);
@@ -249,7 +767,7 @@ pub fn build_visible_tree<'a>(
     // If hiding Windows processes, precompute which PIDs have non-Windows descendants
     let non_windows_pids: HashSet<u32> = if hide_windows {
         let mut visible = HashSet::new();
-        for proc in processes {
+        for proc in &processes {
             if !is_windows_process(proc) {
                 // Mark this process and all ancestors as visible
                 visible.insert(proc.pid);
@@ -338,7 +856,8 @@ pub fn build_visible_tree<'a>(
 
         result.push(TreeRow {
             depth,
-            process: proc,
+            process: (*proc).clone(),
+            is_group: is_group_pid(pid),
             has_children,
             is_expanded,
             is_last_sibling: is_last,
@@ -364,9 +883,13 @@ pub fn build_visible_tree<'a>(
     result
 }
 
-/// Check if a process is a known built-in Windows process.
+/// Check if a process is a known built-in Windows process, or one the user's
+/// allowlist file adds (see [`allowlist`]).
 pub fn is_windows_process(proc: &ProcessInfo) -> bool {
     let name_lower = proc.name.to_lowercase();
+    if let Some(listed) = allowlist::with(|a| a.process_override(&name_lower)) {
+        return listed;
+    }
     WINDOWS_PROCESS_NAMES
         .iter()
         .any(|&known| name_lower == known)
@@ -0,0 +1,161 @@
+//! Export registry-sourced startup entries as a `.reg` file that re-creates
+//! them with `reg import` or by double-clicking in Explorer.
+//!
+//! Values are re-read from the registry at export time so the original value
+//! type (REG_SZ vs REG_EXPAND_SZ) is preserved. The entry's StartupApproved
+//! state is exported alongside it, so a disabled entry is restored disabled.
+
+use crate::models::{RegistryHive, Source, StartupEntry};
+use std::fmt::Write as _;
+use winreg::enums::*;
+use winreg::{RegKey, RegValue};
+
+const STARTUP_APPROVED_PATH: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved";
+
+/// Whether an entry can be exported as a `.reg` file.
+pub fn is_exportable(entry: &StartupEntry) -> bool {
+    matches!(
+        entry.source,
+        Source::RegistryRun { .. } | Source::RegistryRunOnce { .. }
+    )
+}
+
+/// Build the `.reg` file contents for the registry-sourced entries in
+/// `entries`; other sources are skipped. Returns the text and the number of
+/// entries written.
+pub fn build_reg_file(entries: &[&StartupEntry]) -> (String, usize) {
+    let mut out = String::from("Windows Registry Editor Version 5.00\r\n");
+    let mut count = 0;
+
+    for entry in entries {
+        let (hive, key_path, is_run_once) = match &entry.source {
+            Source::RegistryRun { hive, key_path } => (*hive, key_path, false),
+            Source::RegistryRunOnce { hive, key_path } => (*hive, key_path, true),
+            _ => continue,
+        };
+
+        let value = read_value(hive, key_path, &entry.name).unwrap_or_else(|| RegValue {
+            vtype: if entry.expand_sz { REG_EXPAND_SZ } else { REG_SZ },
+            bytes: to_utf16_bytes(&entry.command),
+        });
+
+        let _ = write!(out, "\r\n[{}\\{}]\r\n", hive_name(hive), key_path);
+        out.push_str(&format_value(&entry.name, &value));
+        out.push_str("\r\n");
+
+        // RunOnce entries have no StartupApproved state
+        if !is_run_once {
+            let subkey = approved_subkey(key_path);
+            if let Some(approved) = read_value(
+                hive,
+                &format!(r"{}\{}", STARTUP_APPROVED_PATH, subkey),
+                &entry.name,
+            ) {
+                let _ = write!(
+                    out,
+                    "\r\n[{}\\{}\\{}]\r\n",
+                    hive_name(hive),
+                    STARTUP_APPROVED_PATH,
+                    subkey
+                );
+                out.push_str(&format_value(&entry.name, &approved));
+                out.push_str("\r\n");
+            }
+        }
+
+        count += 1;
+    }
+
+    (out, count)
+}
+
+/// Write `.reg` text to disk as UTF-16LE with a BOM, the encoding regedit
+/// itself produces for version 5.00 files.
+pub fn write_reg_file(path: &std::path::Path, contents: &str) -> std::io::Result<()> {
+    let mut bytes = vec![0xFF, 0xFE];
+    bytes.extend(contents.encode_utf16().flat_map(|u| u.to_le_bytes()));
+    std::fs::write(path, bytes)
+}
+
+fn read_value(hive: RegistryHive, path: &str, name: &str) -> Option<RegValue> {
+    let predef = match hive {
+        RegistryHive::HKCU => RegKey::predef(HKEY_CURRENT_USER),
+        RegistryHive::HKLM => RegKey::predef(HKEY_LOCAL_MACHINE),
+    };
+    predef
+        .open_subkey_with_flags(path, KEY_READ)
+        .ok()?
+        .get_raw_value(name)
+        .ok()
+}
+
+/// 32-bit Run entries (under Wow6432Node) are approved under `Run32`.
+fn approved_subkey(key_path: &str) -> &'static str {
+    if key_path.to_lowercase().contains("wow6432node") {
+        "Run32"
+    } else {
+        "Run"
+    }
+}
+
+fn hive_name(hive: RegistryHive) -> &'static str {
+    match hive {
+        RegistryHive::HKCU => "HKEY_CURRENT_USER",
+        RegistryHive::HKLM => "HKEY_LOCAL_MACHINE",
+    }
+}
+
+/// Format one `"name"=data` line. REG_SZ is written as a quoted string;
+/// everything else as hex, wrapped the way regedit does.
+fn format_value(name: &str, value: &RegValue) -> String {
+    let name = format!("\"{}\"", escape(name));
+    match &value.vtype {
+        REG_SZ => {
+            let text = String::from_utf16_lossy(
+                &value
+                    .bytes
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect::<Vec<u16>>(),
+            );
+            format!("{}=\"{}\"", name, escape(text.trim_end_matches('\0')))
+        }
+        REG_EXPAND_SZ => format_hex(&name, "hex(2):", &value.bytes),
+        REG_BINARY => format_hex(&name, "hex:", &value.bytes),
+        other => format_hex(&name, &format!("hex({:x}):", other.clone() as u32), &value.bytes),
+    }
+}
+
+/// Hex data as comma-separated bytes, continued with `\` so no line exceeds
+/// 80 columns.
+fn format_hex(name: &str, prefix: &str, bytes: &[u8]) -> String {
+    let mut out = format!("{}={}", name, prefix);
+    let mut line_len = out.len();
+    for (i, b) in bytes.iter().enumerate() {
+        let last = i + 1 == bytes.len();
+        let item = if last {
+            format!("{:02x}", b)
+        } else {
+            format!("{:02x},", b)
+        };
+        if line_len + item.len() > 78 && !last {
+            out.push_str("\\\r\n  ");
+            line_len = 2;
+        }
+        out.push_str(&item);
+        line_len += item.len();
+    }
+    out
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn to_utf16_bytes(s: &str) -> Vec<u8> {
+    s.encode_utf16()
+        .chain(std::iter::once(0))
+        .flat_map(|u| u.to_le_bytes())
+        .collect()
+}
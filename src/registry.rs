@@ -1,6 +1,7 @@
-use crate::models::{RegistryHive, Source, StartupEntry};
+use crate::models::{RegistryHive, Source, StartupEntry, IFEO_DEBUGGER_VALUE};
+use crate::skipped::{SkippedItems, SkippedKind};
 use winreg::enums::*;
-use winreg::RegKey;
+use winreg::{RegKey, RegValue};
 
 struct RunKeyInfo {
     path: &'static str,
@@ -42,35 +43,37 @@ const RUN_KEYS: &[RunKeyInfo] = &[
     },
 ];
 
-fn read_run_key(info: &RunKeyInfo) -> Vec<StartupEntry> {
+fn read_run_key(info: &RunKeyInfo, skipped: &mut SkippedItems) -> Vec<StartupEntry> {
     let predef = match info.hive {
         RegistryHive::HKCU => RegKey::predef(HKEY_CURRENT_USER),
         RegistryHive::HKLM => RegKey::predef(HKEY_LOCAL_MACHINE),
     };
 
+    let full_path = format!(r"{}\{}", info.hive, info.path);
     let key = match predef.open_subkey_with_flags(info.path, KEY_READ) {
         Ok(k) => k,
-        Err(_) => return Vec::new(),
+        Err(e) => {
+            skipped.add_io(SkippedKind::RegistryKey, full_path, &e);
+            return Vec::new();
+        }
     };
 
     let mut entries = Vec::new();
-    for value in key.enum_values().flatten() {
-        let (name, reg_value) = value;
+    for value in key.enum_values() {
+        let (name, reg_value) = match value {
+            Ok(value) => value,
+            Err(e) => {
+                skipped.add_io(SkippedKind::RegistryValue, &full_path, &e);
+                continue;
+            }
+        };
         if name.is_empty() {
             continue;
         }
 
-        let command = match reg_value.vtype {
-            REG_SZ | REG_EXPAND_SZ => String::from_utf16_lossy(
-                &reg_value
-                    .bytes
-                    .chunks_exact(2)
-                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
-                    .collect::<Vec<u16>>(),
-            )
-            .trim_end_matches('\0')
-            .to_string(),
-            _ => continue,
+        // Read raw so REG_EXPAND_SZ commands keep their %VARIABLES%
+        let Some(command) = string_value(&reg_value) else {
+            continue;
         };
 
         let source = if info.is_run_once {
@@ -85,16 +88,79 @@ fn read_run_key(info: &RunKeyInfo) -> Vec<StartupEntry> {
             }
         };
 
-        entries.push(StartupEntry::new(name, command, source));
+        let mut entry = StartupEntry::new(name, command, source);
+        entry.expand_sz = reg_value.vtype == REG_EXPAND_SZ;
+        entries.push(entry);
     }
 
     entries
 }
 
-pub fn collect_registry_entries() -> Vec<StartupEntry> {
+/// Image File Execution Options keys (native and 32-bit views).
+const IFEO_KEYS: &[&str] = &[
+    r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\Image File Execution Options",
+    r"SOFTWARE\Wow6432Node\Microsoft\Windows NT\CurrentVersion\Image File Execution Options",
+];
+
+/// Executables hijacked by an IFEO `Debugger` value. Each entry is named
+/// after the executable; its command is the debugger that runs instead.
+pub fn collect_ifeo_entries(skipped: &mut SkippedItems) -> Vec<StartupEntry> {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let mut entries = Vec::new();
+    for base in IFEO_KEYS {
+        let Ok(ifeo) = hklm.open_subkey_with_flags(base, KEY_READ) else {
+            continue;
+        };
+        for exe in ifeo.enum_keys().flatten() {
+            let sub = match ifeo.open_subkey_with_flags(&exe, KEY_READ) {
+                Ok(sub) => sub,
+                Err(e) => {
+                    skipped.add_io(SkippedKind::RegistryKey, format!(r"HKLM\{}\{}", base, exe), &e);
+                    continue;
+                }
+            };
+            let Ok(debugger) = sub.get_raw_value(IFEO_DEBUGGER_VALUE) else {
+                continue;
+            };
+            let Some(command) = string_value(&debugger) else {
+                continue;
+            };
+            if command.trim().is_empty() {
+                continue;
+            }
+            let source = Source::Ifeo {
+                key_path: format!(r"{}\{}", base, exe),
+            };
+            let mut entry = StartupEntry::new(exe, command.trim().to_string(), source);
+            entry.expand_sz = debugger.vtype == REG_EXPAND_SZ;
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// The text of a REG_SZ or REG_EXPAND_SZ value, unexpanded.
+fn string_value(value: &RegValue) -> Option<String> {
+    match value.vtype {
+        REG_SZ | REG_EXPAND_SZ => Some(
+            String::from_utf16_lossy(
+                &value
+                    .bytes
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .collect::<Vec<u16>>(),
+            )
+            .trim_end_matches('\0')
+            .to_string(),
+        ),
+        _ => None,
+    }
+}
+
+pub fn collect_registry_entries(skipped: &mut SkippedItems) -> Vec<StartupEntry> {
     let mut entries = Vec::new();
     for info in RUN_KEYS {
-        entries.extend(read_run_key(info));
+        entries.extend(read_run_key(info, skipped));
     }
     entries
 }
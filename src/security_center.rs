@@ -0,0 +1,164 @@
+//! Windows Security Center status: antivirus, Defender real-time protection,
+//! firewall, and UAC.
+//!
+//! Overall health comes from the WSC API (which also covers third-party
+//! antivirus/firewall products); the registry supplies the details, and is the
+//! fallback where WSC isn't available (e.g. Windows Server).
+
+use crate::models::{SecurityItem, SecurityState};
+use windows::Win32::System::SecurityCenter::{
+    WscGetSecurityProviderHealth, WSC_SECURITY_PROVIDER, WSC_SECURITY_PROVIDER_ANTIVIRUS,
+    WSC_SECURITY_PROVIDER_FIREWALL, WSC_SECURITY_PROVIDER_HEALTH,
+    WSC_SECURITY_PROVIDER_HEALTH_GOOD, WSC_SECURITY_PROVIDER_HEALTH_POOR,
+    WSC_SECURITY_PROVIDER_HEALTH_SNOOZE, WSC_SECURITY_PROVIDER_USER_ACCOUNT_CONTROL,
+};
+use winreg::enums::*;
+use winreg::RegKey;
+
+const DEFENDER_RTP_KEY: &str = r"SOFTWARE\Microsoft\Windows Defender\Real-Time Protection";
+const DEFENDER_RTP_POLICY_KEY: &str =
+    r"SOFTWARE\Policies\Microsoft\Windows Defender\Real-Time Protection";
+const FIREWALL_POLICY_KEY: &str =
+    r"SYSTEM\CurrentControlSet\Services\SharedAccess\Parameters\FirewallPolicy";
+const UAC_POLICY_KEY: &str = r"SOFTWARE\Microsoft\Windows\CurrentVersion\Policies\System";
+
+/// Collect the status rows shown in the Security tab.
+pub fn collect_security_status() -> Vec<SecurityItem> {
+    vec![
+        SecurityItem {
+            name: "Antivirus".to_string(),
+            state: provider_health(WSC_SECURITY_PROVIDER_ANTIVIRUS),
+            detail: "As reported by Windows Security Center".to_string(),
+        },
+        defender_realtime_status(),
+        firewall_status(),
+        uac_status(),
+    ]
+}
+
+/// Map a WSC provider's health to a state; `Unknown` when WSC can't tell us.
+fn provider_health(provider: WSC_SECURITY_PROVIDER) -> SecurityState {
+    let mut health = WSC_SECURITY_PROVIDER_HEALTH::default();
+    if unsafe { WscGetSecurityProviderHealth(provider.0 as u32, &mut health) }.is_err() {
+        return SecurityState::Unknown;
+    }
+    match health {
+        WSC_SECURITY_PROVIDER_HEALTH_GOOD => SecurityState::Good,
+        WSC_SECURITY_PROVIDER_HEALTH_SNOOZE => SecurityState::Warning,
+        WSC_SECURITY_PROVIDER_HEALTH_POOR => SecurityState::Bad,
+        _ => SecurityState::Unknown,
+    }
+}
+
+fn read_hklm_dword(path: &str, name: &str) -> Option<u32> {
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(path)
+        .ok()?
+        .get_value(name)
+        .ok()
+}
+
+fn defender_realtime_status() -> SecurityItem {
+    // Group Policy overrides the local setting
+    let policy = read_hklm_dword(DEFENDER_RTP_POLICY_KEY, "DisableRealtimeMonitoring");
+    let local = read_hklm_dword(DEFENDER_RTP_KEY, "DisableRealtimeMonitoring");
+    let (state, detail) = match (policy, local) {
+        (Some(1), _) => (SecurityState::Bad, "Disabled by Group Policy"),
+        (_, Some(1)) => (SecurityState::Bad, "Turned off"),
+        (Some(0), _) | (_, Some(0)) => (SecurityState::Good, "Turned on"),
+        // No value means the default (on), unless Defender isn't installed at all
+        _ if RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey(DEFENDER_RTP_KEY).is_ok() => {
+            (SecurityState::Good, "Turned on (default)")
+        }
+        _ => (SecurityState::Unknown, "Microsoft Defender settings not found"),
+    };
+    SecurityItem {
+        name: "Defender Real-Time Protection".to_string(),
+        state,
+        detail: detail.to_string(),
+    }
+}
+
+fn firewall_status() -> SecurityItem {
+    let profiles = [
+        ("Domain", "DomainProfile"),
+        ("Private", "StandardProfile"),
+        ("Public", "PublicProfile"),
+    ];
+    let states: Vec<(&str, Option<bool>)> = profiles
+        .iter()
+        .map(|(label, key)| {
+            let path = format!(r"{}\{}", FIREWALL_POLICY_KEY, key);
+            (*label, read_hklm_dword(&path, "EnableFirewall").map(|v| v != 0))
+        })
+        .collect();
+
+    let detail = states
+        .iter()
+        .map(|(label, on)| {
+            let text = match on {
+                Some(true) => "on",
+                Some(false) => "off",
+                None => "unknown",
+            };
+            format!("{}: {}", label, text)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let state = match provider_health(WSC_SECURITY_PROVIDER_FIREWALL) {
+        SecurityState::Unknown => {
+            let on = states.iter().filter(|(_, s)| *s == Some(true)).count();
+            let off = states.iter().filter(|(_, s)| *s == Some(false)).count();
+            match (on, off) {
+                (0, 0) => SecurityState::Unknown,
+                (_, 0) => SecurityState::Good,
+                (0, _) => SecurityState::Bad,
+                _ => SecurityState::Warning,
+            }
+        }
+        health => health,
+    };
+
+    SecurityItem {
+        name: "Firewall".to_string(),
+        state,
+        detail,
+    }
+}
+
+fn uac_status() -> SecurityItem {
+    let enable_lua = read_hklm_dword(UAC_POLICY_KEY, "EnableLUA");
+    let consent = read_hklm_dword(UAC_POLICY_KEY, "ConsentPromptBehaviorAdmin");
+
+    if enable_lua == Some(0) {
+        return SecurityItem {
+            name: "User Account Control".to_string(),
+            state: SecurityState::Bad,
+            detail: "Disabled (EnableLUA = 0)".to_string(),
+        };
+    }
+
+    let detail = match consent {
+        Some(0) => "Elevate without prompting",
+        Some(1) => "Prompt for credentials on the secure desktop",
+        Some(2) => "Always notify (consent on the secure desktop)",
+        Some(3) => "Prompt for credentials",
+        Some(4) => "Prompt for consent",
+        Some(5) => "Notify only when apps make changes (default)",
+        _ => "Enabled",
+    };
+
+    let state = match provider_health(WSC_SECURITY_PROVIDER_USER_ACCOUNT_CONTROL) {
+        // Silent elevation is effectively no protection for admin accounts
+        _ if consent == Some(0) => SecurityState::Warning,
+        SecurityState::Unknown if enable_lua.is_some() => SecurityState::Good,
+        health => health,
+    };
+
+    SecurityItem {
+        name: "User Account Control".to_string(),
+        state,
+        detail: detail.to_string(),
+    }
+}
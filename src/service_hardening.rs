@@ -0,0 +1,253 @@
+//! Start-type suggestions for non-Microsoft services that start
+//! automatically: manual (demand) start for a service the run history has
+//! never seen running, and delayed start for one whose own process uses a
+//! lot of memory, so sign-in isn't held up by it.
+//!
+//! Applied changes are remembered across sessions so each can be reverted.
+//! Stored as a tab-separated text file in the app data directory:
+//! `<service name>\t<previous start type>\t<applied start type>\t<when>\t<display name>`.
+
+use crate::app_data;
+use crate::models::{EnabledStatus, Source, StartupEntry};
+use crate::services;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::os::windows::process::CommandExt;
+use std::path::PathBuf;
+use std::process::Command;
+
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+const FILE_NAME: &str = "service-hardening.tsv";
+const HEADER: &str = "# App Manager service hardening v1";
+const STAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Fewest sessions of run history before "never seen running" counts.
+const MIN_SESSIONS: usize = 5;
+/// Memory of a service's own process above which it counts as heavy.
+const HEAVY_MEMORY_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Start types a suggestion can move between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartMode {
+    Auto,
+    DelayedAuto,
+    Manual,
+}
+
+impl StartMode {
+    /// The start type of a service, if it's one of these.
+    pub fn of(entry: &StartupEntry) -> Option<Self> {
+        match entry.enabled {
+            EnabledStatus::Enabled if entry.delayed_start => Some(StartMode::DelayedAuto),
+            EnabledStatus::Enabled => Some(StartMode::Auto),
+            EnabledStatus::Manual => Some(StartMode::Manual),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartMode::Auto => "Automatic",
+            StartMode::DelayedAuto => "Automatic (Delayed Start)",
+            StartMode::Manual => "Manual",
+        }
+    }
+
+    /// The `start=` value for `sc config`.
+    pub fn sc_value(&self) -> &'static str {
+        match self {
+            StartMode::Auto => "auto",
+            StartMode::DelayedAuto => "delayed-auto",
+            StartMode::Manual => "demand",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(StartMode::Auto),
+            "delayed-auto" => Some(StartMode::DelayedAuto),
+            "demand" => Some(StartMode::Manual),
+            _ => None,
+        }
+    }
+}
+
+/// A safer start type for a service, and why.
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub service: StartupEntry,
+    pub current: StartMode,
+    pub suggested: StartMode,
+    pub reason: String,
+}
+
+/// Suggestions for the non-Microsoft automatic services in `services`,
+/// sorted by service name.
+pub fn suggest(all_services: &[StartupEntry]) -> Vec<Suggestion> {
+    let mut suggestions: Vec<Suggestion> = all_services
+        .iter()
+        .filter(|s| !services::is_microsoft_service(s))
+        .filter_map(|service| {
+            let current = StartMode::of(service).filter(|m| *m != StartMode::Manual)?;
+            let never_ran = service
+                .run_history
+                .filter(|h| h.sessions_observed >= MIN_SESSIONS && h.sessions_running == 0);
+            let (suggested, reason) = if let Some(history) = never_ran {
+                (
+                    StartMode::Manual,
+                    format!(
+                        "Never seen running in the last {} sessions; it still starts when something asks for it",
+                        history.sessions_observed
+                    ),
+                )
+            } else {
+                // Shared svchost figures belong to every service in the process
+                let usage = service.host_usage.filter(|u| u.services_in_process == 1)?;
+                if current != StartMode::Auto || usage.memory_bytes < HEAVY_MEMORY_BYTES {
+                    return None;
+                }
+                (
+                    StartMode::DelayedAuto,
+                    format!(
+                        "Uses {} MB; a delayed start lets sign-in finish first",
+                        usage.memory_bytes / (1024 * 1024)
+                    ),
+                )
+            };
+            Some(Suggestion {
+                service: service.clone(),
+                current,
+                suggested,
+                reason,
+            })
+        })
+        .collect();
+    suggestions.sort_by_key(|s| s.service.name.to_lowercase());
+    suggestions
+}
+
+/// Set a service's start type.
+pub fn set_start_mode(entry: &StartupEntry, mode: StartMode) -> Result<()> {
+    let Source::Service { service_name, .. } = &entry.source else {
+        anyhow::bail!("'{}' is not a service", entry.name);
+    };
+    let output = Command::new("sc")
+        .args(["config", service_name, "start=", mode.sc_value()])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .context("Failed to run sc config")?;
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        anyhow::bail!("sc config failed: {}", stdout.trim());
+    }
+    Ok(())
+}
+
+/// A start type change made from the suggestions.
+#[derive(Debug, Clone)]
+pub struct AppliedChange {
+    pub service_name: String,
+    pub name: String,
+    pub previous: StartMode,
+    pub applied: StartMode,
+    pub when: DateTime<Local>,
+}
+
+/// Applied changes that haven't been reverted, oldest first.
+pub struct AppliedChanges {
+    path: Option<PathBuf>,
+    pub changes: Vec<AppliedChange>,
+}
+
+impl AppliedChanges {
+    /// Load the file, starting empty if it doesn't exist or can't be read.
+    pub fn load() -> Self {
+        let path = app_data::data_dir().map(|dir| dir.join(FILE_NAME));
+        let mut changes = Vec::new();
+        if let Some(text) = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for line in text.lines() {
+                if line.starts_with('#') {
+                    continue;
+                }
+                let mut fields = line.splitn(5, '\t');
+                let (Some(service_name), Some(previous), Some(applied), Some(when), Some(name)) =
+                    (fields.next(), fields.next(), fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                let (Some(previous), Some(applied)) = (StartMode::parse(previous), StartMode::parse(applied)) else {
+                    continue;
+                };
+                let Some(when) = NaiveDateTime::parse_from_str(when, STAMP_FORMAT)
+                    .ok()
+                    .and_then(|t| Local.from_local_datetime(&t).single())
+                else {
+                    continue;
+                };
+                changes.push(AppliedChange {
+                    service_name: service_name.to_string(),
+                    name: name.to_string(),
+                    previous,
+                    applied,
+                    when,
+                });
+            }
+        }
+        Self { path, changes }
+    }
+
+    /// Note that `entry` (with its start type before the change) was set
+    /// to `mode`. Setting it back to the start type it had before the first
+    /// change counts as a revert and forgets it.
+    pub fn record(&mut self, entry: &StartupEntry, mode: StartMode) {
+        let Source::Service { service_name, .. } = &entry.source else {
+            return;
+        };
+        match self.changes.iter().position(|c| c.service_name == *service_name) {
+            Some(index) if self.changes[index].previous == mode => {
+                self.changes.remove(index);
+            }
+            Some(index) => self.changes[index].applied = mode,
+            None => {
+                let Some(previous) = StartMode::of(entry) else {
+                    return;
+                };
+                self.changes.push(AppliedChange {
+                    service_name: service_name.clone(),
+                    name: entry.name.replace(['\t', '\n', '\r'], " "),
+                    previous,
+                    applied: mode,
+                    when: Local::now(),
+                });
+            }
+        }
+        self.save();
+    }
+
+    /// Forget a change without reverting it (the service is gone).
+    pub fn forget(&mut self, service_name: &str) {
+        self.changes.retain(|c| c.service_name != service_name);
+        self.save();
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for change in &self.changes {
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                change.service_name,
+                change.previous.sc_value(),
+                change.applied.sc_value(),
+                change.when.format(STAMP_FORMAT),
+                change.name
+            ));
+        }
+        // Best effort, like the watch list
+        let _ = app_data::write_atomic(path, out);
+    }
+}
@@ -0,0 +1,169 @@
+//! Real-time service state: a background thread asks the Service Control
+//! Manager to report every service's state changes
+//! (`NotifyServiceStatusChangeW`), so the Services tab shows a service
+//! starting or stopping as it happens, whether App Manager or something else
+//! started or stopped it, without a full reload.
+//!
+//! Notifications are delivered as APCs, so the thread waits in an alertable
+//! sleep and re-registers each service after it fires.
+
+use std::ffi::c_void;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use windows::core::HSTRING;
+use windows::Win32::Foundation::ERROR_SUCCESS;
+use windows::Win32::System::Services::{
+    CloseServiceHandle, NotifyServiceStatusChangeW, OpenSCManagerW, OpenServiceW, SC_HANDLE,
+    SC_MANAGER_ENUMERATE_SERVICE, SERVICE_NOTIFY, SERVICE_NOTIFY_2W, SERVICE_NOTIFY_STATUS_CHANGE,
+    SERVICE_QUERY_STATUS, SERVICE_STOPPED,
+};
+use windows::Win32::System::Threading::SleepEx;
+
+/// Every state from `SERVICE_NOTIFY_STOPPED` (1) to `SERVICE_NOTIFY_PAUSED`
+/// (64); state `n` is bit `n - 1`.
+const ALL_STATES: u32 = 0x7F;
+/// How often the thread wakes to check whether it should stop.
+const POLL_MS: u32 = 1000;
+
+/// A service that started or stopped.
+#[derive(Debug, Clone)]
+pub struct StateChange {
+    pub service_name: String,
+    /// Anything but fully stopped (start and stop pending included).
+    pub running: bool,
+    /// 0 when stopped.
+    pub pid: u32,
+}
+
+/// Watches services until dropped.
+pub struct ServiceWatcher {
+    stop: Arc<AtomicBool>,
+    pub changes: mpsc::Receiver<StateChange>,
+}
+
+impl Drop for ServiceWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Start watching `service_names`. `wake` is called after each batch of
+/// changes (e.g. to repaint the window).
+pub fn watch(service_names: Vec<String>, wake: impl Fn() + Send + 'static) -> ServiceWatcher {
+    let stop = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || unsafe { run(service_names, &thread_stop, &tx, &wake) });
+    ServiceWatcher { stop, changes: rx }
+}
+
+struct Watch {
+    name: String,
+    handle: SC_HANDLE,
+    /// Must stay at the same address while a notification is pending.
+    notify: SERVICE_NOTIFY_2W,
+    fired: bool,
+    last: Option<(bool, u32)>,
+}
+
+unsafe extern "system" fn on_notify(parameter: *const c_void) {
+    let notify = &*(parameter as *const SERVICE_NOTIFY_2W);
+    let watch = notify.pContext as *mut Watch;
+    (*watch).fired = true;
+}
+
+/// Ask for a notification when the service leaves `current` (a
+/// `SERVICE_STATUS_CURRENT_STATE` value; 0 for any state, which fires
+/// right away with the current one).
+unsafe fn register(watch: &mut Watch, current: u32) -> bool {
+    let current_bit = if current == 0 { 0 } else { 1 << (current - 1) };
+    watch.fired = false;
+    watch.notify = SERVICE_NOTIFY_2W {
+        dwVersion: SERVICE_NOTIFY_STATUS_CHANGE,
+        pfnNotifyCallback: Some(on_notify),
+        pContext: watch as *mut Watch as *mut c_void,
+        ..Default::default()
+    };
+    let mask = SERVICE_NOTIFY(ALL_STATES & !current_bit);
+    NotifyServiceStatusChangeW(watch.handle, mask, &watch.notify) == ERROR_SUCCESS.0
+}
+
+unsafe fn run(
+    service_names: Vec<String>,
+    stop: &AtomicBool,
+    tx: &mpsc::Sender<StateChange>,
+    wake: &dyn Fn(),
+) {
+    let Ok(scm) = OpenSCManagerW(None, None, SC_MANAGER_ENUMERATE_SERVICE) else {
+        return;
+    };
+    // Boxed so each notification buffer keeps its address
+    let mut watches: Vec<Box<Watch>> = service_names
+        .into_iter()
+        .filter_map(|name| {
+            let handle = OpenServiceW(scm, &HSTRING::from(name.as_str()), SERVICE_QUERY_STATUS).ok()?;
+            Some(Box::new(Watch {
+                name,
+                handle,
+                notify: SERVICE_NOTIFY_2W::default(),
+                fired: false,
+                last: None,
+            }))
+        })
+        .collect();
+    watches.retain_mut(|w| {
+        let registered = register(w, 0);
+        if !registered {
+            let _ = CloseServiceHandle(w.handle);
+        }
+        registered
+    });
+
+    while !stop.load(Ordering::Relaxed) && !watches.is_empty() {
+        // Notifications run as APCs during the alertable sleep
+        SleepEx(POLL_MS, true);
+        let mut sent = false;
+        let mut closed = Vec::new();
+        for (i, watch) in watches.iter_mut().enumerate() {
+            if !watch.fired {
+                continue;
+            }
+            // A failed notification means the service was deleted (or the
+            // client fell behind); either way a reload picks it up
+            if watch.notify.dwNotificationStatus != ERROR_SUCCESS.0 {
+                closed.push(i);
+                continue;
+            }
+            let status = watch.notify.ServiceStatus;
+            let state = (status.dwCurrentState != SERVICE_STOPPED, status.dwProcessId);
+            if !register(watch, status.dwCurrentState.0) {
+                closed.push(i);
+            }
+            if watch.last != Some(state) {
+                watch.last = Some(state);
+                let change = StateChange {
+                    service_name: watch.name.clone(),
+                    running: state.0,
+                    pid: state.1,
+                };
+                if tx.send(change).is_err() {
+                    stop.store(true, Ordering::Relaxed);
+                }
+                sent = true;
+            }
+        }
+        for i in closed.into_iter().rev() {
+            let watch = watches.remove(i);
+            let _ = CloseServiceHandle(watch.handle);
+        }
+        if sent {
+            wake();
+        }
+    }
+
+    // Closing a handle cancels its pending notification
+    for watch in watches {
+        let _ = CloseServiceHandle(watch.handle);
+    }
+    let _ = CloseServiceHandle(scm);
+}
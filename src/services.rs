@@ -1,11 +1,20 @@
-use crate::models::{EnabledStatus, RunState, Source, StartupEntry};
+use crate::allowlist;
+use crate::cmdline;
+use crate::drives;
+use crate::models::{
+    EnabledStatus, LastRanSource, RunState, ServiceSecurity, ServiceSidType, ServiceTrigger, Source, StartupEntry,
+};
+use crate::parallel;
+use crate::skipped::{SkippedItems, SkippedKind};
+use crate::status;
 use crate::version_info;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use winreg::enums::*;
 use winreg::RegKey;
 
-pub fn collect_services() -> Result<Vec<StartupEntry>> {
+/// Unreadable services are recorded in `skipped` and left out.
+pub fn collect_services(skipped: &mut SkippedItems) -> Result<Vec<StartupEntry>> {
     // Step 1: Enumerate all WIN32 services via native EnumServicesStatusExW
     let service_infos = enumerate_services_native()?;
 
@@ -19,11 +28,30 @@ pub fn collect_services() -> Result<Vec<StartupEntry>> {
 
     let mut entries = Vec::new();
     for info in &service_infos {
-        if let Some(entry) = build_entry(&services_key, info, &process_start_times) {
+        if let Some(entry) = build_entry(&services_key, info, &process_start_times, skipped) {
             entries.push(entry);
         }
     }
 
+    // Step 4: Read version info and check binaries in parallel (the slow part)
+    let file_info = parallel::map(&entries, |e| {
+        (
+            version_info::get_product_name(&e.command).unwrap_or_default(),
+            version_info::get_company_name(&e.command).unwrap_or_default(),
+            drives::check_target(&e.command),
+            version_info::get_bitness(&e.command),
+        )
+    });
+    for (entry, (product_name, company_name, (target_missing, volatile_drive), bitness)) in
+        entries.iter_mut().zip(file_info)
+    {
+        entry.product_name = product_name;
+        entry.company_name = company_name;
+        entry.target_missing = target_missing;
+        entry.volatile_drive = volatile_drive;
+        entry.bitness = bitness;
+    }
+
     // Sort by name
     entries.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
 
@@ -199,16 +227,30 @@ fn build_entry(
     services_key: &RegKey,
     info: &ScServiceInfo,
     process_start_times: &HashMap<u32, chrono::DateTime<chrono::Local>>,
+    skipped: &mut SkippedItems,
 ) -> Option<StartupEntry> {
-    let svc_key = services_key.open_subkey(&info.service_name).ok()?;
+    let svc_key = match services_key.open_subkey(&info.service_name) {
+        Ok(key) => key,
+        Err(e) => {
+            skipped.add_io(SkippedKind::Service, &info.service_name, &e);
+            return None;
+        }
+    };
 
-    let image_path: String = svc_key.get_value("ImagePath").ok()?;
+    let image_path: String = match svc_key.get_value("ImagePath") {
+        Ok(path) => path,
+        Err(e) => {
+            skipped.add_io(SkippedKind::Service, &info.service_name, &e);
+            return None;
+        }
+    };
     if image_path.trim().is_empty() {
         return None;
     }
 
     let start_type: u32 = svc_key.get_value("Start").unwrap_or(3);
     let object_name: String = svc_key.get_value("ObjectName").unwrap_or_default();
+    let delayed_autostart: u32 = svc_key.get_value("DelayedAutostart").unwrap_or(0);
 
     let enabled = match start_type {
         2 => EnabledStatus::Enabled,   // SERVICE_AUTO_START
@@ -238,12 +280,20 @@ fn build_entry(
     entry.enabled = enabled;
     entry.run_state = run_state;
     entry.runs_as = clean_account_name(&object_name);
-    entry.product_name = version_info::get_product_name(&image_path).unwrap_or_default();
+    entry.delayed_start = start_type == 2 && delayed_autostart != 0;
+    // Windows has no install date for services; the key's last write time is
+    // the closest thing (it also moves when the service is reconfigured)
+    entry.installed = svc_key.query_info().ok().and_then(|info| {
+        let ft = &info.last_write_time;
+        status::filetime_to_datetime(((ft.dwHighDateTime as u64) << 32) | ft.dwLowDateTime as u64)
+    });
 
     // Use process start time from the service's PID
     if info.pid > 0 {
+        entry.pid = Some(info.pid);
         if let Some(dt) = process_start_times.get(&info.pid) {
             entry.last_ran = Some(*dt);
+            entry.last_ran_source = Some(LastRanSource::ProcessStart);
         }
     }
 
@@ -268,15 +318,20 @@ fn clean_account_name(name: &str) -> String {
 /// Check if a service entry is a known built-in Windows service based on its binary path.
 /// Each service is matched by its specific executable — broad path matching is avoided
 /// because malware can place executables in Windows system folders.
+/// The user's allowlist file can add or remove services (see [`allowlist`]).
 pub fn is_microsoft_service(entry: &StartupEntry) -> bool {
-    let cmd = match &entry.source {
-        Source::Service { command_line, .. } => command_line,
+    let (service_name, cmd) = match &entry.source {
+        Source::Service { service_name, command_line } => (service_name, command_line),
         _ => return false,
     };
 
     let cmd_lower = cmd.to_lowercase();
     let cmd_trimmed = cmd_lower.trim_start_matches('"');
 
+    if let Some(listed) = allowlist::with(|a| a.service_override(&service_name.to_lowercase(), cmd_trimmed)) {
+        return listed;
+    }
+
     // Check environment-variable prefixes (%systemroot%, %windir%)
     if WINDOWS_SERVICE_PREFIXES
         .iter()
@@ -370,3 +425,297 @@ pub fn get_service_description(service_name: &str) -> String {
     };
     svc_key.get_value("Description").unwrap_or_default()
 }
+
+/// Resolve the file that actually implements a service: the `ServiceDll` for
+/// shared `svchost.exe` services, otherwise the image path's executable.
+/// Environment variables are expanded.
+pub fn get_service_binary_path(service_name: &str, image_path: &str) -> String {
+    let exe = cmdline::executable_path(image_path);
+    let is_svchost = std::path::Path::new(&exe)
+        .file_name()
+        .and_then(|f| f.to_str())
+        .is_some_and(|f| f.eq_ignore_ascii_case("svchost.exe"));
+    if is_svchost {
+        // The DLL is usually under Parameters, but some services keep it on the service key
+        let dll = RegKey::predef(HKEY_LOCAL_MACHINE)
+            .open_subkey(format!("SYSTEM\\CurrentControlSet\\Services\\{}", service_name))
+            .ok()
+            .and_then(|svc| {
+                svc.open_subkey("Parameters")
+                    .and_then(|p| p.get_value::<String, _>("ServiceDll"))
+                    .or_else(|_| svc.get_value::<String, _>("ServiceDll"))
+                    .ok()
+            });
+        if let Some(dll) = dll.filter(|d| !d.trim().is_empty()) {
+            return cmdline::expand_env_vars(dll.trim());
+        }
+    }
+    exe
+}
+
+/// Fetch the trigger conditions that can start or stop a trigger-start service
+/// via QueryServiceConfig2W(SERVICE_CONFIG_TRIGGER_INFO). Returns an empty list
+/// for services without triggers or when the service can't be opened.
+pub fn get_service_triggers(service_name: &str) -> Vec<ServiceTrigger> {
+    query_service_triggers(service_name).unwrap_or_default()
+}
+
+/// Fetch a service's SID type and required privileges via QueryServiceConfig2W
+/// (SERVICE_CONFIG_SERVICE_SID_INFO and SERVICE_CONFIG_REQUIRED_PRIVILEGES_INFO).
+/// Returns `None` when the service can't be opened.
+pub fn get_service_security(service_name: &str) -> Option<ServiceSecurity> {
+    const SERVICE_CONFIG_SERVICE_SID_INFO: u32 = 5;
+    const SERVICE_CONFIG_REQUIRED_PRIVILEGES_INFO: u32 = 6;
+
+    let sid_buffer = query_service_config2(service_name, SERVICE_CONFIG_SERVICE_SID_INFO).ok()?;
+    // SERVICE_SID_INFO { DWORD dwServiceSidType }
+    let sid_type = match sid_buffer[0] as u32 {
+        0 => ServiceSidType::None,
+        1 => ServiceSidType::Unrestricted,
+        3 => ServiceSidType::Restricted,
+        other => ServiceSidType::Other(other),
+    };
+
+    // SERVICE_REQUIRED_PRIVILEGES_INFO { LPWSTR pmszRequiredPrivileges } (multi-string)
+    let mut required_privileges = Vec::new();
+    if let Ok(buffer) = query_service_config2(service_name, SERVICE_CONFIG_REQUIRED_PRIVILEGES_INFO) {
+        let mut p = buffer[0] as usize as *const u16;
+        if !p.is_null() {
+            loop {
+                let len = (0..).take_while(|&i| unsafe { *p.add(i) } != 0).count();
+                if len == 0 {
+                    break;
+                }
+                let wide = unsafe { std::slice::from_raw_parts(p, len) };
+                required_privileges.push(String::from_utf16_lossy(wide));
+                p = unsafe { p.add(len + 1) };
+            }
+        }
+    }
+
+    Some(ServiceSecurity {
+        sid_type,
+        required_privileges,
+    })
+}
+
+/// Call QueryServiceConfig2W for one info level and return the raw buffer.
+/// The buffer is `u64`-backed so the pointers inside it are aligned.
+fn query_service_config2(service_name: &str, info_level: u32) -> Result<Vec<u64>> {
+    use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
+    use windows::core::s;
+
+    let lib = unsafe { LoadLibraryA(s!("advapi32.dll")) }
+        .map_err(|e| anyhow::anyhow!("LoadLibrary advapi32: {}", e))?;
+
+    type OpenSCManagerFn = unsafe extern "system" fn(
+        machine: *const u16, database: *const u16, access: u32,
+    ) -> isize;
+    type OpenServiceFn = unsafe extern "system" fn(
+        sc_manager: isize, service_name: *const u16, access: u32,
+    ) -> isize;
+    type QueryConfig2Fn = unsafe extern "system" fn(
+        service: isize, info_level: u32, buffer: *mut u8, buf_size: u32, bytes_needed: *mut u32,
+    ) -> i32;
+    type CloseHandleFn = unsafe extern "system" fn(handle: isize) -> i32;
+
+    let open_scm: OpenSCManagerFn = unsafe {
+        std::mem::transmute(
+            GetProcAddress(lib, s!("OpenSCManagerW"))
+                .ok_or_else(|| anyhow::anyhow!("GetProcAddress OpenSCManagerW failed"))?
+        )
+    };
+    let open_svc: OpenServiceFn = unsafe {
+        std::mem::transmute(
+            GetProcAddress(lib, s!("OpenServiceW"))
+                .ok_or_else(|| anyhow::anyhow!("GetProcAddress OpenServiceW failed"))?
+        )
+    };
+    let query_config2: QueryConfig2Fn = unsafe {
+        std::mem::transmute(
+            GetProcAddress(lib, s!("QueryServiceConfig2W"))
+                .ok_or_else(|| anyhow::anyhow!("GetProcAddress QueryServiceConfig2W failed"))?
+        )
+    };
+    let close_svc: CloseHandleFn = unsafe {
+        std::mem::transmute(
+            GetProcAddress(lib, s!("CloseServiceHandle"))
+                .ok_or_else(|| anyhow::anyhow!("GetProcAddress CloseServiceHandle failed"))?
+        )
+    };
+
+    const SC_MANAGER_CONNECT: u32 = 0x0001;
+    const SERVICE_QUERY_CONFIG: u32 = 0x0001;
+
+    let sc_handle = unsafe { open_scm(std::ptr::null(), std::ptr::null(), SC_MANAGER_CONNECT) };
+    if sc_handle == 0 {
+        anyhow::bail!("OpenSCManagerW failed");
+    }
+    let name_wide: Vec<u16> = service_name.encode_utf16().chain(std::iter::once(0)).collect();
+    let svc_handle = unsafe { open_svc(sc_handle, name_wide.as_ptr(), SERVICE_QUERY_CONFIG) };
+    if svc_handle == 0 {
+        unsafe { close_svc(sc_handle); }
+        anyhow::bail!("OpenServiceW failed for {}", service_name);
+    }
+
+    // First call to get required buffer size
+    let mut bytes_needed: u32 = 0;
+    unsafe {
+        query_config2(svc_handle, info_level, std::ptr::null_mut(), 0, &mut bytes_needed);
+    }
+    let mut buffer = vec![0u64; (bytes_needed as usize).div_ceil(8).max(1)];
+    let ok = unsafe {
+        query_config2(
+            svc_handle, info_level, buffer.as_mut_ptr() as *mut u8,
+            (buffer.len() * 8) as u32, &mut bytes_needed,
+        )
+    };
+    unsafe {
+        close_svc(svc_handle);
+        close_svc(sc_handle);
+    }
+    if ok == 0 {
+        anyhow::bail!("QueryServiceConfig2W failed for {}", service_name);
+    }
+    Ok(buffer)
+}
+
+fn query_service_triggers(service_name: &str) -> Result<Vec<ServiceTrigger>> {
+    use windows::core::GUID;
+
+    const SERVICE_CONFIG_TRIGGER_INFO: u32 = 8;
+
+    // SERVICE_TRIGGER_INFO / SERVICE_TRIGGER / SERVICE_TRIGGER_SPECIFIC_DATA_ITEM
+    #[repr(C)]
+    struct TriggerInfo {
+        triggers: u32,
+        p_triggers: *const Trigger,
+        _reserved: *const u8,
+    }
+    #[repr(C)]
+    struct Trigger {
+        trigger_type: u32,
+        action: u32,
+        subtype: *const GUID,
+        data_items: u32,
+        p_data_items: *const DataItem,
+    }
+    #[repr(C)]
+    struct DataItem {
+        data_type: u32,
+        size: u32,
+        data: *const u8,
+    }
+
+    let buffer = query_service_config2(service_name, SERVICE_CONFIG_TRIGGER_INFO)?;
+
+    let info = unsafe { &*(buffer.as_ptr() as *const TriggerInfo) };
+    let mut triggers = Vec::with_capacity(info.triggers as usize);
+    for i in 0..info.triggers as usize {
+        let trigger = unsafe { &*info.p_triggers.add(i) };
+        let subtype = if trigger.subtype.is_null() {
+            None
+        } else {
+            Some(unsafe { *trigger.subtype })
+        };
+
+        let mut data = Vec::new();
+        for j in 0..trigger.data_items as usize {
+            let item = unsafe { &*trigger.p_data_items.add(j) };
+            if item.data.is_null() || item.size == 0 {
+                continue;
+            }
+            let bytes = unsafe { std::slice::from_raw_parts(item.data, item.size as usize) };
+            data.extend(format_trigger_data(item.data_type, bytes));
+        }
+
+        triggers.push(ServiceTrigger {
+            action: match trigger.action {
+                1 => "Start".to_string(),
+                2 => "Stop".to_string(),
+                other => format!("Action {}", other),
+            },
+            condition: describe_trigger(trigger.trigger_type, subtype.as_ref()),
+            data,
+        });
+    }
+
+    Ok(triggers)
+}
+
+/// Describe a trigger by its SERVICE_TRIGGER_TYPE_* and subtype GUID.
+fn describe_trigger(trigger_type: u32, subtype: Option<&windows::core::GUID>) -> String {
+    let guid = subtype.map(format_guid).unwrap_or_default();
+    let known = subtype.and_then(|g| {
+        WELL_KNOWN_TRIGGER_GUIDS
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(&format_guid(g)))
+            .map(|(_, name)| *name)
+    });
+
+    match (trigger_type, known) {
+        (_, Some(name)) => name.to_string(),
+        (1, _) => format!("Device interface arrival {}", guid),
+        (2, _) => "IP address availability".to_string(),
+        (3, _) => "Domain join/leave".to_string(),
+        (4, _) => "Firewall port event".to_string(),
+        (5, _) => "Group policy change".to_string(),
+        (6, _) => format!("Network endpoint {}", guid),
+        (7, _) => format!("System state change {}", guid),
+        (20, _) => format!("ETW event from provider {}", guid),
+        (30, _) => format!("Aggregate trigger {}", guid),
+        (other, _) => format!("Trigger type {} {}", other, guid).trim_end().to_string(),
+    }
+}
+
+/// Subtype GUIDs documented for the built-in trigger types.
+static WELL_KNOWN_TRIGGER_GUIDS: &[(&str, &str)] = &[
+    ("{4F27F2DE-14E2-430B-A549-7CD48CBC8245}", "First IP address available"),
+    ("{CC4BA62A-162E-4648-847A-B6BDF993E335}", "Last IP address removed"),
+    ("{1CE20ABA-9851-4421-9430-1DDEB766E809}", "Computer joined a domain"),
+    ("{DDAF516E-58C2-4866-9574-C3B615D42EA1}", "Computer left a domain"),
+    ("{B7569E07-8421-4EE0-AD10-86915AFDAD09}", "Firewall port opened"),
+    ("{A144ED38-8E12-4DE4-9D96-E64740B1A524}", "Firewall port closed"),
+    ("{659FCAE6-5BDB-4DA9-B1FF-CA2A178D46E0}", "Machine group policy change"),
+    ("{54FB46C8-F089-464C-B1FD-59D1B62C3B50}", "User group policy change"),
+    ("{BC90D167-9470-4139-A9BA-BE0BBBF5B74D}", "RPC interface request"),
+    ("{1F81D131-3FAC-4537-9E0C-7E7B0C2F4B55}", "Named pipe request"),
+];
+
+/// Render one SERVICE_TRIGGER_SPECIFIC_DATA_ITEM as display strings.
+fn format_trigger_data(data_type: u32, bytes: &[u8]) -> Vec<String> {
+    match data_type {
+        // SERVICE_TRIGGER_DATA_TYPE_STRING: REG_MULTI_SZ-style UTF-16
+        2 => {
+            let wide: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&wide)
+                .split('\0')
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .collect()
+        }
+        // SERVICE_TRIGGER_DATA_TYPE_LEVEL
+        3 => vec![format!("Level {}", bytes[0])],
+        // SERVICE_TRIGGER_DATA_TYPE_KEYWORD_ANY / KEYWORD_ALL
+        4 | 5 if bytes.len() >= 8 => {
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(&bytes[..8]);
+            let kind = if data_type == 4 { "any" } else { "all" };
+            vec![format!("Keywords ({}) 0x{:016X}", kind, u64::from_le_bytes(raw))]
+        }
+        // SERVICE_TRIGGER_DATA_TYPE_BINARY (and anything unrecognized)
+        _ => vec![bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(" ")],
+    }
+}
+
+fn format_guid(guid: &windows::core::GUID) -> String {
+    format!(
+        "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+        guid.data1, guid.data2, guid.data3,
+        guid.data4[0], guid.data4[1], guid.data4[2], guid.data4[3],
+        guid.data4[4], guid.data4[5], guid.data4[6], guid.data4[7],
+    )
+}
@@ -0,0 +1,384 @@
+//! User settings, stored as `key=value` lines in `settings.ini` in the app
+//! data directory. Unknown keys are ignored so older builds can read newer
+//! files.
+
+use crate::app_data;
+use std::path::PathBuf;
+
+const FILE_NAME: &str = "settings.ini";
+/// Where the window was left, written on close in the same format.
+const SESSION_FILE_NAME: &str = "session.ini";
+
+/// Placeholder in the debugger command that is replaced with the target PID.
+pub const PID_PLACEHOLDER: &str = "{pid}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    /// Command used to attach a debugger to a process, with `{pid}` for the
+    /// process ID. Empty means auto-detect (see [`detect_debugger`]).
+    pub debugger_command: String,
+    pub thresholds: UsageThresholds,
+    pub scan_scope: ScanScope,
+    /// How often keep-disabled rules are enforced while the app (or the
+    /// monitor mode) runs. 0 turns periodic enforcement off.
+    pub enforce_interval_minutes: u32,
+    /// Start exported CSV files with a commented block saying where, when,
+    /// and with which filters they were made.
+    pub export_metadata: bool,
+    /// Language of exported column names and metadata labels.
+    pub export_language: ExportLanguage,
+    /// Reopen the tab, scroll position, and filters of the last session.
+    pub restore_session: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            debugger_command: String::new(),
+            thresholds: UsageThresholds::default(),
+            scan_scope: ScanScope::default(),
+            enforce_interval_minutes: 5,
+            export_metadata: false,
+            export_language: ExportLanguage::English,
+            restore_session: true,
+        }
+    }
+}
+
+/// Language of exported column names (see `export_locale`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportLanguage {
+    English,
+    German,
+    French,
+    Spanish,
+    /// The Windows display language, if it is one of the above.
+    System,
+}
+
+impl ExportLanguage {
+    pub const ALL: [ExportLanguage; 5] = [
+        ExportLanguage::English,
+        ExportLanguage::German,
+        ExportLanguage::French,
+        ExportLanguage::Spanish,
+        ExportLanguage::System,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportLanguage::English => "English",
+            ExportLanguage::German => "Deutsch",
+            ExportLanguage::French => "Français",
+            ExportLanguage::Spanish => "Español",
+            ExportLanguage::System => "Windows display language",
+        }
+    }
+
+    /// Value stored in the settings file.
+    fn key(&self) -> &'static str {
+        match self {
+            ExportLanguage::English => "en",
+            ExportLanguage::German => "de",
+            ExportLanguage::French => "fr",
+            ExportLanguage::Spanish => "es",
+            ExportLanguage::System => "system",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportLanguage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|l| l.key() == s).ok_or(())
+    }
+}
+
+/// Which startup locations and enrichment steps a scan includes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanScope {
+    pub registry_run: bool,
+    pub startup_folders: bool,
+    pub task_scheduler: bool,
+    /// Read `C:\Windows\Prefetch` for Last Ran times (admin only).
+    pub prefetch: bool,
+    /// Image File Execution Options `Debugger` hijacks (advanced, off by default).
+    pub ifeo: bool,
+    /// Global pip, npm, cargo, Scoop, and Chocolatey packages for the Dev
+    /// Packages tab (off by default; the tab is hidden while off).
+    pub dev_packages: bool,
+}
+
+impl Default for ScanScope {
+    fn default() -> Self {
+        Self {
+            registry_run: true,
+            startup_folders: true,
+            task_scheduler: true,
+            prefetch: true,
+            ifeo: false,
+            dev_packages: false,
+        }
+    }
+}
+
+/// How a resource usage value compares with the configured thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageLevel {
+    Normal,
+    Warning,
+    High,
+}
+
+/// CPU and memory levels at which usage is colored orange (warning) or red
+/// (high). Usage at a level counts as reaching it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsageThresholds {
+    pub cpu_warning_percent: f32,
+    pub cpu_high_percent: f32,
+    pub memory_warning_mb: u64,
+    pub memory_high_mb: u64,
+}
+
+impl Default for UsageThresholds {
+    fn default() -> Self {
+        Self {
+            cpu_warning_percent: 10.0,
+            cpu_high_percent: 50.0,
+            memory_warning_mb: 500,
+            memory_high_mb: 2048,
+        }
+    }
+}
+
+impl UsageThresholds {
+    pub fn cpu_level(&self, cpu_percent: f32) -> UsageLevel {
+        if cpu_percent >= self.cpu_high_percent {
+            UsageLevel::High
+        } else if cpu_percent >= self.cpu_warning_percent {
+            UsageLevel::Warning
+        } else {
+            UsageLevel::Normal
+        }
+    }
+
+    pub fn memory_level(&self, bytes: u64) -> UsageLevel {
+        let mb = bytes / 1_048_576;
+        if mb >= self.memory_high_mb {
+            UsageLevel::High
+        } else if mb >= self.memory_warning_mb {
+            UsageLevel::Warning
+        } else {
+            UsageLevel::Normal
+        }
+    }
+}
+
+impl Settings {
+    /// Load settings, falling back to defaults for anything missing.
+    pub fn load() -> Self {
+        settings_path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    /// Settings from the text of a settings file, with defaults for anything
+    /// missing.
+    pub fn parse(text: &str) -> Self {
+        let mut settings = Settings::default();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with(['#', ';']) {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().to_string();
+            let t = &mut settings.thresholds;
+            let scope = &mut settings.scan_scope;
+            match key.trim() {
+                "debugger_command" => settings.debugger_command = value,
+                "cpu_warning_percent" => parse_into(&value, &mut t.cpu_warning_percent),
+                "cpu_high_percent" => parse_into(&value, &mut t.cpu_high_percent),
+                "memory_warning_mb" => parse_into(&value, &mut t.memory_warning_mb),
+                "memory_high_mb" => parse_into(&value, &mut t.memory_high_mb),
+                "scan_registry_run" => parse_into(&value, &mut scope.registry_run),
+                "scan_startup_folders" => parse_into(&value, &mut scope.startup_folders),
+                "scan_task_scheduler" => parse_into(&value, &mut scope.task_scheduler),
+                "scan_prefetch" => parse_into(&value, &mut scope.prefetch),
+                "scan_ifeo" => parse_into(&value, &mut scope.ifeo),
+                "scan_dev_packages" => parse_into(&value, &mut scope.dev_packages),
+                "enforce_interval_minutes" => parse_into(&value, &mut settings.enforce_interval_minutes),
+                "export_metadata" => parse_into(&value, &mut settings.export_metadata),
+                "export_language" => parse_into(&value, &mut settings.export_language),
+                "restore_session" => parse_into(&value, &mut settings.restore_session),
+                _ => {}
+            }
+        }
+        settings
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = settings_path().ok_or("App data directory is not available")?;
+        app_data::write_atomic(&path, self.to_text()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// The settings file's contents for these settings.
+    pub fn to_text(&self) -> String {
+        let t = &self.thresholds;
+        let scope = &self.scan_scope;
+        format!(
+            "debugger_command={}\n\
+             cpu_warning_percent={}\n\
+             cpu_high_percent={}\n\
+             memory_warning_mb={}\n\
+             memory_high_mb={}\n\
+             scan_registry_run={}\n\
+             scan_startup_folders={}\n\
+             scan_task_scheduler={}\n\
+             scan_prefetch={}\n\
+             scan_ifeo={}\n\
+             scan_dev_packages={}\n\
+             enforce_interval_minutes={}\n\
+             export_metadata={}\n\
+             export_language={}\n\
+             restore_session={}\n",
+            self.debugger_command.trim(),
+            t.cpu_warning_percent,
+            t.cpu_high_percent,
+            t.memory_warning_mb,
+            t.memory_high_mb,
+            scope.registry_run,
+            scope.startup_folders,
+            scope.task_scheduler,
+            scope.prefetch,
+            scope.ifeo,
+            scope.dev_packages,
+            self.enforce_interval_minutes,
+            self.export_metadata,
+            self.export_language.key(),
+            self.restore_session,
+        )
+    }
+
+    /// The debugger command to use for `pid`, from settings or auto-detected.
+    pub fn debugger_command_for(&self, pid: u32) -> Option<String> {
+        let template = if self.debugger_command.trim().is_empty() {
+            detect_debugger()?
+        } else {
+            self.debugger_command.trim().to_string()
+        };
+        Some(template.replace(PID_PLACEHOLDER, &pid.to_string()))
+    }
+}
+
+/// The view at the end of the last session, restored at the next start
+/// when `restore_session` is on. Tabs are stored by their export file name
+/// (e.g. `processes`), quick filters by their chip label.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionState {
+    pub tab: String,
+    /// Vertical scroll position of the tab's table.
+    pub scroll_offset: f32,
+    pub column_filters_visible: bool,
+    /// Filled-in column filter boxes as (tab, column, text).
+    pub column_filters: Vec<(String, String, String)>,
+    /// Active quick filter chips as (tab, label).
+    pub quick_filters: Vec<(String, String)>,
+}
+
+impl SessionState {
+    /// The last session's state, if one was saved.
+    pub fn load() -> Option<Self> {
+        let text = std::fs::read_to_string(session_path()?).ok()?;
+        Some(Self::parse(&text))
+    }
+
+    /// Filters are one line each, `tab|column|text` and `tab|label`; only
+    /// the filter text can contain `|`.
+    pub fn parse(text: &str) -> Self {
+        let mut state = SessionState::default();
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim();
+            match key.trim() {
+                "tab" => state.tab = value.to_string(),
+                "scroll_offset" => parse_into(value, &mut state.scroll_offset),
+                "column_filters_visible" => parse_into(value, &mut state.column_filters_visible),
+                "column_filter" => {
+                    let mut parts = value.splitn(3, '|');
+                    if let (Some(tab), Some(column), Some(text)) = (parts.next(), parts.next(), parts.next()) {
+                        state.column_filters.push((tab.to_string(), column.to_string(), text.to_string()));
+                    }
+                }
+                "quick_filter" => {
+                    if let Some((tab, label)) = value.split_once('|') {
+                        state.quick_filters.push((tab.to_string(), label.to_string()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = session_path().ok_or("App data directory is not available")?;
+        app_data::write_atomic(&path, self.to_text()).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "tab={}\nscroll_offset={}\ncolumn_filters_visible={}\n",
+            self.tab, self.scroll_offset, self.column_filters_visible
+        );
+        for (tab, column, filter) in &self.column_filters {
+            // Pasted text can hold line breaks the single-line box doesn't show
+            text.push_str(&format!("column_filter={}|{}|{}\n", tab, column, filter.replace(['\r', '\n'], " ")));
+        }
+        for (tab, label) in &self.quick_filters {
+            text.push_str(&format!("quick_filter={}|{}\n", tab, label));
+        }
+        text
+    }
+}
+
+/// Overwrite `target` with `value` if it parses; keep the default otherwise.
+fn parse_into<T: std::str::FromStr>(value: &str, target: &mut T) {
+    if let Ok(v) = value.parse() {
+        *target = v;
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(FILE_NAME))
+}
+
+fn session_path() -> Option<PathBuf> {
+    app_data::data_dir().map(|dir| dir.join(SESSION_FILE_NAME))
+}
+
+/// Find an installed debugger: the Visual Studio JIT debugger, then WinDbg
+/// from the Windows SDK.
+pub fn detect_debugger() -> Option<String> {
+    let windir = std::env::var("SystemRoot").unwrap_or_else(|_| r"C:\Windows".to_string());
+    let program_files_x86 = std::env::var("ProgramFiles(x86)")
+        .unwrap_or_else(|_| r"C:\Program Files (x86)".to_string());
+    let arch = if cfg!(target_arch = "aarch64") { "arm64" } else { "x64" };
+    let candidates = [
+        PathBuf::from(&windir).join("System32").join("vsjitdebugger.exe"),
+        PathBuf::from(&program_files_x86)
+            .join(r"Windows Kits\10\Debuggers")
+            .join(arch)
+            .join("windbg.exe"),
+    ];
+    candidates
+        .iter()
+        .find(|p| p.exists())
+        .map(|p| format!("\"{}\" -p {}", p.display(), PID_PLACEHOLDER))
+}
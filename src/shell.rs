@@ -0,0 +1,89 @@
+//! Hand-offs to Windows Explorer for files referenced by entries, and
+//! elevated launches through the shell.
+
+use std::os::windows::ffi::OsStrExt;
+use std::os::windows::process::CommandExt;
+use std::path::Path;
+use windows::core::{w, PCWSTR};
+use windows::Win32::UI::Shell::{SHObjectProperties, ShellExecuteW, SHOP_FILEPATH};
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+
+/// Open an Explorer window with `path` selected (or its folder, if the file
+/// itself is gone).
+pub fn open_file_location(path: &str) -> Result<(), String> {
+    let target = Path::new(path);
+    let mut command = std::process::Command::new("explorer.exe");
+    if target.exists() {
+        // explorer.exe does its own argument parsing; pass /select verbatim
+        command.raw_arg(format!("/select,\"{}\"", path));
+    } else {
+        let folder = target
+            .parent()
+            .filter(|p| p.exists())
+            .ok_or_else(|| format!("{} does not exist", path))?;
+        command.arg(folder);
+    }
+    command.spawn().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// Show the native file Properties sheet (General, Details, Digital
+/// Signatures, ...) for `path`. The sheet runs on its own; this returns
+/// immediately.
+pub fn show_file_properties(path: &str) -> Result<(), String> {
+    if path.is_empty() {
+        return Err("No file path".to_string());
+    }
+    if !Path::new(path).exists() {
+        return Err(format!("{} does not exist", path));
+    }
+    let wide: Vec<u16> = std::ffi::OsStr::new(path)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let shown = unsafe { SHObjectProperties(None, SHOP_FILEPATH, PCWSTR(wide.as_ptr()), PCWSTR::null()) };
+    if shown.as_bool() {
+        Ok(())
+    } else {
+        Err(format!("Could not open properties for {}", path))
+    }
+}
+
+/// Open `path` with its default program, like double-clicking it in
+/// Explorer.
+pub fn open_file(path: &Path) -> Result<(), String> {
+    let file: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let result = unsafe { ShellExecuteW(None, w!("open"), PCWSTR(file.as_ptr()), None, None, SW_SHOWNORMAL) };
+    if result.0 as isize > 32 {
+        Ok(())
+    } else {
+        Err(format!("Could not open {}", path.display()))
+    }
+}
+
+/// Launch `exe` with `args` through the UAC prompt (`ShellExecute` with the
+/// `runas` verb). Fails if the user declines elevation.
+pub fn run_elevated(exe: &str, args: &str, working_dir: Option<&Path>) -> Result<(), String> {
+    let to_wide = |s: &std::ffi::OsStr| -> Vec<u16> { s.encode_wide().chain(std::iter::once(0)).collect() };
+    let verb = to_wide(std::ffi::OsStr::new("runas"));
+    let file = to_wide(std::ffi::OsStr::new(exe));
+    let params = to_wide(std::ffi::OsStr::new(args));
+    let dir = working_dir.map(|d| to_wide(d.as_os_str()));
+
+    let result = unsafe {
+        ShellExecuteW(
+            None,
+            PCWSTR(verb.as_ptr()),
+            PCWSTR(file.as_ptr()),
+            PCWSTR(params.as_ptr()),
+            dir.as_ref().map_or(PCWSTR::null(), |d| PCWSTR(d.as_ptr())),
+            SW_SHOWNORMAL,
+        )
+    };
+    // Values above 32 mean success; SE_ERR_ACCESSDENIED also covers a declined prompt
+    match result.0 as isize {
+        code if code > 32 => Ok(()),
+        5 => Err("Elevation was cancelled or denied".to_string()),
+        2 | 3 => Err(format!("{} was not found", exe)),
+        code => Err(format!("ShellExecute failed (code {})", code)),
+    }
+}
@@ -0,0 +1,109 @@
+//! Items a collector could see but not read (access denied, a value of the
+//! wrong type, a task whose definition fails to load). Collectors record them
+//! instead of dropping them silently, so the UI can say the view may be
+//! incomplete and why.
+
+use std::io;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkippedKind {
+    Service,
+    Task,
+    TaskFolder,
+    RegistryKey,
+    RegistryValue,
+    StartupFolder,
+    Shortcut,
+}
+
+impl SkippedKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SkippedKind::Service => "Service",
+            SkippedKind::Task => "Scheduled task",
+            SkippedKind::TaskFolder => "Task folder",
+            SkippedKind::RegistryKey => "Registry key",
+            SkippedKind::RegistryValue => "Registry value",
+            SkippedKind::StartupFolder => "Startup folder",
+            SkippedKind::Shortcut => "Shortcut",
+        }
+    }
+
+    fn noun(&self, count: usize) -> &'static str {
+        let (one, many) = match self {
+            SkippedKind::Service => ("service", "services"),
+            SkippedKind::Task => ("scheduled task", "scheduled tasks"),
+            SkippedKind::TaskFolder => ("task folder", "task folders"),
+            SkippedKind::RegistryKey => ("registry key", "registry keys"),
+            SkippedKind::RegistryValue => ("registry value", "registry values"),
+            SkippedKind::StartupFolder => ("startup folder", "startup folders"),
+            SkippedKind::Shortcut => ("shortcut", "shortcuts"),
+        };
+        if count == 1 { one } else { many }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SkippedItem {
+    pub kind: SkippedKind,
+    pub name: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SkippedItems {
+    pub items: Vec<SkippedItem>,
+}
+
+impl SkippedItems {
+    pub fn add(&mut self, kind: SkippedKind, name: impl Into<String>, reason: impl Into<String>) {
+        self.items.push(SkippedItem {
+            kind,
+            name: name.into(),
+            reason: reason.into(),
+        });
+    }
+
+    /// Record an I/O failure. Missing items aren't unreadable, just absent,
+    /// so `NotFound` is ignored.
+    pub fn add_io(&mut self, kind: SkippedKind, name: impl Into<String>, err: &io::Error) {
+        if err.kind() != io::ErrorKind::NotFound {
+            self.add(kind, name, io_reason(err));
+        }
+    }
+
+    /// Record a failed COM call.
+    pub fn add_com(&mut self, kind: SkippedKind, name: impl Into<String>, err: &windows::core::Error) {
+        let reason = if err.code() == windows::Win32::Foundation::E_ACCESSDENIED {
+            "access denied".to_string()
+        } else {
+            err.message().trim().to_string()
+        };
+        self.add(kind, name, reason);
+    }
+
+    /// One line per kind and reason, most common first, e.g.
+    /// "3 services could not be read (access denied)".
+    pub fn summary_lines(&self) -> Vec<String> {
+        let mut groups: Vec<(SkippedKind, &str, usize)> = Vec::new();
+        for item in &self.items {
+            match groups.iter_mut().find(|(k, r, _)| *k == item.kind && *r == item.reason) {
+                Some(group) => group.2 += 1,
+                None => groups.push((item.kind, &item.reason, 1)),
+            }
+        }
+        groups.sort_by_key(|group| std::cmp::Reverse(group.2));
+        groups
+            .into_iter()
+            .map(|(kind, reason, count)| format!("{} {} could not be read ({})", count, kind.noun(count), reason))
+            .collect()
+    }
+}
+
+fn io_reason(err: &io::Error) -> String {
+    match err.kind() {
+        io::ErrorKind::PermissionDenied => "access denied".to_string(),
+        io::ErrorKind::InvalidData => "unexpected value type".to_string(),
+        _ => err.to_string(),
+    }
+}
@@ -1,7 +1,24 @@
-use crate::models::{Source, StartupEntry};
-use std::path::PathBuf;
+use crate::cmdline;
+use crate::models::{EnabledStatus, OtherUser, Source, StartupEntry};
+use crate::skipped::{SkippedItems, SkippedKind};
+use crate::status;
+use std::path::{Path, PathBuf};
+use winreg::enums::*;
+use winreg::RegKey;
+
+const PROFILE_LIST_KEY: &str = r"SOFTWARE\Microsoft\Windows NT\CurrentVersion\ProfileList";
+const STARTUP_APPROVED_FOLDER_KEY: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\StartupFolder";
+/// Startup folder relative to a profile directory.
+const PROFILE_STARTUP_FOLDER: &str = r"AppData\Roaming\Microsoft\Windows\Start Menu\Programs\Startup";
+
+/// Another user's profile from the ProfileList.
+struct UserProfile {
+    user: OtherUser,
+    path: PathBuf,
+}
 
-fn user_startup_folder() -> Option<PathBuf> {
+pub fn user_startup_folder() -> Option<PathBuf> {
     std::env::var("APPDATA").ok().map(|appdata| {
         PathBuf::from(appdata)
             .join("Microsoft")
@@ -37,16 +54,30 @@ fn resolve_lnk(path: &std::path::Path) -> Option<String> {
     Some(format!("{}{}", target, args))
 }
 
-fn scan_startup_folder(folder: &std::path::Path, is_common: bool) -> Vec<StartupEntry> {
+fn scan_startup_folder(
+    folder: &Path,
+    is_common: bool,
+    other_user: Option<&OtherUser>,
+    skipped: &mut SkippedItems,
+) -> Vec<StartupEntry> {
     let mut entries = Vec::new();
 
     let read_dir = match std::fs::read_dir(folder) {
         Ok(rd) => rd,
-        Err(_) => return entries,
+        Err(e) => {
+            skipped.add_io(SkippedKind::StartupFolder, folder.to_string_lossy(), &e);
+            return entries;
+        }
     };
 
-    for dir_entry in read_dir.flatten() {
-        let path = dir_entry.path();
+    for dir_entry in read_dir {
+        let path = match dir_entry {
+            Ok(dir_entry) => dir_entry.path(),
+            Err(e) => {
+                skipped.add_io(SkippedKind::Shortcut, folder.to_string_lossy(), &e);
+                continue;
+            }
+        };
         if !path.is_file() {
             continue;
         }
@@ -88,28 +119,103 @@ fn scan_startup_folder(folder: &std::path::Path, is_common: bool) -> Vec<Startup
         let source = Source::StartupFolder {
             path: path.to_string_lossy().to_string(),
             is_common,
+            other_user: other_user.cloned(),
         };
 
         // For StartupApproved lookup, we need the filename (e.g., "Discord.lnk")
         let mut entry = StartupEntry::new(file_name.clone(), command, source);
         // Use the friendly name for display, keep file_name in entry for approval lookup
         entry.name = name;
+        if let Some(user) = other_user {
+            entry.runs_as = user.name.clone();
+        }
         entries.push(entry);
     }
 
     entries
 }
 
-pub fn collect_startup_folder_entries() -> Vec<StartupEntry> {
+pub fn collect_startup_folder_entries(skipped: &mut SkippedItems) -> Vec<StartupEntry> {
     let mut entries = Vec::new();
 
     if let Some(folder) = user_startup_folder() {
-        entries.extend(scan_startup_folder(&folder, false));
+        entries.extend(scan_startup_folder(&folder, false, None, skipped));
     }
 
     if let Some(folder) = common_startup_folder() {
-        entries.extend(scan_startup_folder(&folder, true));
+        entries.extend(scan_startup_folder(&folder, true, None, skipped));
+    }
+
+    // Other users' folders are only readable when elevated; without admin
+    // rights read_dir fails and each folder is reported as skipped
+    for profile in other_user_profiles() {
+        let folder = profile.path.join(PROFILE_STARTUP_FOLDER);
+        let mut found = scan_startup_folder(&folder, false, Some(&profile.user), skipped);
+        if found.is_empty() {
+            continue;
+        }
+        let approvals = open_user_hive(&profile.user.sid, KEY_READ)
+            .and_then(|root| root.open_subkey_with_flags(STARTUP_APPROVED_FOLDER_KEY, KEY_READ))
+            .ok();
+        for entry in &mut found {
+            let Source::StartupFolder { path, .. } = &entry.source else {
+                continue;
+            };
+            let file_name = Path::new(path).file_name().and_then(|f| f.to_str()).unwrap_or_default();
+            entry.enabled = match &approvals {
+                Some(key) => match key.get_raw_value(file_name) {
+                    Ok(value) => status::approval_from_bytes(&value.bytes),
+                    // No StartupApproved value means it was never disabled
+                    Err(_) => EnabledStatus::Enabled,
+                },
+                None => EnabledStatus::Unknown,
+            };
+        }
+        entries.extend(found);
     }
 
     entries
 }
+
+/// Profiles of the other local and domain accounts that have signed in to
+/// this computer (SIDs `S-1-5-21-*`; built-in service profiles are skipped).
+fn other_user_profiles() -> Vec<UserProfile> {
+    let Ok(list) = RegKey::predef(HKEY_LOCAL_MACHINE).open_subkey_with_flags(PROFILE_LIST_KEY, KEY_READ) else {
+        return Vec::new();
+    };
+    let current = std::env::var("USERPROFILE").unwrap_or_default().to_lowercase();
+    list.enum_keys()
+        .flatten()
+        .filter(|sid| sid.starts_with("S-1-5-21-") && !sid.ends_with(".bak"))
+        .filter_map(|sid| {
+            let key = list.open_subkey_with_flags(&sid, KEY_READ).ok()?;
+            let image_path: String = key.get_value("ProfileImagePath").ok()?;
+            let path = PathBuf::from(cmdline::expand_env_vars(&image_path));
+            if path.to_string_lossy().to_lowercase() == current {
+                return None;
+            }
+            let name = path.file_name()?.to_string_lossy().to_string();
+            Some(UserProfile {
+                user: OtherUser { sid, name },
+                path,
+            })
+        })
+        .collect()
+}
+
+fn profile_path(sid: &str) -> std::io::Result<PathBuf> {
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey_with_flags(format!(r"{}\{}", PROFILE_LIST_KEY, sid), KEY_READ)?;
+    let image_path: String = key.get_value("ProfileImagePath")?;
+    Ok(PathBuf::from(cmdline::expand_env_vars(&image_path)))
+}
+
+/// Open another user's registry: `HKEY_USERS\<sid>` while they are signed in,
+/// otherwise their `NTUSER.DAT` loaded as a private hive (requires admin).
+pub fn open_user_hive(sid: &str, access: u32) -> std::io::Result<RegKey> {
+    if let Ok(root) = RegKey::predef(HKEY_USERS).open_subkey_with_flags(sid, access) {
+        return Ok(root);
+    }
+    let hive_file = profile_path(sid)?.join("NTUSER.DAT");
+    RegKey::load_app_key_with_flags(hive_file, access, 0)
+}
@@ -36,7 +36,8 @@ const STARTUP_APPROVED_PATHS: &[(&str, RegistryHive)] = &[
     ),
 ];
 
-fn filetime_to_datetime(ft: u64) -> Option<DateTime<Local>> {
+/// Convert a FILETIME (100ns intervals since 1601-01-01 UTC) to local time.
+pub fn filetime_to_datetime(ft: u64) -> Option<DateTime<Local>> {
     const FILETIME_UNIX_DIFF: u64 = 116_444_736_000_000_000;
     if ft < FILETIME_UNIX_DIFF || ft == 0 {
         return None;
@@ -47,6 +48,11 @@ fn filetime_to_datetime(ft: u64) -> Option<DateTime<Local>> {
     chrono::DateTime::from_timestamp(secs, nanos).map(|utc| utc.with_timezone(&Local))
 }
 
+/// Enabled status from a raw StartupApproved value.
+pub fn approval_from_bytes(bytes: &[u8]) -> EnabledStatus {
+    parse_startup_approved(bytes).enabled
+}
+
 fn parse_startup_approved(bytes: &[u8]) -> ApprovalInfo {
     if bytes.len() < 12 {
         return ApprovalInfo {
@@ -122,7 +128,7 @@ pub fn get_approval_status(
             // RunOnce entries don't have StartupApproved entries
             return (EnabledStatus::Enabled, None);
         }
-        Source::StartupFolder { path, is_common } => {
+        Source::StartupFolder { path, is_common, .. } => {
             let hive = if *is_common {
                 RegistryHive::HKLM
             } else {
@@ -146,6 +152,10 @@ pub fn get_approval_status(
             // Services use their own start type
             return (EnabledStatus::Unknown, None);
         }
+        Source::Ifeo { .. } => {
+            // A Debugger value is active for as long as it exists
+            return (EnabledStatus::Enabled, None);
+        }
     };
 
     if let Some(info) = approvals.get(&lookup_key) {
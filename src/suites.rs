@@ -0,0 +1,101 @@
+//! Suites: products whose installer registers many Uninstall entries (the
+//! Visual C++ Redistributables, SQL Server, the .NET and Windows SDKs),
+//! grouped on the Installed Apps tab so each shows as one collapsible row
+//! with a combined size.
+//!
+//! Known families are matched by name; anything else groups when at least
+//! [`MIN_GENERIC_MEMBERS`] entries share a publisher and their first two
+//! words ("Adobe Acrobat ...", "NVIDIA PhysX ...").
+
+use crate::models::InstalledApp;
+use std::collections::HashMap;
+
+/// Name prefixes (lower case) of known suites, and the name shown for them.
+const FAMILIES: &[(&str, &str)] = &[
+    ("microsoft visual c++", "Microsoft Visual C++ Redistributables"),
+    ("microsoft sql server", "Microsoft SQL Server"),
+    ("sql server", "Microsoft SQL Server"),
+    ("microsoft .net", "Microsoft .NET"),
+    ("microsoft asp.net", "Microsoft .NET"),
+    ("microsoft windows desktop runtime", "Microsoft .NET"),
+    ("windows software development kit", "Windows SDK"),
+    ("windows sdk", "Windows SDK"),
+    ("windows app certification kit", "Windows SDK"),
+    ("windows driver kit", "Windows Driver Kit"),
+    ("microsoft visual studio", "Microsoft Visual Studio"),
+    ("vs_", "Microsoft Visual Studio"),
+    ("python ", "Python"),
+    ("nvidia ", "NVIDIA"),
+    ("intel(r) ", "Intel"),
+];
+
+/// Fewest entries that make a known suite.
+const MIN_FAMILY_MEMBERS: usize = 2;
+/// Fewest entries with the same publisher and first words that make a
+/// suite; two is often just an app and its updater.
+const MIN_GENERIC_MEMBERS: usize = 3;
+
+/// A group of installed apps.
+#[derive(Debug, Clone)]
+pub struct Suite {
+    /// Stable identity, for remembering which suites are expanded.
+    pub key: String,
+    pub name: String,
+    /// The members' publisher (the first one's, if they differ).
+    pub publisher: String,
+    /// Indexes into the grouped list, in list order.
+    pub members: Vec<usize>,
+    /// Combined estimated size of the members.
+    pub size_kb: u64,
+}
+
+/// Group `apps` into suites. Apps in no suite are left out.
+pub fn group(apps: &[InstalledApp]) -> Vec<Suite> {
+    let mut suites: Vec<Suite> = Vec::new();
+    let mut by_key: HashMap<String, usize> = HashMap::new();
+    for (i, app) in apps.iter().enumerate() {
+        let (key, name) = match family(&app.display_name) {
+            Some(name) => (format!("family|{}", name), name.to_string()),
+            None => match product_words(app) {
+                Some(words) => (format!("{}|{}", app.publisher.to_lowercase(), words.to_lowercase()), words),
+                None => continue,
+            },
+        };
+        let index = *by_key.entry(key.clone()).or_insert_with(|| {
+            suites.push(Suite {
+                key,
+                name,
+                publisher: app.publisher.clone(),
+                members: Vec::new(),
+                size_kb: 0,
+            });
+            suites.len() - 1
+        });
+        suites[index].members.push(i);
+        suites[index].size_kb += app.estimated_size_kb;
+    }
+    suites.retain(|s| {
+        let min = if s.key.starts_with("family|") { MIN_FAMILY_MEMBERS } else { MIN_GENERIC_MEMBERS };
+        s.members.len() >= min
+    });
+    suites
+}
+
+fn family(display_name: &str) -> Option<&'static str> {
+    let name = display_name.trim().to_lowercase();
+    FAMILIES
+        .iter()
+        .find(|(prefix, _)| name.starts_with(prefix))
+        .map(|(_, family)| *family)
+}
+
+/// The first two words of the name, if the app has a publisher and both
+/// are words rather than versions.
+fn product_words(app: &InstalledApp) -> Option<String> {
+    if app.publisher.trim().is_empty() {
+        return None;
+    }
+    let words: Vec<&str> = app.display_name.split_whitespace().take(2).collect();
+    let is_word = |w: &&str| w.chars().any(char::is_alphabetic) && !w.starts_with(|c: char| c.is_ascii_digit());
+    (words.len() == 2 && words.iter().all(is_word)).then(|| words.join(" "))
+}
@@ -1,19 +1,25 @@
-use crate::models::{EnabledStatus, RunState, Source, StartupEntry};
+use crate::models::{
+    EnabledStatus, LastRanSource, RunState, Source, StartupEntry, TaskConditions, TaskRegistration,
+};
+use crate::skipped::{SkippedItems, SkippedKind};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime};
 use windows::core::{Interface, BSTR};
 use windows::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
 };
+use windows::Win32::Foundation::VARIANT_BOOL;
 use windows::Win32::System::TaskScheduler::*;
 use windows::Win32::System::Variant::VARIANT;
+use windows::Win32::UI::Shell::SHLoadIndirectString;
 
-pub fn collect_task_scheduler_entries() -> Result<Vec<StartupEntry>> {
+/// Tasks and folders that can't be read are recorded in `skipped`.
+pub fn collect_task_scheduler_entries(skipped: &mut SkippedItems) -> Result<Vec<StartupEntry>> {
     unsafe {
         let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
     }
 
-    let result = unsafe { collect_inner() };
+    let result = unsafe { collect_inner(skipped) };
 
     unsafe {
         CoUninitialize();
@@ -22,7 +28,64 @@ pub fn collect_task_scheduler_entries() -> Result<Vec<StartupEntry>> {
     result
 }
 
-unsafe fn collect_inner() -> Result<Vec<StartupEntry>> {
+/// Change a task's power, idle, and network conditions by editing its
+/// definition's settings and registering it again.
+pub fn set_task_conditions(task_path: &str, conditions: &TaskConditions) -> Result<()> {
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+    }
+
+    let result = unsafe { set_conditions_inner(task_path, conditions) };
+
+    unsafe {
+        CoUninitialize();
+    }
+
+    result
+}
+
+unsafe fn set_conditions_inner(task_path: &str, conditions: &TaskConditions) -> Result<()> {
+    let service = connect()?;
+    let root_folder = service
+        .GetFolder(&BSTR::from("\\"))
+        .context("Failed to get root folder")?;
+    let task = root_folder
+        .GetTask(&BSTR::from(task_path))
+        .with_context(|| format!("Task '{}' not found", task_path))?;
+    let definition = task.Definition().context("Failed to read the task definition")?;
+    let settings = definition.Settings().context("Failed to read the task settings")?;
+
+    settings.SetDisallowStartIfOnBatteries(conditions.disallow_start_on_batteries.into())?;
+    settings.SetStopIfGoingOnBatteries(conditions.stop_on_batteries.into())?;
+    settings.SetRunOnlyIfIdle(conditions.run_only_if_idle.into())?;
+    settings.SetRunOnlyIfNetworkAvailable(conditions.run_only_if_network.into())?;
+    settings.SetWakeToRun(conditions.wake_to_run.into())?;
+    if conditions.run_only_if_idle {
+        let idle = settings.IdleSettings()?;
+        idle.SetIdleDuration(&BSTR::from(format!("PT{}M", conditions.idle_minutes.max(1))))?;
+    }
+
+    // Keep the existing principal; tasks that store a password can't be
+    // re-registered without it and fail here with access denied
+    let mut logon_type = TASK_LOGON_INTERACTIVE_TOKEN;
+    if let Ok(principal) = definition.Principal() {
+        let _ = principal.LogonType(&mut logon_type);
+    }
+    root_folder
+        .RegisterTaskDefinition(
+            &BSTR::from(task_path),
+            &definition,
+            TASK_UPDATE.0,
+            &VARIANT::default(),
+            &VARIANT::default(),
+            logon_type,
+            &VARIANT::default(),
+        )
+        .context("Failed to save the task")?;
+    Ok(())
+}
+
+unsafe fn connect() -> Result<ITaskService> {
     let service: ITaskService =
         CoCreateInstance(&TaskScheduler, None, CLSCTX_INPROC_SERVER)
             .context("Failed to create ITaskService")?;
@@ -35,29 +98,39 @@ unsafe fn collect_inner() -> Result<Vec<StartupEntry>> {
             &VARIANT::default(),
         )
         .context("Failed to connect to Task Scheduler")?;
+    Ok(service)
+}
 
+unsafe fn collect_inner(skipped: &mut SkippedItems) -> Result<Vec<StartupEntry>> {
+    let service = connect()?;
     let root_folder = service
         .GetFolder(&BSTR::from("\\"))
         .context("Failed to get root folder")?;
 
     let mut entries = Vec::new();
-    enumerate_folder(&root_folder, &mut entries);
+    enumerate_folder(&root_folder, &mut entries, skipped);
     Ok(entries)
 }
 
-unsafe fn enumerate_folder(folder: &ITaskFolder, entries: &mut Vec<StartupEntry>) {
+unsafe fn enumerate_folder(folder: &ITaskFolder, entries: &mut Vec<StartupEntry>, skipped: &mut SkippedItems) {
     // Process tasks in this folder
-    if let Ok(tasks) = folder.GetTasks(0) {
-        if let Ok(count) = tasks.Count() {
-            for i in 1..=count {
-                let index = VARIANT::from(i);
-                if let Ok(task) = tasks.get_Item(&index) {
-                    if let Some(entry) = process_task(&task) {
-                        entries.push(entry);
+    match folder.GetTasks(0) {
+        Ok(tasks) => {
+            if let Ok(count) = tasks.Count() {
+                for i in 1..=count {
+                    let index = VARIANT::from(i);
+                    if let Ok(task) = tasks.get_Item(&index) {
+                        if let Some(entry) = process_task(&task, skipped) {
+                            entries.push(entry);
+                        }
                     }
                 }
             }
         }
+        Err(e) => {
+            let path = folder.Path().map(|p| p.to_string()).unwrap_or_default();
+            skipped.add_com(SkippedKind::TaskFolder, path, &e);
+        }
     }
 
     // Recurse into subfolders
@@ -66,15 +139,22 @@ unsafe fn enumerate_folder(folder: &ITaskFolder, entries: &mut Vec<StartupEntry>
             for i in 1..=count {
                 let index = VARIANT::from(i);
                 if let Ok(subfolder) = folders.get_Item(&index) {
-                    enumerate_folder(&subfolder, entries);
+                    enumerate_folder(&subfolder, entries, skipped);
                 }
             }
         }
     }
 }
 
-unsafe fn process_task(task: &IRegisteredTask) -> Option<StartupEntry> {
-    let definition = task.Definition().ok()?;
+unsafe fn process_task(task: &IRegisteredTask, skipped: &mut SkippedItems) -> Option<StartupEntry> {
+    let definition = match task.Definition() {
+        Ok(definition) => definition,
+        Err(e) => {
+            let path = task.Path().map(|p| p.to_string()).unwrap_or_default();
+            skipped.add_com(SkippedKind::Task, path, &e);
+            return None;
+        }
+    };
 
     // Check if this task has a logon trigger
     let triggers = definition.Triggers().ok()?;
@@ -136,13 +216,131 @@ unsafe fn process_task(task: &IRegisteredTask) -> Option<StartupEntry> {
 
     let mut entry = StartupEntry::new(name, command, source);
     entry.enabled = enabled;
+    entry.last_ran_source = last_ran.map(|_| LastRanSource::TaskScheduler);
     entry.last_ran = last_ran;
     entry.run_state = RunState::Stopped;
     entry.runs_as = runs_as;
+    entry.task_registration = get_task_registration(&definition);
+    entry.task_conditions = get_task_conditions(&definition);
 
     Some(entry)
 }
 
+unsafe fn get_task_registration(definition: &ITaskDefinition) -> Option<TaskRegistration> {
+    let info = definition.RegistrationInfo().ok()?;
+    let read = |f: &dyn Fn(*mut BSTR) -> windows::core::Result<()>| {
+        let mut value = BSTR::default();
+        match f(&mut value) {
+            Ok(()) => resolve_indirect_string(value.to_string().trim()),
+            Err(_) => String::new(),
+        }
+    };
+    let author = read(&|p| info.Author(p));
+    let date = read(&|p| info.Date(p));
+    let description = read(&|p| info.Description(p));
+
+    let registration = TaskRegistration {
+        author,
+        registered: parse_task_date(&date),
+        description,
+    };
+    if registration == TaskRegistration::default() {
+        None
+    } else {
+        Some(registration)
+    }
+}
+
+unsafe fn get_task_conditions(definition: &ITaskDefinition) -> Option<TaskConditions> {
+    let settings = definition.Settings().ok()?;
+    let read = |f: &dyn Fn(*mut VARIANT_BOOL) -> windows::core::Result<()>| {
+        let mut value = VARIANT_BOOL::default();
+        f(&mut value).is_ok() && value.as_bool()
+    };
+    let idle_minutes = settings
+        .IdleSettings()
+        .ok()
+        .and_then(|idle| {
+            let mut duration = BSTR::default();
+            idle.IdleDuration(&mut duration).ok()?;
+            parse_duration_minutes(&duration.to_string())
+        })
+        .unwrap_or(10);
+
+    Some(TaskConditions {
+        disallow_start_on_batteries: read(&|p| settings.DisallowStartIfOnBatteries(p)),
+        stop_on_batteries: read(&|p| settings.StopIfGoingOnBatteries(p)),
+        run_only_if_idle: read(&|p| settings.RunOnlyIfIdle(p)),
+        idle_minutes,
+        run_only_if_network: read(&|p| settings.RunOnlyIfNetworkAvailable(p)),
+        wake_to_run: read(&|p| settings.WakeToRun(p)),
+    })
+}
+
+/// Whole minutes in an ISO 8601 duration like `PT10M` or `PT1H30M`.
+fn parse_duration_minutes(duration: &str) -> Option<u32> {
+    let time = duration.strip_prefix("PT")?;
+    let mut minutes = 0;
+    let mut number = String::new();
+    for c in time.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let n: u32 = number.parse().ok()?;
+        number.clear();
+        match c {
+            'H' => minutes += n * 60,
+            'M' => minutes += n,
+            'S' => minutes += n / 60,
+            _ => return None,
+        }
+    }
+    Some(minutes)
+}
+
+/// Windows' own tasks store author/description as resource references like
+/// `$(@%SystemRoot%\system32\wininet.dll,-16000)`; load the actual text.
+fn resolve_indirect_string(value: &str) -> String {
+    let Some(reference) = value.strip_prefix("$(").and_then(|v| v.strip_suffix(')')) else {
+        return value.to_string();
+    };
+    if !reference.starts_with('@') {
+        return value.to_string();
+    }
+    let source: Vec<u16> = reference.encode_utf16().chain(std::iter::once(0)).collect();
+    let mut buffer = [0u16; 1024];
+    let loaded = unsafe {
+        SHLoadIndirectString(windows::core::PCWSTR(source.as_ptr()), &mut buffer, None)
+    };
+    match loaded {
+        Ok(()) => {
+            let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+            String::from_utf16_lossy(&buffer[..len])
+        }
+        Err(_) => value.to_string(),
+    }
+}
+
+/// Parse a task's registration date. The XML schema uses ISO 8601, with or
+/// without fractional seconds and a UTC offset.
+fn parse_task_date(date: &str) -> Option<DateTime<Local>> {
+    if date.is_empty() {
+        return None;
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(date) {
+        return Some(dt.with_timezone(&Local));
+    }
+    if let Ok(naive) = NaiveDateTime::parse_from_str(date, "%Y-%m-%dT%H:%M:%S%.f") {
+        return naive.and_local_timezone(Local).single();
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(0, 0, 0)?
+        .and_local_timezone(Local)
+        .single()
+}
+
 unsafe fn get_task_user(definition: &ITaskDefinition) -> String {
     if let Ok(principal) = definition.Principal() {
         let mut user_id = BSTR::default();
@@ -0,0 +1,91 @@
+//! Progress and overlay badge on the app's taskbar button (`ITaskbarList3`),
+//! so long operations and new alerts are visible while the window is
+//! minimized or behind others.
+
+use eframe::egui;
+use raw_window_handle::{HasWindowHandle, RawWindowHandle};
+use windows::core::{w, PCWSTR};
+use windows::Win32::Foundation::HWND;
+use windows::Win32::System::Com::{CoCreateInstance, CoInitializeEx, CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED};
+use windows::Win32::UI::Shell::{
+    ITaskbarList3, TaskbarList, TBPF_INDETERMINATE, TBPF_NOPROGRESS, TBPF_NORMAL,
+};
+use windows::Win32::UI::WindowsAndMessaging::{LoadIconW, HICON, IDI_WARNING};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Progress {
+    None,
+    /// A marquee for operations with no known length.
+    Indeterminate,
+    /// Completed and total steps.
+    Value(u64, u64),
+}
+
+pub struct Taskbar {
+    list: ITaskbarList3,
+    hwnd: HWND,
+    /// Last state applied successfully; calls are skipped while unchanged.
+    progress: Option<Progress>,
+    overlay: Option<bool>,
+}
+
+impl Taskbar {
+    /// Connect to the taskbar button of the app's window. `None` if the
+    /// window handle or the COM object isn't available.
+    pub fn new(frame: &eframe::Frame) -> Option<Self> {
+        let RawWindowHandle::Win32(handle) = frame.window_handle().ok()?.as_raw() else {
+            return None;
+        };
+        let hwnd = HWND(handle.hwnd.get() as *mut _);
+        // winit already initialized COM (OLE) on the UI thread; this is a no-op then
+        let _ = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+        let list: ITaskbarList3 = unsafe { CoCreateInstance(&TaskbarList, None, CLSCTX_INPROC_SERVER) }.ok()?;
+        unsafe { list.HrInit() }.ok()?;
+        Some(Self {
+            list,
+            hwnd,
+            progress: None,
+            overlay: None,
+        })
+    }
+
+    pub fn set_progress(&mut self, progress: Progress) {
+        if self.progress == Some(progress) {
+            return;
+        }
+        let applied = unsafe {
+            match progress {
+                Progress::None => self.list.SetProgressState(self.hwnd, TBPF_NOPROGRESS),
+                Progress::Indeterminate => self.list.SetProgressState(self.hwnd, TBPF_INDETERMINATE),
+                Progress::Value(done, total) => self
+                    .list
+                    .SetProgressState(self.hwnd, TBPF_NORMAL)
+                    .and_then(|_| self.list.SetProgressValue(self.hwnd, done, total.max(1))),
+            }
+        };
+        // The button may not exist yet right after startup; retry next frame
+        self.progress = applied.is_ok().then_some(progress);
+    }
+
+    /// Show or clear the warning overlay on the taskbar button.
+    pub fn set_overlay(&mut self, on: bool, description: &str) {
+        if self.overlay == Some(on) {
+            return;
+        }
+        let applied = unsafe {
+            if on {
+                let icon = LoadIconW(None, IDI_WARNING).unwrap_or_default();
+                let text: Vec<u16> = description.encode_utf16().chain(std::iter::once(0)).collect();
+                self.list.SetOverlayIcon(self.hwnd, icon, PCWSTR(text.as_ptr()))
+            } else {
+                self.list.SetOverlayIcon(self.hwnd, HICON::default(), w!(""))
+            }
+        };
+        self.overlay = applied.is_ok().then_some(on);
+    }
+}
+
+/// Whether the app's window is currently minimized.
+pub fn is_minimized(ctx: &egui::Context) -> bool {
+    ctx.input(|i| i.viewport().minimized.unwrap_or(false))
+}
@@ -0,0 +1,194 @@
+//! Launching uninstall/modify commands elevated and watching them finish.
+//!
+//! Many uninstallers (NSIS, Inno Setup, some MSI bootstrappers) copy themselves
+//! to %TEMP% and re-launch, so the process we start can exit long before the
+//! uninstall is done. Instead of polling the registry we keep the launched
+//! process handle and follow its whole process tree until every member exits.
+
+use crate::cmdline;
+use std::collections::HashMap;
+use std::os::windows::ffi::OsStrExt;
+use std::time::{Duration, Instant};
+use sysinfo::System;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE, STILL_ACTIVE};
+use windows::Win32::System::Threading::{GetExitCodeProcess, GetProcessId};
+use windows::Win32::UI::Shell::{
+    ShellExecuteExW, SEE_MASK_NOASYNC, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
+};
+
+/// How often the process tree is re-scanned while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Give up waiting after this long (matches the old registry-polling limit).
+const WAIT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// A process started through `ShellExecuteExW`. The handle is closed on drop.
+pub struct LaunchedProcess {
+    handle: HANDLE,
+    pid: u32,
+}
+
+// The handle is only a kernel object reference; it is safe to move it to the
+// watcher thread.
+unsafe impl Send for LaunchedProcess {}
+
+impl Drop for LaunchedProcess {
+    fn drop(&mut self) {
+        if !self.handle.is_invalid() {
+            let _ = unsafe { CloseHandle(self.handle) };
+        }
+    }
+}
+
+/// How a watched uninstaller finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UninstallOutcome {
+    /// The launched process and all of its descendants exited. Carries the
+    /// exit code of the launched process.
+    Exited(u32),
+    /// Still running when the wait limit was reached.
+    TimedOut,
+    /// The shell did not hand back a process handle (e.g. the command was
+    /// forwarded to an already-running process), so completion is unknown.
+    Untracked,
+}
+
+impl UninstallOutcome {
+    /// Short human-readable description for the status bar.
+    pub fn describe(&self) -> String {
+        match self {
+            UninstallOutcome::Exited(0) => "finished".to_string(),
+            // Standard Windows Installer codes that uninstallers commonly return
+            UninstallOutcome::Exited(1602) => "was cancelled (exit code 1602)".to_string(),
+            UninstallOutcome::Exited(1641) | UninstallOutcome::Exited(3010) => {
+                "finished, restart required".to_string()
+            }
+            UninstallOutcome::Exited(code) => format!("failed (exit code {})", code),
+            UninstallOutcome::TimedOut => "is still running after 10 minutes".to_string(),
+            UninstallOutcome::Untracked => "was launched".to_string(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, UninstallOutcome::Exited(code) if !matches!(code, 0 | 1641 | 3010))
+    }
+}
+
+/// Run a shell command string (like an uninstall or modify path) elevated via
+/// `ShellExecuteExW`, keeping the process handle when the shell provides one.
+pub fn launch_elevated(command: &str) -> Result<Option<LaunchedProcess>, String> {
+    let (exe, args) = cmdline::split(command);
+
+    let exe_wide = to_wide(&exe);
+    let args_wide = to_wide(&args);
+    let verb_wide = to_wide("runas");
+
+    let mut info = SHELLEXECUTEINFOW {
+        cbSize: std::mem::size_of::<SHELLEXECUTEINFOW>() as u32,
+        fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_NOASYNC,
+        lpVerb: PCWSTR(verb_wide.as_ptr()),
+        lpFile: PCWSTR(exe_wide.as_ptr()),
+        lpParameters: PCWSTR(args_wide.as_ptr()),
+        nShow: windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+
+    unsafe { ShellExecuteExW(&mut info) }
+        .map_err(|e| format!("ShellExecuteEx failed ({}): {}", e.message(), exe))?;
+
+    if info.hProcess.is_invalid() {
+        return Ok(None);
+    }
+    let pid = unsafe { GetProcessId(info.hProcess) };
+    Ok(Some(LaunchedProcess {
+        handle: info.hProcess,
+        pid,
+    }))
+}
+
+/// Block until the launched process and every process it spawned have exited.
+///
+/// Descendants are discovered by parent PID on each scan; a child's start time
+/// must not precede its parent's, so a recycled PID isn't mistaken for a child.
+pub fn wait_for_tree(process: LaunchedProcess) -> UninstallOutcome {
+    let started = Instant::now();
+    let mut sys = System::new();
+    // pid -> start time (0 when unknown)
+    let mut tracked: HashMap<u32, u64> = HashMap::new();
+    tracked.insert(process.pid, 0);
+
+    loop {
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+
+        if let Some(start) = tracked.get_mut(&process.pid) {
+            if *start == 0 {
+                if let Some(p) = sys.process(sysinfo::Pid::from_u32(process.pid)) {
+                    *start = p.start_time();
+                }
+            }
+        }
+
+        // Adopt new descendants until nothing changes (grandchildren may
+        // appear in the same scan as their parent).
+        loop {
+            let mut added = false;
+            for (pid, proc) in sys.processes() {
+                let pid = pid.as_u32();
+                if tracked.contains_key(&pid) {
+                    continue;
+                }
+                let Some(parent) = proc.parent().map(|p| p.as_u32()) else {
+                    continue;
+                };
+                let Some(&parent_start) = tracked.get(&parent) else {
+                    continue;
+                };
+                let start = proc.start_time();
+                if parent_start == 0 || start == 0 || start >= parent_start {
+                    tracked.insert(pid, start);
+                    added = true;
+                }
+            }
+            if !added {
+                break;
+            }
+        }
+
+        // Drop members that have exited (or whose PID now belongs to a newer process)
+        tracked.retain(|pid, start| {
+            sys.process(sysinfo::Pid::from_u32(*pid))
+                .is_some_and(|p| *start == 0 || p.start_time() == *start)
+        });
+
+        let root_running = exit_code(&process).is_none();
+        if !root_running {
+            tracked.remove(&process.pid);
+        }
+        if tracked.is_empty() {
+            return UninstallOutcome::Exited(exit_code(&process).unwrap_or(0));
+        }
+        if started.elapsed() >= WAIT_TIMEOUT {
+            return UninstallOutcome::TimedOut;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Exit code of the launched process, or `None` while it is still running.
+fn exit_code(process: &LaunchedProcess) -> Option<u32> {
+    let mut code: u32 = 0;
+    unsafe { GetExitCodeProcess(process.handle, &mut code) }.ok()?;
+    if code == STILL_ACTIVE.0 as u32 {
+        None
+    } else {
+        Some(code)
+    }
+}
+
+fn to_wide(s: &str) -> Vec<u16> {
+    std::ffi::OsStr::new(s)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect()
+}
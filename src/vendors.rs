@@ -0,0 +1,93 @@
+//! Per-vendor footprint: installed apps, services, startup entries, and running
+//! processes grouped by the company that publishes them.
+
+use crate::models::{InstalledApp, ProcessInfo, StartupEntry};
+use std::collections::HashMap;
+
+/// Legal-form suffixes dropped when matching vendor names, so "Contoso Inc."
+/// and "Contoso, Inc" count as one vendor.
+const COMPANY_SUFFIXES: &[&str] = &[
+    "inc", "incorporated", "corporation", "corp", "llc", "ltd", "limited", "gmbh", "co", "ag", "sa", "bv",
+];
+
+/// Everything one vendor has on the machine.
+#[derive(Debug, Clone, Default)]
+pub struct VendorFootprint {
+    /// Vendor name as first seen; empty when the binaries carry no company.
+    pub vendor: String,
+    pub installed_apps: usize,
+    pub services: usize,
+    pub running_services: usize,
+    pub startup_entries: usize,
+    pub processes: usize,
+    /// Working set of the vendor's running processes.
+    pub memory_bytes: u64,
+}
+
+impl VendorFootprint {
+    /// Autostarting items: services plus startup entries.
+    pub fn autostart_count(&self) -> usize {
+        self.services + self.startup_entries
+    }
+}
+
+/// Group everything by vendor, largest memory footprint first.
+pub fn summarize(
+    installed: &[InstalledApp],
+    startup: &[StartupEntry],
+    services: &[StartupEntry],
+    processes: &[ProcessInfo],
+) -> Vec<VendorFootprint> {
+    let mut by_key: HashMap<String, VendorFootprint> = HashMap::new();
+
+    for app in installed {
+        vendor_entry(&mut by_key, &app.publisher).installed_apps += 1;
+    }
+    for e in startup {
+        vendor_entry(&mut by_key, &e.company_name).startup_entries += 1;
+    }
+    for e in services {
+        let vendor = vendor_entry(&mut by_key, &e.company_name);
+        vendor.services += 1;
+        if e.run_state == crate::models::RunState::Running {
+            vendor.running_services += 1;
+        }
+    }
+    for p in processes {
+        let vendor = vendor_entry(&mut by_key, &p.company_name);
+        vendor.processes += 1;
+        vendor.memory_bytes += p.memory_bytes;
+    }
+
+    let mut vendors: Vec<VendorFootprint> = by_key.into_values().collect();
+    vendors.sort_by(|a, b| {
+        b.memory_bytes
+            .cmp(&a.memory_bytes)
+            .then(b.autostart_count().cmp(&a.autostart_count()))
+            .then(a.vendor.to_lowercase().cmp(&b.vendor.to_lowercase()))
+    });
+    vendors
+}
+
+fn vendor_entry<'a>(by_key: &'a mut HashMap<String, VendorFootprint>, name: &str) -> &'a mut VendorFootprint {
+    let name = name.trim();
+    by_key.entry(vendor_key(name)).or_insert_with(|| VendorFootprint {
+        vendor: name.to_string(),
+        ..Default::default()
+    })
+}
+
+/// Case-insensitive key with punctuation and legal-form suffixes removed.
+pub fn vendor_key(name: &str) -> String {
+    let mut words: Vec<String> = name
+        .to_lowercase()
+        .replace(['\u{00ae}', '\u{2122}'], "")
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|w| w.trim_matches('.').replace('.', ""))
+        .filter(|w| !w.is_empty())
+        .collect();
+    while words.len() > 1 && words.last().is_some_and(|w| COMPANY_SUFFIXES.contains(&w.as_str())) {
+        words.pop();
+    }
+    words.join(" ")
+}
@@ -1,27 +1,195 @@
+use crate::cmdline;
+use crate::models::{Bitness, LinkTime};
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::os::windows::ffi::OsStrExt;
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
 use windows::core::PCWSTR;
 use windows::Win32::Storage::FileSystem::{
     GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW,
 };
 
+/// Version strings of one file.
+#[derive(Debug, Clone, Default)]
+struct VersionStrings {
+    product_name: Option<String>,
+    company_name: Option<String>,
+}
+
+/// Version strings already read, keyed by resolved path and file modification
+/// time (so an updated binary is re-read). Dozens of processes and services
+/// share a handful of binaries (svchost.exe alone runs ~80 times), and the
+/// process list is re-collected every few seconds.
+type VersionCache = Mutex<HashMap<(String, Option<SystemTime>), VersionStrings>>;
+
+fn cache() -> &'static VersionCache {
+    static CACHE: OnceLock<VersionCache> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
 /// Extract the "Product Name" from a PE file's version resource.
 /// Returns `None` if the file has no version info or the field is missing.
 pub fn get_product_name(exe_path: &str) -> Option<String> {
+    version_strings(exe_path).product_name
+}
+
+/// Extract the "Company Name" (the vendor) from a PE file's version resource.
+pub fn get_company_name(exe_path: &str) -> Option<String> {
+    version_strings(exe_path).company_name
+}
+
+fn version_strings(exe_path: &str) -> VersionStrings {
+    if exe_path.is_empty() {
+        return VersionStrings::default();
+    }
+
+    // Resolve the file that carries the code (executable, or the DLL for
+    // rundll32 commands) with environment variables expanded
+    let path = cmdline::target_path(exe_path);
+
+    let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let key = (path.to_lowercase(), modified);
+    if let Some(cached) = cache().lock().ok().and_then(|c| c.get(&key).cloned()) {
+        return cached;
+    }
+
+    let strings = read_version_strings(&path);
+    if let Ok(mut c) = cache().lock() {
+        c.insert(key, strings.clone());
+    }
+    strings
+}
+
+/// What a PE file's headers say about it.
+#[derive(Debug, Clone, Copy, Default)]
+struct PeHeader {
+    bitness: Option<Bitness>,
+    link_time: Option<LinkTime>,
+}
+
+/// Bitness of the PE file a command points at, from the machine type in its
+/// COFF header. Cached like product names.
+pub fn get_bitness(exe_path: &str) -> Option<Bitness> {
+    pe_header(exe_path).bitness
+}
+
+/// When the PE file a command points at was linked. `None` if the header
+/// has no time (some packers zero it).
+pub fn get_link_time(exe_path: &str) -> Option<LinkTime> {
+    pe_header(exe_path).link_time
+}
+
+fn pe_header(exe_path: &str) -> PeHeader {
+    type PeHeaderCache = Mutex<HashMap<(String, Option<SystemTime>), PeHeader>>;
+    static CACHE: OnceLock<PeHeaderCache> = OnceLock::new();
+
     if exe_path.is_empty() {
+        return PeHeader::default();
+    }
+    let path = cmdline::target_path(exe_path);
+    let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+    let key = (path.to_lowercase(), modified);
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(cached) = cache.lock().ok().and_then(|c| c.get(&key).copied()) {
+        return cached;
+    }
+
+    let header = read_pe_header(&path).unwrap_or_default();
+    if let Ok(mut c) = cache.lock() {
+        c.insert(key, header);
+    }
+    header
+}
+
+fn read_pe_header(path: &str) -> Option<PeHeader> {
+    use std::io::Read;
+
+    let mut header = [0u8; 4096];
+    let mut file = std::fs::File::open(path).ok()?;
+    let len = file.read(&mut header).ok()?;
+    let header = &header[..len];
+    if !header.starts_with(b"MZ") || header.len() < 0x40 {
+        return None;
+    }
+    let pe_offset = u32::from_le_bytes(header[0x3C..0x40].try_into().ok()?) as usize;
+    // The signature, then the 20-byte COFF header
+    let pe = header.get(pe_offset..pe_offset + 24)?;
+    if &pe[..4] != b"PE\0\0" {
         return None;
     }
+    let bitness = match u16::from_le_bytes([pe[4], pe[5]]) {
+        0x014C | 0x01C4 => Some(Bitness::Bit32), // x86, ARM Thumb-2
+        0x8664 | 0xAA64 => Some(Bitness::Bit64), // x64, ARM64
+        _ => None,
+    };
+    let timestamp = u32::from_le_bytes([pe[8], pe[9], pe[10], pe[11]]);
+    let link_time = if is_reproducible(&mut file, header, pe_offset + 24).unwrap_or(false) {
+        Some(LinkTime::Reproducible)
+    } else {
+        (timestamp != 0)
+            .then(|| chrono::DateTime::from_timestamp(i64::from(timestamp), 0))
+            .flatten()
+            .map(|utc| LinkTime::At(utc.with_timezone(&chrono::Local)))
+    };
+    Some(PeHeader { bitness, link_time })
+}
 
-    // Expand environment variables like %SystemRoot%
-    let expanded = expand_env_vars(exe_path);
+/// Whether the debug directory has an `IMAGE_DEBUG_TYPE_REPRO` entry, which
+/// means `TimeDateStamp` is a hash. `header` is the start of the file and
+/// `optional` the offset of the optional header.
+fn is_reproducible(file: &mut std::fs::File, header: &[u8], optional: usize) -> Option<bool> {
+    use std::io::{Read, Seek, SeekFrom};
+    const DEBUG_DIRECTORY: usize = 6;
+    const DEBUG_TYPE_REPRO: u32 = 16;
 
-    // Strip quotes if present
-    let clean = expanded.trim().trim_matches('"');
+    let u16_at = |offset: usize| header.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let u32_at = |offset: usize| {
+        header
+            .get(offset..offset + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    };
+    let sections = u16_at(optional - 18)? as usize;
+    let optional_size = u16_at(optional - 4)? as usize;
+    // Data directories follow the PE32 or PE32+ fields
+    let (count_offset, directories) = match u16_at(optional)? {
+        0x10B => (optional + 92, optional + 96),
+        0x20B => (optional + 108, optional + 112),
+        _ => return None,
+    };
+    if (u32_at(count_offset)? as usize) <= DEBUG_DIRECTORY {
+        return Some(false);
+    }
+    let debug_rva = u32_at(directories + DEBUG_DIRECTORY * 8)?;
+    let debug_size = u32_at(directories + DEBUG_DIRECTORY * 8 + 4)? as usize;
+    if debug_rva == 0 || debug_size == 0 {
+        return Some(false);
+    }
 
-    // If the path contains arguments, extract just the executable path
-    let path = extract_path(clean);
+    // Find the section holding the directory to get its file offset
+    let section_table = optional + optional_size;
+    let file_offset = (0..sections).find_map(|i| {
+        let section = section_table + i * 40;
+        let virtual_size = u32_at(section + 8)?;
+        let virtual_address = u32_at(section + 12)?;
+        let raw_pointer = u32_at(section + 20)?;
+        (debug_rva >= virtual_address && debug_rva - virtual_address < virtual_size)
+            .then(|| u64::from(debug_rva - virtual_address) + u64::from(raw_pointer))
+    })?;
 
-    let wide_path: Vec<u16> = OsStr::new(&path)
+    // 28 bytes per entry; a handful is all real binaries have
+    let mut entries = vec![0u8; debug_size.min(28 * 32)];
+    file.seek(SeekFrom::Start(file_offset)).ok()?;
+    file.read_exact(&mut entries).ok()?;
+    Some(
+        entries
+            .chunks_exact(28)
+            .any(|entry| u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]) == DEBUG_TYPE_REPRO),
+    )
+}
+
+fn read_version_strings(path: &str) -> VersionStrings {
+    let wide_path: Vec<u16> = OsStr::new(path)
         .encode_wide()
         .chain(std::iter::once(0))
         .collect();
@@ -31,18 +199,21 @@ pub fn get_product_name(exe_path: &str) -> Option<String> {
         let mut handle: u32 = 0;
         let size = GetFileVersionInfoSizeW(PCWSTR(wide_path.as_ptr()), Some(&mut handle));
         if size == 0 {
-            return None;
+            return VersionStrings::default();
         }
 
         // Allocate and fill buffer
         let mut buffer = vec![0u8; size as usize];
-        GetFileVersionInfoW(
+        if GetFileVersionInfoW(
             PCWSTR(wide_path.as_ptr()),
             Some(handle),
             size,
             buffer.as_mut_ptr() as *mut _,
         )
-        .ok()?;
+        .is_err()
+        {
+            return VersionStrings::default();
+        }
 
         // Query translation table to get language and codepage
         let translation_query: Vec<u16> = OsStr::new("\\VarFileInfo\\Translation")
@@ -62,23 +233,32 @@ pub fn get_product_name(exe_path: &str) -> Option<String> {
 
         if !ok.as_bool() || trans_ptr.is_null() || trans_len < 4 {
             // No translation table — try the common US English / Unicode codepage
-            return query_product_name(&buffer, 0x0409, 0x04B0)
-                .or_else(|| query_product_name(&buffer, 0x0409, 0x04E4))
-                .or_else(|| query_product_name(&buffer, 0x0000, 0x04B0));
+            let query = |field| {
+                query_string(&buffer, 0x0409, 0x04B0, field)
+                    .or_else(|| query_string(&buffer, 0x0409, 0x04E4, field))
+                    .or_else(|| query_string(&buffer, 0x0000, 0x04B0, field))
+            };
+            return VersionStrings {
+                product_name: query("ProductName"),
+                company_name: query("CompanyName"),
+            };
         }
 
         // Read the first translation entry (language, codepage)
         let lang = *(trans_ptr as *const u16);
         let codepage = *((trans_ptr as *const u16).add(1));
 
-        query_product_name(&buffer, lang, codepage)
+        VersionStrings {
+            product_name: query_string(&buffer, lang, codepage, "ProductName"),
+            company_name: query_string(&buffer, lang, codepage, "CompanyName"),
+        }
     }
 }
 
-unsafe fn query_product_name(buffer: &[u8], lang: u16, codepage: u16) -> Option<String> {
+unsafe fn query_string(buffer: &[u8], lang: u16, codepage: u16, field: &str) -> Option<String> {
     let query = format!(
-        "\\StringFileInfo\\{:04x}{:04x}\\ProductName",
-        lang, codepage
+        "\\StringFileInfo\\{:04x}{:04x}\\{}",
+        lang, codepage, field
     );
     let wide_query: Vec<u16> = OsStr::new(&query)
         .encode_wide()
@@ -109,51 +289,3 @@ unsafe fn query_product_name(buffer: &[u8], lang: u16, codepage: u16) -> Option<
     let s = String::from_utf16_lossy(trimmed).trim().to_string();
     if s.is_empty() { None } else { Some(s) }
 }
-
-/// Extract the executable path portion from a command string.
-/// Handles quoted paths and paths with arguments.
-fn extract_path(s: &str) -> String {
-    let s = s.trim();
-    if s.is_empty() {
-        return String::new();
-    }
-
-    // If it starts with a quote, take up to the closing quote
-    if let Some(stripped) = s.strip_prefix('"') {
-        if let Some(end) = stripped.find('"') {
-            return stripped[..end].to_string();
-        }
-        return stripped.to_string();
-    }
-
-    // If the path has a known executable extension, find where it ends
-    let lower = s.to_lowercase();
-    for ext in &[".exe", ".dll", ".sys", ".ocx"] {
-        if let Some(pos) = lower.find(ext) {
-            return s[..pos + ext.len()].to_string();
-        }
-    }
-
-    // Fall back to first whitespace-delimited token
-    s.split_whitespace()
-        .next()
-        .unwrap_or(s)
-        .to_string()
-}
-
-fn expand_env_vars(s: &str) -> String {
-    let mut result = s.to_string();
-    while let Some(start) = result.find('%') {
-        if let Some(end) = result[start + 1..].find('%') {
-            let var_name = &result[start + 1..start + 1 + end];
-            if let Ok(value) = std::env::var(var_name) {
-                result = format!("{}{}{}", &result[..start], value, &result[start + 2 + end..]);
-            } else {
-                break;
-            }
-        } else {
-            break;
-        }
-    }
-    result
-}
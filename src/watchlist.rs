@@ -0,0 +1,238 @@
+//! Entries the user disabled or deleted with App Manager, remembered across
+//! sessions so that an application quietly re-enabling or re-creating its
+//! autorun is noticed on the next scan.
+//!
+//! Stored as a tab-separated text file in the app data directory:
+//! `<entry key>\t<disabled|deleted>\t<keep>\t<name>`, where `keep` is `1` for
+//! entries with a keep-disabled rule (re-disabled automatically).
+
+use crate::app_data;
+use crate::history::entry_key;
+use crate::models::{EnabledStatus, EntryId, StartupEntry};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+const FILE_NAME: &str = "watched-entries.tsv";
+const HEADER: &str = "# App Manager watched entries v1";
+
+/// What the user last did to a watched entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchedState {
+    Disabled,
+    Deleted,
+}
+
+impl WatchedState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            WatchedState::Disabled => "disabled",
+            WatchedState::Deleted => "deleted",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct WatchedEntry {
+    name: String,
+    state: WatchedState,
+    keep_disabled: bool,
+}
+
+/// An entry the user asked to always keep disabled.
+#[derive(Debug, Clone)]
+pub struct KeepDisabledRule {
+    pub key: String,
+    pub name: String,
+}
+
+/// How a watched entry came back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReversionKind {
+    ReEnabled,
+    ReCreated,
+}
+
+/// A watched entry found enabled again by a scan.
+#[derive(Debug, Clone)]
+pub struct Reversion {
+    pub id: EntryId,
+    pub name: String,
+    pub location: String,
+    pub kind: ReversionKind,
+    /// The user asked for this entry to always be kept disabled.
+    pub keep_disabled: bool,
+}
+
+impl Reversion {
+    pub fn description(&self) -> &'static str {
+        match self.kind {
+            ReversionKind::ReEnabled => "Re-enabled",
+            ReversionKind::ReCreated => "Re-created",
+        }
+    }
+}
+
+pub struct Watchlist {
+    path: Option<PathBuf>,
+    entries: HashMap<String, WatchedEntry>,
+    /// Keys already reported this session, so each reversion alerts once.
+    reported: HashSet<String>,
+}
+
+impl Watchlist {
+    /// Load the watch file, starting empty if it doesn't exist or can't be read.
+    pub fn load() -> Self {
+        let path = app_data::data_dir().map(|dir| dir.join(FILE_NAME));
+        let mut entries = HashMap::new();
+        if let Some(text) = path.as_ref().and_then(|p| std::fs::read_to_string(p).ok()) {
+            for line in text.lines() {
+                if line.starts_with('#') {
+                    continue;
+                }
+                let mut fields = line.splitn(4, '\t');
+                let (Some(key), Some(state), Some(keep), Some(name)) =
+                    (fields.next(), fields.next(), fields.next(), fields.next())
+                else {
+                    continue;
+                };
+                let state = match state {
+                    "disabled" => WatchedState::Disabled,
+                    "deleted" => WatchedState::Deleted,
+                    _ => continue,
+                };
+                entries.insert(
+                    key.to_string(),
+                    WatchedEntry {
+                        name: name.to_string(),
+                        state,
+                        keep_disabled: keep == "1",
+                    },
+                );
+            }
+        }
+        Self {
+            path,
+            entries,
+            reported: HashSet::new(),
+        }
+    }
+
+    /// Remember that the user disabled or deleted `entry`. A keep-disabled
+    /// rule already set for it is kept.
+    pub fn record(&mut self, entry: &StartupEntry, state: WatchedState) {
+        let key = entry_key(&entry.id());
+        self.reported.remove(&key);
+        let keep_disabled = self.entries.get(&key).is_some_and(|w| w.keep_disabled);
+        self.entries.insert(
+            key,
+            WatchedEntry {
+                name: entry.name.replace(['\t', '\n', '\r'], " "),
+                state,
+                keep_disabled,
+            },
+        );
+        self.save();
+    }
+
+    /// Stop watching an entry (the user enabled it, or accepted it coming back).
+    pub fn forget(&mut self, id: &EntryId) {
+        if self.entries.remove(&entry_key(id)).is_some() {
+            self.save();
+        }
+    }
+
+    /// Turn the keep-disabled rule for a watched entry on or off.
+    pub fn set_keep_disabled(&mut self, id: &EntryId, keep: bool) {
+        if let Some(watched) = self.entries.get_mut(&entry_key(id)) {
+            watched.keep_disabled = keep;
+            self.save();
+        }
+    }
+
+    /// Keys of entries with a keep-disabled rule.
+    pub fn keep_disabled_keys(&self) -> HashSet<String> {
+        self.entries
+            .iter()
+            .filter(|(_, w)| w.keep_disabled)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+
+    /// All keep-disabled rules, sorted by entry name.
+    pub fn keep_disabled_rules(&self) -> Vec<KeepDisabledRule> {
+        let mut rules: Vec<KeepDisabledRule> = self
+            .entries
+            .iter()
+            .filter(|(_, w)| w.keep_disabled)
+            .map(|(key, w)| KeepDisabledRule {
+                key: key.clone(),
+                name: w.name.clone(),
+            })
+            .collect();
+        rules.sort_by_key(|r| r.name.to_lowercase());
+        rules
+    }
+
+    /// Drop the keep-disabled rule for an entry; it stays watched.
+    pub fn remove_rule(&mut self, key: &str) {
+        if let Some(watched) = self.entries.get_mut(key) {
+            watched.keep_disabled = false;
+            self.save();
+        }
+    }
+
+    /// Watched entries that `entries` (a fresh scan) shows enabled again.
+    /// Each is returned once per session; keep-disabled entries are returned
+    /// every time so they can be re-disabled.
+    pub fn find_reversions(&mut self, entries: &[StartupEntry]) -> Vec<Reversion> {
+        let mut result = Vec::new();
+        for entry in entries {
+            let key = entry_key(&entry.id());
+            let Some(watched) = self.entries.get(&key) else {
+                continue;
+            };
+            let kind = match watched.state {
+                WatchedState::Deleted => ReversionKind::ReCreated,
+                WatchedState::Disabled
+                    if !matches!(entry.enabled, EnabledStatus::Disabled | EnabledStatus::Unknown) =>
+                {
+                    ReversionKind::ReEnabled
+                }
+                WatchedState::Disabled => continue,
+            };
+            if !watched.keep_disabled && !self.reported.insert(key) {
+                continue;
+            }
+            result.push(Reversion {
+                id: entry.id(),
+                name: watched.name.clone(),
+                location: entry.source.display_location(),
+                kind,
+                keep_disabled: watched.keep_disabled,
+            });
+        }
+        result
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut keys: Vec<&String> = self.entries.keys().collect();
+        keys.sort();
+        let mut out = String::from(HEADER);
+        out.push('\n');
+        for key in keys {
+            let watched = &self.entries[key];
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\n",
+                key,
+                watched.state.as_str(),
+                if watched.keep_disabled { '1' } else { '0' },
+                watched.name
+            ));
+        }
+        // Best effort, like the run history
+        let _ = app_data::write_atomic(path, out);
+    }
+}